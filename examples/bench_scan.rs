@@ -95,6 +95,29 @@ fn stage_shallow_stat(tree_dirs: &[PathBuf]) -> (usize, u64, std::time::Duration
     (files_statted, total_bytes, start.elapsed())
 }
 
+/// Stage 2b: rough heap cost of a `HashMap<PathBuf, u64>` holding every
+/// file directly inside `dirs` — path bytes plus bucket overhead at
+/// hashbrown's 7/8 load factor.
+fn stage_file_map_footprint(dirs: &[PathBuf]) -> (usize, u64) {
+    let bucket = (std::mem::size_of::<(PathBuf, u64)>() + 1) as u64;
+    let mut files = 0usize;
+    let mut path_bytes: u64 = 0;
+
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|ft| !ft.is_dir()) {
+                files += 1;
+                path_bytes += entry.path().as_os_str().len() as u64;
+            }
+        }
+    }
+
+    (files, path_bytes + files as u64 * bucket * 8 / 7)
+}
+
 // ───────────────────────────────────────── stage 3 ───────────
 
 /// Check if a file should be charged (hard-link dedup).
@@ -415,6 +438,25 @@ fn main() {
     println!("  shallow bytes: {}", human_size(shallow_bytes));
     println!("  time:          {t2:.2?}");
 
+    // ── Stage 2b: per-file size map footprint ───────────────
+    // Only directories whose children are loaded (above the depth limit)
+    // report per-file sizes; the rest just feed their local sum.
+    let listed_dirs: Vec<PathBuf> = tree_dirs
+        .iter()
+        .filter(|d| d.strip_prefix(&root).map_or(0, |r| r.components().count()) < max_depth)
+        .cloned()
+        .collect();
+    let (all_files, all_bytes) = stage_file_map_footprint(&tree_dirs);
+    let (listed_files, listed_bytes) = stage_file_map_footprint(&listed_dirs);
+
+    println!("\nStage 2b — file_sizes footprint (estimated)");
+    println!("  every tree dir:  {all_files} files, {}", human_size(all_bytes));
+    println!("  listed dirs:     {listed_files} files, {}", human_size(listed_bytes));
+    assert!(
+        listed_bytes <= all_bytes,
+        "restricting File messages must not grow the map"
+    );
+
     // ── Stage 3: recursive walk ──────────────────────────────
     println!("\nStage 3 — Full recursive walk + stat");
 
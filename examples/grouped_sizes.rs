@@ -0,0 +1,58 @@
+//! Print a directory's children with sizes, grouped the way the tree view
+//! groups them, using only the `dir_tree` library (no TUI).
+//!
+//! Usage:
+//!   cargo run --example grouped_sizes -- [PATH]
+//!
+//! Defaults to the current directory.
+
+use std::path::PathBuf;
+
+use dir_tree::core::fs::{build_tree, WalkConfig};
+use dir_tree::core::grouping::{self, group_children, GroupedEntry, GroupingConfig, SortMode};
+use dir_tree::core::size::{compile_excludes, tree_sizes_blocking};
+
+fn main() -> anyhow::Result<()> {
+    let root = std::env::args_os()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .canonicalize()?;
+
+    // Only the direct children are listed; their sizes are still recursive.
+    let walk_config = WalkConfig {
+        max_depth: 1,
+        show_git_status: false,
+        ..WalkConfig::default()
+    };
+    let tree = build_tree(&root, &walk_config, false)?;
+    let sizes = tree_sizes_blocking(&tree, true, false, false, &compile_excludes(&[]));
+
+    let mut entries = group_children(&tree, tree.root, &GroupingConfig::default(), Some(&sizes));
+    grouping::sort_grouped(&tree, &mut entries, SortMode::SizeDesc, Some(&sizes), Some(&sizes));
+
+    let total = sizes.get(&root).copied().unwrap_or(0);
+    println!("{}  {}", grouping::human_size_padded(total), root.display());
+    for entry in &entries {
+        match entry {
+            GroupedEntry::Single(id) => {
+                let meta = &tree.get(*id).meta;
+                let size = sizes.get(&meta.path).copied().unwrap_or(meta.size);
+                let slash = if meta.is_dir { "/" } else { "" };
+                println!("{}    {}{slash}", grouping::human_size_padded(size), meta.name);
+            }
+            GroupedEntry::Group {
+                label,
+                count,
+                total_size,
+                ..
+            } => {
+                println!(
+                    "{}    {label} ({count} files)",
+                    grouping::human_size_padded(*total_size)
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,145 @@
+//! Status-bar announcements for the `--a11y` mode.
+//!
+//! Terminal screen readers speak text as it changes, so each tree key
+//! press is summarised in plain words ("expanded src, 14 entries") in the
+//! status message, unless the handler already left a message of its own.
+
+use super::handler::build_rows;
+use super::state::{ActiveView, AppState, PaneFocus};
+use crate::ui::tree_widget::TreeRow;
+
+/// What the selected tree row looked like before a key was handled.
+pub struct Snapshot {
+    row: Option<RowSnapshot>,
+    status: Option<String>,
+}
+
+#[derive(PartialEq)]
+struct RowSnapshot {
+    /// Path of an entry, or the key of a group row.
+    id: String,
+    expanded: bool,
+}
+
+impl Snapshot {
+    pub fn take(state: &AppState) -> Self {
+        Self {
+            row: selected_row(state).map(|(row, _)| row),
+            status: state.status_message.clone(),
+        }
+    }
+}
+
+/// Compare the tree with `before` and describe what changed.
+pub fn announce(state: &mut AppState, before: &Snapshot) {
+    if state.active_view != ActiveView::Tree
+        || state.pane_focus != PaneFocus::Tree
+        || state.status_message != before.status
+    {
+        return;
+    }
+    let Some((row, text)) = selected_row(state) else {
+        return;
+    };
+    let message = match &before.row {
+        Some(prev) if *prev == row => return,
+        Some(prev) if prev.id == row.id => {
+            if row.expanded {
+                expanded_text(&text)
+            } else {
+                format!("collapsed {}", text.name)
+            }
+        }
+        _ => match text.size {
+            Some(size) => format!("selected {}{}, {}", text.kind, text.name, spoken_size(size)),
+            None => format!("selected {}{}", text.kind, text.name),
+        },
+    };
+    state.status_message = Some(message);
+}
+
+/// Spoken parts of the selected row.
+struct RowText {
+    /// `"folder "`, `"group "` or empty, said only on selection.
+    kind: &'static str,
+    name: String,
+    size: Option<u64>,
+    entries: Option<usize>,
+}
+
+fn selected_row(state: &AppState) -> Option<(RowSnapshot, RowText)> {
+    let rows = build_rows(state);
+    match rows.get(state.tree_state.selected)? {
+        TreeRow::Node { node_id, expanded, .. } => {
+            let node = state.tree.get(*node_id);
+            let meta = &node.meta;
+            let sizes = if meta.is_dir { &state.dir_sizes } else { &state.file_sizes };
+            let row = RowSnapshot {
+                id: meta.path.to_string_lossy().into_owned(),
+                expanded: *expanded,
+            };
+            let text = RowText {
+                kind: if meta.is_dir { "folder " } else { "" },
+                name: meta.name.clone(),
+                size: sizes.get(&meta.path).copied(),
+                // Children not listed yet would read as an empty folder.
+                entries: (!node.children.is_empty()).then_some(node.children.len()),
+            };
+            Some((row, text))
+        }
+        TreeRow::Group {
+            label,
+            group_key,
+            expanded,
+            members,
+            ..
+        } => {
+            let row = RowSnapshot {
+                id: group_key.clone(),
+                expanded: *expanded,
+            };
+            let text = RowText {
+                kind: "group ",
+                name: label.clone(),
+                size: None,
+                entries: Some(members.len()),
+            };
+            Some((row, text))
+        }
+    }
+}
+
+fn expanded_text(text: &RowText) -> String {
+    match text.entries {
+        Some(1) => format!("expanded {}, 1 entry", text.name),
+        Some(n) => format!("expanded {}, {n} entries", text.name),
+        None => format!("expanded {}", text.name),
+    }
+}
+
+/// Size in words: `4 kilobytes`, `1.5 megabytes`, `1 byte`.
+fn spoken_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["kilobytes", "megabytes", "gigabytes", "terabytes"];
+    if bytes < 1024 {
+        return if bytes == 1 {
+            "1 byte".to_string()
+        } else {
+            format!("{bytes} bytes")
+        };
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for &next in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+    // A decimal only where it changes what's heard.
+    if size < 10.0 && (size * 10.0).round() % 10.0 != 0.0 {
+        format!("{size:.1} {unit}")
+    } else {
+        format!("{} {unit}", size.round() as u64)
+    }
+}
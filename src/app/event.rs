@@ -3,6 +3,8 @@
 //! Wraps crossterm events into a simpler enum and runs a background task that
 //! forwards them over a channel so the main loop stays non-blocking.
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use crossterm::event::{self, Event as CtEvent, KeyEvent, MouseEvent};
@@ -14,30 +16,58 @@ pub enum AppEvent {
     Key(KeyEvent),
     Mouse(MouseEvent),
     Resize(u16, u16),
+    /// The terminal window regained focus (e.g. a multiplexer reattached).
+    FocusGained,
+    /// Bracketed paste: the whole pasted text in one event.
+    Paste(String),
     Tick,
+    /// The system opener exited with an error after the UI moved on.
+    OpenFailed(String),
 }
 
 /// Spawns a background task that polls the terminal for events and sends them
 /// through the returned channel.
-pub fn spawn_event_reader(tick_rate: Duration) -> mpsc::UnboundedReceiver<AppEvent> {
+///
+/// While `paused` is set the task stops reading, so a child process that
+/// was handed the terminal (e.g. `$EDITOR`) gets all the input.
+///
+/// If the terminal stops delivering input (poll/read error), the task exits
+/// and the channel closes; the main loop treats that as fatal and shuts down.
+/// The returned weak sender lets background work post events without
+/// keeping the channel open.
+pub fn spawn_event_reader(
+    tick_rate: Duration,
+    paused: Arc<AtomicBool>,
+) -> (mpsc::WeakUnboundedSender<AppEvent>, mpsc::UnboundedReceiver<AppEvent>) {
     let (tx, rx) = mpsc::unbounded_channel();
+    let weak_tx = tx.downgrade();
 
     tokio::spawn(async move {
+        // Use crossterm's poll with the tick rate so we can send Tick
+        // events even when nothing is happening.  A poll error means the
+        // terminal is gone.
         loop {
-            // Use crossterm's poll with the tick rate so we can send Tick
-            // events even when nothing is happening.
-            let has_event = event::poll(tick_rate).unwrap_or(false);
+            if paused.load(Ordering::Relaxed) {
+                std::thread::sleep(tick_rate);
+                continue;
+            }
+            let Ok(has_event) = event::poll(tick_rate) else {
+                break;
+            };
             if has_event {
-                if let Ok(ev) = event::read() {
-                    let app_event = match ev {
-                        CtEvent::Key(k) => AppEvent::Key(k),
-                        CtEvent::Mouse(m) => AppEvent::Mouse(m),
-                        CtEvent::Resize(w, h) => AppEvent::Resize(w, h),
-                        _ => continue,
-                    };
-                    if tx.send(app_event).is_err() {
-                        break; // receiver dropped
-                    }
+                let Ok(ev) = event::read() else {
+                    break;
+                };
+                let app_event = match ev {
+                    CtEvent::Key(k) => AppEvent::Key(k),
+                    CtEvent::Mouse(m) => AppEvent::Mouse(m),
+                    CtEvent::Resize(w, h) => AppEvent::Resize(w, h),
+                    CtEvent::FocusGained => AppEvent::FocusGained,
+                    CtEvent::Paste(text) => AppEvent::Paste(text),
+                    _ => continue,
+                };
+                if tx.send(app_event).is_err() {
+                    break; // receiver dropped
                 }
             } else {
                 // No event within tick_rate — send a tick.
@@ -48,6 +78,5 @@ pub fn spawn_event_reader(tick_rate: Duration) -> mpsc::UnboundedReceiver<AppEve
         }
     });
 
-    rx
+    (weak_tx, rx)
 }
-
@@ -1,12 +1,15 @@
 //! Background filesystem/search jobs to keep the UI thread responsive.
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use tokio::sync::mpsc;
 
 use crate::core::{
     fs::{self, WalkConfig},
-    search::SearchEntry,
+    git::{self, GitFileStatus},
+    search::{SearchEntry, SearchOptions},
     tree::{DirTree, EntryMeta},
 };
 
@@ -25,6 +28,32 @@ pub enum FsUpdate {
         root: PathBuf,
         entries: Vec<SearchEntry>,
     },
+    /// One staged deletion was attempted.
+    Deleted {
+        path: PathBuf,
+        result: Result<(), String>,
+    },
+    /// The deletion batch has finished.
+    DeletionsFinished,
+    /// A single confirmed delete (trash or permanent) was attempted.
+    Removed {
+        path: PathBuf,
+        result: Result<(), String>,
+    },
+    /// The filesystem watcher saw `path` change.
+    PathChanged { path: PathBuf },
+    /// The filesystem watcher saw `from` renamed to `to`.
+    PathRenamed { from: PathBuf, to: PathBuf },
+    /// A loaded directory was listed again after a change.
+    DirRescanned {
+        path: PathBuf,
+        children: Vec<EntryMeta>,
+    },
+    /// `git status` finished for the current root.
+    GitStatus {
+        generation: u64,
+        statuses: HashMap<PathBuf, GitFileStatus>,
+    },
 }
 
 pub fn spawn_tree_rebuild(
@@ -59,18 +88,46 @@ pub fn spawn_dir_expand(
     });
 }
 
+/// Quiet period before a requested `git status` starts.
+pub const GIT_STATUS_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Read `git status` for the repository containing `root`.  An empty map
+/// means `root` is not inside a work tree.
+pub fn spawn_git_status(tx: mpsc::UnboundedSender<FsUpdate>, generation: u64, root: PathBuf) {
+    std::thread::spawn(move || {
+        let statuses = git::repo_status(&root).unwrap_or_default();
+        let _ = tx.send(FsUpdate::GitStatus {
+            generation,
+            statuses,
+        });
+    });
+}
+
+/// List an already-loaded directory again so the tree can be reconciled.
+pub fn spawn_dir_rescan(
+    tx: mpsc::UnboundedSender<FsUpdate>,
+    path: PathBuf,
+    walk_config: WalkConfig,
+    one_file_system: bool,
+) {
+    std::thread::spawn(move || {
+        let children = fs::scan_immediate_children(&path, &walk_config, one_file_system);
+        let _ = tx.send(FsUpdate::DirRescanned { path, children });
+    });
+}
+
 pub fn spawn_search_index(
     tx: mpsc::UnboundedSender<FsUpdate>,
     generation: u64,
     root: PathBuf,
-    walk_config: WalkConfig,
+    options: SearchOptions,
     one_file_system: bool,
 ) {
     std::thread::spawn(move || {
         let entries = crate::core::search::build_index(
             &root,
-            walk_config.show_hidden,
-            walk_config.respect_gitignore,
+            options.include_hidden,
+            options.respect_gitignore,
             one_file_system,
         );
         let _ = tx.send(FsUpdate::SearchIndexed {
@@ -81,3 +138,84 @@ pub fn spawn_search_index(
     });
 }
 
+/// Remove staged paths one at a time, reporting each result so the UI can
+/// show progress and per-item errors.  Entries go to the trash unless
+/// `permanent`.
+pub fn spawn_deletions(tx: mpsc::UnboundedSender<FsUpdate>, paths: Vec<PathBuf>, permanent: bool) {
+    std::thread::spawn(move || {
+        for path in paths {
+            let result = delete_entry(&path, permanent).map_err(|e| e.to_string());
+            if tx.send(FsUpdate::Deleted { path, result }).is_err() {
+                return;
+            }
+        }
+        let _ = tx.send(FsUpdate::DeletionsFinished);
+    });
+}
+
+/// Move one entry to the trash, or remove it outright when `permanent`.
+pub fn spawn_delete(tx: mpsc::UnboundedSender<FsUpdate>, path: PathBuf, permanent: bool) {
+    std::thread::spawn(move || {
+        let result = delete_entry(&path, permanent);
+        let _ = tx.send(FsUpdate::Removed {
+            path,
+            result: result.map_err(|e| e.to_string()),
+        });
+    });
+}
+
+/// Trash `path`, or remove it outright when `permanent`.  A path that is
+/// already gone (e.g. inside a directory handled earlier in the same
+/// batch) counts as success either way.
+fn delete_entry(path: &Path, permanent: bool) -> std::io::Result<()> {
+    if permanent {
+        return fs::remove_path(path);
+    }
+    match std::fs::symlink_metadata(path) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        _ => crate::core::trash::move_to_trash(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Results of a staged deletion job, in order.
+    fn run_deletions(paths: Vec<PathBuf>, permanent: bool) -> Vec<(PathBuf, Result<(), String>)> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        spawn_deletions(tx, paths, permanent);
+        let mut results = Vec::new();
+        loop {
+            match rx.blocking_recv() {
+                Some(FsUpdate::Deleted { path, result }) => results.push((path, result)),
+                Some(FsUpdate::DeletionsFinished) => return results,
+                _ => panic!("deletion job ended without finishing"),
+            }
+        }
+    }
+
+    #[test]
+    fn permanent_staged_deletions_remove_files_and_trees() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (dir, file) = (tmp.path().join("dir"), tmp.path().join("file"));
+        std::fs::create_dir_all(dir.join("inner")).unwrap();
+        std::fs::write(dir.join("inner/x"), "x").unwrap();
+        std::fs::write(&file, "y").unwrap();
+
+        // `dir/inner` is gone by the time it comes up; that's fine.
+        let results = run_deletions(vec![dir.clone(), dir.join("inner"), file.clone()], true);
+
+        assert!(results.iter().all(|(_, result)| result.is_ok()), "{results:?}");
+        assert_eq!(results.len(), 3);
+        assert!(!dir.exists() && !file.exists());
+    }
+
+    #[test]
+    fn trashing_a_path_that_is_already_gone_succeeds() {
+        let tmp = tempfile::tempdir().unwrap();
+        let gone = tmp.path().join("gone");
+        assert_eq!(run_deletions(vec![gone.clone()], false), [(gone.clone(), Ok(()))]);
+        assert!(delete_entry(&gone, false).is_ok());
+    }
+}
@@ -3,18 +3,27 @@
 use crossterm::event::{
     KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
 };
-use std::path::Path;
-use std::time::Instant;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use crate::config::{Action, KeyBind};
 use crate::shell::integration;
+use crate::core::inspector::InspectorInfo;
+use crate::core::search::SearchOptions;
+use crate::core::grouping::{GroupBy, SortMode};
 use crate::core::tree::NodeId;
-use crate::ui::inspector::pinned_cards_geometry;
+use crate::ui::badges::{self, StatusBadge};
+use crate::ui::inspector::{
+    card_archive_rows, card_checksum_row, current_archive_rows, current_checksum_row, max_archive_scroll,
+    pinned_cards_geometry,
+};
 use crate::ui::layout::AppLayout;
 use crate::ui::search::search_results_capacity;
 
+use super::announce;
 use super::settings::{SettingsItem, SETTINGS_ITEMS};
-use super::state::{ActiveView, AppState, PaneFocus, RightPaneTab};
+use super::state::{ActiveView, AppState, ExpandAllJob, PaneFocus, RightPaneTab};
 use crate::ui::tree_widget::{TreeRow, TreeWidget};
 
 /// Total selectable rows in the controls submenu (actions + "Reset").
@@ -30,8 +39,18 @@ pub fn handle_key(state: &mut AppState, key: KeyEvent) {
         return;
     }
 
+    if nudge_popup(state, key) {
+        return;
+    }
+
     match state.active_view {
-        ActiveView::Tree => handle_tree_key(state, key),
+        ActiveView::Tree => {
+            let before = state.a11y.then(|| announce::Snapshot::take(state));
+            handle_tree_key(state, key);
+            if let Some(before) = before {
+                announce::announce(state, &before);
+            }
+        }
         ActiveView::SettingsMenu => handle_settings_key(state, key),
         ActiveView::ControlsSubmenu => {
             if state.awaiting_rebind {
@@ -41,12 +60,208 @@ pub fn handle_key(state: &mut AppState, key: KeyEvent) {
             }
         }
         ActiveView::Lightbox => handle_lightbox_key(state, key),
+        ActiveView::PinSets => handle_pin_sets_key(state, key),
+        ActiveView::Bookmarks => handle_bookmarks_key(state, key),
+        ActiveView::TopFiles => handle_top_files_key(state, key),
+        ActiveView::ConfirmDeletions => handle_confirm_deletions_key(state, key),
+        ActiveView::ConfirmDelete => handle_confirm_delete_key(state, key),
+        ActiveView::Rename | ActiveView::NewDir => handle_name_prompt_key(state, key),
+        ActiveView::PathInput => handle_path_input_key(state, key),
+        ActiveView::ConfirmQuit => handle_confirm_quit_key(state, key),
+        ActiveView::ConfirmResetSettings => handle_confirm_reset_settings_key(state, key),
+        ActiveView::ConfigWarnings => {
+            if matches!(key.code, KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q')) {
+                state.config_warnings.clear();
+                state.active_view = ActiveView::SettingsMenu;
+            }
+        }
+        ActiveView::ExcludePatterns => handle_exclude_patterns_key(state, key),
+        ActiveView::ScanStats => {
+            if matches!(key.code, KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q')) {
+                state.active_view = ActiveView::Tree;
+            }
+        }
+    }
+}
+
+/// Process a bracketed paste, inserting the text into whichever input is
+/// active.  Pastes with no input focused are ignored.
+pub fn handle_paste(state: &mut AppState, text: &str) {
+    let text = super::input::sanitize_single_line(text);
+    if text.is_empty() {
+        return;
+    }
+
+    if state.active_view == ActiveView::PinSets {
+        if let Some(name) = state.pin_set_name_input.as_mut() {
+            // Same restrictions as typed characters (see handle_pin_sets_key).
+            name.extend(text.chars().filter(|&c| c != '='));
+            let trimmed = name
+                .trim_start_matches(|c: char| c.is_whitespace() || c == '[' || c == '#')
+                .to_string();
+            *name = trimmed;
+        }
+        return;
+    }
+
+    if state.active_view == ActiveView::ExcludePatterns {
+        if let Some(pattern) = state.exclude_input.as_mut() {
+            pattern.push_str(&text);
+        }
+        return;
+    }
+
+    if state.active_view == ActiveView::PathInput {
+        state.path_input.push_str(&text);
+        state.path_completions.clear();
+        return;
+    }
+
+    if matches!(state.active_view, ActiveView::Rename | ActiveView::NewDir) {
+        state.name_input.extend(text.chars().filter(|c| !c.is_control()));
+        return;
+    }
+
+    if state.active_view == ActiveView::Tree
+        && state.right_pane_tab == RightPaneTab::Search
+        && state.pane_focus == PaneFocus::Inspector
+    {
+        state.search_query.push_str(&text);
+        refresh_search_results(state);
+        reveal_selected_search_in_tree(state);
+    }
+}
+
+/// Ctrl+arrows move the open popup.  Not while capturing a rebind, so
+/// Ctrl+arrow combos stay bindable.
+fn nudge_popup(state: &mut AppState, key: KeyEvent) -> bool {
+    let is_popup = !matches!(state.active_view, ActiveView::Tree | ActiveView::Lightbox);
+    if !is_popup || state.awaiting_rebind || key.modifiers != KeyModifiers::CONTROL {
+        return false;
+    }
+    let (dx, dy) = match key.code {
+        KeyCode::Left => (-2, 0),
+        KeyCode::Right => (2, 0),
+        KeyCode::Up => (0, -1),
+        KeyCode::Down => (0, 1),
+        _ => return false,
+    };
+    // Keep the offset within reach of the screen so nudging back is prompt.
+    let max_x = (state.terminal_area.width / 2) as i16;
+    let max_y = (state.terminal_area.height / 2) as i16;
+    let (x, y) = state.popup_offset;
+    state.popup_offset = ((x + dx).clamp(-max_x, max_x), (y + dy).clamp(-max_y, max_y));
+    true
+}
+
+/// Idle time after which the type-ahead buffer starts over.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(800);
+
+/// The type-ahead buffer, unless it is empty or has lapsed.  In
+/// type-ahead mode it is shown even while empty.
+pub fn active_type_ahead(state: &AppState) -> Option<&str> {
+    if state.type_ahead_mode {
+        return Some(state.type_ahead.as_str());
+    }
+    let fresh = state.type_ahead_at.is_some_and(|at| at.elapsed() < TYPE_AHEAD_TIMEOUT);
+    (fresh && !state.type_ahead.is_empty()).then_some(state.type_ahead.as_str())
+}
+
+/// The printable character `key` types, if any.
+fn typed_char(key: KeyEvent) -> Option<char> {
+    let KeyCode::Char(c) = key.code else {
+        return None;
+    };
+    let chord = key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+    (!chord && !c.is_control()).then_some(c)
+}
+
+/// Add an unbound printable key to the type-ahead buffer and select the
+/// next visible entry whose name starts with the buffer.
+fn type_ahead_key(state: &mut AppState, key: KeyEvent) {
+    let Some(c) = typed_char(key) else {
+        return;
+    };
+    if active_type_ahead(state).is_none() {
+        state.type_ahead.clear();
+    }
+    state.type_ahead.extend(c.to_lowercase());
+    state.type_ahead_at = Some(Instant::now());
+    // A first letter moves past the current row, so repeating it cycles;
+    // a longer prefix may still match the row already selected.
+    jump_to_type_ahead(state, state.type_ahead.chars().count() == 1);
+}
+
+/// Keys while in type-ahead mode: printable keys (bound or not) extend
+/// the buffer, Backspace shortens it, Enter and Esc leave the mode with
+/// the selection where it is.  Any other key leaves the mode and is
+/// handled as usual; returns `false` for those.
+fn type_ahead_mode_key(state: &mut AppState, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Enter | KeyCode::Esc => {
+            state.type_ahead_mode = false;
+            state.type_ahead.clear();
+            true
+        }
+        KeyCode::Backspace => {
+            state.type_ahead.pop();
+            jump_to_type_ahead(state, false);
+            true
+        }
+        _ => match typed_char(key) {
+            Some(c) => {
+                state.type_ahead.extend(c.to_lowercase());
+                jump_to_type_ahead(state, false);
+                true
+            }
+            None => {
+                state.type_ahead_mode = false;
+                state.type_ahead.clear();
+                false
+            }
+        },
+    }
+}
+
+/// Select the first visible entry at or after the selection (after it
+/// when `advance`) whose name starts with the buffer, ignoring case and
+/// wrapping around.
+fn jump_to_type_ahead(state: &mut AppState, advance: bool) {
+    if state.type_ahead.is_empty() {
+        return;
+    }
+    let rows = build_rows(state);
+    if rows.is_empty() {
+        return;
+    }
+    let start = state.tree_state.selected + usize::from(advance);
+    let hit = (0..rows.len())
+        .map(|i| (start + i) % rows.len())
+        .find(|&i| match &rows[i] {
+            TreeRow::Node { node_id, .. } => state
+                .tree
+                .get(*node_id)
+                .meta
+                .name
+                .to_lowercase()
+                .starts_with(&state.type_ahead),
+            TreeRow::Group { .. } => false,
+        });
+    if let Some(i) = hit {
+        state.tree_state.selected = i;
     }
 }
 
 // ── Tree view (configurable bindings) ───────────────────────────
 
 fn handle_tree_key(state: &mut AppState, key: KeyEvent) {
+    // Any key brings a selection the wheel scrolled away from back into view.
+    state.tree_state.scrolled_away_from = None;
+
+    if state.type_ahead_mode && state.pane_focus == PaneFocus::Tree && type_ahead_mode_key(state, key) {
+        return;
+    }
+
     if is_search_shortcut(key) {
         toggle_search_tab(state);
         return;
@@ -80,17 +295,74 @@ fn handle_tree_key(state: &mut AppState, key: KeyEvent) {
         }
         if let Some(action) = state.config.match_key(key) {
             match action {
-                Action::Quit => state.should_quit = true,
+                Action::Quit => request_quit(state),
                 Action::OpenSettings => {
                     state.active_view = ActiveView::SettingsMenu;
                     state.settings_selected = 0;
                 }
+                Action::ToggleDebug => {
+                    state.show_debug_overlay = !state.show_debug_overlay;
+                }
+                Action::ShowDeletions => show_deletions_tab(state),
+                Action::CommitDeletions => request_commit_deletions(state),
+                Action::ToggleMouse => toggle_mouse(state),
+                Action::GrowTreePane => resize_tree_pane(state, SPLIT_STEP_PCT as i16),
+                Action::ShrinkTreePane => resize_tree_pane(state, -(SPLIT_STEP_PCT as i16)),
+                Action::OpenSystem if state.right_pane_tab == RightPaneTab::Inspector => {
+                    let pinned = state
+                        .pinned_inspector
+                        .get(state.inspector_selected_pin)
+                        .map(|info| info.path.clone());
+                    if let Some(path) = pinned {
+                        open_with_system(state, &path);
+                    }
+                }
+                Action::OpenInEditor | Action::OpenInPager
+                    if state.right_pane_tab == RightPaneTab::Inspector =>
+                {
+                    let pinned = state
+                        .pinned_inspector
+                        .get(state.inspector_selected_pin)
+                        .map(|info| info.path.clone());
+                    if let Some(path) = pinned {
+                        open_external(state, path, action == Action::OpenInPager);
+                    }
+                }
+                Action::CopyPath | Action::CopyRelativePath
+                    if state.right_pane_tab == RightPaneTab::Inspector =>
+                {
+                    let pinned = state
+                        .pinned_inspector
+                        .get(state.inspector_selected_pin)
+                        .map(|info| info.path.clone());
+                    if let Some(path) = pinned {
+                        copy_path(state, &path, action == Action::CopyRelativePath);
+                    }
+                }
+                Action::CopyChecksum if state.right_pane_tab == RightPaneTab::Inspector => {
+                    // The selected pin, or the current selection when nothing is pinned.
+                    let info = state
+                        .pinned_inspector
+                        .get(state.inspector_selected_pin)
+                        .or(state.inspector_info.as_ref());
+                    let digest = info.and_then(|info| info.checksum.clone());
+                    copy_checksum(state, digest);
+                }
                 _ => {}
             }
         }
         return;
     }
 
+    if key.code == KeyCode::Esc && active_type_ahead(state).is_some() {
+        state.type_ahead.clear();
+        return;
+    }
+    if key.code == KeyCode::Esc && !state.selected_nodes.is_empty() {
+        clear_selection(state);
+        return;
+    }
+
     // Direct Enter/Shift+Enter actions for the selected tree row.
     if key.code == KeyCode::Enter && is_simple_enter_combo(key.modifiers) {
         if let Some(node_id) = selected_node_id(state) {
@@ -122,16 +394,26 @@ fn handle_tree_key(state: &mut AppState, key: KeyEvent) {
             }
             return;
         }
+        KeyCode::PageUp if key.modifiers.is_empty() => {
+            page_tree(state, -1, 1);
+            return;
+        }
+        KeyCode::PageDown if key.modifiers.is_empty() => {
+            page_tree(state, 1, 1);
+            return;
+        }
         _ => {}
     }
 
     let Some(action) = state.config.match_key(key) else {
+        type_ahead_key(state, key);
         return;
     };
+    state.type_ahead.clear();
 
     match action {
         Action::Quit => {
-            state.should_quit = true;
+            request_quit(state);
         }
         Action::OpenSettings => {
             state.active_view = ActiveView::SettingsMenu;
@@ -144,6 +426,21 @@ fn handle_tree_key(state: &mut AppState, key: KeyEvent) {
             let visible_count = build_rows(state).len();
             state.tree_state.select_next(visible_count);
         }
+        Action::PinSelected => {
+            pin_selected(state);
+        }
+        Action::ToggleSelect => {
+            toggle_select(state);
+        }
+        Action::ClearSelection => {
+            clear_selection(state);
+        }
+        Action::HalfPageUp => {
+            page_tree(state, -1, 2);
+        }
+        Action::HalfPageDown => {
+            page_tree(state, 1, 2);
+        }
         Action::Expand => {
             // Groups: toggle expand/collapse.
             if let Some((key, _)) = selected_group_key(state) {
@@ -168,12 +465,89 @@ fn handle_tree_key(state: &mut AppState, key: KeyEvent) {
             }
             handle_collapse(state);
         }
+        Action::GoToPath => {
+            let mut prefill = state.cwd.to_string_lossy().into_owned();
+            if !prefill.ends_with('/') {
+                prefill.push('/');
+            }
+            state.path_input = prefill;
+            state.path_completions.clear();
+            state.path_input_saved_status = state.status_message.take();
+            state.active_view = ActiveView::PathInput;
+        }
+        Action::ExpandAll => {
+            start_expand_all(state);
+        }
+        Action::CollapseAll => {
+            collapse_all(state);
+        }
+        Action::AddBookmark => {
+            add_bookmark(state);
+        }
+        Action::OpenSystem => {
+            if let Some(path) = selected_node_path(state) {
+                open_with_system(state, &path);
+            }
+        }
+        Action::OpenTerminal => {
+            if let Some(path) = selected_node_path(state) {
+                open_terminal(state, &path);
+            }
+        }
+        Action::OpenInEditor | Action::OpenInPager => {
+            if let Some(path) = selected_node_path(state) {
+                open_external(state, path, action == Action::OpenInPager);
+            }
+        }
+        Action::CopyPath | Action::CopyRelativePath => {
+            if let Some(path) = selected_node_path(state) {
+                copy_path(state, &path, action == Action::CopyRelativePath);
+            }
+        }
+        Action::TypeAhead => {
+            state.type_ahead_mode = true;
+            state.type_ahead.clear();
+        }
+        Action::CopyChecksum => {
+            let digest = state.inspector_info.as_ref().and_then(|info| info.checksum.clone());
+            copy_checksum(state, digest);
+        }
+        Action::OpenBookmarks => {
+            state.bookmark_selected = 0;
+            state.active_view = ActiveView::Bookmarks;
+        }
+        Action::ShowTopFiles => {
+            state.top_selected = 0;
+            refresh_top_entries(state);
+            state.active_view = ActiveView::TopFiles;
+        }
+        Action::ScanStats => {
+            state.active_view = ActiveView::ScanStats;
+        }
+        Action::HistoryBack => {
+            step_root_history(state, false);
+        }
+        Action::HistoryForward => {
+            step_root_history(state, true);
+        }
+        Action::JumpLargestChild => {
+            jump_to_largest_child(state);
+        }
+        Action::JumpLargestBack => {
+            jump_largest_back(state);
+        }
         Action::JumpSiblingUp => {
             jump_to_sibling_dir(state, Direction::Up);
         }
         Action::JumpSiblingDown => {
             jump_to_sibling_dir(state, Direction::Down);
         }
+        Action::JumpFirstSibling => {
+            jump_to_edge_sibling_dir(state, Direction::Up);
+        }
+        Action::JumpLastSibling => {
+            jump_to_edge_sibling_dir(state, Direction::Down);
+        }
         Action::CdIntoDir => {
             if let Some(node_id) = selected_node_id(state) {
                 let node = state.tree.get(node_id);
@@ -186,6 +560,105 @@ fn handle_tree_key(state: &mut AppState, key: KeyEvent) {
             state.walk_config.show_hidden = !state.walk_config.show_hidden;
             rebuild_tree(state);
         }
+        Action::IncreaseDepth => change_walk_depth(state, true),
+        Action::DecreaseDepth => change_walk_depth(state, false),
+        Action::ToggleDebug => {
+            state.show_debug_overlay = !state.show_debug_overlay;
+        }
+        Action::RetrySizeScan => {
+            // Cached per-dir results survive, so only the unfinished
+            // directories are walked again.
+            state.size_scan_failed = None;
+            state.status_message = None;
+            state.needs_size_recompute = true;
+        }
+        Action::PinAllImages => {
+            toggle_pin_all_images(state);
+        }
+        Action::ToggleHeat => {
+            toggle_heat_mode(state);
+        }
+        Action::ToggleMouse => {
+            toggle_mouse(state);
+        }
+        Action::RescanSelected => {
+            rescan_selected(state);
+        }
+        Action::Refresh => {
+            refresh_selected(state);
+        }
+        Action::FocusBadge => {
+            cycle_badge_focus(state);
+        }
+        Action::ToggleBadge => match state.badge_focus {
+            Some(badge) => toggle_badge(state, badge),
+            None => {
+                state.status_message = Some(format!(
+                    "No badge focused ({} to focus one)",
+                    state.config.short_binding(Action::FocusBadge),
+                ));
+            }
+        },
+        Action::CycleSort => {
+            cycle_sort(state);
+        }
+        Action::GrowTreePane => {
+            resize_tree_pane(state, SPLIT_STEP_PCT as i16);
+        }
+        Action::ShrinkTreePane => {
+            resize_tree_pane(state, -(SPLIT_STEP_PCT as i16));
+        }
+        Action::SavePinSet => {
+            if state.pinned_inspector.is_empty() {
+                state.status_message = Some("No pins to save".to_string());
+            } else {
+                state.pin_set_name_input = Some(String::new());
+                state.active_view = ActiveView::PinSets;
+            }
+        }
+        Action::LoadPinSet => {
+            state.pin_set_name_input = None;
+            state.pin_set_selected = 0;
+            state.active_view = ActiveView::PinSets;
+        }
+        Action::StageDelete => {
+            if let Some(node_id) = selected_node_id(state) {
+                let path = state.tree.get(node_id).meta.path.clone();
+                toggle_staged_deletion(state, path);
+            }
+        }
+        Action::Delete => {
+            request_delete(state);
+        }
+        Action::Rename => {
+            if let Some(path) = selected_node_path(state) {
+                if path == state.cwd {
+                    state.status_message = Some("Cannot rename the tree root".to_string());
+                } else {
+                    state.name_input = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    state.rename_target = Some(path);
+                    state.active_view = ActiveView::Rename;
+                }
+            }
+        }
+        Action::NewDir => {
+            if let Some(path) = selected_node_path(state) {
+                let is_dir = path.is_dir() && !path.is_symlink();
+                let parent = if is_dir { Some(path) } else { path.parent().map(Path::to_path_buf) };
+                state.new_dir_parent = parent;
+                state.name_input.clear();
+                state.active_view = ActiveView::NewDir;
+            }
+        }
+        Action::ShowDeletions => {
+            show_deletions_tab(state);
+        }
+        Action::CommitDeletions => {
+            request_commit_deletions(state);
+        }
     }
 }
 
@@ -230,16 +703,8 @@ fn jump_to_sibling_dir(state: &mut AppState, direction: Direction) {
     let rows = build_rows(state);
     let current = state.tree_state.selected;
 
-    let target_depth = match rows.get(current) {
-        Some(TreeRow::Node { depth, is_dir, .. }) => {
-            if *is_dir {
-                *depth
-            } else {
-                depth.saturating_sub(1)
-            }
-        }
-        Some(TreeRow::Group { depth, .. }) => depth.saturating_sub(1),
-        None => return,
+    let Some(target_depth) = sibling_depth(&rows, current) else {
+        return;
     };
 
     match direction {
@@ -272,6 +737,54 @@ fn jump_to_sibling_dir(state: &mut AppState, direction: Direction) {
     }
 }
 
+/// Depth of the directories `jump_to_sibling_dir` steps between: the
+/// selected directory's own, or its parent's for a file or group row.
+fn sibling_depth(rows: &[TreeRow], current: usize) -> Option<usize> {
+    match rows.get(current)? {
+        TreeRow::Node { depth, is_dir, .. } => {
+            if *is_dir {
+                Some(*depth)
+            } else {
+                Some(depth.saturating_sub(1))
+            }
+        }
+        TreeRow::Group { depth, .. } => Some(depth.saturating_sub(1)),
+    }
+}
+
+/// Jump to the first/last sibling directory under the same parent.
+fn jump_to_edge_sibling_dir(state: &mut AppState, direction: Direction) {
+    let rows = build_rows(state);
+    let current = state.tree_state.selected;
+
+    let Some(target_depth) = sibling_depth(&rows, current) else {
+        return;
+    };
+    let row_depth = |row: &TreeRow| match row {
+        TreeRow::Node { depth, .. } | TreeRow::Group { depth, .. } => *depth,
+    };
+
+    // The parent's rows end at the first shallower row either way.
+    let in_parent: Box<dyn Iterator<Item = usize>> = match direction {
+        Direction::Up => Box::new((0..current).rev()),
+        Direction::Down => Box::new((current + 1)..rows.len()),
+    };
+    let mut target = None;
+    for i in in_parent {
+        if row_depth(&rows[i]) < target_depth {
+            break;
+        }
+        if let TreeRow::Node { depth, is_dir, .. } = &rows[i] {
+            if *is_dir && *depth == target_depth {
+                target = Some(i);
+            }
+        }
+    }
+    if let Some(i) = target {
+        state.tree_state.selected = i;
+    }
+}
+
 // ── Settings menu (hardcoded keys) ──────────────────────────────
 
 fn handle_settings_key(state: &mut AppState, key: KeyEvent) {
@@ -293,6 +806,8 @@ fn handle_settings_key(state: &mut AppState, key: KeyEvent) {
                     SettingsItem::Submenu { view, .. } => {
                         state.active_view = *view;
                         state.controls_selected = 0;
+                        state.exclude_selected = 0;
+                        state.exclude_input = None;
                     }
                     SettingsItem::Toggle { get, set, .. } => {
                         let current = get(state);
@@ -301,6 +816,18 @@ fn handle_settings_key(state: &mut AppState, key: KeyEvent) {
                     SettingsItem::Cycle { cycle, .. } => {
                         cycle(state);
                     }
+                    SettingsItem::Command { run, .. } => {
+                        run(state);
+                    }
+                    SettingsItem::Info { .. } => {}
+                }
+            }
+        }
+        KeyCode::Delete | KeyCode::Backspace => {
+            if let Some(item) = SETTINGS_ITEMS.get(state.settings_selected) {
+                if !item.is_default(state) {
+                    item.reset(state);
+                    state.status_message = Some(format!("{} reset to default", item.label()));
                 }
             }
         }
@@ -308,6 +835,19 @@ fn handle_settings_key(state: &mut AppState, key: KeyEvent) {
     }
 }
 
+fn handle_confirm_reset_settings_key(state: &mut AppState, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            super::settings::reset_all_settings(state);
+            state.active_view = ActiveView::SettingsMenu;
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            state.active_view = ActiveView::SettingsMenu;
+        }
+        _ => {}
+    }
+}
+
 // ── Controls submenu (hardcoded navigation, interactive rebinding) ──
 
 fn handle_controls_key(state: &mut AppState, key: KeyEvent) {
@@ -360,46 +900,1182 @@ fn handle_rebind_key(state: &mut AppState, key: KeyEvent) {
     // Esc cancels rebinding.
     if key.code == KeyCode::Esc {
         state.awaiting_rebind = false;
+        state.pending_rebind = None;
+        state.rebind_warning = None;
         return;
     }
 
-    // Don't allow rebinding Ctrl+C (reserved for emergency quit).
-    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
-        return;
+    // Enter confirms a captured binding (and is otherwise capturable).
+    if key.code == KeyCode::Enter && key.modifiers.is_empty() {
+        if let Some(bind) = state.pending_rebind.take() {
+            let action = Action::ALL[state.controls_selected];
+            state.config.add_binding(action, bind);
+            let _ = state.config.save();
+            state.awaiting_rebind = false;
+            state.rebind_warning = None;
+            return;
+        }
     }
 
-    let action = Action::ALL[state.controls_selected];
+    // Capture (or re-capture) the combination; only valid keys are kept.
     let bind = KeyBind::from_key_event(key);
-    state.config.add_binding(action, bind);
-    let _ = state.config.save();
-    state.awaiting_rebind = false;
+    match bind.validate() {
+        Ok(()) => {
+            state.pending_rebind = Some(bind);
+            state.rebind_warning = None;
+        }
+        Err(reason) => {
+            state.pending_rebind = None;
+            state.rebind_warning = Some(reason);
+        }
+    }
 }
 
-// ── Mouse ───────────────────────────────────────────────────────
+// ── Pin sets popup (hardcoded keys) ─────────────────────────────
 
-/// Process a mouse event.
-pub fn handle_mouse(state: &mut AppState, mouse: MouseEvent) {
-    if state.active_view == ActiveView::Lightbox {
-        handle_lightbox_mouse(state, mouse);
+/// Hand `path` to the system's default application.  The main loop
+/// starts the opener, since a late failure comes back as an event.
+fn open_with_system(state: &mut AppState, path: &Path) {
+    state.system_open_requested = Some(path.to_path_buf());
+}
+
+/// Start a terminal in `path`, or in its parent for a file.  Best effort:
+/// failures only end up in the status bar.
+fn open_terminal(state: &mut AppState, path: &Path) {
+    let dir = if path.is_dir() {
+        path
+    } else {
+        path.parent().unwrap_or(path)
+    };
+    let configured = state.config.terminal_emulator.as_deref();
+    state.status_message = Some(match integration::open_terminal(dir, configured) {
+        Ok(program) => format!("Opened {program} in {}", dir.display()),
+        Err(e) => e.to_string(),
+    });
+}
+
+/// Ask the main loop to open `path` in the user's editor, or in the pager
+/// when `pager`.  Directories go to the editor as-is (vim and friends
+/// browse them) but can't be paged.
+fn open_external(state: &mut AppState, path: PathBuf, pager: bool) {
+    if pager && path.is_dir() {
+        state.status_message = Some(format!("Can't page a directory: {}", path.display()));
         return;
     }
-    if state.active_view != ActiveView::Tree {
+    let command = if pager {
+        integration::pager_command()
+    } else {
+        integration::editor_command()
+    };
+    state.external_open_requested = Some((command, path));
+}
+
+/// Copy `path` (relative to the root when `relative`) to the clipboard.
+/// It is recorded even when no clipboard tool works, so the shell wrapper
+/// still prints it on exit.
+fn copy_path(state: &mut AppState, path: &Path, relative: bool) {
+    let text = match path.strip_prefix(&state.cwd) {
+        Ok(rel) if relative && rel.as_os_str().is_empty() => PathBuf::from("."),
+        Ok(rel) if relative => rel.to_path_buf(),
+        _ => path.to_path_buf(),
+    };
+    state.status_message = Some(if integration::copy_path_to_clipboard(&text) {
+        format!("Copied: {}", text.display())
+    } else {
+        "Clipboard unavailable — path printed on exit".to_string()
+    });
+    state.copied_path = Some(text);
+}
+
+/// Bookmark the selected directory (the root when a file is selected).
+fn add_bookmark(state: &mut AppState) {
+    let path = selected_node_id(state)
+        .map(|id| &state.tree.get(id).meta)
+        .filter(|meta| meta.is_dir)
+        .map(|meta| meta.path.clone())
+        .unwrap_or_else(|| state.cwd.clone());
+    if state.config.bookmarks.iter().any(|b| b.path == path) {
+        state.status_message = Some(format!("Already bookmarked: {}", path.display()));
         return;
     }
+    // `=` separates name from path in the config file.
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().replace('=', "_"))
+        .unwrap_or_else(|| "/".to_string());
+    state.status_message = Some(format!("Bookmarked {name}"));
+    state.config.bookmarks.push(crate::config::Bookmark { name, path });
+    let _ = state.config.save();
+}
+
+fn handle_bookmarks_key(state: &mut AppState, key: KeyEvent) {
+    let count = state.config.bookmarks.len();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            state.active_view = ActiveView::Tree;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.bookmark_selected = state.bookmark_selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.bookmark_selected = (state.bookmark_selected + 1).min(count.saturating_sub(1));
+        }
+        KeyCode::Enter => {
+            if let Some(bookmark) = state.config.bookmarks.get(state.bookmark_selected) {
+                let path = bookmark.path.clone();
+                state.active_view = ActiveView::Tree;
+                go_to_path(state, &path);
+            }
+        }
+        KeyCode::Delete | KeyCode::Char('d') if state.bookmark_selected < count => {
+            let removed = state.config.bookmarks.remove(state.bookmark_selected);
+            let _ = state.config.save();
+            state.bookmark_selected = state.bookmark_selected.min(count.saturating_sub(2));
+            state.status_message = Some(format!("Deleted bookmark '{}'", removed.name));
+        }
+        _ => {}
+    }
+}
 
-    let layout = AppLayout::from_area(
-        state.terminal_area,
-        state.config.panel_layout,
-        state.config.panel_split_pct,
-    );
+/// Rows in each section of the top-entries overlay.
+const TOP_ENTRIES: usize = 10;
 
-    match mouse.kind {
-        MouseEventKind::Down(MouseButton::Left) => {
-            if layout.is_on_splitter(mouse.column, mouse.row) {
-                state.dragging_splitter = true;
-                return;
+/// Recompute the largest known directories and files.  Called when the
+/// overlay opens and after every size batch while it is open.
+pub fn refresh_top_entries(state: &mut AppState) {
+    fn largest<'a>(
+        sizes: impl Iterator<Item = (&'a PathBuf, &'a u64)>,
+        n: usize,
+    ) -> Vec<(PathBuf, u64)> {
+        let mut heap = std::collections::BinaryHeap::with_capacity(n + 1);
+        for (path, &size) in sizes {
+            heap.push(std::cmp::Reverse((size, path)));
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|std::cmp::Reverse((size, path))| (path.clone(), size))
+            .collect()
+    }
+    let cwd = &state.cwd;
+    state.top_dirs = largest(state.dir_sizes.iter().filter(|(p, _)| *p != cwd), TOP_ENTRIES);
+    state.top_files = largest(state.file_sizes.iter(), TOP_ENTRIES);
+    let rows = state.top_dirs.len() + state.top_files.len();
+    state.top_selected = state.top_selected.min(rows.saturating_sub(1));
+}
+
+fn handle_top_files_key(state: &mut AppState, key: KeyEvent) {
+    let rows = state.top_dirs.len() + state.top_files.len();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            state.active_view = ActiveView::Tree;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.top_selected = state.top_selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.top_selected = (state.top_selected + 1).min(rows.saturating_sub(1));
+        }
+        KeyCode::Enter => {
+            let entry = state
+                .top_dirs
+                .iter()
+                .chain(&state.top_files)
+                .nth(state.top_selected)
+                .map(|(path, _)| path.clone());
+            if let Some(path) = entry {
+                state.active_view = ActiveView::Tree;
+                state.pane_focus = PaneFocus::Tree;
+                reveal_path_in_tree(state, &path);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_pin_sets_key(state: &mut AppState, key: KeyEvent) {
+    if let Some(name) = state.pin_set_name_input.as_mut() {
+        match key.code {
+            KeyCode::Esc => {
+                state.pin_set_name_input = None;
+                state.active_view = ActiveView::Tree;
+            }
+            KeyCode::Enter => {
+                let name = name.trim().to_string();
+                if !name.is_empty() {
+                    save_pin_set(state, name);
+                    state.pin_set_name_input = None;
+                    state.active_view = ActiveView::Tree;
+                }
+            }
+            KeyCode::Backspace => {
+                name.pop();
+            }
+            // `=` separates name from path in the config file and a leading
+            // `[` / `#` (once trimmed) would read as a section header / comment.
+            KeyCode::Char(ch)
+                if (key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT)
+                    && ch != '='
+                    && !(name.trim().is_empty() && (ch == '[' || ch == '#')) =>
+            {
+                name.push(ch);
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    let count = state.config.pin_sets.len();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            state.active_view = ActiveView::Tree;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.pin_set_selected = state.pin_set_selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.pin_set_selected = (state.pin_set_selected + 1).min(count.saturating_sub(1));
+        }
+        KeyCode::Enter | KeyCode::Char('m') => {
+            let name = state.config.pin_sets.keys().nth(state.pin_set_selected).cloned();
+            if let Some(name) = name {
+                load_pin_set(state, &name, key.code == KeyCode::Char('m'));
+                state.active_view = ActiveView::Tree;
+            }
+        }
+        KeyCode::Delete | KeyCode::Char('d') => {
+            let name = state.config.pin_sets.keys().nth(state.pin_set_selected).cloned();
+            if let Some(name) = name {
+                state.config.pin_sets.remove(&name);
+                let _ = state.config.save();
+                state.pin_set_selected = state
+                    .pin_set_selected
+                    .min(state.config.pin_sets.len().saturating_sub(1));
+                state.status_message = Some(format!("Deleted pin set '{name}'"));
+            }
+        }
+        _ => {}
+    }
+}
+
+// ── Exclude patterns submenu ────────────────────────────────────
+
+fn handle_exclude_patterns_key(state: &mut AppState, key: KeyEvent) {
+    if let Some(pattern) = state.exclude_input.as_mut() {
+        match key.code {
+            KeyCode::Esc => state.exclude_input = None,
+            KeyCode::Enter => {
+                let pattern = pattern.trim().to_string();
+                // Invalid globs stay in the input with the error shown.
+                if !pattern.is_empty() && crate::core::size::exclude_pattern_error(&pattern).is_none() {
+                    state.exclude_input = None;
+                    if !state.config.exclude_patterns.contains(&pattern) {
+                        state.config.exclude_patterns.push(pattern);
+                        state.exclude_selected = state.config.exclude_patterns.len() - 1;
+                        let _ = state.config.save();
+                        super::settings::exclude_patterns_changed(state);
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                pattern.pop();
+            }
+            // A leading `[` / `#` would read as a section header / comment.
+            KeyCode::Char(ch)
+                if (key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT)
+                    && !(pattern.is_empty() && (ch == '[' || ch == '#')) =>
+            {
+                pattern.push(ch);
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    let count = state.config.exclude_patterns.len();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            state.active_view = ActiveView::Tree;
+        }
+        KeyCode::Left | KeyCode::Char('h') => {
+            state.active_view = ActiveView::SettingsMenu;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.exclude_selected = state.exclude_selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.exclude_selected = (state.exclude_selected + 1).min(count.saturating_sub(1));
+        }
+        KeyCode::Char('a') | KeyCode::Insert => {
+            state.exclude_input = Some(String::new());
+        }
+        KeyCode::Delete | KeyCode::Char('d') if state.exclude_selected < count => {
+            state.config.exclude_patterns.remove(state.exclude_selected);
+            let _ = state.config.save();
+            state.exclude_selected = state.exclude_selected.min(count.saturating_sub(2));
+            super::settings::exclude_patterns_changed(state);
+        }
+        _ => {}
+    }
+}
+
+/// Store the current pins under `name`, replacing any set with that name.
+fn save_pin_set(state: &mut AppState, name: String) {
+    let paths: Vec<String> = state
+        .pinned_inspector
+        .iter()
+        .map(|info| info.path.display().to_string())
+        .collect();
+    let count = paths.len();
+    state.config.pin_sets.insert(name.clone(), paths);
+    let _ = state.config.save();
+    state.status_message = Some(format!("Saved {count} pins as '{name}'"));
+}
+
+/// Load a saved pin set, replacing the current pins or merging into them.
+/// Paths go through the async inspector like startup pin restoration.
+fn load_pin_set(state: &mut AppState, name: &str, merge: bool) {
+    let Some(paths) = state.config.pin_sets.get(name).cloned() else {
+        return;
+    };
+    if !merge {
+        state.pinned_inspector.clear();
+        state.bulk_pins.clear();
+        state.inspector_selected_pin = 0;
+        state.inspector_pin_scroll = 0;
+    }
+
+    let mut added = 0;
+    let mut missing = 0;
+    for path_str in &paths {
+        let path = PathBuf::from(path_str);
+        if !path.exists() {
+            missing += 1;
+            continue;
+        }
+        if state.pinned_inspector.iter().any(|info| info.path == path) {
+            continue;
+        }
+        state.pinned_inspector.push(crate::core::inspector::InspectorInfo::pending(&path));
+        state.pending_inspections.push_back(path);
+        added += 1;
+    }
+    clamp_inspector_selection_and_scroll(state);
+    persist_pins(state);
+
+    let verb = if merge { "Merged" } else { "Loaded" };
+    state.status_message = Some(if missing > 0 {
+        format!("{verb} '{name}': {added} pins, {missing} missing")
+    } else {
+        format!("{verb} '{name}': {added} pins")
+    });
+}
+
+// ── Staged deletions ────────────────────────────────────────────
+
+/// Quit, unless deletions are staged — then ask first.
+fn request_quit(state: &mut AppState) {
+    if state.pending_deletions.is_empty() || state.deletion_progress.is_some() {
+        state.should_quit = true;
+    } else {
+        state.active_view = ActiveView::ConfirmQuit;
+    }
+}
+
+/// Add `path` to the deletion queue, or remove it if already staged.
+fn toggle_staged_deletion(state: &mut AppState, path: PathBuf) {
+    if state.deletion_progress.is_some() {
+        state.status_message = Some("Deletion in progress".to_string());
+        return;
+    }
+    if path == state.cwd {
+        state.status_message = Some("Cannot stage the tree root".to_string());
+        return;
+    }
+    if let Some(idx) = state.pending_deletions.iter().position(|p| *p == path) {
+        state.pending_deletions.remove(idx);
+        state.deletions_selected = state
+            .deletions_selected
+            .min(state.pending_deletions.len().saturating_sub(1));
+        state.status_message = Some(format!("Unstaged {}", path.display()));
+        return;
+    }
+    state.pending_deletions.push(path);
+    state.status_message = Some(format!(
+        "{} staged for deletion ({} reclaimable) · {}: review",
+        state.pending_deletions.len(),
+        crate::core::grouping::human_size(reclaimable_bytes(state)),
+        state.config.short_binding(Action::ShowDeletions),
+    ));
+}
+
+/// Known size of a staged path (directory total or file size).
+pub fn staged_size(state: &AppState, path: &Path) -> Option<u64> {
+    state
+        .dir_sizes
+        .get(path)
+        .or_else(|| state.file_sizes.get(path))
+        .copied()
+}
+
+/// Total size freed by the queue.  Entries nested under another staged
+/// directory are already counted by their ancestor.
+pub fn reclaimable_bytes(state: &AppState) -> u64 {
+    state
+        .pending_deletions
+        .iter()
+        .filter(|p| {
+            !state
+                .pending_deletions
+                .iter()
+                .any(|other| other != *p && p.starts_with(other))
+        })
+        .filter_map(|p| staged_size(state, p))
+        .sum()
+}
+
+fn show_deletions_tab(state: &mut AppState) {
+    if state.right_pane_tab == RightPaneTab::Deletions {
+        state.right_pane_tab = state.right_pane_prev_tab;
+        return;
+    }
+    state.right_pane_prev_tab = state.right_pane_tab;
+    state.right_pane_tab = RightPaneTab::Deletions;
+    state.pane_focus = PaneFocus::Inspector;
+}
+
+fn request_commit_deletions(state: &mut AppState) {
+    if state.deletion_progress.is_some() {
+        state.status_message = Some("Deletion in progress".to_string());
+        return;
+    }
+    if state.pending_deletions.is_empty() {
+        state.status_message = Some("No deletions staged".to_string());
+        return;
+    }
+    state.deletion_confirm_input.clear();
+    state.active_view = ActiveView::ConfirmDeletions;
+}
+
+/// Keys for the deletions tab while the right pane is focused.
+fn handle_deletions_key(state: &mut AppState, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Esc => {
+            state.right_pane_tab = state.right_pane_prev_tab;
+            true
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.deletions_selected = state.deletions_selected.saturating_sub(1);
+            true
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.deletions_selected = (state.deletions_selected + 1)
+                .min(state.pending_deletions.len().saturating_sub(1));
+            true
+        }
+        KeyCode::Delete | KeyCode::Backspace | KeyCode::Char('d') => {
+            if let Some(path) = state.pending_deletions.get(state.deletions_selected).cloned() {
+                toggle_staged_deletion(state, path);
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Typed confirmation: only the exact word "yes" commits.
+fn handle_confirm_deletions_key(state: &mut AppState, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            state.active_view = ActiveView::Tree;
+            state.status_message = Some("Deletion cancelled".to_string());
+        }
+        KeyCode::Enter => {
+            state.active_view = ActiveView::Tree;
+            if state.deletion_confirm_input.trim() != "yes" {
+                state.status_message = Some("Deletion cancelled (type \"yes\" to confirm)".to_string());
+                return;
+            }
+            let paths = state.pending_deletions.clone();
+            state.deletion_errors.clear();
+            state.deletion_progress = Some((0, paths.len()));
+            state.deletion_job_requested = Some((paths, state.config.permanent_delete));
+        }
+        KeyCode::Backspace => {
+            state.deletion_confirm_input.pop();
+        }
+        KeyCode::Char(ch) if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT => {
+            state.deletion_confirm_input.push(ch);
+        }
+        _ => {}
+    }
+}
+
+// ── Go to path ──────────────────────────────────────────────────
+
+fn handle_path_input_key(state: &mut AppState, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            state.active_view = ActiveView::Tree;
+            state.path_completions.clear();
+            state.status_message = state.path_input_saved_status.take();
+        }
+        KeyCode::Enter => {
+            state.active_view = ActiveView::Tree;
+            state.path_completions.clear();
+            state.path_input_saved_status = None;
+            let input = std::mem::take(&mut state.path_input);
+            let target = super::input::resolve_typed_path(input.trim(), &state.cwd);
+            go_to_path(state, &target);
+        }
+        KeyCode::Tab => {
+            let (completed, candidates) = super::input::complete_path(&state.path_input, &state.cwd);
+            state.path_input = completed;
+            state.path_completions = candidates;
+        }
+        KeyCode::Backspace => {
+            state.path_input.pop();
+            state.path_completions.clear();
+        }
+        KeyCode::Char(ch) if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT => {
+            state.path_input.push(ch);
+            state.path_completions.clear();
+        }
+        _ => {}
+    }
+}
+
+/// Select `target` in the tree, moving the root up to a common ancestor
+/// when it lies outside the current tree.
+fn go_to_path(state: &mut AppState, target: &Path) {
+    match target.canonicalize() {
+        Ok(path) if path == state.cwd => select_root_row(state),
+        Ok(path) => reveal_path_in_tree(state, &path),
+        Err(e) => {
+            state.status_message = Some(format!("Go to {}: {e}", target.display()));
+        }
+    }
+}
+
+fn select_root_row(state: &mut AppState) {
+    let root = state.tree.root;
+    if let Some(row) = build_rows(state)
+        .iter()
+        .position(|row| matches!(row, TreeRow::Node { node_id, .. } if *node_id == root))
+    {
+        state.tree_state.selected = row;
+    }
+}
+
+// ── Rename / new directory ──────────────────────────────────────
+
+fn handle_name_prompt_key(state: &mut AppState, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            state.active_view = ActiveView::Tree;
+            state.rename_target = None;
+            state.new_dir_parent = None;
+        }
+        KeyCode::Enter => {
+            let view = std::mem::replace(&mut state.active_view, ActiveView::Tree);
+            let name = std::mem::take(&mut state.name_input);
+            request_git_status(state);
+            if view == ActiveView::Rename {
+                if let Some(old) = state.rename_target.take() {
+                    match rename_entry(state, &old, name.trim()) {
+                        Ok(Some(new)) => {
+                            state.status_message = Some(format!("Renamed to {}", new.display()));
+                        }
+                        Ok(None) => {}
+                        Err(e) => state.status_message = Some(format!("Rename failed: {e}")),
+                    }
+                }
+            } else if let Some(parent) = state.new_dir_parent.take() {
+                match create_dir(state, &parent, name.trim()) {
+                    Ok(path) => state.status_message = Some(format!("Created {}", path.display())),
+                    Err(e) => state.status_message = Some(format!("Create failed: {e}")),
+                }
+            }
+        }
+        KeyCode::Backspace => {
+            state.name_input.pop();
+        }
+        KeyCode::Char(ch) if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT => {
+            state.name_input.push(ch);
+        }
+        _ => {}
+    }
+}
+
+/// Rename `old` to `name` within its directory and patch the tree and every
+/// path-keyed cache.  `Ok(None)` when the name is unchanged.
+fn rename_entry(state: &mut AppState, old: &Path, name: &str) -> Result<Option<PathBuf>, String> {
+    validate_entry_name(name)?;
+    let Some(parent) = old.parent() else {
+        return Err("no parent directory".to_string());
+    };
+    let new = parent.join(name);
+    if new == old {
+        return Ok(None);
+    }
+    // A case-only rename on a case-insensitive filesystem finds `old`
+    // itself at `new`; anything else there would be overwritten.
+    if let Ok(target) = std::fs::symlink_metadata(&new) {
+        let same = std::fs::symlink_metadata(old).is_ok_and(|meta| crate::core::size::is_same_file(&meta, &target));
+        if !same {
+            return Err(format!("'{name}' already exists"));
+        }
+    }
+    std::fs::rename(old, &new).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => "permission denied".to_string(),
+        _ => e.to_string(),
+    })?;
+
+    apply_path_rename(state, old, &new);
+    Ok(Some(new))
+}
+
+/// Move `old` (and everything below it) to `new` within the same
+/// directory in the tree and every path-keyed cache: sizes, staged
+/// deletions, expanded groups, pins and history selections.  Expanded
+/// groups of the parent whose label no longer exists after the rename are
+/// dropped, so a later group with that label doesn't open by itself.
+pub fn apply_path_rename(state: &mut AppState, old: &Path, new: &Path) {
+    if let Some(id) = state.tree.nodes.iter().position(|n| n.meta.path == old) {
+        state.tree.rename_node(id, new.to_path_buf());
+    }
+    let remap = |path: &Path| path.strip_prefix(old).ok().map(|rel| new.join(rel));
+    fn remap_keys<V>(map: &mut HashMap<PathBuf, V>, remap: impl Fn(&Path) -> Option<PathBuf>) {
+        let moved: Vec<PathBuf> = map.keys().filter(|p| remap(p).is_some()).cloned().collect();
+        for path in moved {
+            if let (Some(value), Some(to)) = (map.remove(&path), remap(&path)) {
+                map.insert(to, value);
+            }
+        }
+    }
+    remap_keys(&mut state.dir_sizes, remap);
+    remap_keys(&mut state.dir_sizes_alt, remap);
+    remap_keys(&mut state.dir_apparent_sizes, remap);
+    remap_keys(&mut state.dir_entry_counts, remap);
+    remap_keys(&mut state.file_sizes, remap);
+    remap_keys(&mut state.file_sizes_alt, remap);
+    remap_keys(&mut state.dir_local_sums, remap);
+    state.stale_sizes = state
+        .stale_sizes
+        .drain()
+        .map(|p| remap(&p).unwrap_or(p))
+        .collect();
+    state.dir_excluded = state
+        .dir_excluded
+        .drain()
+        .map(|p| remap(&p).unwrap_or(p))
+        .collect();
+    for path in &mut state.pending_deletions {
+        if let Some(to) = remap(path) {
+            *path = to;
+        }
+    }
+    state.selected_nodes = state
+        .selected_nodes
+        .drain()
+        .map(|p| remap(&p).unwrap_or(p))
+        .collect();
+    // Group keys are "<dir>:<label>".
+    state.expanded_groups = state
+        .expanded_groups
+        .drain()
+        .map(|key| match key.rsplit_once(':') {
+            Some((dir, label)) => match remap(Path::new(dir)) {
+                Some(to) => format!("{}:{label}", to.display()),
+                None => key,
+            },
+            None => key,
+        })
+        .collect();
+
+    let mut pins_moved = false;
+    for idx in 0..state.pinned_inspector.len() {
+        if let Some(to) = remap(&state.pinned_inspector[idx].path) {
+            state.pinned_inspector[idx] = InspectorInfo::pending(&to);
+            state.pending_inspections.push_back(to);
+            pins_moved = true;
+        }
+    }
+    if pins_moved {
+        persist_pins(state);
+    }
+    for (dir, batch) in std::mem::take(&mut state.bulk_pins) {
+        let batch = batch.into_iter().map(|p| remap(&p).unwrap_or(p)).collect();
+        state.bulk_pins.insert(remap(&dir).unwrap_or(dir), batch);
+    }
+    for (_, selected) in &mut state.root_history {
+        if let Some(to) = selected.as_deref().and_then(remap) {
+            *selected = Some(to);
+        }
+    }
+    if state.inspector_path.as_deref().is_some_and(|p| p.starts_with(old)) {
+        // Re-inspect under the new name on the next frame.
+        state.inspector_path = None;
+    }
+    if let Some(parent) = new.parent() {
+        prune_expanded_groups(state, parent);
+    }
+    state.search_reindex_requested = true;
+}
+
+/// Forget expanded groups of `dir` whose label its children no longer
+/// produce.
+fn prune_expanded_groups(state: &mut AppState, dir: &Path) {
+    let Some(dir_id) = state.tree.nodes.iter().position(|n| n.meta.path == dir) else {
+        return;
+    };
+    let labels: HashSet<String> = crate::core::grouping::group_children(
+        &state.tree,
+        dir_id,
+        &state.grouping_config,
+        Some(&state.file_sizes),
+    )
+    .into_iter()
+    .filter_map(|entry| match entry {
+        crate::core::grouping::GroupedEntry::Group { label, .. } => Some(label),
+        crate::core::grouping::GroupedEntry::Single(_) => None,
+    })
+    .collect();
+    let prefix = format!("{}:", dir.display());
+    // A ':' left after the prefix means the key belongs to a sibling
+    // whose own name starts with "<dir>:".
+    state.expanded_groups.retain(|key| {
+        key.strip_prefix(&prefix)
+            .is_none_or(|label| label.contains(':') || labels.contains(label))
+    });
+}
+
+/// A same-directory rename reported by the watcher.  Entries the tree
+/// already shows under the new name (an in-app rename) are left alone;
+/// moves across directories are picked up when both are re-listed.
+pub fn note_path_renamed(state: &mut AppState, from: PathBuf, to: PathBuf) {
+    if from.parent() != to.parent() || !from.starts_with(&state.cwd) {
+        return;
+    }
+    let known = |path: &Path| state.tree.nodes.iter().any(|n| n.meta.path == path);
+    if known(&from) && !known(&to) {
+        apply_path_rename(state, &from, &to);
+    }
+}
+
+/// Create directory `name` inside `parent`, add it to the tree (expanding
+/// the parent if needed) and select it.
+fn create_dir(state: &mut AppState, parent: &Path, name: &str) -> Result<PathBuf, String> {
+    validate_entry_name(name)?;
+    let path = parent.join(name);
+    std::fs::create_dir(&path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::AlreadyExists => format!("'{name}' already exists"),
+        std::io::ErrorKind::PermissionDenied => "permission denied".to_string(),
+        _ => e.to_string(),
+    })?;
+
+    if let Some(parent_id) = state.tree.nodes.iter().position(|n| n.meta.path == parent) {
+        // An unloaded parent picks the new directory up when it is listed.
+        if !state.tree.get(parent_id).children.is_empty() {
+            if let Ok(meta) = crate::core::tree::EntryMeta::from_path(&path) {
+                state.tree.insert_child_sorted(parent_id, meta);
+            }
+        }
+        state.needs_size_recompute = true;
+    }
+    state.search_reindex_requested = true;
+    reveal_path_in_tree(state, &path);
+    Ok(path)
+}
+
+/// Reject names that would escape the directory or that no filesystem takes.
+fn validate_entry_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\0']) {
+        return Err(format!("invalid name '{name}'"));
+    }
+    Ok(())
+}
+
+/// Ask before deleting the selected entry right away.
+fn request_delete(state: &mut AppState) {
+    if state.delete_job_requested.is_some() {
+        state.status_message = Some("Deletion in progress".to_string());
+        return;
+    }
+    let Some(path) = selected_node_path(state) else {
+        return;
+    };
+    if path == state.cwd {
+        state.status_message = Some("Cannot delete the tree root".to_string());
+        return;
+    }
+    state.delete_target = Some(path);
+    state.active_view = ActiveView::ConfirmDelete;
+}
+
+fn handle_confirm_delete_key(state: &mut AppState, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+            state.active_view = ActiveView::Tree;
+            if let Some(path) = state.delete_target.take() {
+                state.status_message = Some(format!("Deleting {}", path.display()));
+                state.delete_job_requested = Some((path, state.config.permanent_delete));
+            }
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            state.active_view = ActiveView::Tree;
+            state.delete_target = None;
+            state.status_message = Some("Deletion cancelled".to_string());
+        }
+        _ => {}
+    }
+}
+
+/// Drop a deleted entry from the tree and invalidate the sizes that
+/// included it, then recompute.
+pub fn apply_removed(state: &mut AppState, path: PathBuf, result: Result<(), String>) {
+    if let Err(e) = result {
+        state.status_message = Some(format!("Delete failed: {e}"));
+        return;
+    }
+
+    forget_path(state, &path);
+    state.status_message = Some(format!("Deleted {}", path.display()));
+}
+
+/// Drop `path` and everything below it from the tree, the size maps,
+/// staged deletions and pins.  A plain file's bytes are taken off its
+/// ancestors directly; anything else marks their sizes stale.
+fn forget_path(state: &mut AppState, path: &Path) {
+    let sizes_adjusted = crate::app::size_runtime::adjust_for_removal(state, path);
+    let top = match build_rows(state).get(state.tree_state.offset) {
+        Some(TreeRow::Node { node_id, .. }) => Some(state.tree.nodes[*node_id].meta.path.clone()),
+        _ => None,
+    };
+    preserving_selection(state, |state| {
+        if let Some(id) = state.tree.nodes.iter().position(|n| n.meta.path == path) {
+            state.tree.remove_subtree(id);
+        }
+    });
+    let rows = build_rows(state);
+    // Keep the first visible row in place when rows above it went away.
+    if let Some(idx) = top.and_then(|top| {
+        rows.iter().position(|row| {
+            matches!(row, TreeRow::Node { node_id, .. } if state.tree.nodes[*node_id].meta.path == top)
+        })
+    }) {
+        state.tree_state.offset = idx;
+    }
+    state.tree_state.selected = state.tree_state.selected.min(rows.len().saturating_sub(1));
+    state.tree_state.offset = state.tree_state.offset.min(state.tree_state.selected);
+    state.last_left_click = None;
+
+    state.dir_sizes.retain(|p, _| !p.starts_with(path));
+    state.dir_sizes_alt.retain(|p, _| !p.starts_with(path));
+    state.dir_apparent_sizes.retain(|p, _| !p.starts_with(path));
+    state.dir_entry_counts.retain(|p, _| !p.starts_with(path));
+    state.dir_excluded.retain(|p| !p.starts_with(path));
+    state.file_sizes.retain(|p, _| !p.starts_with(path));
+    state.file_sizes_alt.retain(|p, _| !p.starts_with(path));
+    state.stale_sizes.retain(|p| !p.starts_with(path));
+    state.dir_local_sums.retain(|p, _| !p.starts_with(path));
+    state.expanded_groups.retain(|key| {
+        !key.rsplit_once(':')
+            .is_some_and(|(dir, _)| Path::new(dir).starts_with(path))
+    });
+    if !sizes_adjusted {
+        // The parent's local sum counted the entry; ancestors' totals did too.
+        match path.parent() {
+            Some(parent) => invalidate_local_size(state, parent),
+            None => state.needs_size_recompute = true,
+        }
+    }
+
+    state.pending_deletions.retain(|p| !p.starts_with(path));
+    state.selected_nodes.retain(|p| !p.starts_with(path));
+    let pins_before = state.pinned_inspector.len();
+    state.pinned_inspector.retain(|info| !info.path.starts_with(path));
+    if state.pinned_inspector.len() != pins_before {
+        clamp_inspector_selection_and_scroll(state);
+        persist_pins(state);
+    }
+}
+
+/// Record a change reported by the filesystem watcher.  The directory that
+/// holds `path` is rescanned once the debounce window closes.
+pub fn note_path_changed(state: &mut AppState, path: PathBuf) {
+    let Some(dir) = path.parent() else {
+        return;
+    };
+    if !dir.starts_with(&state.cwd) {
+        return;
+    }
+    let now = Instant::now();
+    let rate = state.watch_event_rate.entry(dir.to_path_buf()).or_insert((now, 0));
+    if rate.0.elapsed() >= super::watch_runtime::WATCH_STORM_WINDOW {
+        *rate = (now, 0);
+    }
+    rate.1 += 1;
+    state.watch_changed.entry(dir.to_path_buf()).or_default().insert(path);
+    state.watch_changed_at.get_or_insert(now);
+    request_git_status(state);
+}
+
+/// Ask for a fresh `git status`.  Runs once requests stop arriving for
+/// [`super::fs_runtime::GIT_STATUS_DEBOUNCE`], so a burst of changes costs one run.
+pub fn request_git_status(state: &mut AppState) {
+    if state.walk_config.show_git_status {
+        state.git_status_requested_at = Some(Instant::now());
+    }
+}
+
+/// Swap in the statuses from a finished `git status` run.
+pub fn apply_git_status(
+    state: &mut AppState,
+    generation: u64,
+    statuses: HashMap<PathBuf, crate::core::git::GitFileStatus>,
+) {
+    if state.git_status_in_flight != Some(generation) {
+        return;
+    }
+    state.git_status_in_flight = None;
+    if state.walk_config.show_git_status {
+        state.git_statuses = statuses;
+    }
+}
+
+/// Queue a rescan of every directory changed in the last debounce window.
+/// Directories that were never listed are skipped; expanding them lists
+/// them fresh anyway.  Sizes are patched per changed file, or the
+/// directory's sizes invalidated when that can't be done exactly or the
+/// directory is in a storm of events.
+pub fn flush_watch_changes(state: &mut AppState) {
+    state.watch_changed_at = None;
+    for (dir, paths) in std::mem::take(&mut state.watch_changed) {
+        let loaded = state
+            .tree
+            .nodes
+            .iter()
+            .find(|n| n.meta.path == dir)
+            .is_some_and(|n| n.parent.is_none() || n.expanded || !n.children.is_empty());
+        if !loaded {
+            continue;
+        }
+        let storming = state
+            .watch_event_rate
+            .get(&dir)
+            .is_some_and(|&(_, events)| events > super::watch_runtime::WATCH_STORM_EVENTS);
+        if !storming
+            && paths
+                .iter()
+                .all(|path| crate::app::size_runtime::adjust_for_change(state, path))
+        {
+            state.watch_sized_dirs.insert(dir.clone());
+        } else {
+            state.watch_sized_dirs.remove(&dir);
+            invalidate_local_size(state, &dir);
+        }
+        if !state.pending_rescan_paths.contains(&dir) {
+            state.pending_rescan_paths.push_back(dir);
+        }
+    }
+    state
+        .watch_event_rate
+        .retain(|_, (start, _)| start.elapsed() < super::watch_runtime::WATCH_STORM_WINDOW);
+}
+
+/// Apply a rescanned directory listing, then keep a manual refresh's
+/// selection in place.
+pub fn apply_dir_rescanned(state: &mut AppState, path: PathBuf, children: Vec<crate::core::tree::EntryMeta>) {
+    let refreshing = state.refresh_pending.remove(&path);
+    if refreshing {
+        state.watch_sized_dirs.remove(&path);
+    }
+    reconcile_dir(state, path, children);
+    if refreshing {
+        restore_refresh_selection(state);
+    }
+}
+
+/// Reselect the path selected when the refresh started, or its nearest
+/// ancestor still in the tree.
+fn restore_refresh_selection(state: &mut AppState) {
+    let Some(target) = state.refresh_select.clone() else {
+        return;
+    };
+    let rows = build_rows(state);
+    let row_of = |path: &Path| {
+        rows.iter().position(|row| {
+            matches!(row, TreeRow::Node { node_id, .. } if state.tree.get(*node_id).meta.path == path)
+        })
+    };
+    if let Some(idx) = target.ancestors().find_map(row_of) {
+        state.tree_state.selected = idx;
+    }
+    if state.refresh_pending.is_empty() {
+        state.refresh_select = None;
+        state.status_message = Some("Refresh finished".to_string());
+    }
+}
+
+/// Reconcile a rescanned directory's children with the tree: entries that
+/// are gone are forgotten, new ones inserted, the rest keep their subtree
+/// and expansion and only refresh their metadata.
+fn reconcile_dir(state: &mut AppState, path: PathBuf, children: Vec<crate::core::tree::EntryMeta>) {
+    let Some(parent_id) = state.tree.nodes.iter().position(|n| n.meta.path == path) else {
+        return;
+    };
+    let listed: HashMap<PathBuf, crate::core::tree::EntryMeta> =
+        children.into_iter().map(|m| (m.path.clone(), m)).collect();
+    let gone: Vec<PathBuf> = state
+        .tree
+        .get(parent_id)
+        .children
+        .iter()
+        .map(|&c| state.tree.get(c).meta.path.clone())
+        .filter(|p| !listed.contains_key(p))
+        .collect();
+    let changed = !gone.is_empty();
+    for child in &gone {
+        forget_path(state, child);
+    }
+
+    // Ids shift when subtrees are removed; look the parent up again.
+    let Some(parent_id) = state.tree.nodes.iter().position(|n| n.meta.path == path) else {
+        return;
+    };
+    let mut added = false;
+    preserving_selection(state, |state| {
+        let existing: HashMap<PathBuf, NodeId> = state
+            .tree
+            .get(parent_id)
+            .children
+            .iter()
+            .map(|&c| (state.tree.get(c).meta.path.clone(), c))
+            .collect();
+        for (child_path, meta) in listed {
+            match existing.get(&child_path) {
+                Some(&id) => {
+                    let node = &mut state.tree.get_mut(id).meta;
+                    node.size = meta.size;
+                    node.modified = meta.modified;
+                    node.symlink_target = meta.symlink_target;
+                }
+                None => {
+                    state.tree.insert_child_sorted(parent_id, meta);
+                    added = true;
+                }
+            }
+        }
+    });
+
+    // The directory's own files may have changed size even if none came
+    // or went, so its local sum is recomputed unless the watcher already
+    // patched it.
+    if !state.watch_sized_dirs.remove(&path) {
+        invalidate_local_size(state, &path);
+    }
+    if changed || added {
+        state.search_reindex_requested = true;
+    }
+}
+
+/// Drop `dir`'s cached local sum and mark it and its ancestors stale, so
+/// the next scan re-walks just that directory.
+fn invalidate_local_size(state: &mut AppState, dir: &Path) {
+    state.dir_local_sums.remove(dir);
+    let stale: Vec<PathBuf> = state
+        .dir_sizes
+        .keys()
+        .filter(|p| dir.starts_with(p))
+        .cloned()
+        .collect();
+    state.stale_sizes.extend(stale);
+    state.needs_size_recompute = true;
+}
+
+fn handle_confirm_quit_key(state: &mut AppState, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => state.should_quit = true,
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            state.active_view = ActiveView::Tree;
+        }
+        _ => {}
+    }
+}
+
+/// Apply one finished deletion from the background job.
+pub fn apply_deletion_result(state: &mut AppState, path: PathBuf, result: Result<(), String>) {
+    if let Some((done, _)) = state.deletion_progress.as_mut() {
+        *done += 1;
+    }
+    match result {
+        Ok(()) => {
+            state.pending_deletions.retain(|p| *p != path);
+            state.pinned_inspector.retain(|info| !info.path.starts_with(&path));
+        }
+        Err(e) => state.deletion_errors.push((path, e)),
+    }
+}
+
+/// Wrap up a deletion job: report, persist pins, and rescan the tree.
+pub fn finish_deletions(state: &mut AppState) {
+    let done = state.deletion_progress.take().map(|(d, _)| d).unwrap_or(0);
+    let failed = state.deletion_errors.len();
+    state.deletions_selected = state
+        .deletions_selected
+        .min(state.pending_deletions.len().saturating_sub(1));
+    state.status_message = Some(if failed > 0 {
+        format!("Deleted {} items, {failed} failed", done - failed)
+    } else {
+        format!("Deleted {done} items")
+    });
+    clamp_inspector_selection_and_scroll(state);
+    persist_pins(state);
+    rebuild_tree(state);
+}
+
+// ── Mouse ───────────────────────────────────────────────────────
+
+/// Process a mouse event.
+pub fn handle_mouse(state: &mut AppState, mouse: MouseEvent) {
+    // Events already queued when capture was switched off.
+    if !state.mouse_enabled {
+        return;
+    }
+    if state.active_view == ActiveView::Lightbox {
+        handle_lightbox_mouse(state, mouse);
+        return;
+    }
+    if state.active_view != ActiveView::Tree {
+        return;
+    }
+
+    let layout = AppLayout::from_area(
+        state.terminal_area,
+        state.config.panel_layout,
+        state.config.panel_split_pct,
+        state.config.status_lines,
+    );
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if layout.is_on_splitter(mouse.column, mouse.row) {
+                state.dragging_splitter = true;
+                return;
+            }
+            state.dragging_splitter = false;
+
+            if let Some(&(_, badge)) = state
+                .badge_hit_zones
+                .iter()
+                .find(|(rect, _)| point_in_rect(*rect, mouse.column, mouse.row))
+            {
+                toggle_badge(state, badge);
+                return;
             }
-            state.dragging_splitter = false;
 
             if point_in_rect(layout.inspector_area, mouse.column, mouse.row) {
                 state.pane_focus = PaneFocus::Inspector;
@@ -411,6 +2087,10 @@ pub fn handle_mouse(state: &mut AppState, mouse: MouseEvent) {
                 return;
             }
             state.pane_focus = PaneFocus::Tree;
+            if scrub_tree_scrollbar(state, &layout, mouse.column, mouse.row) {
+                state.dragging_tree_scrollbar = true;
+                return;
+            }
             let tree_content_top = layout.tree_area.y.saturating_add(1);
             let tree_content_bottom = layout
                 .tree_area
@@ -476,6 +2156,10 @@ pub fn handle_mouse(state: &mut AppState, mouse: MouseEvent) {
                 }
             }
         }
+        MouseEventKind::Drag(MouseButton::Left) if state.dragging_tree_scrollbar => {
+            let right = layout.tree_area.right().saturating_sub(1);
+            scrub_tree_scrollbar(state, &layout, right, mouse.row);
+        }
         MouseEventKind::Drag(MouseButton::Left) => {
             if state.dragging_splitter {
                 if let Some(pct) = layout.split_pct_from_pointer(mouse.column, mouse.row) {
@@ -486,6 +2170,7 @@ pub fn handle_mouse(state: &mut AppState, mouse: MouseEvent) {
         }
         MouseEventKind::Up(MouseButton::Left) => {
             state.dragging_splitter = false;
+            state.dragging_tree_scrollbar = false;
         }
         MouseEventKind::ScrollUp => {
             if point_in_rect(layout.inspector_area, mouse.column, mouse.row)
@@ -495,16 +2180,23 @@ pub fn handle_mouse(state: &mut AppState, mouse: MouseEvent) {
                     if state.search_selected > 0 {
                         state.search_selected -= 1;
                         clamp_search_selection_and_scroll(state);
-                        reveal_selected_search_in_tree(state);
+                        schedule_search_reveal(state);
                     }
                     return;
                 }
+                if state.right_pane_tab == RightPaneTab::Deletions {
+                    state.deletions_selected = state.deletions_selected.saturating_sub(1);
+                    return;
+                }
+                if scroll_archive_at(state, layout.inspector_area, mouse.column, mouse.row, false) {
+                    return;
+                }
                 if state.inspector_pin_scroll > 0 {
                     state.inspector_pin_scroll -= 1;
                 }
                 return;
             }
-            state.tree_state.select_prev();
+            scroll_tree(state, -1);
         }
         MouseEventKind::ScrollDown => {
             if point_in_rect(layout.inspector_area, mouse.column, mouse.row)
@@ -514,17 +2206,24 @@ pub fn handle_mouse(state: &mut AppState, mouse: MouseEvent) {
                     if state.search_selected + 1 < state.search_results.len() {
                         state.search_selected += 1;
                         clamp_search_selection_and_scroll(state);
-                        reveal_selected_search_in_tree(state);
+                        schedule_search_reveal(state);
                     }
                     return;
                 }
+                if state.right_pane_tab == RightPaneTab::Deletions {
+                    state.deletions_selected = (state.deletions_selected + 1)
+                        .min(state.pending_deletions.len().saturating_sub(1));
+                    return;
+                }
+                if scroll_archive_at(state, layout.inspector_area, mouse.column, mouse.row, true) {
+                    return;
+                }
                 let geom = inspector_geom(state);
                 state.inspector_pin_scroll =
                     (state.inspector_pin_scroll + 1).min(geom.max_scroll);
                 return;
             }
-            let visible_count = build_rows(state).len();
-            state.tree_state.select_next(visible_count);
+            scroll_tree(state, 1);
         }
         _ => {}
     }
@@ -723,9 +2422,79 @@ fn reveal_path_in_tree(state: &mut AppState, target: &std::path::Path) {
     }
 }
 
-fn build_rows(state: &AppState) -> Vec<TreeRow> {
+/// Add the selected row to the multi-selection, or take it out.  A group
+/// row toggles all its members: they are all added unless all already are.
+fn toggle_select(state: &mut AppState) {
+    let rows = build_rows(state);
+    let paths: Vec<PathBuf> = match rows.get(state.tree_state.selected) {
+        Some(TreeRow::Node { node_id, .. }) => vec![state.tree.get(*node_id).meta.path.clone()],
+        Some(TreeRow::Group { members, .. }) => members
+            .iter()
+            .map(|&id| state.tree.get(id).meta.path.clone())
+            .collect(),
+        None => return,
+    };
+    if paths.iter().all(|p| state.selected_nodes.contains(p)) {
+        for path in &paths {
+            state.selected_nodes.remove(path);
+        }
+    } else {
+        state.selected_nodes.extend(paths);
+    }
+    state.status_message = Some(selection_summary(state));
+}
+
+fn clear_selection(state: &mut AppState) {
+    state.selected_nodes.clear();
+    state.status_message = Some("Selection cleared".to_string());
+}
+
+/// `"3 selected"`.
+pub fn selection_summary(state: &AppState) -> String {
+    format!("{} selected", state.selected_nodes.len())
+}
+
+/// Jump the tree view so the scrollbar thumb sits at `row`, when
+/// `(col, row)` is on the tree's scrollbar.  Returns whether it was.
+fn scrub_tree_scrollbar(state: &mut AppState, layout: &AppLayout, col: u16, row: u16) -> bool {
+    let inner = ratatui::widgets::Block::bordered().inner(layout.tree_area);
+    let track = crate::ui::tree_widget::scrollbar_area(layout.tree_area, inner);
+    let rows = build_rows(state).len();
+    let height = inner.height as usize;
+    let on_track = col == track.right().saturating_sub(1) && row >= track.y && row < track.bottom();
+    if state.a11y || rows <= height || !on_track {
+        return false;
+    }
+    let offset = crate::ui::scrollbar::offset_at(track, rows, height, row);
+    let keep = state.config.wheel_keeps_selection;
+    state.tree_state.scroll_to(offset, height, rows, keep);
+    true
+}
+
+/// Scroll the tree view one wheel notch in `direction` (±1).
+fn scroll_tree(state: &mut AppState, direction: isize) {
+    let step = state.config.scroll_lines as isize;
+    let rows = build_rows(state).len();
+    let keep = state.config.wheel_keeps_selection;
+    state
+        .tree_state
+        .scroll_view(direction * step, state.tree_viewport_rows, rows, keep);
+}
+
+/// Page the tree by `1 / divisor` of its viewport in `direction` (±1).
+fn page_tree(state: &mut AppState, direction: isize, divisor: usize) {
+    let height = state.tree_viewport_rows;
+    let step = (height / divisor).max(1) as isize;
+    let rows = build_rows(state).len();
+    state.tree_state.scroll_page(direction * step, height, rows);
+}
+
+pub fn build_rows(state: &AppState) -> Vec<TreeRow> {
     TreeWidget::new(&state.tree, &state.grouping_config)
+        .dir_sizes(&state.dir_sizes)
+        .file_sizes(&state.file_sizes)
         .expanded_groups(&state.expanded_groups)
+        .sort_mode(state.walk_config.sort_mode)
         .build_rows()
 }
 
@@ -791,6 +2560,9 @@ fn handle_inspector_focus_key(state: &mut AppState, key: KeyEvent) -> bool {
     if state.right_pane_tab == RightPaneTab::Search {
         return handle_search_key(state, key);
     }
+    if state.right_pane_tab == RightPaneTab::Deletions {
+        return handle_deletions_key(state, key);
+    }
 
     match key.code {
         KeyCode::Up | KeyCode::Char('k') => {
@@ -844,15 +2616,114 @@ fn handle_inspector_focus_key(state: &mut AppState, key: KeyEvent) -> bool {
             remove_selected_pin(state);
             true
         }
+        KeyCode::Char('c') => {
+            // The selected pin, or the current selection when nothing is pinned.
+            let index = (state.inspector_selected_pin < state.pinned_inspector.len())
+                .then_some(state.inspector_selected_pin);
+            request_checksum(state, index);
+            true
+        }
         _ => false,
     }
 }
 
+/// Hash pin `index` (or the current selection for `None`) now, whatever
+/// its size.  The hash runs on a background thread; the card reads
+/// "computing…" until it lands.
+fn request_checksum(state: &mut AppState, index: Option<usize>) {
+    let info = match index {
+        Some(i) => state.pinned_inspector.get_mut(i),
+        None => state.inspector_info.as_mut(),
+    };
+    let Some(info) = info else {
+        return;
+    };
+    let message = if info.kind != "File" {
+        "Only files have a SHA-256".to_string()
+    } else if info.checksum.is_some() || info.checksum_pending {
+        return;
+    } else if let Some(e) = &info.error {
+        format!("Can't hash {}: {e}", info.name)
+    } else {
+        info.checksum_pending = true;
+        format!("Computing SHA-256 of {}", info.name)
+    };
+    state.status_message = Some(message);
+}
+
+/// Copy a SHA-256 digest shown in the inspector to the clipboard.
+fn copy_checksum(state: &mut AppState, digest: Option<String>) {
+    let Some(digest) = digest else {
+        state.status_message = Some("No SHA-256 to copy yet".to_string());
+        return;
+    };
+    state.status_message = Some(if integration::copy_text_to_clipboard(&digest) {
+        format!("Copied SHA-256: {digest}")
+    } else {
+        "No clipboard tool found".to_string()
+    });
+}
+
+/// Scroll the "Archive contents" list under the pointer by one member.
+/// Returns `false` when the pointer isn't over one (or it doesn't scroll).
+fn scroll_archive_at(
+    state: &mut AppState,
+    inspector_area: ratatui::layout::Rect,
+    col: u16,
+    row: u16,
+    down: bool,
+) -> bool {
+    if state.right_pane_tab != RightPaneTab::Inspector {
+        return false;
+    }
+    let inner = ratatui::widgets::Block::default()
+        .borders(ratatui::widgets::Borders::ALL)
+        .inner(inspector_area);
+    let geom = pinned_cards_geometry(
+        inner,
+        state.inspector_info.as_ref(),
+        &state.pinned_inspector,
+        state.inspector_pin_scroll,
+    );
+    let over_current = state
+        .inspector_info
+        .as_ref()
+        .and_then(|info| current_archive_rows(inner, info))
+        .is_some_and(|rows| point_in_rect(rows, col, row));
+    let info = if over_current {
+        state.inspector_info.as_mut()
+    } else {
+        let pin_index = geom.cards.iter().find_map(|card| {
+            let pin = &state.pinned_inspector[card.pin_index];
+            card_archive_rows(card.card_rect, pin)
+                .filter(|rows| point_in_rect(*rows, col, row))
+                .map(|_| card.pin_index)
+        });
+        pin_index.and_then(|i| state.pinned_inspector.get_mut(i))
+    };
+    let Some(info) = info else {
+        return false;
+    };
+    let max = max_archive_scroll(info);
+    if max == 0 {
+        return false;
+    }
+    info.archive_scroll = if down {
+        (info.archive_scroll + 1).min(max)
+    } else {
+        info.archive_scroll.saturating_sub(1)
+    };
+    true
+}
+
 fn handle_inspector_click(state: &mut AppState, inspector_area: ratatui::layout::Rect, col: u16, row: u16) {
     if state.right_pane_tab == RightPaneTab::Search {
         handle_search_click(state, inspector_area, row);
         return;
     }
+    if state.right_pane_tab == RightPaneTab::Deletions {
+        return;
+    }
 
     let inner = ratatui::widgets::Block::default()
         .borders(ratatui::widgets::Borders::ALL)
@@ -864,11 +2735,34 @@ fn handle_inspector_click(state: &mut AppState, inspector_area: ratatui::layout:
         state.inspector_pin_scroll,
     );
 
+    if let Some(info) = &state.inspector_info {
+        if current_checksum_row(inner, info) == Some(row) {
+            if info.checksum.is_some() {
+                let digest = info.checksum.clone();
+                copy_checksum(state, digest);
+            } else {
+                request_checksum(state, None);
+            }
+            return;
+        }
+    }
+
     for card in geom.cards {
         if point_in_rect(card.unpin_rect, col, row) {
             remove_pin_at(state, card.pin_index);
             return;
         }
+        let pin = &state.pinned_inspector[card.pin_index];
+        if card_checksum_row(card.card_rect, pin) == Some(row) {
+            let digest = pin.checksum.clone();
+            state.inspector_selected_pin = card.pin_index;
+            if digest.is_some() {
+                copy_checksum(state, digest);
+            } else {
+                request_checksum(state, Some(card.pin_index));
+            }
+            return;
+        }
         if point_in_rect(card.card_rect, col, row) {
             state.inspector_selected_pin = card.pin_index;
             clamp_inspector_selection_and_scroll(state);
@@ -933,6 +2827,7 @@ fn inspector_geom(state: &AppState) -> crate::ui::inspector::PinnedCardsGeometry
         state.terminal_area,
         state.config.panel_layout,
         state.config.panel_split_pct,
+        state.config.status_lines,
     );
     let inner = ratatui::widgets::Block::default()
         .borders(ratatui::widgets::Borders::ALL)
@@ -977,10 +2872,35 @@ fn clamp_inspector_selection_and_scroll(state: &mut AppState) {
     state.inspector_pin_scroll = state.inspector_pin_scroll.min(geom.max_scroll);
 }
 
-fn rebuild_tree(state: &mut AppState) {
+pub fn rebuild_tree(state: &mut AppState) {
     queue_tree_rebuild(state, state.cwd.clone());
 }
 
+/// Deepest walk the depth keys go to; `--depth` may start deeper.
+const MAX_WALK_DEPTH: usize = 10;
+
+/// Walk one level deeper (or shallower) within `1..=MAX_WALK_DEPTH` and
+/// reload the tree at the new depth.
+fn change_walk_depth(state: &mut AppState, deeper: bool) {
+    if state.fixed_tree {
+        rebuild_tree(state);
+        return;
+    }
+    let depth = state.walk_config.max_depth;
+    let new = if deeper {
+        (depth + 1).min(MAX_WALK_DEPTH.max(depth))
+    } else {
+        depth.saturating_sub(1).max(1)
+    };
+    if new == depth {
+        state.status_message = Some(format!("Depth: {depth} (limit)"));
+        return;
+    }
+    state.walk_config.max_depth = new;
+    rebuild_tree(state);
+    state.status_message = Some(format!("Depth: {new}"));
+}
+
 fn move_root_to_parent(state: &mut AppState) {
     let Some(parent) = state.cwd.parent().map(|p| p.to_path_buf()) else {
         state.status_message = Some("Already at filesystem root".to_string());
@@ -1046,7 +2966,7 @@ fn handle_search_key(state: &mut AppState, key: KeyEvent) -> bool {
             if state.search_selected > 0 {
                 state.search_selected -= 1;
                 clamp_search_selection_and_scroll(state);
-                reveal_selected_search_in_tree(state);
+                schedule_search_reveal(state);
             }
             true
         }
@@ -1054,7 +2974,7 @@ fn handle_search_key(state: &mut AppState, key: KeyEvent) -> bool {
             if state.search_selected + 1 < state.search_results.len() {
                 state.search_selected += 1;
                 clamp_search_selection_and_scroll(state);
-                reveal_selected_search_in_tree(state);
+                schedule_search_reveal(state);
             }
             true
         }
@@ -1062,7 +2982,7 @@ fn handle_search_key(state: &mut AppState, key: KeyEvent) -> bool {
             if !state.search_results.is_empty() {
                 state.search_selected = 0;
                 clamp_search_selection_and_scroll(state);
-                reveal_selected_search_in_tree(state);
+                schedule_search_reveal(state);
             }
             true
         }
@@ -1070,7 +2990,7 @@ fn handle_search_key(state: &mut AppState, key: KeyEvent) -> bool {
             if !state.search_results.is_empty() {
                 state.search_selected = state.search_results.len() - 1;
                 clamp_search_selection_and_scroll(state);
-                reveal_selected_search_in_tree(state);
+                schedule_search_reveal(state);
             }
             true
         }
@@ -1081,7 +3001,38 @@ fn handle_search_key(state: &mut AppState, key: KeyEvent) -> bool {
             true
         }
         KeyCode::Char('c') if key.modifiers == KeyModifiers::ALT => {
-            state.search_case_sensitive = !state.search_case_sensitive;
+            let mut options = state.search_options;
+            options.case_sensitive = !options.case_sensitive;
+            set_search_options(state, options);
+            reveal_selected_search_in_tree(state);
+            true
+        }
+        KeyCode::Char('h') if key.modifiers == KeyModifiers::ALT => {
+            let mut options = state.search_options;
+            options.include_hidden = !options.include_hidden;
+            set_search_options(state, options);
+            true
+        }
+        KeyCode::Char('g') if key.modifiers == KeyModifiers::ALT => {
+            let mut options = state.search_options;
+            options.respect_gitignore = !options.respect_gitignore;
+            set_search_options(state, options);
+            true
+        }
+        KeyCode::Char('o') if key.modifiers == KeyModifiers::ALT => {
+            if let Some(result) = state.search_results.get(state.search_selected).cloned() {
+                open_with_system(state, &result.path);
+            }
+            true
+        }
+        KeyCode::Char(ch @ ('y' | 'Y')) if key.modifiers.contains(KeyModifiers::ALT) => {
+            if let Some(result) = state.search_results.get(state.search_selected).cloned() {
+                copy_path(state, &result.path, ch == 'Y');
+            }
+            true
+        }
+        KeyCode::Char('m') if key.modifiers == KeyModifiers::ALT => {
+            state.search_mode = state.search_mode.next();
             refresh_search_results(state);
             reveal_selected_search_in_tree(state);
             true
@@ -1120,61 +3071,486 @@ fn ensure_search_index(state: &mut AppState) {
     }
 }
 
-fn refresh_search_results(state: &mut AppState) {
-    ensure_search_index(state);
-    state.search_results = crate::core::search::search_entries(
-        &state.search_index,
-        &state.search_query,
-        state.search_case_sensitive,
-        300,
-    );
-    if state.search_results.is_empty() {
-        state.search_selected = 0;
-    } else {
-        state.search_selected = state.search_selected.min(state.search_results.len() - 1);
+fn refresh_search_results(state: &mut AppState) {
+    ensure_search_index(state);
+    match crate::core::search::search_entries(
+        &state.search_index,
+        &state.search_query,
+        state.search_options.case_sensitive,
+        state.search_mode,
+        300,
+    ) {
+        Ok(results) => {
+            state.search_results = results;
+            state.search_error = None;
+        }
+        Err(e) => {
+            state.search_results.clear();
+            state.search_error = Some(e);
+        }
+    }
+    if state.search_results.is_empty() {
+        state.search_selected = 0;
+    } else {
+        state.search_selected = state.search_selected.min(state.search_results.len() - 1);
+    }
+    clamp_search_selection_and_scroll(state);
+}
+
+/// Apply and persist search options.  Changes to what is indexed rebuild
+/// the index in the background; results refresh when it arrives.
+pub fn set_search_options(state: &mut AppState, options: SearchOptions) {
+    let old = std::mem::replace(&mut state.search_options, options);
+    if old.include_hidden != options.include_hidden
+        || old.respect_gitignore != options.respect_gitignore
+    {
+        state.search_reindex_requested = true;
+    }
+    refresh_search_results(state);
+    state.config.search = options;
+    let _ = state.config.save();
+}
+
+/// Recompute ranked search results using the current in-memory search index.
+pub fn refresh_search(state: &mut AppState) {
+    refresh_search_results(state);
+}
+
+fn reveal_selected_search_in_tree(state: &mut AppState) {
+    state.search_reveal_due = None;
+    if state.search_results.is_empty() {
+        return;
+    }
+    if let Some(path) = state.search_results.get(state.search_selected).map(|r| r.path.clone()) {
+        reveal_path_in_tree(state, &path);
+    }
+}
+
+/// How long search navigation must pause before the tree follows, so
+/// holding an arrow key doesn't expand (or re-root) for every result.
+const SEARCH_REVEAL_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(150);
+
+/// Follow the selected search result in the tree once navigation
+/// settles.  A result that is already a visible row is selected at once.
+fn schedule_search_reveal(state: &mut AppState) {
+    state.search_reveal_due = None;
+    let Some(path) = state.search_results.get(state.search_selected).map(|r| r.path.clone()) else {
+        return;
+    };
+    let visible = build_rows(state).iter().position(|row| {
+        matches!(row, TreeRow::Node { node_id, .. } if state.tree.get(*node_id).meta.path == path)
+    });
+    match visible {
+        Some(row) => {
+            state.tree_state.selected = row;
+            state.pending_reveal_path = None;
+        }
+        None => state.search_reveal_due = Some(Instant::now() + SEARCH_REVEAL_DEBOUNCE),
+    }
+}
+
+/// Run a scheduled search reveal once it is due (polled by the main loop).
+pub fn run_due_search_reveal(state: &mut AppState) {
+    if state.search_reveal_due.is_some_and(|due| due <= Instant::now()) {
+        reveal_selected_search_in_tree(state);
+    }
+}
+
+pub fn toggle_pin_for_path(state: &mut AppState, path: &Path) {
+    // Already pinned -> unpin.
+    if let Some((idx, _)) = state
+        .pinned_inspector
+        .iter()
+        .enumerate()
+        .find(|(_, info)| info.path == path)
+    {
+        remove_pin_at(state, idx);
+        return;
+    }
+    if pin_path(state, path) {
+        state.inspector_selected_pin = state.pinned_inspector.len().saturating_sub(1);
+        clamp_inspector_selection_and_scroll(state);
+        persist_pins(state);
+    }
+}
+
+/// Append a pin for `path` and queue its inspection.  Returns `false`,
+/// pinning nothing, for directories, unreadable paths and paths already
+/// pinned.  The caller persists the pins.
+pub fn pin_path(state: &mut AppState, path: &Path) -> bool {
+    if state.pinned_inspector.iter().any(|info| info.path == path) {
+        return false;
+    }
+    // Only files are pinnable.
+    if std::fs::metadata(path).map(|m| m.is_dir()).unwrap_or(true) {
+        return false;
+    }
+
+    let mut info = crate::core::inspector::InspectorInfo::pending(path);
+    state.pending_inspections.push_back(path.to_path_buf());
+    state.fill_known_sizes(&mut info);
+    state.pinned_inspector.push(info);
+    true
+}
+
+/// Pin every file in the multi-selection that isn't pinned yet, then
+/// clear the selection.  Directories are skipped.
+fn pin_selected(state: &mut AppState) {
+    if state.selected_nodes.is_empty() {
+        state.status_message = Some("Nothing selected".to_string());
+        return;
+    }
+    let mut paths: Vec<PathBuf> = state.selected_nodes.drain().collect();
+    paths.sort();
+    let first_new = state.pinned_inspector.len();
+    let pinned = paths.iter().filter(|path| pin_path(state, path)).count();
+    state.status_message = Some(match pinned {
+        0 => "No unpinned files in the selection".to_string(),
+        1 => "Pinned 1 file".to_string(),
+        n => format!("Pinned {n} files"),
+    });
+    if pinned > 0 {
+        state.right_pane_tab = RightPaneTab::Inspector;
+        state.inspector_selected_pin = first_new;
+        clamp_inspector_selection_and_scroll(state);
+        persist_pins(state);
+    }
+}
+
+fn toggle_heat_mode(state: &mut AppState) {
+    state.config.heat_mode = !state.config.heat_mode;
+    let _ = state.config.save();
+    state.status_message = Some(format!(
+        "Size heat: {}",
+        if state.config.heat_mode { "on" } else { "off" }
+    ));
+}
+
+/// Re-walk only the selected directory's subtree (a file's parent when a
+/// file is selected).  Cached local sums under it are dropped; every other
+/// directory finalizes from cache.
+fn rescan_selected(state: &mut AppState) {
+    if let Some(dir) = selected_dir_or_parent(state) {
+        rescan_sizes_under(state, dir);
+    }
+}
+
+/// The selected directory, or the one holding the selected file.
+fn selected_dir_or_parent(state: &AppState) -> Option<PathBuf> {
+    let node = state.tree.get(selected_node_id(state)?);
+    if node.meta.is_dir {
+        Some(node.meta.path.clone())
+    } else {
+        node.parent.map(|pid| state.tree.get(pid).meta.path.clone())
+    }
+}
+
+/// Re-read the selected directory from disk.  Expanded directories below
+/// it are listed again in place, keeping their expansion; collapsed ones
+/// forget their children and list afresh when next expanded.  On the root
+/// this amounts to a full rebuild.  Sizes under it are recomputed.
+fn refresh_selected(state: &mut AppState) {
+    if state.fixed_tree {
+        state.status_message = Some("The --stdin tree is not read from disk".to_string());
+        return;
+    }
+    let Some(dir) = selected_dir_or_parent(state) else {
+        return;
+    };
+    let Some(dir_id) = state.tree.nodes.iter().position(|n| n.meta.path == dir) else {
+        return;
+    };
+
+    let mut relist = Vec::new();
+    let mut unlist = Vec::new();
+    let mut stack = vec![dir_id];
+    while let Some(id) = stack.pop() {
+        let node = state.tree.get(id);
+        if id == dir_id || node.expanded {
+            relist.push(node.meta.path.clone());
+            stack.extend(node.children.iter().copied().filter(|&c| state.tree.get(c).meta.is_dir));
+        } else if !node.children.is_empty() {
+            unlist.push(node.meta.path.clone());
+        }
+    }
+    // Only hidden rows go, so the selected row index stays valid.
+    for path in unlist {
+        if let Some(id) = state.tree.nodes.iter().position(|n| n.meta.path == path) {
+            state.tree.clear_children(id);
+        }
+    }
+    state.last_left_click = None;
+
+    state.refresh_select = selected_node_id(state).map(|id| state.tree.get(id).meta.path.clone());
+    for path in relist {
+        if state.refresh_pending.insert(path.clone()) && !state.pending_rescan_paths.contains(&path) {
+            state.pending_rescan_paths.push_back(path);
+        }
+    }
+    rescan_sizes_under(state, dir.clone());
+    request_git_status(state);
+    state.status_message = Some(format!("Refreshing {}", dir.display()));
+}
+
+/// Drop cached sizes under `dir` (and mark its ancestors stale) so the
+/// next scan recomputes them.
+fn rescan_sizes_under(state: &mut AppState, dir: PathBuf) {
+    if state.is_slow_fs() {
+        // No background scan here: walk just this directory.
+        if let Some(cancel) = state.size_on_demand_cancel.take() {
+            cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+        if state.dir_sizes.contains_key(&dir) {
+            state.stale_sizes.insert(dir.clone());
+        }
+        state.status_message = Some(format!("Computing size of {}", dir.display()));
+        state.size_on_demand = Some(dir);
+        return;
+    }
+
+    state.dir_local_sums.retain(|path, _| !path.starts_with(&dir));
+    // Ancestors' totals include the subtree, so they are stale too.
+    let stale: Vec<PathBuf> = state
+        .dir_sizes
+        .keys()
+        .filter(|path| path.starts_with(&dir) || dir.starts_with(path))
+        .cloned()
+        .collect();
+    state.stale_sizes.extend(stale);
+    state.needs_size_recompute = true;
+    state.status_message = Some(format!("Rescanning {}", dir.display()));
+}
+
+/// Recompute the effective slow-filesystem flag from the session setting
+/// and the startup probe, and stop or restart the background work it gates.
+pub fn apply_slow_fs_mode(state: &mut AppState) {
+    let slow = state.slow_fs_mode.resolve(state.slow_fs_detected);
+    if slow == state.is_slow_fs() {
+        return;
+    }
+    state.slow_fs.store(slow, std::sync::atomic::Ordering::Relaxed);
+    state.walk_config.read_mtimes = !slow;
+    if !slow {
+        if let Some(cancel) = state.size_on_demand_cancel.take() {
+            cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+        state.size_on_demand = None;
+    }
+    // Cancels the full scan when entering slow mode, restarts it on leaving.
+    state.needs_size_recompute = true;
+    state.search_reindex_requested = true;
+}
+
+/// Turn mouse capture on/off; the main loop applies it to the terminal.
+/// Move badge focus to the next flippable badge, wrapping to none after
+/// the last.
+fn cycle_badge_focus(state: &mut AppState) {
+    let toggleable: Vec<StatusBadge> = badges::active_badges(state)
+        .into_iter()
+        .filter(|b| b.is_toggleable(state))
+        .collect();
+    state.badge_focus = match state.badge_focus.and_then(|f| toggleable.iter().position(|&b| b == f)) {
+        Some(i) => toggleable.get(i + 1).copied(),
+        None => toggleable.first().copied(),
+    };
+    if toggleable.is_empty() {
+        state.status_message = Some("No status badges to focus".to_string());
+    }
+}
+
+/// Flip the setting a status badge stands for.
+pub fn toggle_badge(state: &mut AppState, badge: StatusBadge) {
+    if !badge.is_toggleable(state) {
+        return;
+    }
+    let on_off = |on: bool| if on { "on" } else { "off" };
+    match badge {
+        StatusBadge::Hidden => {
+            state.walk_config.show_hidden = !state.walk_config.show_hidden;
+            rebuild_tree(state);
+            state.status_message = Some(format!("Hidden files: {}", on_off(state.walk_config.show_hidden)));
+        }
+        StatusBadge::NoDedup => {
+            state.config.dedup_hard_links = !state.config.dedup_hard_links;
+            let _ = state.config.save();
+            state.dir_local_sums.clear();
+            state.needs_size_recompute = true;
+            state.status_message = Some(format!("Dedup hard links: {}", on_off(state.config.dedup_hard_links)));
+        }
+        StatusBadge::OneFileSystem => {
+            state.config.one_file_system = !state.config.one_file_system;
+            let _ = state.config.save();
+            state.dir_local_sums.clear();
+            rebuild_tree(state);
+            state.status_message = Some(format!("One file system: {}", on_off(state.config.one_file_system)));
+        }
+        StatusBadge::Listed => {}
+    }
+    if state.badge_focus.is_some_and(|b| !b.is_active(state)) {
+        state.badge_focus = None;
+    }
+}
+
+fn toggle_mouse(state: &mut AppState) {
+    state.mouse_enabled = !state.mouse_enabled;
+    state.dragging_splitter = false;
+    state.dragging_tree_scrollbar = false;
+    state.last_left_click = None;
+    state.config.mouse = state.mouse_enabled;
+    let _ = state.config.save();
+    state.status_message = Some(if state.mouse_enabled {
+        "Mouse: on".to_string()
+    } else {
+        "Mouse: off (terminal selection enabled)".to_string()
+    });
+}
+
+fn cycle_sort(state: &mut AppState) {
+    set_sort_mode(state, state.walk_config.sort_mode.next());
+    state.status_message = Some(format!("Sort: {}", state.walk_config.sort_mode.label()));
+}
+
+/// Switch the sort order, reorder the loaded tree and persist the choice.
+pub fn set_sort_mode(state: &mut AppState, mode: SortMode) {
+    preserving_selection(state, |state| {
+        state.walk_config.sort_mode = mode;
+        crate::core::fs::resort_tree(&mut state.tree, mode);
+    });
+    state.config.sort_mode = mode;
+    let _ = state.config.save();
+    // Size orders need totals; start a scan if none has run yet.
+    if mode.is_size() && state.dir_sizes.is_empty() && !state.scanning {
+        state.needs_size_recompute = true;
+    }
+}
+
+/// Switch how files are grouped in the tree and persist the choice.
+pub fn set_group_by(state: &mut AppState, group_by: GroupBy) {
+    preserving_selection(state, |state| state.grouping_config.group_by = group_by);
+    state.config.group_by = group_by;
+    let _ = state.config.save();
+}
+
+/// Set how many files a bucket needs before it is grouped (`0` disables
+/// grouping) and persist the choice.  Rows are rebuilt every frame, so
+/// no rescan is needed.
+pub fn set_grouping_min_size(state: &mut AppState, min: usize) {
+    preserving_selection(state, |state| state.grouping_config.min_group_size = min);
+    state.config.grouping_min_size = min;
+    let _ = state.config.save();
+}
+
+/// Pass the syntax highlighting setting to the inspector worker and
+/// re-inspect the selection and pins so their previews follow it.
+pub fn apply_syntax_highlight(state: &mut AppState) {
+    let on = state.config.syntax_highlight;
+    state.syntax_highlight.store(on, std::sync::atomic::Ordering::Relaxed);
+    for info in state.inspector_info.iter_mut().chain(state.pinned_inspector.iter_mut()) {
+        info.text_highlight = None;
+    }
+    if on {
+        let pins = state.pinned_inspector.iter().map(|info| info.path.clone());
+        state.pending_inspections.extend(pins);
+        state.inspector_path = None;
     }
-    clamp_search_selection_and_scroll(state);
 }
 
-/// Recompute ranked search results using the current in-memory search index.
-pub fn refresh_search(state: &mut AppState) {
-    refresh_search_results(state);
+/// Run `f` and keep the same node selected if it reorders the rows.
+pub fn preserving_selection(state: &mut AppState, f: impl FnOnce(&mut AppState)) {
+    // Track the path, not the id: `f` may compact the arena.
+    let selected = selected_node_id(state).map(|id| state.tree.nodes[id].meta.path.clone());
+    f(state);
+    let Some(selected) = selected else {
+        return;
+    };
+    let rows = build_rows(state);
+    if let Some(idx) = rows.iter().position(|row| {
+        matches!(row, TreeRow::Node { node_id, .. } if state.tree.nodes[*node_id].meta.path == selected)
+    }) {
+        state.tree_state.selected = idx;
+    }
 }
 
-fn reveal_selected_search_in_tree(state: &mut AppState) {
-    if state.search_results.is_empty() {
+/// Keyboard step for widening / narrowing the tree pane.
+const SPLIT_STEP_PCT: u16 = 5;
+
+/// Move the tree/inspector splitter by `delta` percent (keyboard
+/// counterpart to dragging it with the mouse).
+fn resize_tree_pane(state: &mut AppState, delta: i16) {
+    let pct = (state.config.panel_split_pct as i16 + delta).clamp(10, 90) as u16;
+    if pct == state.config.panel_split_pct {
         return;
     }
-    if let Some(path) = state.search_results.get(state.search_selected).map(|r| r.path.clone()) {
-        reveal_path_in_tree(state, &path);
-    }
+    state.config.panel_split_pct = pct;
+    let _ = state.config.save();
+    state.status_message = Some(format!("Panel split: {pct}%"));
 }
 
-fn toggle_pin_for_path(state: &mut AppState, path: &Path) {
-    // Already pinned -> unpin.
-    if let Some((idx, _)) = state
-        .pinned_inspector
-        .iter()
-        .enumerate()
-        .find(|(_, info)| info.path == path)
-    {
-        remove_pin_at(state, idx);
+/// Pin every image directly inside the selected directory (or unpin the
+/// batch if this directory was bulk-pinned before).
+fn toggle_pin_all_images(state: &mut AppState) {
+    let Some(node_id) = selected_node_id(state) else {
+        return;
+    };
+    let node = state.tree.get(node_id);
+    if !node.meta.is_dir {
+        state.status_message = Some("Select a directory to pin its images".to_string());
         return;
     }
+    let dir = node.meta.path.clone();
 
-    // Only files are pinnable.
-    if std::fs::metadata(path).map(|m| m.is_dir()).unwrap_or(true) {
+    // Second invocation on the same directory: unpin that batch (unless the
+    // user already unpinned all of it by hand, in which case pin afresh).
+    if let Some(batch) = state.bulk_pins.remove(&dir) {
+        let before = state.pinned_inspector.len();
+        state.pinned_inspector.retain(|info| !batch.contains(&info.path));
+        let removed = before - state.pinned_inspector.len();
+        if removed > 0 {
+            clamp_inspector_selection_and_scroll(state);
+            persist_pins(state);
+            state.status_message = Some(format!("Unpinned {removed} images"));
+            return;
+        }
+    }
+
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        state.status_message = Some("Cannot read directory".to_string());
+        return;
+    };
+    let mut images: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .map(|e| e.path())
+        .filter(|p| image::ImageFormat::from_path(p).is_ok())
+        .filter(|p| !state.pinned_inspector.iter().any(|info| &info.path == p))
+        .collect();
+    if images.is_empty() {
+        state.status_message = Some("No unpinned images in this directory".to_string());
         return;
     }
+    images.sort();
 
-    let mut info = crate::core::inspector::inspect_path(path);
-    if let Some(sz) = state.dir_sizes.get(path).copied() {
-        info.size_bytes = Some(sz);
-    } else if let Some(sz) = state.file_sizes.get(path).copied() {
-        info.size_bytes = Some(sz);
+    let limit = state.config.bulk_pin_limit;
+    let total = images.len();
+    images.truncate(limit);
+
+    let first_new = state.pinned_inspector.len();
+    for path in &images {
+        let mut info = crate::core::inspector::InspectorInfo::pending(path);
+        state.fill_known_sizes(&mut info);
+        state.pinned_inspector.push(info);
+        state.pending_inspections.push_back(path.clone());
     }
-    state.pinned_inspector.push(info);
-    state.inspector_selected_pin = state.pinned_inspector.len().saturating_sub(1);
+    state.status_message = Some(if total > limit {
+        format!("Pinned first {limit} of {total} images (limit {limit})")
+    } else {
+        format!("Pinned {total} images")
+    });
+    state.bulk_pins.insert(dir, images);
+
+    state.right_pane_tab = RightPaneTab::Inspector;
+    state.pane_focus = PaneFocus::Inspector;
+    state.inspector_selected_pin = first_new;
     clamp_inspector_selection_and_scroll(state);
     persist_pins(state);
 }
@@ -1241,6 +3617,7 @@ fn clamp_search_selection_and_scroll(state: &mut AppState) {
         state.terminal_area,
         state.config.panel_layout,
         state.config.panel_split_pct,
+        state.config.status_lines,
     );
     let inner = ratatui::widgets::Block::default()
         .borders(ratatui::widgets::Borders::ALL)
@@ -1264,7 +3641,8 @@ fn clamp_search_selection_and_scroll(state: &mut AppState) {
 }
 
 fn request_expand_path(state: &mut AppState, path: std::path::PathBuf) {
-    if state.expand_in_flight.contains(&path) {
+    // A listed tree already holds every entry it will ever show.
+    if state.fixed_tree || state.expand_in_flight.contains(&path) {
         return;
     }
     let has_children = state
@@ -1281,12 +3659,250 @@ fn request_expand_path(state: &mut AppState, path: std::path::PathBuf) {
     state.pending_expand_paths.push_back(path);
 }
 
+/// Directory listings an "expand all" keeps in flight at once, so a large
+/// subtree is listed in chunks rather than all at once.
+const EXPAND_ALL_CHUNK: usize = 16;
+
+fn start_expand_all(state: &mut AppState) {
+    let Some(node_id) = selected_node_id(state) else {
+        return;
+    };
+    let node = state.tree.get(node_id);
+    if !node.meta.is_dir || node.meta.is_symlink {
+        return;
+    }
+    state.expand_all = Some(ExpandAllJob {
+        root: node.meta.path.clone(),
+        max_depth: node.depth + state.config.expand_all_max_depth,
+        requested: HashSet::new(),
+    });
+    continue_expand_all(state);
+}
+
+/// Advance the running "expand all": mark every loaded directory in the
+/// subtree expanded and request listings for unloaded ones, a chunk at a
+/// time.  Called again whenever expansions land.
+pub fn continue_expand_all(state: &mut AppState) {
+    let Some(job) = state.expand_all.as_mut() else {
+        return;
+    };
+    let root = job.root.clone();
+    let Some(root_id) = state.tree.nodes.iter().position(|n| n.meta.path == root) else {
+        state.expand_all = None;
+        return;
+    };
+
+    let max_nodes = state.config.expand_all_max_nodes;
+    let mut budget = EXPAND_ALL_CHUNK.saturating_sub(state.expand_in_flight.len());
+    let mut to_request = Vec::new();
+    let mut unfinished = false;
+    let mut visited = 0;
+    let mut capped = false;
+    let mut queue = VecDeque::from([root_id]);
+    while let Some(id) = queue.pop_front() {
+        visited += 1;
+        if visited > max_nodes {
+            capped = true;
+            break;
+        }
+        let node = state.tree.get(id);
+        if !node.meta.is_dir || node.meta.is_symlink || node.depth >= job.max_depth {
+            continue;
+        }
+        if node.children.is_empty() && !job.requested.contains(&node.meta.path) {
+            unfinished = true;
+            if budget > 0 {
+                budget -= 1;
+                job.requested.insert(node.meta.path.clone());
+                to_request.push(node.meta.path.clone());
+            }
+            continue;
+        }
+        queue.extend(node.children.iter().copied());
+        state.tree.get_mut(id).expanded = true;
+    }
+
+    for path in to_request {
+        if let Some(id) = state.tree.nodes.iter().position(|n| n.meta.path == path) {
+            state.tree.get_mut(id).expanded = true;
+        }
+        request_expand_path(state, path);
+    }
+
+    let in_flight = state.expand_in_flight.iter().any(|p| p.starts_with(&root));
+    if capped {
+        state.expand_all = None;
+        state.status_message = Some(format!("Expand all stopped at {max_nodes} entries"));
+    } else if !unfinished && !in_flight {
+        state.expand_all = None;
+        state.status_message = Some(format!("Expanded {visited} entries"));
+    }
+}
+
+/// Select the biggest child of the selected directory, expanding it first.
+/// Refuses while any child's size is still unknown or stale, since the
+/// biggest entry could still change.
+fn jump_to_largest_child(state: &mut AppState) {
+    let Some(node_id) = selected_node_id(state) else {
+        return;
+    };
+    let node = state.tree.get(node_id);
+    if !node.meta.is_dir || node.meta.is_symlink {
+        return;
+    }
+    let dir = node.meta.path.clone();
+    if node.children.is_empty() {
+        state.tree.get_mut(node_id).expanded = true;
+        request_expand_path(state, dir);
+        state.status_message = Some("Listing directory…".to_string());
+        return;
+    }
+
+    let mut largest: Option<(u64, PathBuf)> = None;
+    for &child in &state.tree.get(node_id).children {
+        let meta = &state.tree.get(child).meta;
+        let size = if meta.is_dir && !meta.is_symlink {
+            state
+                .dir_sizes
+                .get(&meta.path)
+                .filter(|_| !state.stale_sizes.contains(&meta.path))
+        } else {
+            state.file_sizes.get(&meta.path)
+        };
+        let Some(&size) = size else {
+            state.status_message = Some("Sizes still computing".to_string());
+            return;
+        };
+        if largest.as_ref().is_none_or(|(best, _)| size > *best) {
+            largest = Some((size, meta.path.clone()));
+        }
+    }
+    let Some((_, target)) = largest else {
+        return;
+    };
+
+    // A jump that doesn't continue the current trail starts a new one.
+    if state.largest_trail.last().is_some_and(|(_, to)| *to != dir) {
+        state.largest_trail.clear();
+    }
+    state.largest_trail.push((dir, target.clone()));
+    state.tree.get_mut(node_id).expanded = true;
+    reveal_path_in_tree(state, &target);
+    state.status_message = None;
+}
+
+/// Retrace the last "jump to largest child" step, or go to the parent
+/// directory when the selection has moved off the trail.
+fn jump_largest_back(state: &mut AppState) {
+    let Some(selected) = selected_node_path(state) else {
+        return;
+    };
+    let target = match state.largest_trail.last() {
+        Some((from, to)) if *to == selected => {
+            let from = from.clone();
+            state.largest_trail.pop();
+            from
+        }
+        _ => {
+            state.largest_trail.clear();
+            match selected.parent() {
+                Some(parent) if selected != state.cwd => parent.to_path_buf(),
+                _ => return,
+            }
+        }
+    };
+    if target == state.cwd {
+        select_root_row(state);
+    } else {
+        reveal_path_in_tree(state, &target);
+    }
+}
+
+/// Collapse every directory below the root, along with any expanded
+/// groups, and put the cursor back on the root.  Loaded children are
+/// kept, so expanding again is instant.
+fn collapse_all(state: &mut AppState) {
+    state.expand_all = None;
+    let root = state.tree.root;
+    for (id, node) in state.tree.nodes.iter_mut().enumerate() {
+        node.expanded = id == root;
+    }
+    state.expanded_groups.clear();
+    state.tree_state.selected = 0;
+    state.tree_state.offset = 0;
+    state.last_left_click = None;
+}
+
 fn queue_tree_rebuild(state: &mut AppState, root: std::path::PathBuf) {
+    if state.fixed_tree {
+        state.status_message = Some("The --stdin tree cannot be re-rooted or reloaded".to_string());
+        return;
+    }
     state.pending_tree_rebuild = Some(root);
+    state.root_history_target = None;
     state.pending_expand_paths.clear();
     state.expand_in_flight.clear();
 }
 
+/// Most roots kept in the back/forward history.
+const ROOT_HISTORY_MAX: usize = 50;
+
+/// Re-root to the previous or next directory in the history.
+fn step_root_history(state: &mut AppState, forward: bool) {
+    let index = if forward {
+        state.root_history_index.checked_add(1)
+    } else {
+        state.root_history_index.checked_sub(1)
+    };
+    let Some((root, _)) = index.and_then(|i| state.root_history.get(i)) else {
+        state.status_message = Some(if forward {
+            "No later directory in history".to_string()
+        } else {
+            "No earlier directory in history".to_string()
+        });
+        return;
+    };
+    queue_tree_rebuild(state, root.clone());
+    if state.pending_tree_rebuild.is_some() {
+        state.root_history_target = index;
+    }
+}
+
+/// Update the history after a rebuild moved the root.  `left_selected` is
+/// the path that was selected under the old root.  A back/forward move
+/// only shifts the position and reselects what was selected there; any
+/// other re-root drops the forward entries and appends the new root.
+pub fn record_root_change(state: &mut AppState, left_selected: Option<PathBuf>) {
+    if let Some(entry) = state.root_history.get_mut(state.root_history_index) {
+        entry.1 = left_selected;
+    }
+    let target = state.root_history_target.take();
+    match target.filter(|&i| state.root_history.get(i).is_some_and(|(root, _)| *root == state.cwd)) {
+        Some(index) => {
+            state.root_history_index = index;
+            state.status_message = Some(format!(
+                "{} ({}/{})",
+                state.cwd.display(),
+                index + 1,
+                state.root_history.len()
+            ));
+            if let Some(path) = state.root_history[index].1.clone() {
+                if path != state.cwd && path.starts_with(&state.cwd) {
+                    reveal_path_in_tree(state, &path);
+                }
+            }
+        }
+        None => {
+            state.root_history.truncate(state.root_history_index + 1);
+            state.root_history.push((state.cwd.clone(), None));
+            if state.root_history.len() > ROOT_HISTORY_MAX {
+                state.root_history.remove(0);
+            }
+            state.root_history_index = state.root_history.len() - 1;
+        }
+    }
+}
+
 /// Retry asynchronous reveal-path work after background scan updates.
 pub fn retry_pending_reveal(state: &mut AppState) {
     let Some(path) = state.pending_reveal_path.clone() else {
@@ -1294,3 +3910,506 @@ pub fn retry_pending_reveal(state: &mut AppState) {
     };
     reveal_path_in_tree(state, &path);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::core::tree::{DirTree, EntryMeta};
+
+    /// State over an in-memory tree rooted at `/t`.  Entries are paths
+    /// relative to the root, directories ending in `/`, parents first.
+    fn state_with(entries: &[&str]) -> AppState {
+        let root = Path::new("/t");
+        let mut tree = DirTree::new(EntryMeta::missing(root, true));
+        for entry in entries {
+            let (rel, is_dir) = match entry.strip_suffix('/') {
+                Some(dir) => (dir, true),
+                None => (*entry, false),
+            };
+            let path = root.join(rel);
+            let parent = path.parent().unwrap();
+            let parent_id = tree.nodes.iter().position(|n| n.meta.path == parent).unwrap();
+            tree.add_child(parent_id, EntryMeta::missing(&path, is_dir));
+        }
+        AppState::new(root.to_path_buf(), tree, AppConfig::default())
+    }
+
+    #[test]
+    fn paste_into_path_prompt_takes_the_first_line() {
+        let mut state = state_with(&[]);
+        state.active_view = ActiveView::PathInput;
+        state.path_input = "~/".to_string();
+        handle_paste(&mut state, "src\tdir\n/etc/passwd\n");
+        assert_eq!(state.path_input, "~/src dir");
+    }
+
+    #[test]
+    fn paste_into_rename_prompt_drops_control_characters() {
+        let mut state = state_with(&[]);
+        state.active_view = ActiveView::Rename;
+        handle_paste(&mut state, "new\u{1b}name\r\n");
+        assert_eq!(state.name_input, "newname");
+    }
+
+    #[test]
+    fn pin_set_names_never_start_with_a_bracket_or_hash() {
+        let mut state = state_with(&[]);
+        state.active_view = ActiveView::PinSets;
+        state.pin_set_name_input = Some(String::new());
+        handle_paste(&mut state, " [x");
+        assert_eq!(state.pin_set_name_input.as_deref(), Some("x"));
+
+        state.pin_set_name_input = Some(String::new());
+        for ch in [' ', '[', '#', 'y', '['] {
+            handle_pin_sets_key(&mut state, KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        assert_eq!(state.pin_set_name_input.as_deref().map(str::trim), Some("y["));
+    }
+
+    #[test]
+    fn paste_in_the_tree_changes_nothing() {
+        let mut state = state_with(&["a/"]);
+        let before = state.tree_state.selected;
+        handle_paste(&mut state, "zzz");
+        assert_eq!(state.tree_state.selected, before);
+        assert!(state.path_input.is_empty() && state.search_query.is_empty());
+    }
+
+    /// Tree row showing `rel`, relative to the `/t` root.
+    fn row_of(state: &AppState, rel: &str) -> usize {
+        let path = Path::new("/t").join(rel);
+        build_rows(state)
+            .iter()
+            .position(|row| {
+                matches!(row, TreeRow::Node { node_id, .. } if state.tree.nodes[*node_id].meta.path == path)
+            })
+            .unwrap()
+    }
+
+    fn remove(state: &mut AppState, rel: &str) {
+        apply_removed(state, Path::new("/t").join(rel), Ok(()));
+    }
+
+    #[test]
+    fn removing_the_selected_row_selects_the_one_that_took_its_place() {
+        let mut state = state_with(&["a", "b", "c"]);
+        state.tree_state.selected = row_of(&state, "b");
+        let before = state.tree_state.selected;
+        remove(&mut state, "b");
+        assert_eq!(state.tree_state.selected, before);
+        assert_eq!(row_of(&state, "c"), before);
+    }
+
+    #[test]
+    fn removing_the_last_row_selects_the_new_last_row() {
+        let mut state = state_with(&["a", "b", "c"]);
+        state.tree_state.selected = row_of(&state, "c");
+        remove(&mut state, "c");
+        assert_eq!(state.tree_state.selected, row_of(&state, "b"));
+        assert_eq!(state.tree_state.selected, build_rows(&state).len() - 1);
+    }
+
+    #[test]
+    fn removing_a_row_above_the_offset_keeps_the_view_in_place() {
+        let mut state = state_with(&["a/", "b/", "c/", "d/", "e/"]);
+        state.tree_state.offset = row_of(&state, "c");
+        state.tree_state.selected = row_of(&state, "d");
+        remove(&mut state, "a");
+        assert_eq!(state.tree_state.offset, row_of(&state, "c"));
+        assert_eq!(state.tree_state.selected, row_of(&state, "d"));
+    }
+
+    fn paths(state: &AppState) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = state.tree.nodes.iter().map(|n| n.meta.path.clone()).collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn renaming_an_expanded_dir_moves_its_children_and_their_sizes() {
+        let mut state = state_with(&["foo/", "foo/sub/", "foo/sub/y", "foo/x"]);
+        for path in ["/t/foo", "/t/foo/sub"] {
+            let id = state.tree.nodes.iter().position(|n| n.meta.path == Path::new(path)).unwrap();
+            state.tree.nodes[id].expanded = true;
+        }
+        state.dir_sizes.insert(PathBuf::from("/t/foo/sub"), 7);
+        state.file_sizes.insert(PathBuf::from("/t/foo/sub/y"), 7);
+        state.selected_nodes.insert(PathBuf::from("/t/foo/x"));
+
+        apply_path_rename(&mut state, Path::new("/t/foo"), Path::new("/t/baz"));
+
+        let expected: Vec<PathBuf> = ["/t", "/t/baz", "/t/baz/sub", "/t/baz/sub/y", "/t/baz/x"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(paths(&state), expected);
+        let sub = state.tree.nodes.iter().find(|n| n.meta.path == Path::new("/t/baz/sub")).unwrap();
+        assert_eq!(sub.meta.name, "sub");
+        assert!(sub.expanded);
+        assert_eq!(state.dir_sizes.get(Path::new("/t/baz/sub")), Some(&7));
+        assert_eq!(state.file_sizes.get(Path::new("/t/baz/sub/y")), Some(&7));
+        assert!(state.selected_nodes.contains(Path::new("/t/baz/x")));
+        // Every row is still reachable under the new name.
+        assert_eq!(row_of(&state, "baz/sub/y"), row_of(&state, "baz/sub") + 1);
+    }
+
+    #[test]
+    fn renaming_onto_an_existing_case_variant_fails() {
+        let tmp = tempfile::tempdir().unwrap();
+        let lower = tmp.path().join("readme");
+        let upper = tmp.path().join("README");
+        std::fs::write(&lower, "lower").unwrap();
+        std::fs::write(&upper, "upper").unwrap();
+        if std::fs::read_to_string(&lower).unwrap() == "upper" {
+            return; // case-insensitive filesystem: only one file exists
+        }
+        let tree = DirTree::new(EntryMeta::missing(tmp.path(), true));
+        let mut state = AppState::new(tmp.path().to_path_buf(), tree, AppConfig::default());
+
+        assert_eq!(rename_entry(&mut state, &lower, "README"), Err("'README' already exists".to_string()));
+        assert_eq!(std::fs::read_to_string(&lower).unwrap(), "lower");
+        assert_eq!(std::fs::read_to_string(&upper).unwrap(), "upper");
+    }
+
+    #[test]
+    fn renaming_a_dir_leaves_a_sibling_sharing_its_prefix_alone() {
+        let mut state = state_with(&["foo/", "foo/x", "foobar/", "foobar/z"]);
+        state.dir_sizes.insert(PathBuf::from("/t/foobar"), 3);
+        state.file_sizes.insert(PathBuf::from("/t/foobar/z"), 3);
+        state.selected_nodes.insert(PathBuf::from("/t/foobar/z"));
+        state.stale_sizes.insert(PathBuf::from("/t/foobar"));
+
+        apply_path_rename(&mut state, Path::new("/t/foo"), Path::new("/t/baz"));
+
+        let expected: Vec<PathBuf> = ["/t", "/t/baz", "/t/baz/x", "/t/foobar", "/t/foobar/z"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(paths(&state), expected);
+        assert_eq!(state.dir_sizes.get(Path::new("/t/foobar")), Some(&3));
+        assert_eq!(state.file_sizes.get(Path::new("/t/foobar/z")), Some(&3));
+        assert!(state.selected_nodes.contains(Path::new("/t/foobar/z")));
+        assert!(state.stale_sizes.contains(Path::new("/t/foobar")));
+    }
+
+    #[test]
+    fn renaming_to_another_extension_moves_the_entry_between_groups() {
+        let mut state = state_with(&[
+            "a.txt", "b.txt", "c.txt", "d.txt", "e.txt", "f.rs", "g.rs", "h.rs", "i.rs",
+        ]);
+        state.expanded_groups.insert("/t:*.txt".to_string());
+        state.expanded_groups.insert("/t:*.rs".to_string());
+        let group_of = |state: &AppState, name: &str| {
+            let root = state.tree.root;
+            crate::core::grouping::group_children(&state.tree, root, &state.grouping_config, None)
+                .into_iter()
+                .find_map(|entry| match entry {
+                    crate::core::grouping::GroupedEntry::Group { label, members, .. }
+                        if members.iter().any(|&id| state.tree.get(id).meta.name == name) =>
+                    {
+                        Some(label)
+                    }
+                    _ => None,
+                })
+        };
+        assert_eq!(group_of(&state, "a.txt").as_deref(), Some("*.txt"));
+        assert_eq!(group_of(&state, "f.rs"), None);
+
+        apply_path_rename(&mut state, Path::new("/t/a.txt"), Path::new("/t/a.rs"));
+
+        assert_eq!(group_of(&state, "a.rs").as_deref(), Some("*.rs"));
+        assert_eq!(group_of(&state, "b.txt"), None);
+        let mut expanded: Vec<&str> = state.expanded_groups.iter().map(String::as_str).collect();
+        expanded.sort();
+        assert_eq!(expanded, ["/t:*.rs"]);
+    }
+
+    #[test]
+    fn rescan_selected_marks_the_subtree_and_its_ancestors_stale() {
+        let mut state = state_with(&["a/", "a/b/", "a/b/c/", "a/b/f", "a/d/", "e/"]);
+        for dir in ["/t", "/t/a", "/t/a/b", "/t/a/b/c", "/t/a/d", "/t/e"] {
+            state.dir_sizes.insert(PathBuf::from(dir), 1);
+            state.dir_local_sums.insert(PathBuf::from(dir), Default::default());
+        }
+        for dir in ["/t/a", "/t/a/b"] {
+            let id = state.tree.nodes.iter().position(|n| n.meta.path == Path::new(dir)).unwrap();
+            state.tree.nodes[id].expanded = true;
+        }
+        // A selected file rescans the directory holding it.
+        state.tree_state.selected = row_of(&state, "a/b/f");
+
+        rescan_selected(&mut state);
+
+        let mut stale: Vec<&Path> = state.stale_sizes.iter().map(PathBuf::as_path).collect();
+        stale.sort();
+        assert_eq!(stale, [Path::new("/t"), Path::new("/t/a"), Path::new("/t/a/b"), Path::new("/t/a/b/c")]);
+        let mut local: Vec<&Path> = state.dir_local_sums.keys().map(PathBuf::as_path).collect();
+        local.sort();
+        assert_eq!(local, [Path::new("/t"), Path::new("/t/a"), Path::new("/t/a/d"), Path::new("/t/e")]);
+        assert!(state.needs_size_recompute);
+        // Shown totals stay up until the new ones land.
+        assert_eq!(state.dir_sizes.len(), 6);
+    }
+
+    #[test]
+    fn rescan_selected_on_a_slow_filesystem_walks_just_that_dir() {
+        let mut state = state_with(&["a/", "e/"]);
+        state.slow_fs.store(true, std::sync::atomic::Ordering::Relaxed);
+        state.dir_sizes.insert(PathBuf::from("/t/a"), 1);
+        state.dir_local_sums.insert(PathBuf::from("/t/a"), Default::default());
+        state.tree_state.selected = row_of(&state, "a");
+
+        rescan_selected(&mut state);
+
+        assert_eq!(state.size_on_demand.as_deref(), Some(Path::new("/t/a")));
+        assert_eq!(state.stale_sizes.len(), 1);
+        assert!(state.dir_local_sums.contains_key(Path::new("/t/a")));
+        assert!(!state.needs_size_recompute);
+    }
+
+    fn set_sizes(state: &mut AppState, sizes: &[(&str, u64)]) {
+        for (rel, size) in sizes {
+            let path = Path::new("/t").join(rel);
+            let is_dir = state.tree.nodes.iter().any(|n| n.meta.path == path && n.meta.is_dir);
+            let map = if is_dir { &mut state.dir_sizes } else { &mut state.file_sizes };
+            map.insert(path, *size);
+        }
+    }
+
+    fn selected(state: &AppState) -> PathBuf {
+        selected_node_path(state).unwrap()
+    }
+
+    #[test]
+    fn jump_to_largest_descends_the_heaviest_path_and_retraces_it() {
+        let mut state = state_with(&["a/", "a/big/", "a/big/f", "a/small", "b"]);
+        set_sizes(&mut state, &[("a", 30), ("a/big", 20), ("a/big/f", 20), ("a/small", 10), ("b", 5)]);
+
+        jump_to_largest_child(&mut state);
+        assert_eq!(selected(&state), Path::new("/t/a"));
+        jump_to_largest_child(&mut state);
+        assert_eq!(selected(&state), Path::new("/t/a/big"));
+        jump_to_largest_child(&mut state);
+        assert_eq!(selected(&state), Path::new("/t/a/big/f"));
+
+        jump_largest_back(&mut state);
+        assert_eq!(selected(&state), Path::new("/t/a/big"));
+        jump_largest_back(&mut state);
+        assert_eq!(selected(&state), Path::new("/t/a"));
+        jump_largest_back(&mut state);
+        assert_eq!(selected(&state), Path::new("/t"));
+        assert!(state.largest_trail.is_empty());
+    }
+
+    #[test]
+    fn jump_to_largest_waits_for_unknown_or_stale_sizes() {
+        let mut state = state_with(&["a/", "b"]);
+        set_sizes(&mut state, &[("a", 3)]);
+        jump_to_largest_child(&mut state);
+        assert_eq!(selected(&state), Path::new("/t"));
+        assert_eq!(state.status_message.as_deref(), Some("Sizes still computing"));
+
+        set_sizes(&mut state, &[("b", 5)]);
+        state.stale_sizes.insert(PathBuf::from("/t/a"));
+        jump_to_largest_child(&mut state);
+        assert_eq!(selected(&state), Path::new("/t"));
+
+        state.stale_sizes.clear();
+        jump_to_largest_child(&mut state);
+        assert_eq!(selected(&state), Path::new("/t/b"));
+    }
+
+    #[test]
+    fn jump_back_off_the_trail_goes_to_the_parent() {
+        let mut state = state_with(&["a/", "a/x", "a/y"]);
+        set_sizes(&mut state, &[("a", 3), ("a/x", 2), ("a/y", 1)]);
+        jump_to_largest_child(&mut state);
+        jump_to_largest_child(&mut state);
+        assert_eq!(selected(&state), Path::new("/t/a/x"));
+
+        // Step off the trail by hand.
+        state.tree_state.selected = row_of(&state, "a/y");
+        jump_largest_back(&mut state);
+        assert_eq!(selected(&state), Path::new("/t/a"));
+        assert!(state.largest_trail.is_empty());
+    }
+
+    #[test]
+    fn a_storm_of_watcher_events_invalidates_instead_of_patching() {
+        let mut state = state_with(&["calm/", "busy/"]);
+        for dir in ["/t", "/t/calm", "/t/busy"] {
+            state.dir_sizes.insert(PathBuf::from(dir), 0);
+            state.dir_local_sums.insert(PathBuf::from(dir), Default::default());
+            let id = state.tree.nodes.iter().position(|n| n.meta.path == Path::new(dir)).unwrap();
+            state.tree.nodes[id].expanded = true;
+        }
+        note_path_changed(&mut state, PathBuf::from("/t/calm/new"));
+        for i in 0..=crate::app::watch_runtime::WATCH_STORM_EVENTS {
+            note_path_changed(&mut state, PathBuf::from(format!("/t/busy/f{i}")));
+        }
+
+        flush_watch_changes(&mut state);
+
+        // The calm dir's new (already gone) file was accounted in place.
+        assert!(state.watch_sized_dirs.contains(Path::new("/t/calm")));
+        assert!(state.dir_local_sums.contains_key(Path::new("/t/calm")));
+        assert!(!state.watch_sized_dirs.contains(Path::new("/t/busy")));
+        assert!(!state.dir_local_sums.contains_key(Path::new("/t/busy")));
+        let mut stale: Vec<&Path> = state.stale_sizes.iter().map(PathBuf::as_path).collect();
+        stale.sort();
+        assert_eq!(stale, [Path::new("/t"), Path::new("/t/busy")]);
+        assert!(state.needs_size_recompute);
+        assert_eq!(state.pending_rescan_paths.len(), 2);
+    }
+
+    #[test]
+    fn y_on_a_focused_pin_is_left_to_copy_path() {
+        let mut state = state_with(&["a"]);
+        state.pinned_inspector.push(InspectorInfo::new(Path::new("/t/a")));
+        state.pane_focus = PaneFocus::Inspector;
+        state.right_pane_tab = RightPaneTab::Inspector;
+
+        let y = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(!handle_inspector_focus_key(&mut state, y));
+        assert_eq!(state.config.match_key(y), Some(Action::CopyPath));
+
+        let ctrl_y = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(state.config.match_key(ctrl_y), Some(Action::CopyChecksum));
+        handle_tree_key(&mut state, ctrl_y);
+        assert_eq!(state.status_message.as_deref(), Some("No SHA-256 to copy yet"));
+    }
+
+    #[test]
+    fn collapse_all_folds_everything_below_the_root() {
+        let mut state = state_with(&["a/", "a/b/", "a/b/f", "c/", "c/g", "h"]);
+        for dir in ["/t/a", "/t/a/b", "/t/c"] {
+            let id = state.tree.nodes.iter().position(|n| n.meta.path == Path::new(dir)).unwrap();
+            state.tree.nodes[id].expanded = true;
+        }
+        state.expanded_groups.insert("/t/c:images".to_string());
+        state.tree_state.selected = row_of(&state, "a/b/f");
+        state.tree_state.offset = 2;
+
+        collapse_all(&mut state);
+
+        assert_eq!(build_rows(&state).len(), 4);
+        assert_eq!((state.tree_state.selected, state.tree_state.offset), (0, 0));
+        assert_eq!(selected_node_path(&state).as_deref(), Some(Path::new("/t")));
+        assert!(state.tree.get(state.tree.root).expanded);
+        assert!(state.tree.nodes.iter().skip(1).all(|n| !n.expanded));
+        assert!(state.expanded_groups.is_empty());
+        // Loaded children stay for an instant re-expand.
+        assert_eq!(state.tree.nodes.len(), 7);
+    }
+
+    /// Finish a re-root to `root` the way a completed rebuild does.
+    fn rerooted(state: &mut AppState, root: &str) {
+        state.pending_tree_rebuild = None;
+        state.cwd = PathBuf::from(root);
+        record_root_change(state, None);
+    }
+
+    fn history(state: &AppState) -> Vec<&Path> {
+        state.root_history.iter().map(|(root, _)| root.as_path()).collect()
+    }
+
+    #[test]
+    fn new_navigation_drops_the_forward_history() {
+        let mut state = state_with(&[]);
+        rerooted(&mut state, "/a");
+        rerooted(&mut state, "/b");
+
+        step_root_history(&mut state, false);
+        assert_eq!(state.pending_tree_rebuild.as_deref(), Some(Path::new("/a")));
+        rerooted(&mut state, "/a");
+        assert_eq!(state.root_history_index, 1);
+        assert_eq!(history(&state), [Path::new("/t"), Path::new("/a"), Path::new("/b")]);
+
+        rerooted(&mut state, "/c");
+        assert_eq!(history(&state), [Path::new("/t"), Path::new("/a"), Path::new("/c")]);
+        step_root_history(&mut state, true);
+        assert_eq!(state.pending_tree_rebuild, None);
+        assert_eq!(state.status_message.as_deref(), Some("No later directory in history"));
+    }
+
+    #[test]
+    fn root_history_is_capped() {
+        let mut state = state_with(&[]);
+        for i in 0..ROOT_HISTORY_MAX + 10 {
+            rerooted(&mut state, &format!("/d{i}"));
+        }
+        assert_eq!(state.root_history.len(), ROOT_HISTORY_MAX);
+        assert_eq!(state.root_history_index, ROOT_HISTORY_MAX - 1);
+        assert_eq!(history(&state)[0], Path::new("/d10"));
+
+        // Back still walks the entries that are left.
+        step_root_history(&mut state, false);
+        let expected = format!("/d{}", ROOT_HISTORY_MAX + 8);
+        assert_eq!(state.pending_tree_rebuild.as_deref(), Some(Path::new(&expected)));
+    }
+
+    #[test]
+    fn committed_deletions_follow_the_trash_setting() {
+        for permanent in [false, true] {
+            let mut state = state_with(&["a", "b"]);
+            state.config.permanent_delete = permanent;
+            state.pending_deletions = vec![PathBuf::from("/t/a"), PathBuf::from("/t/b")];
+            state.active_view = ActiveView::ConfirmDeletions;
+            for ch in "yes".chars() {
+                handle_confirm_deletions_key(&mut state, KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+            }
+            handle_confirm_deletions_key(&mut state, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+            let (paths, requested_permanent) = state.deletion_job_requested.take().unwrap();
+            assert_eq!(paths, state.pending_deletions);
+            assert_eq!(requested_permanent, permanent);
+        }
+    }
+
+    fn press(state: &mut AppState, code: KeyCode) {
+        handle_tree_key(state, KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn type_ahead_mode_captures_bound_keys_until_enter() {
+        let mut state = state_with(&["queue", "rates", "rq", "sq"]);
+        press(&mut state, KeyCode::Char('\''));
+        assert_eq!(active_type_ahead(&state), Some(""));
+
+        // `q` (Quit) and `s` (CycleSort) are bound; here they just type.
+        press(&mut state, KeyCode::Char('s'));
+        press(&mut state, KeyCode::Char('q'));
+        assert_eq!(selected(&state), Path::new("/t/sq"));
+        assert!(!state.should_quit);
+        press(&mut state, KeyCode::Backspace);
+        press(&mut state, KeyCode::Backspace);
+        press(&mut state, KeyCode::Char('r'));
+        press(&mut state, KeyCode::Char('q'));
+        assert_eq!(selected(&state), Path::new("/t/rq"));
+        assert_eq!(active_type_ahead(&state), Some("rq"));
+
+        press(&mut state, KeyCode::Enter);
+        assert!(!state.type_ahead_mode);
+        assert_eq!(active_type_ahead(&state), None);
+        assert_eq!(selected(&state), Path::new("/t/rq"));
+        // Back to bindings: `q` quits again.
+        press(&mut state, KeyCode::Char('q'));
+        assert!(state.should_quit);
+    }
+
+    #[test]
+    fn type_ahead_mode_ends_on_esc_or_a_non_printable_key() {
+        let mut state = state_with(&["a", "b", "c"]);
+        press(&mut state, KeyCode::Char('\''));
+        press(&mut state, KeyCode::Char('b'));
+        press(&mut state, KeyCode::Esc);
+        assert!(!state.type_ahead_mode);
+        assert_eq!(selected(&state), Path::new("/t/b"));
+
+        press(&mut state, KeyCode::Char('\''));
+        press(&mut state, KeyCode::Down);
+        assert!(!state.type_ahead_mode);
+        assert_eq!(selected(&state), Path::new("/t/c"));
+    }
+}
@@ -0,0 +1,120 @@
+//! Helpers shared by the text-input prompts (search, pin set names, …).
+
+use std::path::{Path, PathBuf};
+
+/// Longest paste kept for a single-line input, in characters (Linux's
+/// `PATH_MAX`); a paste of a whole file shouldn't freeze the prompt.
+pub const PASTE_MAX_CHARS: usize = 4096;
+
+/// Clean pasted text for a single-line input: keep only the first line,
+/// drop control characters (tabs become spaces) and cap the length at
+/// [`PASTE_MAX_CHARS`].
+pub fn sanitize_single_line(text: &str) -> String {
+    let first = text.split(['\n', '\r']).find(|l| !l.is_empty()).unwrap_or("");
+    first
+        .chars()
+        .map(|c| if c == '\t' { ' ' } else { c })
+        .filter(|c| !c.is_control())
+        .take(PASTE_MAX_CHARS)
+        .collect()
+}
+
+/// Resolve a typed path: a leading `~` expands to `$HOME`, relative paths
+/// are taken from `base`.
+pub fn resolve_typed_path(input: &str, base: &Path) -> PathBuf {
+    let home = || std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+    let path = if input == "~" {
+        home()
+    } else if let Some(rest) = input.strip_prefix("~/") {
+        home().join(rest)
+    } else {
+        PathBuf::from(input)
+    };
+    if path.is_absolute() {
+        path
+    } else {
+        base.join(path)
+    }
+}
+
+/// Complete the last component of a typed path.  Returns the new input and,
+/// when several entries still match, their names (directories end in `/`).
+/// Dot entries are only offered once the component starts with `.`.
+pub fn complete_path(input: &str, base: &Path) -> (String, Vec<String>) {
+    let (dir_part, prefix) = match input.rfind('/') {
+        Some(i) => input.split_at(i + 1),
+        None => ("", input),
+    };
+    let dir = if dir_part.is_empty() {
+        base.to_path_buf()
+    } else {
+        resolve_typed_path(dir_part, base)
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return (input.to_string(), Vec::new());
+    };
+    let mut matches: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                return None;
+            }
+            let is_dir = entry.path().is_dir();
+            Some(if is_dir { format!("{name}/") } else { name })
+        })
+        .collect();
+    matches.sort();
+
+    match matches.as_slice() {
+        [] => (input.to_string(), Vec::new()),
+        [only] => (format!("{dir_part}{only}"), Vec::new()),
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.as_str(), |acc, name| {
+                let len = acc
+                    .char_indices()
+                    .zip(name.chars())
+                    .take_while(|((_, a), b)| a == b)
+                    .last()
+                    .map_or(0, |((i, a), _)| i + a.len_utf8());
+                &acc[..len]
+            });
+            let completed = format!("{dir_part}{common}");
+            (completed, matches)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paste_keeps_the_first_non_empty_line() {
+        assert_eq!(sanitize_single_line("/tmp/a\n/tmp/b\n"), "/tmp/a");
+        assert_eq!(sanitize_single_line("\r\n\n/tmp/a\r\n"), "/tmp/a");
+        assert_eq!(sanitize_single_line("\n\r\n"), "");
+    }
+
+    #[test]
+    fn paste_drops_control_characters() {
+        assert_eq!(sanitize_single_line("a\tb"), "a b");
+        assert_eq!(sanitize_single_line("\x1b[31mred\x1b[0m\x07"), "[31mred[0m");
+        assert_eq!(sanitize_single_line("caf\u{e9}\u{7f}"), "caf\u{e9}");
+    }
+
+    #[test]
+    fn oversized_paste_is_capped() {
+        let long = "\u{e9}".repeat(PASTE_MAX_CHARS * 2);
+        assert_eq!(sanitize_single_line(&long).chars().count(), PASTE_MAX_CHARS);
+        let exact = "x".repeat(PASTE_MAX_CHARS);
+        assert_eq!(sanitize_single_line(&exact), exact);
+    }
+
+    #[test]
+    fn typed_paths_resolve_against_the_base() {
+        let base = Path::new("/base");
+        assert_eq!(resolve_typed_path("sub/dir", base), PathBuf::from("/base/sub/dir"));
+        assert_eq!(resolve_typed_path("/abs", base), PathBuf::from("/abs"));
+    }
+}
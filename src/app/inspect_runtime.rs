@@ -0,0 +1,237 @@
+//! Background inspector worker.
+//!
+//! Inspection touches file headers (MIME sniffing, image headers) and can
+//! `read_dir` large directories, so it runs on a dedicated thread that owns
+//! an [`InspectorCtx`] and its caches.  Results flow back to the event loop
+//! over a channel and are matched to the current selection / pins by path.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use crate::app::preview_runtime;
+use crate::app::state::{AppState, CachedChecksum};
+use crate::core::inspector::{self, InspectorCtx, InspectorInfo, MimeCacheStats};
+
+/// Finished checksum for a file as it was when hashing started.
+pub struct ChecksumResult {
+    pub path: PathBuf,
+    pub modified_unix: Option<u64>,
+    pub size_bytes: Option<u64>,
+    /// The digest, or the read error.
+    pub result: Result<String, String>,
+}
+
+/// Spawn the long-lived inspector worker.  Paths sent on the returned
+/// sender are inspected in order and the results delivered on `tx`.
+/// While `slow_fs` is set, file types come from extensions only; while
+/// `highlight` is set, text previews are syntax-highlighted.
+pub fn spawn_inspector_worker(
+    tx: mpsc::UnboundedSender<InspectorInfo>,
+    stats: Arc<MimeCacheStats>,
+    slow_fs: Arc<AtomicBool>,
+    highlight: Arc<AtomicBool>,
+) -> std::sync::mpsc::Sender<PathBuf> {
+    let (req_tx, req_rx) = std::sync::mpsc::channel::<PathBuf>();
+    std::thread::spawn(move || {
+        let mut ctx = InspectorCtx::new(stats, slow_fs, highlight);
+        while let Ok(path) = req_rx.recv() {
+            let info = ctx.inspect_path(&path);
+            if tx.send(info).is_err() {
+                break; // receiver dropped
+            }
+        }
+    });
+    req_tx
+}
+
+/// Send the queued inspections to the worker.  A path it is already
+/// inspecting isn't sent twice; it goes again once the first result is
+/// in, in case the file changed after that inspection started.
+pub fn send_inspections(state: &mut AppState, req_tx: &std::sync::mpsc::Sender<PathBuf>) {
+    while let Some(path) = state.pending_inspections.pop_front() {
+        if let Some(again) = state.inspections_in_flight.get_mut(&path) {
+            *again = true;
+            continue;
+        }
+        state.inspections_in_flight.insert(path.clone(), false);
+        let _ = req_tx.send(path);
+    }
+}
+
+/// Apply a finished inspection to the current selection and any pinned
+/// cards showing the same path.
+pub fn apply_inspected(state: &mut AppState, mut info: InspectorInfo) {
+    if state.inspections_in_flight.remove(&info.path) == Some(true) {
+        state.pending_inspections.push_back(info.path.clone());
+    }
+    // An unchanged file keeps its digest; one still hashing stays pending.
+    info.checksum = cached_checksum(&state.checksum_cache, &info);
+    info.checksum_pending = state.checksum_in_flight.contains_key(&info.path);
+    // Archive listings keep their scroll position.
+    for pin in state.pinned_inspector.iter_mut().filter(|p| p.path == info.path) {
+        let scroll = pin.archive_scroll;
+        *pin = info.clone();
+        pin.archive_scroll = scroll;
+    }
+    if state.inspector_path.as_ref() == Some(&info.path) {
+        if let Some(old) = state.inspector_info.as_ref().filter(|old| old.path == info.path) {
+            info.archive_scroll = old.archive_scroll;
+        }
+        preview_runtime::request_preview(state, &mut info);
+        state.inspector_info = Some(info);
+    }
+}
+
+/// Digest cached for `info`'s file, if its size and mtime still match.
+fn cached_checksum(cache: &HashMap<PathBuf, CachedChecksum>, info: &InspectorInfo) -> Option<String> {
+    cache
+        .get(&info.path)
+        .filter(|c| c.size_bytes == info.size_bytes && c.modified_unix == info.modified_unix)
+        .map(|c| c.digest.clone())
+}
+
+/// Start a background SHA-256 for each inspected file (selection and pins)
+/// that has none yet.  Files over [`inspector::CHECKSUM_MAX_BYTES`] and
+/// everything on slow filesystems wait until one is requested (by
+/// setting `checksum_pending`).
+pub fn enqueue_checksums(state: &mut AppState, tx: &mpsc::UnboundedSender<ChecksumResult>) {
+    let automatic = !state.is_slow_fs();
+    let infos = state.inspector_info.iter_mut().chain(state.pinned_inspector.iter_mut());
+    for info in infos {
+        let fits = info.size_bytes.is_some_and(|sz| sz <= inspector::CHECKSUM_MAX_BYTES);
+        let wanted = info.checksum_pending || (automatic && fits);
+        // A failed hash leaves `error` set, so it isn't retried every frame.
+        if info.kind != "File" || !wanted || info.checksum.is_some() || info.error.is_some() {
+            continue;
+        }
+        if let Some(digest) = cached_checksum(&state.checksum_cache, info) {
+            info.checksum = Some(digest);
+            info.checksum_pending = false;
+            continue;
+        }
+        info.checksum_pending = true;
+        if state.checksum_in_flight.contains_key(&info.path) {
+            continue;
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+        state.checksum_in_flight.insert(info.path.clone(), cancel.clone());
+        let (path, modified_unix, size_bytes) = (info.path.clone(), info.modified_unix, info.size_bytes);
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            let result = match inspector::sha256_file(&path, &cancel) {
+                Ok(digest) => Ok(digest),
+                // Nothing shows the file any more.
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => return,
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = tx.send(ChecksumResult {
+                path,
+                modified_unix,
+                size_bytes,
+                result,
+            });
+        });
+    }
+}
+
+/// Stop hashing files that neither the inspector nor a pin shows any
+/// more, e.g. after an unpin.  A multi-GB hash would otherwise keep the
+/// disk busy for nothing.
+pub fn cancel_unshown_checksums(state: &mut AppState) {
+    let shown: Vec<&PathBuf> = state
+        .inspector_info
+        .iter()
+        .chain(&state.pinned_inspector)
+        .map(|info| &info.path)
+        .collect();
+    state.checksum_in_flight.retain(|path, cancel| {
+        let keep = shown.contains(&path);
+        if !keep {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        keep
+    });
+}
+
+/// Stop every running hash (on exit).
+pub fn cancel_all_checksums(state: &mut AppState) {
+    for (_, cancel) in state.checksum_in_flight.drain() {
+        cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Fill a finished checksum into every card showing its path, and cache it.
+pub fn apply_checksum(state: &mut AppState, done: ChecksumResult) {
+    state.checksum_in_flight.remove(&done.path);
+    let infos = state.inspector_info.iter_mut().chain(state.pinned_inspector.iter_mut());
+    for info in infos.filter(|info| info.path == done.path) {
+        info.checksum_pending = false;
+        match &done.result {
+            Ok(digest) => info.checksum = Some(digest.clone()),
+            Err(e) => info.error = Some(format!("SHA-256: {e}")),
+        }
+    }
+    if let Ok(digest) = done.result {
+        state.checksum_cache.insert(
+            done.path,
+            CachedChecksum {
+                modified_unix: done.modified_unix,
+                size_bytes: done.size_bytes,
+                digest,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    use crate::config::AppConfig;
+    use crate::core::tree::{DirTree, EntryMeta};
+
+    fn state() -> AppState {
+        let root = Path::new("/t");
+        AppState::new(root.to_path_buf(), DirTree::new(EntryMeta::missing(root, true)), AppConfig::default())
+    }
+
+    #[test]
+    fn a_path_in_flight_is_not_sent_twice() {
+        let mut state = state();
+        let (req_tx, req_rx) = std::sync::mpsc::channel();
+        let (a, b) = (PathBuf::from("/t/a"), PathBuf::from("/t/b"));
+
+        state.pending_inspections.extend([a.clone(), b.clone(), a.clone()]);
+        send_inspections(&mut state, &req_tx);
+        state.pending_inspections.push_back(b.clone());
+        send_inspections(&mut state, &req_tx);
+        assert_eq!(req_rx.try_iter().collect::<Vec<_>>(), [a.clone(), b.clone()]);
+
+        // Queued again while in flight: sent once more after the result.
+        apply_inspected(&mut state, InspectorInfo::new(&a));
+        apply_inspected(&mut state, InspectorInfo::new(&b));
+        send_inspections(&mut state, &req_tx);
+        assert_eq!(req_rx.try_iter().collect::<Vec<_>>(), [a, b]);
+        assert_eq!(state.inspections_in_flight.len(), 2);
+    }
+
+    #[test]
+    fn a_finished_path_can_be_sent_again() {
+        let mut state = state();
+        let (req_tx, req_rx) = std::sync::mpsc::channel();
+        let a = PathBuf::from("/t/a");
+
+        state.pending_inspections.push_back(a.clone());
+        send_inspections(&mut state, &req_tx);
+        apply_inspected(&mut state, InspectorInfo::new(&a));
+        assert!(state.pending_inspections.is_empty());
+        state.pending_inspections.push_back(a.clone());
+        send_inspections(&mut state, &req_tx);
+        assert_eq!(req_rx.try_iter().collect::<Vec<_>>(), [a.clone(), a]);
+    }
+}
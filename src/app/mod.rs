@@ -1,9 +1,17 @@
 //! Application orchestration — state management, event loop, and input handling.
 
+pub mod announce;
 pub mod event;
 pub mod fs_runtime;
 pub mod handler;
+pub mod input;
+pub mod inspect_runtime;
+pub mod preview_runtime;
 pub mod settings;
 pub mod size_runtime;
 pub mod state;
+pub mod updates;
+pub mod watch_runtime;
+
+pub use updates::apply_pending_updates;
 
@@ -0,0 +1,83 @@
+//! External previewer runtime.
+//!
+//! At most one previewer runs at a time: selecting another entry cancels
+//! the one in flight (its process group is killed) before the next starts.
+//! Results are matched to the current selection by path.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::app::state::AppState;
+use crate::core::inspector::InspectorInfo;
+use crate::core::previewer::{self, ExternalPreview};
+
+/// A finished previewer run.
+#[derive(Debug)]
+pub struct PreviewResult {
+    pub path: PathBuf,
+    pub result: Result<Vec<String>, String>,
+}
+
+/// Run `command` for `path` on a background thread.  Returns the flag that
+/// cancels it.
+pub fn spawn_previewer(
+    tx: mpsc::UnboundedSender<PreviewResult>,
+    path: PathBuf,
+    command: String,
+    timeout: Duration,
+) -> Arc<AtomicBool> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&cancel);
+    std::thread::spawn(move || {
+        let result = previewer::run_previewer(&command, timeout, &flag);
+        if !flag.load(Ordering::Relaxed) {
+            let _ = tx.send(PreviewResult { path, result });
+        }
+    });
+    cancel
+}
+
+/// Cancel the previewer in flight (if any) and drop a queued request.
+pub fn cancel_preview(state: &mut AppState) {
+    if let Some(cancel) = state.preview_cancel.take() {
+        cancel.store(true, Ordering::Relaxed);
+    }
+    state.preview_request = None;
+}
+
+/// Queue a previewer for a freshly inspected selection when one of the
+/// configured patterns matches.  Images and directories are skipped.
+pub fn request_preview(state: &mut AppState, info: &mut InspectorInfo) {
+    let is_file = info.kind == "File" || info.target_kind.as_deref() == Some("File");
+    if !is_file || info.is_image() {
+        return;
+    }
+    let Some(template) =
+        previewer::find_previewer(&state.config.previewers, &info.path, info.detected_type.as_deref())
+    else {
+        return;
+    };
+    let command = previewer::expand_template(template, info.preview_path());
+    cancel_preview(state);
+    state.preview_request = Some((info.path.clone(), command));
+    info.external_preview = Some(ExternalPreview::Running);
+}
+
+/// Store a finished preview on the current selection if it still matches.
+pub fn apply_preview_result(state: &mut AppState, result: PreviewResult) {
+    let Some(info) = state.inspector_info.as_mut() else {
+        return;
+    };
+    if info.path != result.path {
+        return;
+    }
+    state.preview_cancel = None;
+    info.external_preview = Some(match result.result {
+        Ok(lines) => ExternalPreview::Output(lines),
+        Err(reason) => ExternalPreview::Failed(reason),
+    });
+}
@@ -3,8 +3,9 @@
 //! Keeping these definitions outside the input handler lets both the handler
 //! and UI renderers consume the same source of truth without cross-importing.
 
+use super::handler;
 use super::state::{ActiveView, AppState};
-use crate::config::PanelLayoutMode;
+use crate::config::{AppConfig, PanelLayoutMode, SlowFsMode};
 
 /// A single item in the settings menu.
 pub enum SettingsItem {
@@ -18,12 +19,25 @@ pub enum SettingsItem {
         label: &'static str,
         get: fn(&AppState) -> bool,
         set: fn(&mut AppState, bool),
+        default: bool,
     },
     /// Cycles through a finite set of values.
     Cycle {
         label: &'static str,
         value: fn(&AppState) -> String,
         cycle: fn(&mut AppState),
+        is_default: fn(&AppState) -> bool,
+        reset: fn(&mut AppState),
+    },
+    /// Runs a one-off command (e.g. opening a confirmation).
+    Command {
+        label: &'static str,
+        run: fn(&mut AppState),
+    },
+    /// Read-only information row (e.g. version, config path).
+    Info {
+        label: &'static str,
+        value: fn(&AppState) -> String,
     },
 }
 
@@ -32,9 +46,127 @@ impl SettingsItem {
         match self {
             Self::Submenu { label, .. }
             | Self::Toggle { label, .. }
-            | Self::Cycle { label, .. } => label,
+            | Self::Cycle { label, .. }
+            | Self::Command { label, .. }
+            | Self::Info { label, .. } => label,
         }
     }
+
+    /// Whether the item currently holds its default value.  Submenus and
+    /// commands have no value and always count as default.
+    pub fn is_default(&self, state: &AppState) -> bool {
+        match self {
+            Self::Toggle { get, default, .. } => get(state) == *default,
+            Self::Cycle { is_default, .. } => is_default(state),
+            Self::Submenu { .. } | Self::Command { .. } | Self::Info { .. } => true,
+        }
+    }
+
+    /// Restore the item's default value (no-op for submenus / commands).
+    pub fn reset(&self, state: &mut AppState) {
+        match self {
+            Self::Toggle { set, default, .. } => {
+                if self.is_default(state) {
+                    return;
+                }
+                set(state, *default);
+            }
+            Self::Cycle { reset, .. } => reset(state),
+            Self::Submenu { .. } | Self::Command { .. } | Self::Info { .. } => {}
+        }
+    }
+}
+
+/// Reset every settings item to its default (keybindings are untouched).
+pub fn reset_all_settings(state: &mut AppState) {
+    for item in SETTINGS_ITEMS {
+        if !item.is_default(state) {
+            item.reset(state);
+        }
+    }
+    state.status_message = Some("All settings reset to defaults".to_string());
+}
+
+/// Swap in a config re-read from disk and apply what changed live, the
+/// way the matching settings items would, without writing it back.
+pub fn apply_reloaded_config(state: &mut AppState, config: AppConfig) {
+    let old = std::mem::replace(&mut state.config, config);
+
+    let sort_mode = state.config.sort_mode;
+    if sort_mode != state.walk_config.sort_mode {
+        handler::preserving_selection(state, |state| {
+            state.walk_config.sort_mode = sort_mode;
+            crate::core::fs::resort_tree(&mut state.tree, sort_mode);
+        });
+    }
+    if state.config.group_by != state.grouping_config.group_by {
+        let group_by = state.config.group_by;
+        handler::preserving_selection(state, |state| state.grouping_config.group_by = group_by);
+    }
+    if state.config.grouping_min_size != state.grouping_config.min_group_size {
+        let min = state.config.grouping_min_size;
+        handler::preserving_selection(state, |state| state.grouping_config.min_group_size = min);
+    }
+    if state.config.show_git_status != state.walk_config.show_git_status {
+        state.walk_config.show_git_status = state.config.show_git_status;
+        state.git_statuses.clear();
+        handler::request_git_status(state);
+    }
+    if state.config.one_file_system != old.one_file_system {
+        // Directory visibility changes; rebuild like the toggle does.
+        state.dir_local_sums.clear();
+        handler::rebuild_tree(state);
+    } else if state.config.dedup_hard_links != old.dedup_hard_links {
+        state.dir_local_sums.clear();
+        state.needs_size_recompute = true;
+    }
+    if state.config.size_view != old.size_view {
+        // The size maps are still arranged for the old view.
+        let view = state.config.size_view;
+        state.config.size_view = old.size_view;
+        state.set_size_view(view);
+    }
+    if state.config.slow_fs != old.slow_fs {
+        state.slow_fs_mode = state.config.slow_fs;
+        handler::apply_slow_fs_mode(state);
+    }
+    if state.config.syntax_highlight != old.syntax_highlight {
+        handler::apply_syntax_highlight(state);
+    }
+    if state.config.mouse != old.mouse {
+        state.mouse_enabled = state.config.mouse;
+    }
+    if state.config.a11y != old.a11y {
+        state.a11y = state.config.a11y;
+    }
+    if state.config.search != old.search {
+        let options = state.config.search;
+        handler::set_search_options(state, options);
+    }
+    if state.config.size_cache != old.size_cache {
+        state.size_cache_enabled = state.config.size_cache;
+    }
+    if state.config.exclude_patterns != old.exclude_patterns {
+        exclude_patterns_changed(state);
+    }
+}
+
+/// Re-walk everything after the exclude patterns changed: cached local
+/// results (and entries carried over from the on-disk cache) were
+/// computed with the old ones.
+pub fn exclude_patterns_changed(state: &mut AppState) {
+    state.dir_local_sums.clear();
+    state.size_cache_carry.clear();
+    state.needs_size_recompute = true;
+}
+
+/// Settings value of the grouping threshold: `"5 files"` or `"disabled"`.
+fn grouping_threshold_label(min: usize) -> String {
+    if min == 0 {
+        "disabled".to_string()
+    } else {
+        format!("{min} files")
+    }
 }
 
 /// All items shown in the settings popup, in display order.
@@ -46,6 +178,7 @@ pub static SETTINGS_ITEMS: &[SettingsItem] = &[
     SettingsItem::Toggle {
         label: "Dedup Hard Links",
         get: |s| s.config.dedup_hard_links,
+        default: true,
         set: |s, v| {
             s.config.dedup_hard_links = v;
             let _ = s.config.save();
@@ -53,9 +186,14 @@ pub static SETTINGS_ITEMS: &[SettingsItem] = &[
             s.needs_size_recompute = true;
         },
     },
+    SettingsItem::Submenu {
+        label: "Size Exclusions",
+        view: ActiveView::ExcludePatterns,
+    },
     SettingsItem::Toggle {
         label: "One File System",
         get: |s| s.config.one_file_system,
+        default: false,
         set: |s, v| {
             s.config.one_file_system = v;
             let _ = s.config.save();
@@ -69,14 +207,246 @@ pub static SETTINGS_ITEMS: &[SettingsItem] = &[
                 s.tree_state.selected = 0;
                 s.tree_state.offset = 0;
                 s.file_sizes.clear();
+                s.file_sizes_alt.clear();
                 s.dir_local_sums.clear();
                 s.needs_size_recompute = true;
             }
         },
     },
+    SettingsItem::Toggle {
+        label: "Persist Size Cache",
+        get: |s| s.size_cache_enabled,
+        default: true,
+        set: |s, v| {
+            // Turning it back on mid-session only affects the write on exit.
+            s.size_cache_enabled = v;
+            s.config.size_cache = v;
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Cycle {
+        label: "Size View",
+        value: |s| s.config.size_view.label().to_string(),
+        is_default: |s| s.config.size_view == AppConfig::default().size_view,
+        reset: |s| {
+            s.set_size_view(AppConfig::default().size_view);
+            let _ = s.config.save();
+        },
+        cycle: |s| {
+            s.set_size_view(s.config.size_view.next());
+            let _ = s.config.save();
+            s.status_message = Some(format!("Size view: {}", s.config.size_view.label()));
+        },
+    },
+    SettingsItem::Cycle {
+        label: "Icons",
+        value: |s| s.config.icons.label().to_string(),
+        is_default: |s| s.config.icons == AppConfig::default().icons,
+        reset: |s| {
+            s.config.icons = AppConfig::default().icons;
+            let _ = s.config.save();
+        },
+        cycle: |s| {
+            s.config.icons = s.config.icons.next();
+            let _ = s.config.save();
+            s.status_message = Some(format!("Icons: {}", s.config.icons.label()));
+        },
+    },
+    SettingsItem::Cycle {
+        label: "Size Share",
+        value: |s| s.config.size_share.label().to_string(),
+        is_default: |s| s.config.size_share == AppConfig::default().size_share,
+        reset: |s| {
+            s.config.size_share = AppConfig::default().size_share;
+            let _ = s.config.save();
+        },
+        cycle: |s| {
+            s.config.size_share = s.config.size_share.next();
+            let _ = s.config.save();
+            s.status_message = Some(format!("Size share: {}", s.config.size_share.label()));
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Size Heat Colors",
+        get: |s| s.config.heat_mode,
+        default: false,
+        set: |s, v| {
+            s.config.heat_mode = v;
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Watch Filesystem",
+        get: |s| s.config.watch_fs,
+        default: false,
+        set: |s, v| {
+            s.config.watch_fs = v;
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Git Status Badges",
+        get: |s| s.walk_config.show_git_status,
+        default: true,
+        set: |s, v| {
+            s.walk_config.show_git_status = v;
+            s.config.show_git_status = v;
+            let _ = s.config.save();
+            s.git_statuses.clear();
+            handler::request_git_status(s);
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Entry Counts",
+        get: |s| s.config.show_entry_counts,
+        default: true,
+        set: |s, v| {
+            s.config.show_entry_counts = v;
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Toggle {
+        label: "File Counts",
+        get: |s| s.config.show_file_counts,
+        default: false,
+        set: |s, v| {
+            s.config.show_file_counts = v;
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Syntax Highlighting",
+        get: |s| s.config.syntax_highlight,
+        default: true,
+        set: |s, v| {
+            s.config.syntax_highlight = v;
+            let _ = s.config.save();
+            handler::apply_syntax_highlight(s);
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Indent Guides",
+        get: |s| s.config.show_indent_guides,
+        default: true,
+        set: |s, v| {
+            s.config.show_indent_guides = v;
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Alternate Row Shading",
+        get: |s| s.config.alternate_rows,
+        default: false,
+        set: |s, v| {
+            s.config.alternate_rows = v;
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Screen Reader Mode (experimental)",
+        get: |s| s.a11y,
+        default: false,
+        set: |s, v| {
+            s.a11y = v;
+            s.config.a11y = v;
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Highlight Recent Changes",
+        get: |s| s.config.highlight_recent,
+        default: false,
+        set: |s, v| {
+            s.config.highlight_recent = v;
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Cycle {
+        label: "Recent Window",
+        value: |s| s.config.recent_window.label().to_string(),
+        is_default: |s| s.config.recent_window == AppConfig::default().recent_window,
+        reset: |s| {
+            s.config.recent_window = AppConfig::default().recent_window;
+            let _ = s.config.save();
+        },
+        cycle: |s| {
+            s.config.recent_window = s.config.recent_window.next();
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Confirm cd on Exit",
+        get: |s| s.config.confirm_cd,
+        default: false,
+        set: |s, v| {
+            s.config.confirm_cd = v;
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Cycle {
+        label: "Slow Filesystem Mode",
+        value: |s| {
+            if s.slow_fs_mode == SlowFsMode::Auto {
+                let detected = if s.slow_fs_detected { "slow" } else { "fast" };
+                format!("auto ({detected})")
+            } else {
+                s.slow_fs_mode.label().to_string()
+            }
+        },
+        is_default: |s| s.slow_fs_mode == AppConfig::default().slow_fs,
+        reset: |s| {
+            s.slow_fs_mode = AppConfig::default().slow_fs;
+            s.config.slow_fs = s.slow_fs_mode;
+            let _ = s.config.save();
+            handler::apply_slow_fs_mode(s);
+        },
+        cycle: |s| {
+            s.slow_fs_mode = s.slow_fs_mode.next();
+            s.config.slow_fs = s.slow_fs_mode;
+            let _ = s.config.save();
+            handler::apply_slow_fs_mode(s);
+            s.status_message = Some(if s.is_slow_fs() {
+                "Slow filesystem mode: on".to_string()
+            } else {
+                "Slow filesystem mode: off".to_string()
+            });
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Delete Permanently",
+        get: |s| s.config.permanent_delete,
+        default: false,
+        set: |s, v| {
+            s.config.permanent_delete = v;
+            let _ = s.config.save();
+        },
+    },
+    SettingsItem::Toggle {
+        label: "Mouse Capture",
+        get: |s| s.mouse_enabled,
+        default: true,
+        set: |s, v| {
+            s.mouse_enabled = v;
+            s.dragging_splitter = false;
+            s.dragging_tree_scrollbar = false;
+            s.config.mouse = v;
+            let _ = s.config.save();
+        },
+    },
     SettingsItem::Cycle {
         label: "Double-click Window",
-        value: |s| format!("{}ms", s.config.double_click_ms),
+        value: |s| {
+            if s.mouse_enabled {
+                format!("{}ms", s.config.double_click_ms)
+            } else {
+                "mouse off".to_string()
+            }
+        },
+        is_default: |s| s.config.double_click_ms == AppConfig::default().double_click_ms,
+        reset: |s| {
+            s.config.double_click_ms = AppConfig::default().double_click_ms;
+            let _ = s.config.save();
+        },
         cycle: |s| {
             const WINDOWS: &[u64] = &[150, 200, 250, 300, 400, 500];
             let current = s.config.double_click_ms;
@@ -90,6 +460,11 @@ pub static SETTINGS_ITEMS: &[SettingsItem] = &[
     SettingsItem::Cycle {
         label: "Panel Layout",
         value: |s| s.config.panel_layout.label().to_string(),
+        is_default: |s| s.config.panel_layout == AppConfig::default().panel_layout,
+        reset: |s| {
+            s.config.panel_layout = AppConfig::default().panel_layout;
+            let _ = s.config.save();
+        },
         cycle: |s| {
             let idx = PanelLayoutMode::ALL
                 .iter()
@@ -100,9 +475,30 @@ pub static SETTINGS_ITEMS: &[SettingsItem] = &[
             s.status_message = Some(format!("Layout: {}", s.config.panel_layout.label()));
         },
     },
+    SettingsItem::Cycle {
+        label: "Status Lines",
+        value: |s| match s.config.status_lines {
+            0 => "hidden".to_string(),
+            n => n.to_string(),
+        },
+        is_default: |s| s.config.status_lines == AppConfig::default().status_lines,
+        reset: |s| {
+            s.config.status_lines = AppConfig::default().status_lines;
+            let _ = s.config.save();
+        },
+        cycle: |s| {
+            s.config.status_lines = (s.config.status_lines + 1) % 3;
+            let _ = s.config.save();
+        },
+    },
     SettingsItem::Cycle {
         label: "Panel Split",
         value: |s| format!("{}%", s.config.panel_split_pct),
+        is_default: |s| s.config.panel_split_pct == AppConfig::default().panel_split_pct,
+        reset: |s| {
+            s.config.panel_split_pct = AppConfig::default().panel_split_pct;
+            let _ = s.config.save();
+        },
         cycle: |s| {
             const SPLITS: &[u16] = &[30, 40, 50, 60, 70];
             let idx = SPLITS
@@ -114,5 +510,108 @@ pub static SETTINGS_ITEMS: &[SettingsItem] = &[
             s.status_message = Some(format!("Panel split: {}%", s.config.panel_split_pct));
         },
     },
+    SettingsItem::Cycle {
+        label: "Default Search Mode",
+        value: |s| s.config.default_search_mode.label().to_string(),
+        is_default: |s| s.config.default_search_mode == AppConfig::default().default_search_mode,
+        reset: |s| {
+            s.config.default_search_mode = AppConfig::default().default_search_mode;
+            let _ = s.config.save();
+        },
+        cycle: |s| {
+            s.config.default_search_mode = s.config.default_search_mode.next();
+            s.search_mode = s.config.default_search_mode;
+            let _ = s.config.save();
+            s.status_message =
+                Some(format!("Default search mode: {}", s.config.default_search_mode.label()));
+        },
+    },
+    SettingsItem::Cycle {
+        label: "Group Files By",
+        value: |s| s.grouping_config.group_by.label().to_string(),
+        is_default: |s| s.grouping_config.group_by == AppConfig::default().group_by,
+        reset: |s| handler::set_group_by(s, AppConfig::default().group_by),
+        cycle: |s| {
+            handler::set_group_by(s, s.grouping_config.group_by.next());
+            s.status_message = Some(format!("Group by: {}", s.grouping_config.group_by.label()));
+        },
+    },
+    SettingsItem::Cycle {
+        label: "Group Threshold",
+        value: |s| grouping_threshold_label(s.grouping_config.min_group_size),
+        is_default: |s| s.grouping_config.min_group_size == AppConfig::default().grouping_min_size,
+        reset: |s| handler::set_grouping_min_size(s, AppConfig::default().grouping_min_size),
+        cycle: |s| {
+            // 0 (disabled) comes last.
+            const SIZES: &[usize] = &[3, 5, 8, 12, 20, 50, 0];
+            let current = s.grouping_config.min_group_size;
+            let idx = SIZES.iter().position(|&n| n == current).unwrap_or(1);
+            let next = SIZES[(idx + 1) % SIZES.len()];
+            handler::set_grouping_min_size(s, next);
+            s.status_message = Some(format!("Group threshold: {}", grouping_threshold_label(next)));
+        },
+    },
+    SettingsItem::Cycle {
+        label: "Sort Order",
+        value: |s| s.walk_config.sort_mode.label().to_string(),
+        is_default: |s| s.walk_config.sort_mode == AppConfig::default().sort_mode,
+        reset: |s| handler::set_sort_mode(s, AppConfig::default().sort_mode),
+        cycle: |s| {
+            handler::set_sort_mode(s, s.walk_config.sort_mode.next());
+            s.status_message = Some(format!("Sort: {}", s.walk_config.sort_mode.label()));
+        },
+    },
+    SettingsItem::Cycle {
+        label: "Bulk Pin Limit",
+        value: |s| s.config.bulk_pin_limit.to_string(),
+        is_default: |s| s.config.bulk_pin_limit == AppConfig::default().bulk_pin_limit,
+        reset: |s| {
+            s.config.bulk_pin_limit = AppConfig::default().bulk_pin_limit;
+            let _ = s.config.save();
+        },
+        cycle: |s| {
+            const LIMITS: &[usize] = &[10, 25, 50, 100, 200];
+            let idx = LIMITS
+                .iter()
+                .position(|n| *n == s.config.bulk_pin_limit)
+                .unwrap_or(2);
+            s.config.bulk_pin_limit = LIMITS[(idx + 1) % LIMITS.len()];
+            let _ = s.config.save();
+            s.status_message = Some(format!("Bulk pin limit: {}", s.config.bulk_pin_limit));
+        },
+    },
+    SettingsItem::Command {
+        label: "Clear Size Cache",
+        run: crate::app::size_runtime::clear_size_cache,
+    },
+    SettingsItem::Command {
+        label: "Edit Config File…",
+        run: |s| s.edit_config_requested = true,
+    },
+    SettingsItem::Command {
+        label: "Reset All Settings",
+        run: |s| s.active_view = ActiveView::ConfirmResetSettings,
+    },
+    // ── About ──
+    SettingsItem::Info {
+        label: "Version",
+        value: |_| crate::VERSION.to_string(),
+    },
+    SettingsItem::Info {
+        label: "Config",
+        value: |_| crate::config::config_path().display().to_string(),
+    },
+    SettingsItem::Info {
+        label: "Size Cache",
+        value: |_| crate::core::size_cache::cache_path().display().to_string(),
+    },
+    // Pins, pin sets and bookmarks are saved in the config file.
+    SettingsItem::Info {
+        label: "Session",
+        value: |_| format!("{} (pins, pin sets, bookmarks)", crate::config::config_path().display()),
+    },
+    SettingsItem::Info {
+        label: "Terminal",
+        value: |_| crate::ui::caps::TerminalCaps::detect().summary(),
+    },
 ];
-
@@ -3,23 +3,125 @@
 //! This module owns worker orchestration and cascade finalization while
 //! keeping low-level filesystem math in `core::size`.
 
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use crate::app::state::AppState;
-use crate::core::size::{self, classify_file, get_dev, is_same_device, DirLocalResult, InodeMap};
+use crate::config::SizeView;
+use crate::core::size::{
+    self, allocated_size, classify_file, get_dev, is_same_device, merge_inode_maps, DirLocalResult, EntryCounts,
+    InodeMap, ScanCounters,
+};
+use crate::core::size_cache::{self, CacheEntry};
+use crossbeam_deque::{Injector, Steal, Stealer, Worker};
+use globset::GlobSet;
 
 #[derive(Debug)]
 pub enum SizeUpdate {
-    File { path: PathBuf, size: u64 },
+    File { path: PathBuf, size: u64, allocated: u64 },
+    /// A directory's own walk; `local.mtime` is the directory mtime
+    /// before the walk (cache watermark).  `elapsed` is the walk time
+    /// summed over every worker that took a piece of it.
     DirLocalDone {
         dir: PathBuf,
-        unique_sum: u64,
-        hardlinks: InodeMap,
+        local: DirLocalResult,
+        elapsed: Duration,
     },
     WorkerDone,
+    /// Running totals, sent every [`PROGRESS_INTERVAL`] while a scan runs
+    /// and once more by each worker as it finishes.
+    Progress {
+        files_scanned: u64,
+        bytes_seen: u64,
+        dirs_remaining: usize,
+        dirs_walked: u64,
+    },
+    /// A worker stopped before the jobs ran out (it panicked).
+    WorkerFailed { reason: String },
+    /// Total of one directory walked on demand (slow-filesystem mode).
+    DirTotal {
+        dir: PathBuf,
+        total: u64,
+        apparent: u64,
+        allocated: u64,
+        counts: EntryCounts,
+        /// An excluded subdirectory was left out of the total.
+        excluded: bool,
+    },
+}
+
+/// How often a running scan reports [`SizeUpdate::Progress`].
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Counters of the current scan as last reported by the workers.  Kept
+/// at the final values once the scan finishes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScanProgress {
+    pub files_scanned: u64,
+    pub bytes_seen: u64,
+    pub dirs_remaining: usize,
+    /// Directories read so far, hidden ones included.
+    pub dirs_walked: u64,
+}
+
+impl ScanProgress {
+    /// e.g. "48k files, 3.2 GiB, 12 dirs left".
+    pub fn summary(&self) -> String {
+        format!(
+            "{} files, {}, {} dirs left",
+            crate::core::grouping::human_count(self.files_scanned),
+            crate::core::grouping::human_size(self.bytes_seen),
+            self.dirs_remaining,
+        )
+    }
+}
+
+/// Where the time went in one completed scan, for the scan statistics
+/// popup.  Only cheap counters are kept while the scan runs.
+#[derive(Debug, Clone)]
+pub struct ScanStats {
+    pub wall_time: Duration,
+    pub workers: usize,
+    pub dirs_walked: u64,
+    pub files_statted: u64,
+    pub bytes_summed: u64,
+    /// Tree directories whose own sums came from `dir_local_sums`.
+    pub cache_hits: usize,
+    /// Tree directories walked this time.
+    pub cache_misses: usize,
+    /// Slowest tree directories by summed walk time, slowest first.
+    pub slowest: Vec<(PathBuf, Duration)>,
+}
+
+/// Directories listed under "slowest" in [`ScanStats`].
+const SLOWEST_DIRS: usize = 5;
+
+/// How long workers may all be gone while `WorkerDone` messages are still
+/// outstanding before the scan is declared failed.  Covers messages that
+/// are merely in flight.
+const WORKER_EXIT_GRACE: Duration = Duration::from_secs(2);
+
+/// Decrements the live-worker count when a worker thread ends, and reports
+/// a failure if it ended without finishing cleanly (e.g. it panicked).
+struct WorkerExitGuard {
+    tx: tokio::sync::mpsc::UnboundedSender<(u64, SizeUpdate)>,
+    generation: u64,
+    alive: Arc<AtomicUsize>,
+    clean: bool,
+}
+
+impl Drop for WorkerExitGuard {
+    fn drop(&mut self) {
+        self.alive.fetch_sub(1, Ordering::Relaxed);
+        if !self.clean {
+            let reason = "size worker crashed".to_string();
+            let _ = self.tx.send((self.generation, SizeUpdate::WorkerFailed { reason }));
+        }
+    }
 }
 
 /// Shared read-only context available to every worker thread.
@@ -28,12 +130,321 @@ struct WorkerCtx {
     /// Workers skip these during their walk because the cascade
     /// handles them separately.
     tree_dirs: HashSet<PathBuf>,
+    /// Tree directories whose children are loaded.  Only their files can
+    /// be rendered, so only they report [`SizeUpdate::File`]; the rest
+    /// just contribute to their directory's local sum.
+    listed_dirs: HashSet<PathBuf>,
     /// Whether hard-link dedup is enabled.
     dedup_hard_links: bool,
     /// When `true`, don't descend into directories on a different device.
     one_file_system: bool,
     /// Device ID of the root directory (for `one_file_system` checks).
     root_dev: u64,
+    /// Compiled `exclude_patterns`; matching directories aren't walked.
+    excludes: GlobSet,
+    /// Files and bytes walked so far, across all workers.
+    counters: ScanCounters,
+    /// Jobs queued at the start, and how many have been walked.
+    job_count: usize,
+    dirs_done: AtomicUsize,
+    /// Jobs walked, one directory each.
+    dirs_walked: AtomicU64,
+}
+
+impl WorkerCtx {
+    fn progress_update(&self) -> SizeUpdate {
+        let done = self.dirs_done.load(Ordering::Relaxed);
+        SizeUpdate::Progress {
+            files_scanned: self.counters.files.load(Ordering::Relaxed),
+            bytes_seen: self.counters.bytes.load(Ordering::Relaxed),
+            dirs_remaining: self.job_count.saturating_sub(done),
+            dirs_walked: self.dirs_walked.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// How long an idle worker waits before looking for stealable work again
+/// while others are still walking.
+const IDLE_BACKOFF: Duration = Duration::from_micros(200);
+
+/// One unit of work for the size workers.
+enum SizeJob {
+    /// A display-tree directory's own entries.
+    TreeDir(PathBuf),
+    /// A directory below `owner` that isn't in the tree; its sizes are
+    /// folded into `owner`'s local result.
+    Subtree { dir: PathBuf, owner: Arc<SplitDir> },
+}
+
+/// Work-stealing job pool.  Tree directories start in `injector` (largest
+/// first); subdirectories a worker discovers go on its own deque, where
+/// idle workers can steal them.
+struct JobPool {
+    injector: Injector<SizeJob>,
+    stealers: Vec<Stealer<SizeJob>>,
+    /// Jobs queued or running.  Workers only exit once this is zero, since
+    /// a running job may still split off more work.
+    pending: AtomicUsize,
+}
+
+/// Counts a job as finished when dropped, so a panicking job doesn't
+/// leave the other workers waiting for it forever.
+struct JobDone<'a>(&'a AtomicUsize);
+
+impl Drop for JobDone<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Local result of a tree directory whose hidden subtree is being walked
+/// in pieces.  Whoever finishes the last piece reports it.
+struct SplitDir {
+    dir: PathBuf,
+    local: Mutex<DirLocalResult>,
+    pending: AtomicUsize,
+    /// Walk time of the finished pieces, in nanoseconds.
+    busy_nanos: AtomicU64,
+}
+
+/// Everything one worker thread needs to walk jobs and report them.
+struct SizeWorker {
+    ctx: Arc<WorkerCtx>,
+    pool: Arc<JobPool>,
+    /// This worker's own deque; the others steal from its far end.
+    local: Worker<SizeJob>,
+    cancel: Arc<AtomicBool>,
+    tx: tokio::sync::mpsc::UnboundedSender<(u64, SizeUpdate)>,
+    generation: u64,
+}
+
+impl SizeWorker {
+    fn send(&self, update: SizeUpdate) {
+        let _ = self.tx.send((self.generation, update));
+    }
+
+    /// Next job: from the own deque, then the injector, then another
+    /// worker.  `None` once every job is done (or the scan is cancelled).
+    fn next_job(&self) -> Option<SizeJob> {
+        loop {
+            if self.cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            if let Some(job) = self.local.pop() {
+                return Some(job);
+            }
+            // One at a time from the injector to keep its largest-first order.
+            let stolen = std::iter::repeat_with(|| {
+                self.pool
+                    .injector
+                    .steal()
+                    .or_else(|| self.pool.stealers.iter().map(Stealer::steal).collect())
+            })
+            .find(|steal| !steal.is_retry())
+            .and_then(Steal::success);
+            if stolen.is_some() {
+                return stolen;
+            }
+            if self.pool.pending.load(Ordering::Acquire) == 0 {
+                return None;
+            }
+            // Others are still walking and may split off more work.
+            std::thread::sleep(IDLE_BACKOFF);
+        }
+    }
+
+    /// Whether a non-tree subdirectory should be walked; flags `local`
+    /// when an exclude pattern skips it.
+    fn should_descend(&self, path: &Path, local: &mut DirLocalResult) -> bool {
+        if size::is_excluded(&self.ctx.excludes, path) {
+            local.excluded = true;
+            return false;
+        }
+        if self.ctx.one_file_system {
+            // Check mount boundary before descending.
+            return std::fs::metadata(path).is_ok_and(|meta| is_same_device(&meta, self.ctx.root_dev));
+        }
+        true
+    }
+
+    /// Walk a tree directory's own files; hidden subdirectories become
+    /// [`SizeJob::Subtree`] jobs instead of being recursed here.
+    fn walk_tree_dir(&self, dir: PathBuf) {
+        let ctx = &self.ctx;
+        let started = Instant::now();
+        let mtime = size::dir_mtime(&dir);
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(e) => e,
+            Err(_) => {
+                ctx.dirs_done.fetch_add(1, Ordering::Relaxed);
+                self.send(SizeUpdate::DirLocalDone {
+                    dir,
+                    local: DirLocalResult::default(),
+                    elapsed: started.elapsed(),
+                });
+                return;
+            }
+        };
+
+        let mut local = DirLocalResult {
+            mtime,
+            ..DirLocalResult::default()
+        };
+        let report_files = ctx.listed_dirs.contains(&dir);
+        let (mut own_files, mut own_bytes) = (0, 0);
+        let mut hidden = Vec::new();
+
+        for entry in entries.flatten() {
+            if self.cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            let ft = match entry.file_type() {
+                Ok(ft) => ft,
+                Err(_) => continue,
+            };
+            let path = entry.path();
+
+            if ft.is_file() {
+                if let Ok(meta) = entry.metadata() {
+                    let allocated = allocated_size(&meta);
+                    if report_files {
+                        self.send(SizeUpdate::File {
+                            path: path.clone(),
+                            size: meta.len(),
+                            allocated,
+                        });
+                    }
+                    let (size, inode_key) = classify_file(&meta, ctx.dedup_hard_links);
+                    local.add_file(size, allocated, inode_key);
+                    local.counts.files += 1;
+                    own_files += 1;
+                    own_bytes += meta.len();
+                }
+            } else if ft.is_dir() {
+                if ctx.tree_dirs.contains(&path) {
+                    // Tree child dir — cascade handles it.
+                } else if self.should_descend(&path, &mut local) {
+                    hidden.push(path);
+                }
+            } else if ft.is_symlink() {
+                local.counts.symlinks += 1;
+                if let Ok(meta) = std::fs::symlink_metadata(&path) {
+                    let s = meta.len();
+                    let allocated = allocated_size(&meta);
+                    if report_files {
+                        self.send(SizeUpdate::File {
+                            path: path.clone(),
+                            size: s,
+                            allocated,
+                        });
+                    }
+                    local.add_file(s, allocated, None);
+                }
+            } else {
+                local.counts.add_special(&ft);
+            }
+        }
+        ctx.counters.add(own_files, own_bytes);
+        local.folded = hidden
+            .iter()
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect();
+
+        let owner = Arc::new(SplitDir {
+            dir,
+            local: Mutex::new(local),
+            pending: AtomicUsize::new(1),
+            busy_nanos: AtomicU64::new(0),
+        });
+        self.split(&owner, hidden);
+        self.finish_piece(&owner, started);
+    }
+
+    /// Walk one hidden directory into `owner`: its own entries here, its
+    /// subdirectories as further jobs.
+    fn walk_subtree(&self, dir: &Path, owner: &Arc<SplitDir>) {
+        let ctx = &self.ctx;
+        let started = Instant::now();
+        let mut local = DirLocalResult {
+            folded_mtime: size::dir_mtime(dir),
+            ..DirLocalResult::default()
+        };
+        let mut subdirs = Vec::new();
+        let (mut files, mut bytes) = (0, 0);
+        for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+            if self.cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            let ft = match entry.file_type() {
+                Ok(ft) => ft,
+                Err(_) => continue,
+            };
+            let path = entry.path();
+            if ft.is_dir() {
+                if self.should_descend(&path, &mut local) {
+                    subdirs.push(path);
+                }
+            } else if ft.is_file() {
+                if let Ok(meta) = entry.metadata() {
+                    let (size, inode_key) = classify_file(&meta, ctx.dedup_hard_links);
+                    local.add_file(size, allocated_size(&meta), inode_key);
+                    local.counts.files += 1;
+                    files += 1;
+                    bytes += meta.len();
+                }
+            } else if ft.is_symlink() {
+                local.counts.symlinks += 1;
+                if let Ok(meta) = std::fs::symlink_metadata(&path) {
+                    local.add_file(meta.len(), allocated_size(&meta), None);
+                }
+            } else {
+                local.counts.add_special(&ft);
+            }
+        }
+        ctx.counters.add(files, bytes);
+        if let Ok(mut owner_local) = owner.local.lock() {
+            owner_local.absorb(local);
+        }
+        self.split(owner, subdirs);
+        self.finish_piece(owner, started);
+    }
+
+    /// Queue `dirs` as pieces of `owner` on this worker's deque.
+    fn split(&self, owner: &Arc<SplitDir>, dirs: Vec<PathBuf>) {
+        if dirs.is_empty() {
+            return;
+        }
+        // Count the pieces before they can finish.
+        owner.pending.fetch_add(dirs.len(), Ordering::AcqRel);
+        self.pool.pending.fetch_add(dirs.len(), Ordering::AcqRel);
+        for dir in dirs {
+            self.local.push(SizeJob::Subtree {
+                dir,
+                owner: Arc::clone(owner),
+            });
+        }
+    }
+
+    /// Mark one piece of `owner` (begun at `started`) walked, reporting
+    /// the directory once every piece is in.
+    fn finish_piece(&self, owner: &SplitDir, started: Instant) {
+        let nanos = u64::try_from(started.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        owner.busy_nanos.fetch_add(nanos, Ordering::Relaxed);
+        if owner.pending.fetch_sub(1, Ordering::AcqRel) != 1 {
+            return;
+        }
+        let local = match owner.local.lock() {
+            Ok(mut local) => std::mem::take(&mut *local),
+            Err(_) => return,
+        };
+        self.ctx.dirs_done.fetch_add(1, Ordering::Relaxed);
+        self.send(SizeUpdate::DirLocalDone {
+            dir: owner.dir.clone(),
+            local,
+            elapsed: Duration::from_nanos(owner.busy_nanos.load(Ordering::Relaxed)),
+        });
+    }
 }
 
 pub struct SizeComputeState {
@@ -47,11 +458,37 @@ pub struct SizeComputeState {
     children_unique: HashMap<PathBuf, u64>,
     /// Per-dir: merged hardlink maps from tree-children.
     children_hardlinks: HashMap<PathBuf, InodeMap>,
+    /// Per-dir: accumulated non-deduped hardlink bytes from tree-children.
+    children_hardlink_apparent: HashMap<PathBuf, u64>,
+    /// Per-dir: `children_unique` in allocated bytes.
+    children_allocated_unique: HashMap<PathBuf, u64>,
+    /// Per-dir: `children_hardlinks` in allocated bytes.
+    children_allocated_hardlinks: HashMap<PathBuf, InodeMap>,
+    /// Per-dir: accumulated symlink / special file counts from tree-children.
+    children_counts: HashMap<PathBuf, EntryCounts>,
+    /// Dirs with an excluded tree-child or an exclusion somewhere below one.
+    children_excluded: HashSet<PathBuf>,
+    /// Compiled `exclude_patterns`, shared with the workers.
+    excludes: GlobSet,
     /// Per-dir: the local walk result (unique_sum + hardlinks).
     local_done: HashMap<PathBuf, DirLocalResult>,
     finished: HashSet<PathBuf>,
     /// Shared flag used to signal worker threads to stop early.
     cancel: Arc<AtomicBool>,
+    /// Worker threads still running.
+    alive: Arc<AtomicUsize>,
+    /// Worker thread handles, joined on quit.
+    workers: Vec<JoinHandle<()>>,
+    /// When every worker thread was first seen gone with work outstanding.
+    all_exited_at: Option<Instant>,
+    /// Why the scan failed, if a worker reported or was detected dying.
+    failure: Option<String>,
+    // ── statistics (see [`ScanStats`]) ──
+    started: Instant,
+    worker_count: usize,
+    cache_hits: usize,
+    /// Walk time of each tree directory reported so far.
+    walk_times: Vec<(PathBuf, Duration)>,
 }
 
 impl SizeComputeState {
@@ -59,9 +496,452 @@ impl SizeComputeState {
         self.cancel.store(true, Ordering::Relaxed);
     }
 
+    /// Cancel the scan and wait up to `timeout` for the workers to exit.
+    /// A thread still blocked in a slow syscall after that is left behind.
+    pub fn shutdown(self, timeout: Duration) {
+        self.request_cancel();
+        let deadline = Instant::now() + timeout;
+        for handle in self.workers {
+            while !handle.is_finished() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            if handle.is_finished() {
+                let _ = handle.join();
+            }
+        }
+    }
+
     pub fn is_scanning(&self) -> bool {
         self.remaining_workers > 0
     }
+
+    /// Detect workers that exited without sending `WorkerDone`.  Once every
+    /// thread is gone and the grace period has passed, the scan is failed.
+    pub fn check_workers(&mut self) {
+        if self.remaining_workers == 0 || self.alive.load(Ordering::Relaxed) > 0 {
+            self.all_exited_at = None;
+            return;
+        }
+        let since = *self.all_exited_at.get_or_insert_with(Instant::now);
+        if since.elapsed() >= WORKER_EXIT_GRACE {
+            self.remaining_workers = 0;
+            self.failure
+                .get_or_insert_with(|| "size workers exited without finishing".to_string());
+        }
+    }
+
+    /// Statistics of this scan, given its final progress counters.
+    fn stats(&self, progress: &ScanProgress) -> ScanStats {
+        let mut slowest = self.walk_times.clone();
+        slowest.sort_by_key(|&(_, elapsed)| std::cmp::Reverse(elapsed));
+        slowest.truncate(SLOWEST_DIRS);
+        ScanStats {
+            wall_time: self.started.elapsed(),
+            workers: self.worker_count,
+            dirs_walked: progress.dirs_walked,
+            files_statted: progress.files_scanned,
+            bytes_summed: progress.bytes_seen,
+            cache_hits: self.cache_hits,
+            cache_misses: self.walk_times.len(),
+            slowest,
+        }
+    }
+
+    /// Take the failure reason (reported once).
+    pub fn take_failure(&mut self) -> Option<String> {
+        self.failure.take()
+    }
+}
+
+/// Walk one directory on a background thread and report its total as
+/// [`SizeUpdate::DirTotal`].  Used instead of the full scan on slow
+/// filesystems.  Returns the cancel flag.
+pub fn spawn_dir_total(
+    tx: tokio::sync::mpsc::UnboundedSender<(u64, SizeUpdate)>,
+    dir: PathBuf,
+    dedup_hard_links: bool,
+    one_file_system: bool,
+    exclude_patterns: Vec<String>,
+) -> Arc<AtomicBool> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&cancel);
+    std::thread::spawn(move || {
+        let root_dev = get_dev(&dir);
+        let excludes = size::compile_excludes(&exclude_patterns);
+        let result = size::recursive_dir_size(
+            &dir,
+            &flag,
+            dedup_hard_links,
+            one_file_system,
+            root_dev,
+            &excludes,
+            None,
+        );
+        if flag.load(Ordering::Relaxed) {
+            return;
+        }
+        let total_unique = result.unique_sum;
+        let total = total_unique.saturating_add(result.hardlinks.values().sum::<u64>());
+        let apparent = total_unique.saturating_add(result.hardlink_apparent);
+        let allocated = result
+            .allocated_unique_sum
+            .saturating_add(result.allocated_hardlinks.values().sum::<u64>());
+        let counts = result.counts;
+        let _ = tx.send((
+            0,
+            SizeUpdate::DirTotal {
+                dir,
+                total,
+                apparent,
+                allocated,
+                counts,
+                excluded: result.excluded,
+            },
+        ));
+    });
+    cancel
+}
+
+/// Record an on-demand directory total.
+pub fn apply_dir_total(
+    state: &mut AppState,
+    dir: PathBuf,
+    total: u64,
+    apparent: u64,
+    allocated: u64,
+    counts: EntryCounts,
+    excluded: bool,
+) {
+    state.stale_sizes.remove(&dir);
+    state.size_on_demand_cancel = None;
+    state.record_dir_size(dir.clone(), total, allocated);
+    state.dir_entry_counts.insert(dir.clone(), counts);
+    if excluded {
+        state.dir_excluded.insert(dir.clone());
+    } else {
+        state.dir_excluded.remove(&dir);
+    }
+    let shown = state.dir_sizes.get(&dir).copied().unwrap_or(total);
+    state.status_message = Some(format!("{}: {}", dir.display(), crate::core::grouping::human_size(shown)));
+    state.dir_apparent_sizes.insert(dir, apparent);
+}
+
+/// Seed `dir_local_sums` and `file_sizes` from the on-disk cache so the
+/// first scan can finalize unchanged directories without any workers.
+/// Only entries under the root are checked (one `stat` each); the rest are
+/// carried over untouched so [`save_size_cache`] writes them back.
+pub fn load_size_cache(state: &mut AppState) {
+    if !state.size_cache_enabled {
+        return;
+    }
+    let cached = size_cache::load(
+        &size_cache::cache_path(),
+        state.config.dedup_hard_links,
+        state.config.one_file_system,
+        &state.config.exclude_patterns,
+    );
+    // Checking freshness would stat every cached dir, which is exactly
+    // what slow-filesystem mode avoids; sizes are on demand there anyway.
+    let validate = !state.is_slow_fs();
+    let excludes = size::compile_excludes(&state.config.exclude_patterns);
+    let (root_dev, one_file_system) = (get_dev(&state.cwd), state.config.one_file_system);
+    let descend = |path: &Path| {
+        !size::is_excluded(&excludes, path)
+            && (!one_file_system
+                || std::fs::metadata(path).is_ok_and(|meta| is_same_device(&meta, root_dev)))
+    };
+    for (dir, entry) in cached {
+        if !validate || !dir.starts_with(&state.cwd) {
+            state.size_cache_carry.insert(dir, entry);
+        } else if size_cache::is_fresh(&dir, &entry, descend) {
+            for (name, size, allocated) in &entry.files {
+                state.record_file_size(dir.join(name), *size, *allocated);
+            }
+            state.dir_local_sums.insert(dir, entry.local);
+        }
+    }
+}
+
+/// Write this session's local results (plus carried-over entries) to the
+/// on-disk cache.  Does nothing when the cache is disabled.
+pub fn save_size_cache(state: &AppState) -> anyhow::Result<()> {
+    if !state.size_cache_enabled {
+        return Ok(());
+    }
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let mut files: HashMap<&std::path::Path, Vec<(String, u64, u64)>> = HashMap::new();
+    let (apparent_sizes, allocated_sizes) = state.file_size_views();
+    for (path, &size) in apparent_sizes {
+        if let (Some(parent), Some(name)) = (path.parent(), path.file_name()) {
+            if state.dir_local_sums.contains_key(parent) {
+                let allocated = allocated_sizes.get(path).copied().unwrap_or(size);
+                files
+                    .entry(parent)
+                    .or_default()
+                    .push((name.to_string_lossy().into_owned(), size, allocated));
+            }
+        }
+    }
+    let current = state.dir_local_sums.iter().map(|(dir, local)| {
+        let entry = CacheEntry {
+            local: local.clone(),
+            files: files.remove(dir.as_path()).unwrap_or_default(),
+            last_used: now,
+        };
+        (dir.clone(), entry)
+    });
+    let carried = state
+        .size_cache_carry
+        .iter()
+        .filter(|(dir, _)| !state.dir_local_sums.contains_key(*dir))
+        .map(|(dir, entry)| (dir.clone(), entry.clone()));
+    size_cache::save(
+        &size_cache::cache_path(),
+        state.config.dedup_hard_links,
+        state.config.one_file_system,
+        &state.config.exclude_patterns,
+        current.collect::<Vec<_>>().into_iter().chain(carried),
+    )
+}
+
+/// Delete the on-disk size cache and forget entries carried over from it.
+/// Results computed this session are still written on exit while the
+/// cache is enabled.
+pub fn clear_size_cache(state: &mut AppState) {
+    state.size_cache_carry.clear();
+    state.status_message = Some(match size_cache::clear(&size_cache::cache_path()) {
+        Ok(()) => "Size cache cleared".to_string(),
+        Err(e) => format!("Failed to clear size cache: {e}"),
+    });
+}
+
+/// Size a `--stdin` tree from its listed files alone: each file is
+/// stat'ed and every directory totals the listed files below it.  Listed
+/// directories with nothing listed inside stay unsized.
+pub fn record_listed_sizes(state: &mut AppState) {
+    let files: Vec<PathBuf> = state
+        .tree
+        .nodes
+        .iter()
+        .filter(|n| !n.meta.is_dir && !n.meta.missing)
+        .map(|n| n.meta.path.clone())
+        .collect();
+    let mut totals: HashMap<PathBuf, (u64, u64)> = HashMap::new();
+    for path in files {
+        let Ok(meta) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        let (apparent, allocated) = (meta.len(), crate::core::size::allocated_size(&meta));
+        for dir in path.ancestors().skip(1).take_while(|d| d.starts_with(&state.cwd)) {
+            let total = totals.entry(dir.to_path_buf()).or_default();
+            total.0 += apparent;
+            total.1 += allocated;
+        }
+        state.record_file_size(path, apparent, allocated);
+    }
+    for (dir, (apparent, allocated)) in totals {
+        state.record_dir_size(dir, apparent, allocated);
+    }
+}
+
+/// Take a removed file's bytes off its parent's cached local result and
+/// every ancestor total, so deleting one file doesn't re-walk a directory
+/// of 100k entries.  Returns `false`, changing nothing, when that can't be
+/// done exactly: a scan is running (its totals would overwrite ours), the
+/// entry isn't a plain file, its size or the parent's local result is
+/// unknown, or the parent holds hard links.  Callers fall back to
+/// invalidating the parent.
+///
+/// The parent's cached mtime is left alone, so the on-disk cache still
+/// drops the patched entry on the next load.
+pub fn adjust_for_removal(state: &mut AppState, path: &Path) -> bool {
+    if state.scanning {
+        return false;
+    }
+    let Some(parent) = path.parent() else {
+        return false;
+    };
+    let is_plain_file = state
+        .tree
+        .nodes
+        .iter()
+        .any(|n| n.meta.path == path && !n.meta.is_dir && !n.meta.is_symlink);
+    if !is_plain_file {
+        return false;
+    }
+    let (apparent_files, allocated_files) = state.file_size_views();
+    let (Some(apparent), Some(allocated)) =
+        (apparent_files.get(path).copied(), allocated_files.get(path).copied())
+    else {
+        return false;
+    };
+    let Some(local) = state.dir_local_sums.get_mut(parent) else {
+        return false;
+    };
+    if !local.remove_unique_file(apparent, allocated) {
+        return false;
+    }
+
+    shift_ancestor_totals(state, parent, Some((apparent, allocated)), None);
+    true
+}
+
+/// Re-stat one file the watcher reported changed and move its parent's
+/// cached local result and every ancestor total by the difference, so a
+/// build or download keeps totals live without re-walking the directory.
+/// Handles files that appeared, grew or shrank, and files that came and
+/// went before the tree listed them; removals of listed files are left to
+/// [`adjust_for_removal`] when the directory is re-listed.
+///
+/// Returns `false`, possibly after adjusting other files of the same
+/// batch, when the change can't be applied exactly: a scan is running,
+/// the path is (or was) a directory or symlink, a hard link is involved,
+/// or the parent's local result or the file's previous size is unknown.
+/// Callers then invalidate the parent.
+pub fn adjust_for_change(state: &mut AppState, path: &Path) -> bool {
+    if state.scanning {
+        return false;
+    }
+    let Some(parent) = path.parent() else {
+        return false;
+    };
+    let Some(parent_id) = state.tree.nodes.iter().position(|n| n.meta.path == parent) else {
+        return false;
+    };
+    let node = state.tree.nodes.iter().find(|n| n.meta.path == path);
+    if node.is_some_and(|n| n.meta.is_dir || n.meta.is_symlink) {
+        return false;
+    }
+    let listed = node.is_some();
+
+    let (apparent_files, allocated_files) = state.file_size_views();
+    let old = apparent_files
+        .get(path)
+        .map(|&apparent| (apparent, allocated_files.get(path).copied().unwrap_or(apparent)));
+    let new = match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_file() => {
+            let (apparent, inode_key) = classify_file(&meta, state.config.dedup_hard_links);
+            if inode_key.is_some() {
+                return false;
+            }
+            Some((apparent, allocated_size(&meta)))
+        }
+        Ok(_) => return false,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(_) => return false,
+    };
+    if new.is_none() && listed {
+        return true;
+    }
+    // A file without a recorded size is only new if the parent's file
+    // sizes are known at all: cached results may lack them.
+    if old.is_none() && (listed || !file_sizes_known(state, parent_id)) {
+        return false;
+    }
+
+    let Some(local) = state.dir_local_sums.get_mut(parent) else {
+        return false;
+    };
+    if let Some((apparent, allocated)) = old {
+        if !local.remove_unique_file(apparent, allocated) {
+            return false;
+        }
+    }
+    if let Some((apparent, allocated)) = new {
+        local.add_file(apparent, allocated, None);
+        local.counts.files += 1;
+    }
+    shift_ancestor_totals(state, parent, old, new);
+    match new {
+        Some((apparent, allocated)) => state.record_file_size(path.to_path_buf(), apparent, allocated),
+        None => {
+            state.file_sizes.remove(path);
+            state.file_sizes_alt.remove(path);
+        }
+    }
+    true
+}
+
+/// Whether every file the tree lists under `dir_id` has a recorded size,
+/// or the directory held no bytes to begin with.  Sizes come either from
+/// the walk or from the on-disk cache for the whole directory at once.
+fn file_sizes_known(state: &AppState, dir_id: crate::core::tree::NodeId) -> bool {
+    let dir = &state.tree.get(dir_id).meta.path;
+    if state
+        .dir_local_sums
+        .get(dir)
+        .is_some_and(|local| local.unique_sum == 0 && local.hardlinks.is_empty())
+    {
+        return true;
+    }
+    let mut files = state
+        .tree
+        .get(dir_id)
+        .children
+        .iter()
+        .map(|&c| &state.tree.get(c).meta)
+        .filter(|meta| !meta.is_dir)
+        .peekable();
+    files.peek().is_some() && files.all(|meta| state.file_sizes.contains_key(&meta.path))
+}
+
+/// Move `dir` and every ancestor total from counting the file `old` to
+/// counting `new`, both `(apparent, allocated)` or `None` when the file
+/// is absent, which also moves the recursive file count.
+fn shift_ancestor_totals(
+    state: &mut AppState,
+    dir: &Path,
+    old: Option<(u64, u64)>,
+    new: Option<(u64, u64)>,
+) {
+    let shift = |total: &mut u64, old: u64, new: u64| {
+        *total = total.saturating_sub(old).saturating_add(new);
+    };
+    let (old_files, new_files) = (old.is_some() as u64, new.is_some() as u64);
+    let (old, new) = (old.unwrap_or_default(), new.unwrap_or_default());
+    let ((shown_old, other_old), (shown_new, other_new)) = match state.config.size_view {
+        SizeView::Apparent => (old, new),
+        SizeView::Allocated => ((old.1, old.0), (new.1, new.0)),
+    };
+    for dir in dir.ancestors() {
+        if let Some(total) = state.dir_sizes.get_mut(dir) {
+            shift(total, shown_old, shown_new);
+        }
+        if let Some(total) = state.dir_sizes_alt.get_mut(dir) {
+            shift(total, other_old, other_new);
+        }
+        if let Some(total) = state.dir_apparent_sizes.get_mut(dir) {
+            shift(total, old.0, new.0);
+        }
+        if let Some(counts) = state.dir_entry_counts.get_mut(dir) {
+            shift(&mut counts.files, old_files, new_files);
+        }
+    }
+}
+
+/// Surface a failed size scan with a retry hint.
+pub fn mark_scan_failed(state: &mut AppState, reason: String) {
+    let key = state.config.short_binding(crate::config::Action::RetrySizeScan);
+    state.status_message = Some(format!("Size scan failed: {reason} · {key}: retry"));
+    state.size_scan_failed = Some(reason);
+}
+
+/// Replace `size_compute` with a fresh scan, cancelling the running one.
+/// Directories whose local results are known finalize right away.
+pub fn restart_size_computation(
+    state: &mut AppState,
+    size_compute: &mut Option<SizeComputeState>,
+    tx: &tokio::sync::mpsc::UnboundedSender<(u64, SizeUpdate)>,
+) {
+    if let Some(old) = size_compute.as_ref() {
+        old.request_cancel();
+    }
+    state.size_scan_failed = None;
+    let compute = size_compute.insert(start_size_computation(state, tx));
+    state.scanning = compute.is_scanning();
+    finalize_ready_dirs(state, compute);
 }
 
 pub fn start_size_computation(
@@ -70,14 +950,19 @@ pub fn start_size_computation(
 ) -> SizeComputeState {
     state.size_compute_generation = state.size_compute_generation.wrapping_add(1);
     let generation = state.size_compute_generation;
+    state.scan_progress = ScanProgress::default();
 
     let cancel = Arc::new(AtomicBool::new(false));
 
     // Build a set of all directory paths that are nodes in the display tree.
     let mut tree_dirs = HashSet::new();
+    let mut listed_dirs = HashSet::new();
     for node in &state.tree.nodes {
         if node.meta.is_dir {
             tree_dirs.insert(node.meta.path.clone());
+            if !node.children.is_empty() {
+                listed_dirs.insert(node.meta.path.clone());
+            }
         }
     }
 
@@ -88,7 +973,8 @@ pub fn start_size_computation(
     let mut children_unique: HashMap<PathBuf, u64> = HashMap::new();
     let mut children_hardlinks: HashMap<PathBuf, InodeMap> = HashMap::new();
     let mut local_done: HashMap<PathBuf, DirLocalResult> = HashMap::new();
-    let mut jobs: VecDeque<PathBuf> = VecDeque::new();
+    // Each job with its last known own size (total minus tree children).
+    let mut jobs: Vec<(PathBuf, Option<u64>)> = Vec::new();
 
     for node in &state.tree.nodes {
         if !node.meta.is_dir {
@@ -119,14 +1005,42 @@ pub fn start_size_computation(
         children_unique.insert(dir_path.clone(), 0);
         children_hardlinks.insert(dir_path.clone(), InodeMap::new());
 
-        // Reuse cached local result if available.
-        if let Some(cached) = state.dir_local_sums.get(&dir_path) {
-            local_done.insert(dir_path, cached.clone());
-        } else {
-            jobs.push_back(dir_path);
+        // Reuse cached local result if available.  A directory listed since
+        // it was walked has never reported its files, so walk it again.
+        let files_known = node.children.iter().all(|&cid| {
+            let child = &state.tree.nodes[cid].meta;
+            child.is_dir || state.file_sizes.contains_key(&child.path)
+        });
+        match state.dir_local_sums.get(&dir_path) {
+            Some(cached) if files_known => {
+                local_done.insert(dir_path, cached.clone());
+            }
+            _ => {
+                let own_estimate = state.dir_sizes.get(&dir_path).map(|&total| {
+                    let in_children: u64 = node
+                        .children
+                        .iter()
+                        .map(|&cid| &state.tree.nodes[cid].meta)
+                        .filter(|child| child.is_dir)
+                        .filter_map(|child| state.dir_sizes.get(&child.path))
+                        .sum();
+                    total.saturating_sub(in_children)
+                });
+                jobs.push((dir_path, own_estimate));
+            }
         }
     }
 
+    // Largest first, so the slow walks start while there are still other
+    // jobs to keep the remaining workers busy.  Never-sized dirs follow in
+    // tree order (the sort is stable).
+    jobs.sort_by_key(|&(_, estimate)| std::cmp::Reverse(estimate));
+    let job_count = jobs.len();
+    let injector = Injector::new();
+    for (dir, _) in jobs {
+        injector.push(SizeJob::TreeDir(dir));
+    }
+
     // Sort dirs deepest-first for O(n) cascade finalization.
     dirs.sort_by(|a, b| {
         let da = dir_depth.get(a).copied().unwrap_or(0);
@@ -134,15 +1048,21 @@ pub fn start_size_computation(
         db.cmp(&da)
     });
 
-    let queue = Arc::new(Mutex::new(jobs));
     let dedup_hard_links = state.config.dedup_hard_links;
     let one_file_system = state.config.one_file_system;
     let root_dev = get_dev(&state.cwd);
+    let excludes = size::compile_excludes(&state.config.exclude_patterns);
     let ctx = Arc::new(WorkerCtx {
         tree_dirs,
+        listed_dirs,
         dedup_hard_links,
         one_file_system,
         root_dev,
+        excludes: excludes.clone(),
+        counters: ScanCounters::default(),
+        job_count,
+        dirs_done: AtomicUsize::new(0),
+        dirs_walked: AtomicU64::new(0),
     });
 
     let max_threads = std::thread::available_parallelism()
@@ -150,142 +1070,69 @@ pub fn start_size_computation(
         .unwrap_or(4)
         .max(1);
 
-    let job_count = queue.lock().ok().map_or(0, |q| q.len());
     let worker_count = max_threads.min(job_count.max(1));
+    let alive = Arc::new(AtomicUsize::new(0));
+    let mut workers = Vec::with_capacity(worker_count);
 
     if job_count > 0 {
-        for _ in 0..worker_count {
-            let queue = Arc::clone(&queue);
-            let tx = tx.clone();
-            let cancel = Arc::clone(&cancel);
-            let ctx = Arc::clone(&ctx);
-            std::thread::spawn(move || {
-                loop {
-                    if cancel.load(Ordering::Relaxed) {
-                        break;
-                    }
-
-                    let dir = {
-                        let mut q = match queue.lock() {
-                            Ok(guard) => guard,
-                            Err(_) => break,
-                        };
-                        match q.pop_front() {
-                            Some(d) => d,
-                            None => break,
-                        }
-                    };
-
-                    let entries = match std::fs::read_dir(&dir) {
-                        Ok(e) => e,
-                        Err(_) => {
-                            let _ = tx.send((
-                                generation,
-                                SizeUpdate::DirLocalDone {
-                                    dir,
-                                    unique_sum: 0,
-                                    hardlinks: InodeMap::new(),
-                                },
-                            ));
-                            continue;
-                        }
-                    };
-
-                    let mut unique_sum: u64 = 0;
-                    let mut hardlinks = InodeMap::new();
-
-                    for entry in entries.flatten() {
-                        if cancel.load(Ordering::Relaxed) {
-                            break;
-                        }
-                        let ft = match entry.file_type() {
-                            Ok(ft) => ft,
-                            Err(_) => continue,
-                        };
-                        let path = entry.path();
-
-                        if ft.is_file() {
-                            if let Ok(meta) = entry.metadata() {
-                                let s = meta.len();
-                                let _ = tx.send((
-                                    generation,
-                                    SizeUpdate::File {
-                                        path: path.clone(),
-                                        size: s,
-                                    },
-                                ));
-                                let (size, inode_key) = classify_file(&meta, ctx.dedup_hard_links);
-                                match inode_key {
-                                    None => unique_sum = unique_sum.saturating_add(size),
-                                    Some(key) => {
-                                        hardlinks.entry(key).or_insert(size);
-                                    }
-                                }
-                            }
-                        } else if ft.is_dir() {
-                            if ctx.tree_dirs.contains(&path) {
-                                // Tree child dir — cascade handles it.
-                            } else if ctx.one_file_system {
-                                // Check mount boundary before descending.
-                                if let Ok(meta) = std::fs::metadata(&path) {
-                                    if is_same_device(&meta, ctx.root_dev) {
-                                        let (sub_unique, sub_hardlinks) = size::recursive_dir_size(
-                                            &path,
-                                            &cancel,
-                                            ctx.dedup_hard_links,
-                                            true,
-                                            ctx.root_dev,
-                                        );
-                                        unique_sum = unique_sum.saturating_add(sub_unique);
-                                        for (k, v) in sub_hardlinks {
-                                            hardlinks.entry(k).or_insert(v);
-                                        }
-                                    }
-                                }
-                            } else {
-                                let (sub_unique, sub_hardlinks) = size::recursive_dir_size(
-                                    &path,
-                                    &cancel,
-                                    ctx.dedup_hard_links,
-                                    false,
-                                    0,
-                                );
-                                unique_sum = unique_sum.saturating_add(sub_unique);
-                                for (k, v) in sub_hardlinks {
-                                    hardlinks.entry(k).or_insert(v);
-                                }
-                            }
-                        } else if ft.is_symlink() {
-                            if let Ok(meta) = std::fs::symlink_metadata(&path) {
-                                let s = meta.len();
-                                let _ = tx.send((
-                                    generation,
-                                    SizeUpdate::File {
-                                        path: path.clone(),
-                                        size: s,
-                                    },
-                                ));
-                                unique_sum = unique_sum.saturating_add(s);
-                            }
-                        }
+        let deques: Vec<Worker<SizeJob>> = (0..worker_count).map(|_| Worker::new_lifo()).collect();
+        let pool = Arc::new(JobPool {
+            injector,
+            stealers: deques.iter().map(Worker::stealer).collect(),
+            pending: AtomicUsize::new(job_count),
+        });
+        for local in deques {
+            let worker = SizeWorker {
+                ctx: Arc::clone(&ctx),
+                pool: Arc::clone(&pool),
+                local,
+                cancel: Arc::clone(&cancel),
+                tx: tx.clone(),
+                generation,
+            };
+            alive.fetch_add(1, Ordering::Relaxed);
+            let exit_guard = WorkerExitGuard {
+                tx: tx.clone(),
+                generation,
+                alive: Arc::clone(&alive),
+                clean: false,
+            };
+            workers.push(std::thread::spawn(move || {
+                // Bind the whole guard so the closure owns (and drops) it.
+                let mut exit_guard = exit_guard;
+                while let Some(job) = worker.next_job() {
+                    let _done = JobDone(&worker.pool.pending);
+                    worker.ctx.dirs_walked.fetch_add(1, Ordering::Relaxed);
+                    match job {
+                        SizeJob::TreeDir(dir) => worker.walk_tree_dir(dir),
+                        SizeJob::Subtree { dir, owner } => worker.walk_subtree(&dir, &owner),
                     }
-
-                    let _ = tx.send((
-                        generation,
-                        SizeUpdate::DirLocalDone {
-                            dir,
-                            unique_sum,
-                            hardlinks,
-                        },
-                    ));
                 }
 
-                let _ = tx.send((generation, SizeUpdate::WorkerDone));
-            });
+                // Final totals land before this worker counts as done.
+                worker.send(worker.ctx.progress_update());
+                exit_guard.clean = true;
+                worker.send(SizeUpdate::WorkerDone);
+            }));
         }
+
+        // Periodic progress while any worker is still walking.
+        let tx = tx.clone();
+        let cancel = Arc::clone(&cancel);
+        let alive = Arc::clone(&alive);
+        std::thread::spawn(move || loop {
+            std::thread::sleep(PROGRESS_INTERVAL);
+            if cancel.load(Ordering::Relaxed) || alive.load(Ordering::Relaxed) == 0 {
+                break;
+            }
+            if tx.send((generation, ctx.progress_update())).is_err() {
+                break;
+            }
+        });
     }
 
-    SizeComputeState {
+    let cache_hits = local_done.len();
+    let compute = SizeComputeState {
         generation,
         remaining_workers: if job_count > 0 { worker_count } else { 0 },
         dirs,
@@ -293,10 +1140,29 @@ pub fn start_size_computation(
         pending_children,
         children_unique,
         children_hardlinks,
+        children_hardlink_apparent: HashMap::new(),
+        children_allocated_unique: HashMap::new(),
+        children_allocated_hardlinks: HashMap::new(),
+        children_counts: HashMap::new(),
+        children_excluded: HashSet::new(),
+        excludes,
         local_done,
         finished: HashSet::new(),
         cancel,
+        alive,
+        workers,
+        all_exited_at: None,
+        failure: None,
+        started: Instant::now(),
+        worker_count: if job_count > 0 { worker_count } else { 0 },
+        cache_hits,
+        walk_times: Vec::new(),
+    };
+    if job_count == 0 {
+        // Everything came from the cache; the scan is already complete.
+        state.last_scan_stats = Some(compute.stats(&state.scan_progress));
     }
+    compute
 }
 
 /// Process a single size update message.  Returns `true` if a `DirLocalDone`
@@ -307,6 +1173,19 @@ pub fn apply_size_update(
     generation: u64,
     update: SizeUpdate,
 ) -> bool {
+    // On-demand totals don't belong to a scan generation.
+    if let SizeUpdate::DirTotal {
+        dir,
+        total,
+        apparent,
+        allocated,
+        counts,
+        excluded,
+    } = update
+    {
+        apply_dir_total(state, dir, total, apparent, allocated, counts, excluded);
+        return false;
+    }
     if generation != state.size_compute_generation {
         return false;
     }
@@ -317,28 +1196,47 @@ pub fn apply_size_update(
         return false;
     }
     match update {
-        SizeUpdate::File { path, size } => {
-            state.file_sizes.insert(path, size);
+        SizeUpdate::File { path, size, allocated } => {
+            state.record_file_size(path, size, allocated);
             false
         }
-        SizeUpdate::DirLocalDone {
-            dir,
-            unique_sum,
-            hardlinks,
-        } => {
-            let result = DirLocalResult {
-                unique_sum,
-                hardlinks,
-            };
+        SizeUpdate::DirLocalDone { dir, local, elapsed } => {
+            compute.walk_times.push((dir.clone(), elapsed));
             // Cache for future recomputes.
-            state.dir_local_sums.insert(dir.clone(), result.clone());
-            compute.local_done.insert(dir, result);
+            state.dir_local_sums.insert(dir.clone(), local.clone());
+            compute.local_done.insert(dir, local);
             true
         }
+        SizeUpdate::Progress {
+            files_scanned,
+            bytes_seen,
+            dirs_remaining,
+            dirs_walked,
+        } => {
+            // Frozen once the last worker is done.
+            if compute.remaining_workers > 0 {
+                state.scan_progress = ScanProgress {
+                    files_scanned,
+                    bytes_seen,
+                    dirs_remaining,
+                    dirs_walked,
+                };
+            }
+            false
+        }
         SizeUpdate::WorkerDone => {
             compute.remaining_workers = compute.remaining_workers.saturating_sub(1);
+            if compute.remaining_workers == 0 && compute.failure.is_none() {
+                state.last_scan_stats = Some(compute.stats(&state.scan_progress));
+            }
+            false
+        }
+        SizeUpdate::WorkerFailed { reason } => {
+            compute.remaining_workers = compute.remaining_workers.saturating_sub(1);
+            compute.failure.get_or_insert(reason);
             false
         }
+        SizeUpdate::DirTotal { .. } => false,
     }
 }
 
@@ -368,28 +1266,37 @@ pub fn finalize_ready_dirs(state: &mut AppState, compute: &mut SizeComputeState)
         let local = compute.local_done.remove(&dir).expect("local_done checked");
         let children_unique = compute.children_unique.remove(&dir).unwrap_or(0);
         let children_hl = compute.children_hardlinks.remove(&dir).unwrap_or_default();
+        let children_hl_apparent = compute.children_hardlink_apparent.remove(&dir).unwrap_or(0);
+        let children_alloc_unique = compute.children_allocated_unique.remove(&dir).unwrap_or(0);
+        let children_alloc_hl = compute.children_allocated_hardlinks.remove(&dir).unwrap_or_default();
+        let mut counts = compute.children_counts.remove(&dir).unwrap_or_default();
+        counts.merge(local.counts);
 
         let total_unique = local.unique_sum.saturating_add(children_unique);
+        // Every hard-link occurrence counted, as a dedup-unaware tool would.
+        let hl_apparent = local.hardlink_apparent.saturating_add(children_hl_apparent);
 
-        // Merge hardlink maps: pick the larger map as the base to minimise
-        // insertions, then extend from the smaller one.
-        let mut merged_hardlinks;
-        if local.hardlinks.len() >= children_hl.len() {
-            merged_hardlinks = local.hardlinks;
-            for (k, v) in children_hl {
-                merged_hardlinks.entry(k).or_insert(v);
-            }
-        } else {
-            merged_hardlinks = children_hl;
-            for (k, v) in local.hardlinks {
-                merged_hardlinks.entry(k).or_insert(v);
-            }
-        }
-
+        let merged_hardlinks = merge_inode_maps(local.hardlinks, children_hl);
         let hardlink_bytes: u64 = merged_hardlinks.values().sum();
         let total = total_unique.saturating_add(hardlink_bytes);
 
-        state.dir_sizes.insert(dir.clone(), total);
+        // The same cascade in allocated bytes, so either view is ready.
+        let alloc_unique = local.allocated_unique_sum.saturating_add(children_alloc_unique);
+        let merged_alloc_hardlinks = merge_inode_maps(local.allocated_hardlinks, children_alloc_hl);
+        let allocated = alloc_unique.saturating_add(merged_alloc_hardlinks.values().sum::<u64>());
+
+        let excluded = local.excluded || compute.children_excluded.remove(&dir);
+        state.record_dir_size(dir.clone(), total, allocated);
+        state.dir_entry_counts.insert(dir.clone(), counts);
+        state
+            .dir_apparent_sizes
+            .insert(dir.clone(), total_unique.saturating_add(hl_apparent));
+        if excluded {
+            state.dir_excluded.insert(dir.clone());
+        } else {
+            state.dir_excluded.remove(&dir);
+        }
+        state.stale_sizes.remove(&dir);
         compute.finished.insert(dir.clone());
 
         // Propagate to parent — move the merged map, don't copy.
@@ -397,20 +1304,363 @@ pub fn finalize_ready_dirs(state: &mut AppState, compute: &mut SizeComputeState)
             if let Some(remaining) = compute.pending_children.get_mut(parent) {
                 *remaining = remaining.saturating_sub(1);
             }
+            // An excluded tree dir keeps its own total but adds nothing
+            // to its parent's.
+            if size::is_excluded(&compute.excludes, &dir) {
+                compute.children_excluded.insert(parent.clone());
+                continue;
+            }
+            if excluded {
+                compute.children_excluded.insert(parent.clone());
+            }
             if let Some(sum) = compute.children_unique.get_mut(parent) {
                 *sum = sum.saturating_add(total_unique);
             }
+            let parent_apparent = compute.children_hardlink_apparent.entry(parent.clone()).or_insert(0);
+            *parent_apparent = parent_apparent.saturating_add(hl_apparent);
             // Merge into parent's children_hardlinks.  If the parent has
             // no accumulated map yet, just move ours in wholesale.
             let parent_hl = compute.children_hardlinks.entry(parent.clone()).or_default();
-            if parent_hl.is_empty() {
-                *parent_hl = merged_hardlinks;
-            } else {
-                for (k, v) in merged_hardlinks {
-                    parent_hl.entry(k).or_insert(v);
+            *parent_hl = merge_inode_maps(std::mem::take(parent_hl), merged_hardlinks);
+            let parent_alloc = compute.children_allocated_unique.entry(parent.clone()).or_insert(0);
+            *parent_alloc = parent_alloc.saturating_add(alloc_unique);
+            let parent_alloc_hl = compute.children_allocated_hardlinks.entry(parent.clone()).or_default();
+            *parent_alloc_hl = merge_inode_maps(std::mem::take(parent_alloc_hl), merged_alloc_hardlinks);
+            compute.children_counts.entry(parent.clone()).or_default().merge(counts);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::core::fs::{build_tree, WalkConfig};
+
+    /// Nested directories below and between tree nodes, file and directory
+    /// symlinks, and a hard link shared by two subtrees.
+    fn sample_tree() -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("big/nested/deeper/deepest")).unwrap();
+        std::fs::create_dir_all(root.join("empty")).unwrap();
+        std::fs::create_dir_all(root.join("wide")).unwrap();
+        std::fs::write(root.join("a.txt"), [0u8; 3]).unwrap();
+        std::fs::write(root.join("big/nested/y.bin"), [0u8; 50]).unwrap();
+        std::fs::write(root.join("big/nested/deeper/x.bin"), [0u8; 1000]).unwrap();
+        std::fs::write(root.join("big/nested/deeper/deepest/z.bin"), [0u8; 7]).unwrap();
+        for i in 0..20 {
+            let dir = root.join(format!("wide/d{i}"));
+            std::fs::create_dir(&dir).unwrap();
+            std::fs::write(dir.join("f"), vec![0u8; i + 1]).unwrap();
+        }
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("../a.txt", root.join("big/a-link")).unwrap();
+            std::os::unix::fs::symlink("nested", root.join("big/dir-link")).unwrap();
+            std::fs::hard_link(root.join("big/nested/y.bin"), root.join("wide/y-hard")).unwrap();
+        }
+        tmp
+    }
+
+    /// Run a full scan of `state`'s tree the way the event loop does.
+    /// Returns the tree directories that were walked rather than taken
+    /// from cache.
+    fn run_scan(state: &mut AppState) -> Vec<PathBuf> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut compute = None;
+        restart_size_computation(state, &mut compute, &tx);
+        drop(tx);
+        let deadline = Instant::now() + Duration::from_secs(20);
+        let mut walked = Vec::new();
+        while compute.as_ref().is_some_and(SizeComputeState::is_scanning) {
+            assert!(Instant::now() < deadline, "size scan did not finish");
+            match rx.try_recv() {
+                Ok((generation, update)) => {
+                    if let SizeUpdate::DirLocalDone { dir, .. } = &update {
+                        walked.push(dir.clone());
+                    }
+                    if apply_size_update(state, &mut compute, generation, update) {
+                        finalize_ready_dirs(state, compute.as_mut().unwrap());
+                    }
                 }
+                Err(_) => std::thread::sleep(Duration::from_millis(1)),
             }
         }
+        state.scanning = false;
+        let compute = compute.unwrap();
+        assert_eq!(compute.failure, None);
+        // Every worker returns once the jobs run out.
+        for handle in compute.workers {
+            handle.join().expect("size worker panicked");
+        }
+        assert_eq!(compute.alive.load(Ordering::Relaxed), 0);
+        walked.sort();
+        walked
     }
-}
 
+    /// Deduped and apparent totals of `dir` from a single-threaded walk.
+    fn walked_totals(dir: &Path) -> (u64, u64) {
+        let no_excludes = size::compile_excludes(&[]);
+        let r = size::recursive_dir_size(dir, &AtomicBool::new(false), true, false, 0, &no_excludes, None);
+        (r.unique_sum + r.hardlinks.values().sum::<u64>(), r.unique_sum + r.hardlink_apparent)
+    }
+
+    #[test]
+    fn work_stealing_totals_match_a_plain_walk() {
+        let tmp = sample_tree();
+        // Depth 1 leaves the nested dirs to subtree jobs; depth 4 puts them
+        // all in the tree, so the cascade does the summing.
+        for max_depth in [1, 4] {
+            let walk = WalkConfig {
+                max_depth,
+                show_git_status: false,
+                ..WalkConfig::default()
+            };
+            let tree = build_tree(tmp.path(), &walk, false).unwrap();
+            let mut state = AppState::new(tmp.path().to_path_buf(), tree, AppConfig::default());
+            run_scan(&mut state);
+
+            let dirs: Vec<PathBuf> = state
+                .tree
+                .nodes
+                .iter()
+                .filter(|n| n.meta.is_dir)
+                .map(|n| n.meta.path.clone())
+                .collect();
+            assert!(dirs.len() > 1);
+            for dir in dirs {
+                let (total, apparent) = walked_totals(&dir);
+                let shown = (state.dir_sizes.get(&dir).copied(), state.dir_apparent_sizes.get(&dir).copied());
+                assert_eq!(shown, (Some(total), Some(apparent)), "{} at depth {max_depth}", dir.display());
+            }
+            #[cfg(unix)]
+            {
+                // `y.bin` is linked from two subtrees: once deduped, twice apparent.
+                let root = tmp.path();
+                assert_eq!(state.dir_apparent_sizes[root] - state.dir_sizes[root], 50);
+            }
+        }
+    }
+
+    #[test]
+    fn watcher_changes_patch_totals_in_place() {
+        let tmp = sample_tree();
+        let root = tmp.path();
+        let walk = WalkConfig {
+            max_depth: 4,
+            show_git_status: false,
+            ..WalkConfig::default()
+        };
+        let tree = build_tree(root, &walk, false).unwrap();
+        let mut state = AppState::new(root.to_path_buf(), tree, AppConfig::default());
+        run_scan(&mut state);
+
+        std::fs::write(root.join("a.txt"), [0u8; 300]).unwrap();
+        assert!(adjust_for_change(&mut state, &root.join("a.txt")));
+        std::fs::write(root.join("big/nested/deeper/new.bin"), [0u8; 20]).unwrap();
+        assert!(adjust_for_change(&mut state, &root.join("big/nested/deeper/new.bin")));
+        for dir in [root.to_path_buf(), root.join("big"), root.join("big/nested/deeper")] {
+            assert_eq!(state.dir_sizes.get(&dir).copied(), Some(walked_totals(&dir).0), "{}", dir.display());
+        }
+
+        // Not exact: the caller invalidates the parent instead.
+        assert!(!adjust_for_change(&mut state, &root.join("big")));
+        #[cfg(unix)]
+        assert!(!adjust_for_change(&mut state, &root.join("big/nested/y.bin")));
+        state.scanning = true;
+        assert!(!adjust_for_change(&mut state, &root.join("a.txt")));
+    }
+
+    #[test]
+    fn removing_a_deep_file_shrinks_every_ancestor() {
+        let tmp = sample_tree();
+        let root = tmp.path();
+        let walk = WalkConfig {
+            max_depth: 4,
+            show_git_status: false,
+            ..WalkConfig::default()
+        };
+        let tree = build_tree(root, &walk, false).unwrap();
+        let mut state = AppState::new(root.to_path_buf(), tree, AppConfig::default());
+        run_scan(&mut state);
+        let file = root.join("big/nested/deeper/x.bin");
+        let ancestors: Vec<PathBuf> = file
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(root))
+            .map(Path::to_path_buf)
+            .collect();
+        let before: Vec<u64> = ancestors.iter().map(|dir| state.dir_sizes[dir]).collect();
+
+        std::fs::remove_file(&file).unwrap();
+        crate::app::handler::apply_removed(&mut state, file, Ok(()));
+
+        assert_eq!(ancestors.len(), 4);
+        for (dir, before) in ancestors.iter().zip(before) {
+            assert_eq!(state.dir_sizes[dir], before - 1000, "{}", dir.display());
+            assert_eq!(state.dir_sizes[dir], walked_totals(dir).0, "{}", dir.display());
+        }
+        assert!(state.stale_sizes.is_empty());
+        assert!(!state.needs_size_recompute);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn removing_a_hard_link_marks_ancestors_stale_instead() {
+        let tmp = sample_tree();
+        let root = tmp.path();
+        let walk = WalkConfig {
+            max_depth: 4,
+            show_git_status: false,
+            ..WalkConfig::default()
+        };
+        let tree = build_tree(root, &walk, false).unwrap();
+        let mut state = AppState::new(root.to_path_buf(), tree, AppConfig::default());
+        run_scan(&mut state);
+        let wide = root.join("wide");
+        let before = (state.dir_sizes[root], state.dir_sizes[&wide]);
+
+        // `y-hard` shares its inode with `big/nested/y.bin`.
+        std::fs::remove_file(wide.join("y-hard")).unwrap();
+        crate::app::handler::apply_removed(&mut state, wide.join("y-hard"), Ok(()));
+
+        assert_eq!((state.dir_sizes[root], state.dir_sizes[&wide]), before);
+        assert!(state.stale_sizes.contains(root) && state.stale_sizes.contains(&wide));
+        assert!(!state.stale_sizes.contains(&root.join("big")));
+        assert!(!state.dir_local_sums.contains_key(&wide));
+        assert!(state.needs_size_recompute);
+    }
+
+    #[test]
+    fn rescanning_a_dir_walks_only_its_subtree() {
+        use crate::ui::tree_widget::TreeRow;
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let tmp = sample_tree();
+        let root = tmp.path();
+        let walk = WalkConfig {
+            max_depth: 4,
+            show_git_status: false,
+            ..WalkConfig::default()
+        };
+        let tree = build_tree(root, &walk, false).unwrap();
+        let mut state = AppState::new(root.to_path_buf(), tree, AppConfig::default());
+        let mut tree_dirs: Vec<PathBuf> = state
+            .tree
+            .nodes
+            .iter()
+            .filter(|n| n.meta.is_dir)
+            .map(|n| n.meta.path.clone())
+            .collect();
+        tree_dirs.sort();
+        assert_eq!(run_scan(&mut state), tree_dirs);
+        let before = state.dir_sizes.clone();
+
+        let big = root.join("big");
+        state.tree_state.selected = crate::app::handler::build_rows(&state)
+            .iter()
+            .position(|row| matches!(row, TreeRow::Node { node_id, .. } if state.tree.nodes[*node_id].meta.path == big))
+            .unwrap();
+        crate::app::handler::handle_key(&mut state, KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT));
+        assert!(state.needs_size_recompute);
+
+        let expected: Vec<PathBuf> = tree_dirs.iter().filter(|dir| dir.starts_with(&big)).cloned().collect();
+        assert_eq!(expected.len(), 4);
+        assert_eq!(run_scan(&mut state), expected);
+        // Everything outside `big` finalized from cache to the same totals.
+        assert_eq!(state.dir_sizes, before);
+        assert!(state.stale_sizes.is_empty());
+    }
+
+    #[test]
+    fn cached_results_expire_when_a_folded_subtree_changes() {
+        let tmp = sample_tree();
+        let root = tmp.path();
+        let walk = WalkConfig {
+            max_depth: 1,
+            show_git_status: false,
+            ..WalkConfig::default()
+        };
+        let tree = build_tree(root, &walk, false).unwrap();
+        let mut state = AppState::new(root.to_path_buf(), tree, AppConfig::default());
+        run_scan(&mut state);
+
+        // `big/nested` is not in the tree, so it is folded into `big`.
+        let big = root.join("big");
+        let entry = CacheEntry {
+            local: state.dir_local_sums[&big].clone(),
+            files: Vec::new(),
+            last_used: 0,
+        };
+        assert_eq!(entry.local.folded, ["nested"]);
+        assert!(size_cache::is_fresh(&big, &entry, |_| true));
+
+        std::fs::remove_file(root.join("big/nested/deeper/deepest/z.bin")).unwrap();
+        assert_eq!(size::dir_mtime(&big), entry.local.mtime);
+        assert!(!size_cache::is_fresh(&big, &entry, |_| true));
+    }
+
+    #[test]
+    fn a_scan_whose_channel_is_dropped_fails_until_retried() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let tmp = sample_tree();
+        let root = tmp.path();
+        let tree = build_tree(root, &WalkConfig::default(), false).unwrap();
+        let mut state = AppState::new(root.to_path_buf(), tree, AppConfig::default());
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut compute = None;
+        restart_size_computation(&mut state, &mut compute, &tx);
+        // Every `WorkerDone` is lost with the receiver.
+        drop(rx);
+        let compute = compute.as_mut().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(20);
+        while compute.alive.load(Ordering::Relaxed) > 0 {
+            assert!(Instant::now() < deadline, "size workers did not exit");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        // Gone workers are given a grace period before the scan fails.
+        compute.check_workers();
+        assert!(compute.is_scanning());
+        compute.all_exited_at = Some(Instant::now() - WORKER_EXIT_GRACE);
+        compute.check_workers();
+        assert!(!compute.is_scanning());
+        // What the event loop does on its next tick.
+        mark_scan_failed(&mut state, compute.take_failure().unwrap());
+        assert_eq!(compute.take_failure(), None);
+        assert_eq!(state.size_scan_failed.as_deref(), Some("size workers exited without finishing"));
+        let status = state.status_message.clone().unwrap();
+        assert!(status.starts_with("Size scan failed: size workers exited without finishing"), "{status}");
+        assert!(status.ends_with("retry"), "{status}");
+
+        crate::app::handler::handle_key(&mut state, KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert!(state.needs_size_recompute);
+        assert_eq!(state.size_scan_failed, None);
+        run_scan(&mut state);
+        assert_eq!(state.dir_sizes.get(root).copied(), Some(walked_totals(root).0));
+    }
+
+    #[test]
+    fn listed_sizes_total_only_the_listed_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        std::fs::create_dir(root.join("sub")).unwrap();
+        std::fs::write(root.join("a"), [0u8; 10]).unwrap();
+        std::fs::write(root.join("sub/b"), [0u8; 5]).unwrap();
+        std::fs::write(root.join("sub/unlisted"), [0u8; 100]).unwrap();
+        let listed = ["a", "sub/b", "sub/gone"].map(PathBuf::from);
+        let tree = crate::core::fs::build_tree_from_paths(&root, listed, Default::default());
+        let mut state = AppState::new(root.clone(), tree, AppConfig::default());
+
+        record_listed_sizes(&mut state);
+
+        let (dirs, _) = state.dir_size_views();
+        assert_eq!(dirs.get(&root), Some(&15));
+        assert_eq!(dirs.get(&root.join("sub")), Some(&5));
+        let (files, _) = state.file_size_views();
+        assert_eq!(files.get(&root.join("a")), Some(&10));
+        assert_eq!(files.get(&root.join("sub/gone")), None);
+    }
+}
@@ -5,14 +5,16 @@
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use crate::config::AppConfig;
+use crate::config::{AppConfig, ConfigWarning, KeyBind, SizeView, SlowFsMode};
 use crate::core::{
     fs::WalkConfig,
+    git::GitFileStatus,
     grouping::GroupingConfig,
-    inspector::InspectorInfo,
-    search::{SearchEntry, SearchResult},
+    inspector::{InspectorInfo, MimeCacheStats},
+    search::{SearchEntry, SearchMode, SearchOptions, SearchResult},
     tree::{DirTree, NodeId},
 };
 use crate::ui::tree_widget::TreeWidgetState;
@@ -27,6 +29,32 @@ pub enum ActiveView {
     ControlsSubmenu,
     /// Full-screen image lightbox overlay.
     Lightbox,
+    /// Save / load named pin sets.
+    PinSets,
+    /// Saved directory bookmarks.
+    Bookmarks,
+    /// Largest directories and files found so far.
+    TopFiles,
+    /// Typed "yes" confirmation before committing staged deletions.
+    ConfirmDeletions,
+    /// Yes/no confirmation before deleting the selected entry.
+    ConfirmDelete,
+    /// Text input for renaming the selected entry.
+    Rename,
+    /// Text input for a new directory's name.
+    NewDir,
+    /// Path typed into the status bar ("go to path").
+    PathInput,
+    /// Quit requested while deletions are still staged.
+    ConfirmQuit,
+    /// Confirmation for "Reset All Settings".
+    ConfirmResetSettings,
+    /// Problems found when re-reading the config file.
+    ConfigWarnings,
+    /// Size-walk exclude patterns (settings submenu).
+    ExcludePatterns,
+    /// Statistics of the last completed size scan.
+    ScanStats,
 }
 
 /// Which main pane currently owns keyboard focus.
@@ -43,6 +71,25 @@ pub enum RightPaneTab {
     #[default]
     Inspector,
     Search,
+    /// Staged deletions awaiting review.
+    Deletions,
+}
+
+/// Recursive expansion started by "expand all", advanced as listings land.
+pub struct ExpandAllJob {
+    /// Directory the expansion started from.
+    pub root: PathBuf,
+    /// Directories at this tree depth or deeper stay collapsed.
+    pub max_depth: usize,
+    /// Directories already listed for this job (empty ones stay empty).
+    pub requested: HashSet<PathBuf>,
+}
+
+/// A finished SHA-256, valid while the file's size and mtime match.
+pub struct CachedChecksum {
+    pub modified_unix: Option<u64>,
+    pub size_bytes: Option<u64>,
+    pub digest: String,
 }
 
 /// Top-level application state.
@@ -75,27 +122,119 @@ pub struct AppState {
     /// When `true`, the controls submenu is waiting for the user to press
     /// a key to rebind the action at `controls_selected`.
     pub awaiting_rebind: bool,
-    /// Computed directory sizes (path → total bytes).  Populated asynchronously
-    /// by a background thread.
+    /// Captured binding waiting for Enter to confirm.
+    pub pending_rebind: Option<KeyBind>,
+    /// Why the last captured key was rejected.
+    pub rebind_warning: Option<String>,
+    /// User offset (cols, rows) applied to popups; kept for the session.
+    pub popup_offset: (i16, i16),
+    /// Selected row in the pin sets popup.
+    pub pin_set_selected: usize,
+    /// Selected row in the bookmarks popup.
+    pub bookmark_selected: usize,
+    /// Largest directories (root excluded) and files, refreshed while the
+    /// top-entries overlay is open.
+    pub top_dirs: Vec<(PathBuf, u64)>,
+    pub top_files: Vec<(PathBuf, u64)>,
+    /// Selected row in the top-entries overlay (directories first).
+    pub top_selected: usize,
+    /// Name being typed when saving a pin set (`None` = browsing the list).
+    pub pin_set_name_input: Option<String>,
+    /// Selected row in the exclude patterns submenu.
+    pub exclude_selected: usize,
+    /// Pattern being typed in the exclude patterns submenu (`None` =
+    /// browsing the list).
+    pub exclude_input: Option<String>,
+    /// Computed directory sizes (path → total bytes) in the active
+    /// [`SizeView`].  Populated asynchronously by a background thread.
     pub dir_sizes: HashMap<PathBuf, u64>,
-    /// Computed file sizes (path → bytes). Populated asynchronously.
+    /// `dir_sizes` in the other size view, swapped in when it changes.
+    pub dir_sizes_alt: HashMap<PathBuf, u64>,
+    /// Directory totals counting every hard-link occurrence (what a
+    /// dedup-unaware tool would report).  Filled alongside `dir_sizes`.
+    pub dir_apparent_sizes: HashMap<PathBuf, u64>,
+    /// Recursive symlink / special file counts per directory, filled by
+    /// the size cascade.
+    pub dir_entry_counts: HashMap<PathBuf, crate::core::size::EntryCounts>,
+    /// Directories whose totals left out an excluded subdirectory.
+    pub dir_excluded: HashSet<PathBuf>,
+    /// Computed file sizes (path → bytes) in the active size view.
+    /// Populated asynchronously.
     pub file_sizes: HashMap<PathBuf, u64>,
+    /// `file_sizes` in the other size view.
+    pub file_sizes_alt: HashMap<PathBuf, u64>,
     /// Cached per-directory local walk results from workers.  On expand, only
     /// the expanded dir's entry is invalidated — all others survive so we
     /// skip redundant I/O.
     pub dir_local_sums: HashMap<PathBuf, crate::core::size::DirLocalResult>,
+    /// Directories whose shown size is out of date while a targeted rescan
+    /// runs (rendered dimmed until the new total lands).
+    pub stale_sizes: HashSet<PathBuf>,
+    /// On-disk size cache entries outside the current root, written back
+    /// on exit.
+    pub size_cache_carry: HashMap<PathBuf, crate::core::size_cache::CacheEntry>,
+    /// Whether the on-disk size cache is read and written this session
+    /// (the `size_cache` config key, unless `--no-cache` was given).
+    pub size_cache_enabled: bool,
     /// Flag set by event handlers to trigger a background size recomputation.
     pub needs_size_recompute: bool,
     /// Monotonic generation id used to ignore stale background size updates.
     pub size_compute_generation: u64,
     /// `true` while background size workers are still running.
     pub scanning: bool,
+    /// Counters of the current (or last finished) size scan.
+    pub scan_progress: crate::app::size_runtime::ScanProgress,
+    /// Statistics of the last size scan that completed.
+    pub last_scan_stats: Option<crate::app::size_runtime::ScanStats>,
+    /// Reason the last size scan failed, until it is retried.
+    pub size_scan_failed: Option<String>,
+    /// Slow-filesystem setting for this session (config, or `--slow-fs`).
+    pub slow_fs_mode: SlowFsMode,
+    /// Result of the startup latency probe (used by `SlowFsMode::Auto`).
+    pub slow_fs_detected: bool,
+    /// Effective slow-filesystem flag, shared with the inspector worker.
+    pub slow_fs: Arc<AtomicBool>,
+    /// Syntax highlighting of text previews, shared with the inspector
+    /// worker.
+    pub syntax_highlight: Arc<AtomicBool>,
+    /// The tree holds a path list read with `--stdin`: it is never walked,
+    /// watched or rebuilt from disk, and sizes cover the listed files only.
+    pub fixed_tree: bool,
+    /// Directory whose total was requested in slow-filesystem mode.
+    pub size_on_demand: Option<PathBuf>,
+    /// Cancels the on-demand size walk in flight.
+    pub size_on_demand_cancel: Option<Arc<AtomicBool>>,
+    /// When a debounced inspection of the selection is due.
+    pub inspect_due: Option<std::time::Instant>,
+    /// When the tree should follow the selected search result.
+    pub search_reveal_due: Option<std::time::Instant>,
+    /// Whether mouse capture is wanted; the main loop applies changes to
+    /// the terminal.
+    pub mouse_enabled: bool,
+    /// Whether screen-reader mode is on: the config key, or `--a11y` for
+    /// this session only.
+    pub a11y: bool,
     /// Last left-clicked directory node and click time, for double-click.
     pub last_left_click: Option<(NodeId, std::time::Instant)>,
+    /// Letters typed in the tree for a type-ahead jump, lower-cased, and
+    /// when the last one arrived.
+    pub type_ahead: String,
+    pub type_ahead_at: Option<std::time::Instant>,
+    /// Explicit type-ahead ([`crate::config::Action::TypeAhead`]): every
+    /// printable key goes to the buffer, which doesn't lapse, until Enter
+    /// or Esc.
+    pub type_ahead_mode: bool,
+    /// Paths picked for batch operations with "toggle select".  Paths
+    /// rather than node ids, which shift when subtrees are removed.
+    pub selected_nodes: HashSet<PathBuf>,
     /// Last terminal area used to render the frame (for mouse hit-testing).
     pub terminal_area: Rect,
+    /// Rows inside the tree pane at the last draw, for paging.
+    pub tree_viewport_rows: usize,
     /// True while dragging the tree/inspector splitter with mouse.
     pub dragging_splitter: bool,
+    /// True while dragging the tree's scrollbar thumb.
+    pub dragging_tree_scrollbar: bool,
     /// Path currently shown in the inspector cache.
     pub inspector_path: Option<PathBuf>,
     /// Cached inspector payload for the selected row.
@@ -123,20 +262,39 @@ pub struct AppState {
     pub image_cache: HashMap<PathBuf, Arc<image::RgbaImage>>,
     /// Paths currently being decoded on background threads.
     pub image_decoding: HashSet<PathBuf>,
+    /// Files whose SHA-256 is being computed on a background thread,
+    /// with the flag that stops the hash.
+    pub checksum_in_flight: HashMap<PathBuf, Arc<AtomicBool>>,
+    /// Finished SHA-256 digests, so re-selecting a file doesn't re-hash it.
+    pub checksum_cache: HashMap<PathBuf, CachedChecksum>,
+    /// Images waiting for a decode slot, in priority order.
+    pub image_decode_queue: VecDeque<PathBuf>,
+    /// Pins added by "pin all images", keyed by the directory they came
+    /// from, so re-invoking on that directory can unpin the batch.
+    pub bulk_pins: HashMap<PathBuf, Vec<PathBuf>>,
     /// Index of the image currently shown in the lightbox (into `pinned_inspector`).
     pub lightbox_index: usize,
     /// Hit zones from the last lightbox render (for mouse click dispatch).
     pub lightbox_hit_zones: Option<crate::ui::lightbox::LightboxHitZones>,
+    /// Status-bar badge picked with the badge-focus key, if any.
+    pub badge_focus: Option<crate::ui::badges::StatusBadge>,
+    /// Screen cells of the badges drawn in the last frame.
+    pub badge_hit_zones: Vec<(ratatui::layout::Rect, crate::ui::badges::StatusBadge)>,
     /// Search root directory.
     pub search_root: PathBuf,
     /// Flat search index for `search_root`.
     pub search_index: Vec<SearchEntry>,
     /// Current search query.
     pub search_query: String,
-    /// Search option: case-sensitive matching.
-    pub search_case_sensitive: bool,
+    /// Case sensitivity and what the index includes; starts from the
+    /// `[search]` config section.
+    pub search_options: SearchOptions,
+    /// How the query is matched (substring / fuzzy).
+    pub search_mode: SearchMode,
     /// Ranked matches for the current query.
     pub search_results: Vec<SearchResult>,
+    /// Why the current query can't be run (invalid regex).
+    pub search_error: Option<String>,
     /// Selected row in `search_results`.
     pub search_selected: usize,
     /// Scroll offset for search results.
@@ -147,12 +305,25 @@ pub struct AppState {
     pub pending_tree_rebuild: Option<PathBuf>,
     /// Current tree rebuild generation in flight.
     pub tree_rebuild_in_flight: Option<u64>,
+    /// Roots visited, oldest first, each with the path that was selected
+    /// when it was left.
+    pub root_history: Vec<(PathBuf, Option<PathBuf>)>,
+    /// Position of the current root in `root_history`.
+    pub root_history_index: usize,
+    /// History position the queued rebuild moves to, when it came from
+    /// back/forward rather than a fresh re-root.
+    pub root_history_target: Option<usize>,
     /// Monotonic generation id for tree rebuild requests.
     pub tree_rebuild_generation: u64,
     /// Queue of directory paths to lazily expand in background.
     pub pending_expand_paths: VecDeque<PathBuf>,
     /// Paths currently expanding in background.
     pub expand_in_flight: HashSet<PathBuf>,
+    /// Running "expand all", if any.
+    pub expand_all: Option<ExpandAllJob>,
+    /// `(directory, child)` steps taken by "jump to largest child", so
+    /// "back up" can retrace them.
+    pub largest_trail: Vec<(PathBuf, PathBuf)>,
     /// Pending reveal target path that should be retried after async scans.
     pub pending_reveal_path: Option<PathBuf>,
     /// Whether search index should be rebuilt for the current root.
@@ -163,15 +334,97 @@ pub struct AppState {
     pub search_reindex_generation: u64,
     /// Non-size background scanning in progress (tree/search/expand jobs).
     pub fs_scanning: bool,
+    /// Paths staged for deletion, in the order they were staged.
+    pub pending_deletions: Vec<PathBuf>,
+    /// Selected row in the deletions tab.
+    pub deletions_selected: usize,
+    /// Text typed into the deletion confirmation prompt.
+    pub deletion_confirm_input: String,
+    /// Confirmed deletion batch waiting to be handed to the fs runtime.
+    pub deletion_job_requested: Option<(Vec<PathBuf>, bool)>,
+    /// `(done, total)` for the deletion job in flight.
+    pub deletion_progress: Option<(usize, usize)>,
+    /// Failures from the last deletion job.
+    pub deletion_errors: Vec<(PathBuf, String)>,
+    /// Entry being renamed in [`ActiveView::Rename`].
+    pub rename_target: Option<PathBuf>,
+    /// Paths reported changed by the watcher since `watch_changed_at`,
+    /// keyed by the directory holding them.
+    pub watch_changed: HashMap<PathBuf, HashSet<PathBuf>>,
+    /// First watcher event of the current debounce window.
+    pub watch_changed_at: Option<std::time::Instant>,
+    /// Per directory: start of the current one-second window and the
+    /// watcher events seen in it, to spot rename storms.
+    pub watch_event_rate: HashMap<PathBuf, (std::time::Instant, u32)>,
+    /// Directories whose sizes were already patched for their watcher
+    /// events; re-listing them keeps the cached sums.
+    pub watch_sized_dirs: HashSet<PathBuf>,
+    /// Loaded directories waiting to be listed again.
+    pub pending_rescan_paths: VecDeque<PathBuf>,
+    /// Directories a manual refresh is still waiting to re-list.
+    pub refresh_pending: HashSet<PathBuf>,
+    /// Path to keep selected (or its nearest surviving ancestor) while a
+    /// refresh lands.
+    pub refresh_select: Option<PathBuf>,
+    /// "Edit Config File…" was chosen; the main loop hands the terminal
+    /// to the editor.
+    pub edit_config_requested: bool,
+    /// Command and path to run with the terminal released, for "open in
+    /// editor / pager".
+    pub external_open_requested: Option<(String, PathBuf)>,
+    /// Path to hand to the system's default application.
+    pub system_open_requested: Option<PathBuf>,
+    /// Lines of the config file that could not be applied.
+    pub config_warnings: Vec<ConfigWarning>,
+    /// Changed paths in the enclosing git work tree (empty outside one).
+    pub git_statuses: HashMap<PathBuf, GitFileStatus>,
+    /// When `git status` was last asked for; it runs once this settles.
+    pub git_status_requested_at: Option<std::time::Instant>,
+    /// `git status` generation currently in flight.
+    pub git_status_in_flight: Option<u64>,
+    /// Monotonic generation id for `git status` runs.
+    pub git_status_generation: u64,
+    /// Path typed in [`ActiveView::PathInput`].
+    pub path_input: String,
+    /// Candidates from the last ambiguous Tab completion.
+    pub path_completions: Vec<String>,
+    /// Status message to restore when the path input is cancelled.
+    pub path_input_saved_status: Option<String>,
+    /// Directory that [`ActiveView::NewDir`] creates into.
+    pub new_dir_parent: Option<PathBuf>,
+    /// Name typed into the rename / new-directory prompt.
+    pub name_input: String,
+    /// Entry awaiting confirmation in [`ActiveView::ConfirmDelete`].
+    pub delete_target: Option<PathBuf>,
+    /// Confirmed single delete `(path, permanent)` for the fs runtime.
+    pub delete_job_requested: Option<(PathBuf, bool)>,
+    /// Paths queued for the background inspector worker.
+    pub pending_inspections: VecDeque<PathBuf>,
+    /// Paths sent to the inspector worker whose result hasn't arrived,
+    /// and whether they were queued again meanwhile.
+    pub inspections_in_flight: HashMap<PathBuf, bool>,
+    /// MIME cache counters shared with the inspector worker.
+    pub mime_stats: Arc<MimeCacheStats>,
+    /// Whether the debug overlay (cache counters etc.) is shown.
+    pub show_debug_overlay: bool,
+    /// External previewer `(path, command)` waiting to be started.
+    pub preview_request: Option<(PathBuf, String)>,
+    /// Cancels the external previewer in flight.
+    pub preview_cancel: Option<Arc<AtomicBool>>,
 }
 
 impl AppState {
     pub fn new(cwd: PathBuf, tree: DirTree, config: AppConfig) -> Self {
+        let search_options = config.search;
+        let syntax_highlight = config.syntax_highlight;
         Self {
             tree,
             tree_state: TreeWidgetState::default(),
             walk_config: WalkConfig::default(),
-            grouping_config: GroupingConfig::default(),
+            grouping_config: GroupingConfig {
+                group_by: config.group_by,
+                min_group_size: config.grouping_min_size,
+            },
             cwd: cwd.clone(),
             selected_dir: None,
             should_quit: false,
@@ -181,15 +434,54 @@ impl AppState {
             settings_selected: 0,
             controls_selected: 0,
             awaiting_rebind: false,
+            pending_rebind: None,
+            rebind_warning: None,
+            popup_offset: (0, 0),
+            pin_set_selected: 0,
+            bookmark_selected: 0,
+            top_dirs: Vec::new(),
+            top_files: Vec::new(),
+            top_selected: 0,
+            pin_set_name_input: None,
+            exclude_selected: 0,
+            exclude_input: None,
             dir_sizes: HashMap::new(),
+            dir_sizes_alt: HashMap::new(),
+            dir_apparent_sizes: HashMap::new(),
+            dir_entry_counts: HashMap::new(),
+            dir_excluded: HashSet::new(),
             file_sizes: HashMap::new(),
+            file_sizes_alt: HashMap::new(),
             dir_local_sums: HashMap::new(),
+            stale_sizes: HashSet::new(),
+            size_cache_carry: HashMap::new(),
+            size_cache_enabled: true,
             needs_size_recompute: false,
             size_compute_generation: 0,
             scanning: false,
+            scan_progress: Default::default(),
+            last_scan_stats: None,
+            size_scan_failed: None,
+            slow_fs_mode: SlowFsMode::Auto,
+            slow_fs_detected: false,
+            fixed_tree: false,
+            slow_fs: Arc::new(AtomicBool::new(false)),
+            syntax_highlight: Arc::new(AtomicBool::new(syntax_highlight)),
+            size_on_demand: None,
+            size_on_demand_cancel: None,
+            inspect_due: None,
+            search_reveal_due: None,
+            mouse_enabled: true,
+            a11y: false,
             last_left_click: None,
+            type_ahead: String::new(),
+            type_ahead_at: None,
+            type_ahead_mode: false,
+            selected_nodes: HashSet::new(),
             terminal_area: Rect::default(),
+            tree_viewport_rows: 0,
             dragging_splitter: false,
+            dragging_tree_scrollbar: false,
             inspector_path: None,
             inspector_info: None,
             pane_focus: PaneFocus::Tree,
@@ -202,26 +494,142 @@ impl AppState {
             pin_scroll_anim: crate::ui::smooth_scroll::SmoothScroll::new(0.35),
             image_cache: HashMap::new(),
             image_decoding: HashSet::new(),
+            checksum_in_flight: HashMap::new(),
+            checksum_cache: HashMap::new(),
+            image_decode_queue: VecDeque::new(),
+            bulk_pins: HashMap::new(),
             lightbox_index: 0,
             lightbox_hit_zones: None,
+            badge_focus: None,
+            badge_hit_zones: Vec::new(),
             search_root: cwd.clone(),
             search_index: Vec::new(),
             search_query: String::new(),
-            search_case_sensitive: false,
+            search_options,
+            search_mode: SearchMode::default(),
             search_results: Vec::new(),
+            search_error: None,
             search_selected: 0,
             search_scroll: 0,
             copied_path: None,
             pending_tree_rebuild: None,
             tree_rebuild_in_flight: None,
+            root_history: vec![(cwd.clone(), None)],
+            root_history_index: 0,
+            root_history_target: None,
             tree_rebuild_generation: 0,
             pending_expand_paths: VecDeque::new(),
             expand_in_flight: HashSet::new(),
+            expand_all: None,
+            largest_trail: Vec::new(),
             pending_reveal_path: None,
             search_reindex_requested: true,
             search_reindex_in_flight: None,
             search_reindex_generation: 0,
             fs_scanning: false,
+            pending_deletions: Vec::new(),
+            deletions_selected: 0,
+            deletion_confirm_input: String::new(),
+            deletion_job_requested: None,
+            deletion_progress: None,
+            deletion_errors: Vec::new(),
+            rename_target: None,
+            watch_changed: HashMap::new(),
+            watch_changed_at: None,
+            watch_event_rate: HashMap::new(),
+            watch_sized_dirs: HashSet::new(),
+            pending_rescan_paths: VecDeque::new(),
+            refresh_pending: HashSet::new(),
+            refresh_select: None,
+            edit_config_requested: false,
+            external_open_requested: None,
+            system_open_requested: None,
+            config_warnings: Vec::new(),
+            git_statuses: HashMap::new(),
+            git_status_requested_at: Some(std::time::Instant::now()),
+            git_status_in_flight: None,
+            git_status_generation: 0,
+            path_input: String::new(),
+            path_completions: Vec::new(),
+            path_input_saved_status: None,
+            new_dir_parent: None,
+            name_input: String::new(),
+            delete_target: None,
+            delete_job_requested: None,
+            pending_inspections: VecDeque::new(),
+            inspections_in_flight: HashMap::new(),
+            mime_stats: Arc::new(MimeCacheStats::default()),
+            show_debug_overlay: false,
+            preview_request: None,
+            preview_cancel: None,
+        }
+    }
+
+    /// Whether slow-filesystem mode is in effect.
+    pub fn is_slow_fs(&self) -> bool {
+        self.slow_fs.load(Ordering::Relaxed)
+    }
+
+    /// Store a directory total in both size views.
+    pub fn record_dir_size(&mut self, dir: PathBuf, apparent: u64, allocated: u64) {
+        let (shown, other) = self.in_view_order(apparent, allocated);
+        self.dir_sizes_alt.insert(dir.clone(), other);
+        self.dir_sizes.insert(dir, shown);
+    }
+
+    /// Store a file size in both size views.
+    pub fn record_file_size(&mut self, path: PathBuf, apparent: u64, allocated: u64) {
+        let (shown, other) = self.in_view_order(apparent, allocated);
+        self.file_sizes_alt.insert(path.clone(), other);
+        self.file_sizes.insert(path, shown);
+    }
+
+    /// `(apparent, allocated)` directory size maps, whichever view is active.
+    pub fn dir_size_views(&self) -> (&HashMap<PathBuf, u64>, &HashMap<PathBuf, u64>) {
+        match self.config.size_view {
+            SizeView::Apparent => (&self.dir_sizes, &self.dir_sizes_alt),
+            SizeView::Allocated => (&self.dir_sizes_alt, &self.dir_sizes),
+        }
+    }
+
+    /// `(apparent, allocated)` file size maps, whichever view is active.
+    pub fn file_size_views(&self) -> (&HashMap<PathBuf, u64>, &HashMap<PathBuf, u64>) {
+        match self.config.size_view {
+            SizeView::Apparent => (&self.file_sizes, &self.file_sizes_alt),
+            SizeView::Allocated => (&self.file_sizes_alt, &self.file_sizes),
+        }
+    }
+
+    /// Switch the size view.  Both views are computed by the same walk,
+    /// so this only swaps the maps.
+    pub fn set_size_view(&mut self, view: SizeView) {
+        if view != self.config.size_view {
+            self.config.size_view = view;
+            std::mem::swap(&mut self.dir_sizes, &mut self.dir_sizes_alt);
+            std::mem::swap(&mut self.file_sizes, &mut self.file_sizes_alt);
+        }
+    }
+
+    /// Copy already-computed sizes for `info.path` into an inspector record.
+    pub fn fill_known_sizes(&self, info: &mut InspectorInfo) {
+        let (dir_apparent, dir_allocated) = self.dir_size_views();
+        let (file_apparent, file_allocated) = self.file_size_views();
+        if let Some(sz) = dir_apparent.get(&info.path).copied() {
+            info.size_bytes = Some(sz);
+            info.allocated_bytes = dir_allocated.get(&info.path).copied();
+            info.apparent_bytes = self.dir_apparent_sizes.get(&info.path).copied();
+            info.entry_counts = self.dir_entry_counts.get(&info.path).copied();
+        } else if let Some(sz) = file_apparent.get(&info.path).copied() {
+            info.size_bytes = Some(sz);
+            info.allocated_bytes = file_allocated.get(&info.path).copied().or(info.allocated_bytes);
+        }
+        info.show_allocated = self.config.size_view == SizeView::Allocated;
+    }
+
+    fn in_view_order(&self, apparent: u64, allocated: u64) -> (u64, u64) {
+        match self.config.size_view {
+            SizeView::Apparent => (apparent, allocated),
+            SizeView::Allocated => (allocated, apparent),
         }
     }
 }
@@ -0,0 +1,345 @@
+//! Ordered application of background results.
+//!
+//! The main loop drains every pending [`FsUpdate`] and [`SizeUpdate`] into
+//! a [`PendingUpdates`] batch and applies it in one go before drawing, so a
+//! frame never shows a rebuilt tree next to size maps or a selection that
+//! belong to the previous one.
+
+use crate::app::fs_runtime::FsUpdate;
+use crate::app::handler;
+use crate::app::size_runtime::{
+    apply_size_update, finalize_ready_dirs, mark_scan_failed, SizeComputeState, SizeUpdate,
+};
+use crate::app::state::{ActiveView, AppState};
+
+/// Messages drained from the background channels since the last frame.
+#[derive(Default)]
+pub struct PendingUpdates {
+    pub fs: Vec<FsUpdate>,
+    pub sizes: Vec<(u64, SizeUpdate)>,
+}
+
+impl PendingUpdates {
+    pub fn is_empty(&self) -> bool {
+        self.fs.is_empty() && self.sizes.is_empty()
+    }
+}
+
+/// Apply a drained batch in a fixed order: tree structure → expansions →
+/// other fs results → selection restoration → size maps.
+pub fn apply_pending_updates(
+    state: &mut AppState,
+    size_compute: &mut Option<SizeComputeState>,
+    pending: PendingUpdates,
+) {
+    let PendingUpdates { fs, sizes } = pending;
+    let had_fs = !fs.is_empty();
+
+    let mut tree_rebuilt = false;
+    let mut expansions = Vec::new();
+    let mut others = Vec::new();
+    for update in fs {
+        match update {
+            FsUpdate::TreeRebuilt { generation, root, result } => {
+                tree_rebuilt |= apply_tree_rebuilt(state, generation, root, result);
+            }
+            update @ FsUpdate::DirExpanded { .. } => expansions.push(update),
+            other => others.push(other),
+        }
+    }
+
+    let expanded_any = !expansions.is_empty();
+    for update in expansions {
+        if let FsUpdate::DirExpanded { path, result } = update {
+            apply_dir_expanded(state, path, result);
+        }
+    }
+    if expanded_any {
+        handler::continue_expand_all(state);
+    }
+
+    let mut git_dirty = tree_rebuilt;
+    for update in others {
+        git_dirty |= matches!(
+            update,
+            FsUpdate::Deleted { .. } | FsUpdate::Removed { .. } | FsUpdate::DirRescanned { .. }
+        );
+        match update {
+            FsUpdate::SearchIndexed { generation, root, entries } => {
+                if state.search_reindex_in_flight == Some(generation) && root == state.search_root {
+                    state.search_reindex_in_flight = None;
+                    state.search_index = entries;
+                    handler::refresh_search(state);
+                }
+            }
+            FsUpdate::Deleted { path, result } => {
+                handler::apply_deletion_result(state, path, result);
+            }
+            FsUpdate::DeletionsFinished => {
+                handler::finish_deletions(state);
+            }
+            FsUpdate::Removed { path, result } => {
+                handler::apply_removed(state, path, result);
+            }
+            FsUpdate::PathChanged { path } => {
+                handler::note_path_changed(state, path);
+            }
+            FsUpdate::PathRenamed { from, to } => {
+                handler::note_path_renamed(state, from, to);
+            }
+            FsUpdate::DirRescanned { path, children } => {
+                handler::apply_dir_rescanned(state, path, children);
+            }
+            FsUpdate::GitStatus { generation, statuses } => {
+                handler::apply_git_status(state, generation, statuses);
+            }
+            FsUpdate::TreeRebuilt { .. } | FsUpdate::DirExpanded { .. } => {}
+        }
+    }
+    if git_dirty {
+        handler::request_git_status(state);
+    }
+
+    if had_fs {
+        handler::retry_pending_reveal(state);
+    }
+
+    // Sizes last.  After a rebuild every in-flight size message describes
+    // the old tree, so the running computation is dropped instead.
+    if tree_rebuilt {
+        if let Some(old) = size_compute.take() {
+            old.request_cancel();
+        }
+        state.scanning = false;
+    } else if !sizes.is_empty() {
+        // Sorting by size reorders rows as totals arrive; keep the cursor
+        // on the same entry rather than the same row index.
+        if state.walk_config.sort_mode.is_size() {
+            handler::preserving_selection(state, |state| {
+                apply_size_batch(state, size_compute, sizes);
+            });
+        } else {
+            apply_size_batch(state, size_compute, sizes);
+        }
+    }
+
+    state.fs_scanning = state.tree_rebuild_in_flight.is_some()
+        || !state.expand_in_flight.is_empty()
+        || state.search_reindex_in_flight.is_some();
+}
+
+fn apply_size_batch(
+    state: &mut AppState,
+    size_compute: &mut Option<SizeComputeState>,
+    sizes: Vec<(u64, SizeUpdate)>,
+) {
+    let mut need_finalize = false;
+    for (generation, update) in sizes {
+        need_finalize |= apply_size_update(state, size_compute, generation, update);
+    }
+    if let Some(compute) = size_compute.as_mut() {
+        if need_finalize {
+            finalize_ready_dirs(state, compute);
+        }
+        if let Some(reason) = compute.take_failure() {
+            mark_scan_failed(state, reason);
+        }
+        state.scanning = compute.is_scanning();
+    }
+    if state.active_view == ActiveView::TopFiles {
+        handler::refresh_top_entries(state);
+    }
+}
+
+/// Swap in a rebuilt tree.  Returns `true` when the tree was replaced.
+fn apply_tree_rebuilt(
+    state: &mut AppState,
+    generation: u64,
+    root: std::path::PathBuf,
+    result: anyhow::Result<crate::core::tree::DirTree>,
+) -> bool {
+    if state.tree_rebuild_in_flight != Some(generation) {
+        return false;
+    }
+    state.tree_rebuild_in_flight = None;
+    match result {
+        Ok(tree) => {
+            let left_selected = (root != state.cwd).then(|| handler::selected_node_path(state));
+            state.cwd = root;
+            state.tree = tree;
+            let cwd = state.cwd.clone();
+            state.selected_nodes.retain(|p| p.starts_with(&cwd));
+            state.tree_state.selected = 0;
+            state.tree_state.offset = 0;
+            state.dir_sizes.clear();
+            state.dir_sizes_alt.clear();
+            state.dir_apparent_sizes.clear();
+            state.dir_entry_counts.clear();
+            state.dir_excluded.clear();
+            state.stale_sizes.clear();
+            state.file_sizes.clear();
+            state.file_sizes_alt.clear();
+            state.dir_local_sums.clear();
+            state.needs_size_recompute = true;
+
+            state.search_root = state.cwd.clone();
+            state.search_index.clear();
+            state.search_reindex_requested = true;
+            handler::refresh_search(state);
+            if let Some(left_selected) = left_selected {
+                handler::record_root_change(state, left_selected);
+            }
+            true
+        }
+        Err(_) => {
+            state.root_history_target = None;
+            state.status_message = Some("Cannot open directory".to_string());
+            false
+        }
+    }
+}
+
+fn apply_dir_expanded(
+    state: &mut AppState,
+    path: std::path::PathBuf,
+    result: anyhow::Result<Vec<crate::core::tree::EntryMeta>>,
+) {
+    state.expand_in_flight.remove(&path);
+    let Ok(children) = result else {
+        return;
+    };
+    let Some(parent_id) = state.tree.nodes.iter().position(|n| n.meta.path == path) else {
+        return;
+    };
+    if state.tree.get(parent_id).children.is_empty() {
+        for meta in children {
+            state.tree.add_child(parent_id, meta);
+        }
+        state.dir_local_sums.remove(&path);
+        state.needs_size_recompute = true;
+        // The slow-fs search index only covers loaded nodes.
+        if state.is_slow_fs() {
+            state.search_reindex_requested = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    use crate::config::AppConfig;
+    use crate::core::grouping::SortMode;
+    use crate::core::size::EntryCounts;
+    use crate::core::tree::{DirTree, EntryMeta};
+
+    /// State over `/t` holding the directories `names`.
+    fn state_with_dirs(names: &[&str]) -> AppState {
+        let root = Path::new("/t");
+        let mut tree = DirTree::new(EntryMeta::missing(root, true));
+        for name in names {
+            tree.add_child(tree.root, EntryMeta::missing(&root.join(name), true));
+        }
+        AppState::new(root.to_path_buf(), tree, AppConfig::default())
+    }
+
+    fn dir_total(dir: &str, total: u64) -> (u64, SizeUpdate) {
+        let update = SizeUpdate::DirTotal {
+            dir: PathBuf::from(dir),
+            total,
+            apparent: total,
+            allocated: total,
+            counts: EntryCounts::default(),
+            excluded: false,
+        };
+        (0, update)
+    }
+
+    #[test]
+    fn sizes_batched_with_a_rebuild_are_dropped() {
+        let mut state = state_with_dirs(&["a"]);
+        state.dir_sizes.insert(PathBuf::from("/t/a"), 1);
+        state.tree_rebuild_in_flight = Some(7);
+        let pending = PendingUpdates {
+            fs: vec![FsUpdate::TreeRebuilt {
+                generation: 7,
+                root: PathBuf::from("/u"),
+                result: Ok(DirTree::new(EntryMeta::missing(Path::new("/u"), true))),
+            }],
+            sizes: vec![dir_total("/t/a", 5)],
+        };
+
+        apply_pending_updates(&mut state, &mut None, pending);
+
+        assert_eq!(state.cwd, Path::new("/u"));
+        assert!(state.dir_sizes.is_empty());
+        assert!(state.needs_size_recompute && !state.scanning);
+    }
+
+    #[test]
+    fn a_stale_rebuild_leaves_the_tree_and_sizes_alone() {
+        let mut state = state_with_dirs(&["a"]);
+        state.tree_rebuild_in_flight = Some(8);
+        let pending = PendingUpdates {
+            fs: vec![FsUpdate::TreeRebuilt {
+                generation: 7,
+                root: PathBuf::from("/u"),
+                result: Ok(DirTree::new(EntryMeta::missing(Path::new("/u"), true))),
+            }],
+            sizes: vec![dir_total("/t/a", 5)],
+        };
+
+        apply_pending_updates(&mut state, &mut None, pending);
+
+        assert_eq!(state.cwd, Path::new("/t"));
+        assert_eq!(state.tree_rebuild_in_flight, Some(8));
+        assert_eq!(state.dir_sizes.get(Path::new("/t/a")), Some(&5));
+    }
+
+    #[test]
+    fn removal_in_the_same_batch_as_its_parent_listing_applies_after_it() {
+        let mut state = state_with_dirs(&["a"]);
+        state.expand_in_flight.insert(PathBuf::from("/t/a"));
+        let listing = ["/t/a/x", "/t/a/y"].map(|p| EntryMeta::missing(Path::new(p), false));
+        let pending = PendingUpdates {
+            // Arrival order is removal first; the listing must still go in first.
+            fs: vec![
+                FsUpdate::Removed {
+                    path: PathBuf::from("/t/a/x"),
+                    result: Ok(()),
+                },
+                FsUpdate::DirExpanded {
+                    path: PathBuf::from("/t/a"),
+                    result: Ok(listing.to_vec()),
+                },
+            ],
+            sizes: Vec::new(),
+        };
+
+        apply_pending_updates(&mut state, &mut None, pending);
+
+        let paths: Vec<&Path> = state.tree.nodes.iter().map(|n| n.meta.path.as_path()).collect();
+        assert_eq!(paths, [Path::new("/t"), Path::new("/t/a"), Path::new("/t/a/y")]);
+        assert!(state.expand_in_flight.is_empty());
+    }
+
+    #[test]
+    fn size_sorted_rows_keep_the_cursor_on_the_same_entry() {
+        let mut state = state_with_dirs(&["a", "b"]);
+        state.walk_config.sort_mode = SortMode::SizeDesc;
+        state.dir_sizes.insert(PathBuf::from("/t/a"), 5);
+        state.dir_sizes.insert(PathBuf::from("/t/b"), 2);
+        state.tree_state.selected = 1;
+        assert_eq!(handler::selected_node_path(&state).as_deref(), Some(Path::new("/t/a")));
+
+        let pending = PendingUpdates {
+            fs: Vec::new(),
+            sizes: vec![dir_total("/t/b", 10)],
+        };
+        apply_pending_updates(&mut state, &mut None, pending);
+
+        assert_eq!(state.tree_state.selected, 2);
+        assert_eq!(handler::selected_node_path(&state).as_deref(), Some(Path::new("/t/a")));
+    }
+}
@@ -0,0 +1,126 @@
+//! Live filesystem watching (`watch_fs` setting).
+//!
+//! Every directory node of the tree is watched non-recursively, so unloaded
+//! subtrees are never walked just to register watches.  Raw `notify` events
+//! are forwarded by a background thread as [`FsUpdate::PathChanged`] (plus
+//! [`FsUpdate::PathRenamed`] for renames, so expansion and pins follow the
+//! entry); the main loop debounces them and rescans only the directories
+//! that changed.
+//! Sizes follow file by file: each changed file is stat'ed and its bytes
+//! moved in the cached totals, unless its directory sees a storm of
+//! events, which is cheaper to re-walk once.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use notify::event::{ModifyKind, RenameMode};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+
+use crate::app::fs_runtime::FsUpdate;
+use crate::core::tree::DirTree;
+
+/// Changes are collected for this long before the affected directories
+/// are rescanned.
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Events per second for one directory above which its sizes are
+/// recomputed instead of patched file by file.
+pub const WATCH_STORM_EVENTS: u32 = 200;
+
+/// Window over which [`WATCH_STORM_EVENTS`] is counted.
+pub const WATCH_STORM_WINDOW: Duration = Duration::from_secs(1);
+
+pub struct FsWatcher {
+    watcher: RecommendedWatcher,
+    root: PathBuf,
+    watched: HashSet<PathBuf>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl FsWatcher {
+    /// Start a watcher for the tree rooted at `root`.  Call [`sync`](Self::sync)
+    /// to register the directories.
+    pub fn spawn(tx: mpsc::UnboundedSender<FsUpdate>, root: PathBuf) -> notify::Result<Self> {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (raw_tx, raw_rx) = std::sync::mpsc::channel::<notify::Result<notify::Event>>();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = raw_tx.send(event);
+        })?;
+
+        let flag = cancel.clone();
+        std::thread::spawn(move || {
+            // Ends once the watcher (and with it `raw_tx`) is dropped.
+            while let Ok(event) = raw_rx.recv() {
+                if flag.load(Ordering::Relaxed) {
+                    return;
+                }
+                let Ok(event) = event else {
+                    continue;
+                };
+                if matches!(event.kind, EventKind::Access(_)) {
+                    continue;
+                }
+                if let (EventKind::Modify(ModifyKind::Name(RenameMode::Both)), [from, to]) =
+                    (event.kind, event.paths.as_slice())
+                {
+                    let renamed = FsUpdate::PathRenamed {
+                        from: from.clone(),
+                        to: to.clone(),
+                    };
+                    if tx.send(renamed).is_err() {
+                        return;
+                    }
+                }
+                for path in event.paths {
+                    if tx.send(FsUpdate::PathChanged { path }).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            watcher,
+            root,
+            watched: HashSet::new(),
+            cancel,
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Stop forwarding events.  The watcher itself goes away when dropped.
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Watch every directory node of `tree` and drop watches for
+    /// directories no longer in it.
+    pub fn sync(&mut self, tree: &DirTree) {
+        let dirs: HashSet<PathBuf> = tree
+            .nodes
+            .iter()
+            .filter(|n| n.meta.is_dir && !n.meta.is_symlink)
+            .map(|n| n.meta.path.clone())
+            .collect();
+        for gone in self.watched.difference(&dirs) {
+            let _ = self.watcher.unwatch(gone);
+        }
+        for dir in dirs.difference(&self.watched) {
+            // A directory that cannot be watched (permissions, inotify
+            // limit) simply does not refresh live.
+            let _ = self.watcher.watch(dir, RecursiveMode::NonRecursive);
+        }
+        self.watched = dirs;
+    }
+}
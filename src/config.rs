@@ -3,11 +3,16 @@
 //! Bindings are stored as a simple key-value text file at
 //! `$XDG_CONFIG_HOME/dir-tree/config.toml` (default `~/.config/dir-tree/config.toml`).
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+use crate::core::grouping::{GroupBy, SortMode};
+use crate::core::search::{SearchMode, SearchOptions};
+use crate::ui::theme::{format_hex_rgb, parse_hex_rgb, HeatGradient};
+
 // ───────────────────────────────────────── actions ───────────
 
 /// All configurable user actions in the tree view.
@@ -19,9 +24,57 @@ pub enum Action {
     Collapse,
     JumpSiblingUp,
     JumpSiblingDown,
+    JumpFirstSibling,
+    JumpLastSibling,
     CdIntoDir,
     ToggleHidden,
     OpenSettings,
+    ToggleDebug,
+    PinAllImages,
+    ToggleHeat,
+    SavePinSet,
+    LoadPinSet,
+    StageDelete,
+    ShowDeletions,
+    CommitDeletions,
+    RetrySizeScan,
+    ToggleMouse,
+    GrowTreePane,
+    ShrinkTreePane,
+    RescanSelected,
+    CycleSort,
+    Delete,
+    Rename,
+    NewDir,
+    ExpandAll,
+    CollapseAll,
+    JumpLargestChild,
+    JumpLargestBack,
+    GoToPath,
+    AddBookmark,
+    OpenBookmarks,
+    OpenSystem,
+    CopyPath,
+    CopyRelativePath,
+    ShowTopFiles,
+    Refresh,
+    FocusBadge,
+    ToggleBadge,
+    ScanStats,
+    HistoryBack,
+    HistoryForward,
+    HalfPageDown,
+    HalfPageUp,
+    ToggleSelect,
+    ClearSelection,
+    PinSelected,
+    OpenInEditor,
+    OpenInPager,
+    OpenTerminal,
+    IncreaseDepth,
+    DecreaseDepth,
+    CopyChecksum,
+    TypeAhead,
     Quit,
 }
 
@@ -66,6 +119,205 @@ impl PanelLayoutMode {
     }
 }
 
+/// Whether to run in slow-filesystem mode (no background size walk, no
+/// MIME sniffing, no full search index, longer debounces).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SlowFsMode {
+    /// Decide from the startup directory's measured latency.
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl SlowFsMode {
+    pub const ALL: [Self; 3] = [Self::Auto, Self::On, Self::Off];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::On => "on",
+            Self::Off => "off",
+        }
+    }
+
+    pub fn config_value(self) -> &'static str {
+        self.label()
+    }
+
+    pub fn from_config_value(v: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.config_value() == v)
+    }
+
+    /// Next mode in `ALL`, wrapping around.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Effective setting given the auto-detection result.
+    pub fn resolve(self, detected_slow: bool) -> bool {
+        match self {
+            Self::Auto => detected_slow,
+            Self::On => true,
+            Self::Off => false,
+        }
+    }
+}
+
+/// Which byte count sizes are shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SizeView {
+    /// File lengths, as `ls -l` / `du --apparent-size` report them.
+    #[default]
+    Apparent,
+    /// Blocks actually allocated on disk, as plain `du` reports them.
+    Allocated,
+}
+
+impl SizeView {
+    pub const ALL: [Self; 2] = [Self::Apparent, Self::Allocated];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Apparent => "apparent",
+            Self::Allocated => "allocated",
+        }
+    }
+
+    pub fn config_value(self) -> &'static str {
+        self.label()
+    }
+
+    pub fn from_config_value(v: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.config_value() == v)
+    }
+
+    /// Next view in `ALL`, wrapping around.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Prefix drawn before names in the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IconSet {
+    /// Names only.
+    None,
+    /// `▶` / `▼` for directories, `~` for symlinks.
+    #[default]
+    Ascii,
+    /// Nerd-font glyphs for folders and known file types.
+    NerdFont,
+}
+
+impl IconSet {
+    pub const ALL: [Self; 3] = [Self::None, Self::Ascii, Self::NerdFont];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Ascii => "ascii",
+            Self::NerdFont => "nerd-font",
+        }
+    }
+
+    pub fn config_value(self) -> &'static str {
+        self.label()
+    }
+
+    pub fn from_config_value(v: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.config_value() == v)
+    }
+
+    /// Next set in `ALL`, wrapping around.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Column before each tree row showing its share of the parent's size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SizeShare {
+    #[default]
+    Off,
+    /// Percentage of the parent directory's total.
+    Percent,
+    /// ncdu-style `[####----]` bar scaled to the largest sibling.
+    Bar,
+}
+
+impl SizeShare {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Percent, Self::Bar];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Percent => "percent",
+            Self::Bar => "bar",
+        }
+    }
+
+    pub fn config_value(self) -> &'static str {
+        self.label()
+    }
+
+    pub fn from_config_value(v: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.config_value() == v)
+    }
+
+    /// Next mode in `ALL`, wrapping around.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// How far back "recently changed" highlighting reaches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecentWindow {
+    TenMinutes,
+    #[default]
+    Hour,
+    Day,
+}
+
+impl RecentWindow {
+    pub const ALL: [Self; 3] = [Self::TenMinutes, Self::Hour, Self::Day];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::TenMinutes => "10m",
+            Self::Hour => "1h",
+            Self::Day => "24h",
+        }
+    }
+
+    pub fn config_value(self) -> &'static str {
+        self.label()
+    }
+
+    pub fn from_config_value(v: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|w| w.config_value() == v)
+    }
+
+    /// Next window in `ALL`, wrapping around.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|w| *w == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn duration(self) -> Duration {
+        match self {
+            Self::TenMinutes => Duration::from_secs(10 * 60),
+            Self::Hour => Duration::from_secs(60 * 60),
+            Self::Day => Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
 impl Action {
     /// Ordered list of all actions (used for the controls menu).
     pub const ALL: &[Action] = &[
@@ -75,9 +327,57 @@ impl Action {
         Action::Collapse,
         Action::JumpSiblingUp,
         Action::JumpSiblingDown,
+        Action::JumpFirstSibling,
+        Action::JumpLastSibling,
         Action::CdIntoDir,
         Action::ToggleHidden,
         Action::OpenSettings,
+        Action::ToggleDebug,
+        Action::PinAllImages,
+        Action::ToggleHeat,
+        Action::SavePinSet,
+        Action::LoadPinSet,
+        Action::StageDelete,
+        Action::ShowDeletions,
+        Action::CommitDeletions,
+        Action::RetrySizeScan,
+        Action::ToggleMouse,
+        Action::GrowTreePane,
+        Action::ShrinkTreePane,
+        Action::RescanSelected,
+        Action::CycleSort,
+        Action::Delete,
+        Action::Rename,
+        Action::NewDir,
+        Action::ExpandAll,
+        Action::CollapseAll,
+        Action::JumpLargestChild,
+        Action::JumpLargestBack,
+        Action::GoToPath,
+        Action::AddBookmark,
+        Action::OpenBookmarks,
+        Action::OpenSystem,
+        Action::CopyPath,
+        Action::CopyRelativePath,
+        Action::ShowTopFiles,
+        Action::Refresh,
+        Action::FocusBadge,
+        Action::ToggleBadge,
+        Action::ScanStats,
+        Action::HistoryBack,
+        Action::HistoryForward,
+        Action::HalfPageDown,
+        Action::HalfPageUp,
+        Action::ToggleSelect,
+        Action::ClearSelection,
+        Action::PinSelected,
+        Action::OpenInEditor,
+        Action::OpenInPager,
+        Action::OpenTerminal,
+        Action::IncreaseDepth,
+        Action::DecreaseDepth,
+        Action::CopyChecksum,
+        Action::TypeAhead,
         Action::Quit,
     ];
 
@@ -90,13 +390,126 @@ impl Action {
             Action::Collapse => "Collapse / Parent",
             Action::JumpSiblingUp => "Prev Sibling Dir",
             Action::JumpSiblingDown => "Next Sibling Dir",
+            Action::JumpFirstSibling => "First Sibling Dir",
+            Action::JumpLastSibling => "Last Sibling Dir",
             Action::CdIntoDir => "Enter Directory",
             Action::ToggleHidden => "Toggle Hidden",
             Action::OpenSettings => "Open Settings",
+            Action::ToggleDebug => "Toggle Debug Overlay",
+            Action::PinAllImages => "Pin All Images in Dir",
+            Action::ToggleHeat => "Toggle Size Heat",
+            Action::SavePinSet => "Save Pin Set",
+            Action::LoadPinSet => "Load Pin Set",
+            Action::StageDelete => "Stage for Deletion",
+            Action::ShowDeletions => "Show Staged Deletions",
+            Action::CommitDeletions => "Commit Deletions",
+            Action::RetrySizeScan => "Retry Size Scan",
+            Action::ToggleMouse => "Toggle Mouse",
+            Action::GrowTreePane => "Widen Tree Pane",
+            Action::ShrinkTreePane => "Narrow Tree Pane",
+            Action::RescanSelected => "Rescan Selected Sizes",
+            Action::CycleSort => "Cycle Sort Order",
+            Action::Delete => "Delete (Trash)",
+            Action::Rename => "Rename",
+            Action::NewDir => "New Directory",
+            Action::ExpandAll => "Expand All",
+            Action::CollapseAll => "Collapse All",
+            Action::JumpLargestChild => "Jump to Largest Child",
+            Action::JumpLargestBack => "Back Up Largest Path",
+            Action::GoToPath => "Go to Path",
+            Action::AddBookmark => "Add Bookmark",
+            Action::OpenBookmarks => "Open Bookmarks",
+            Action::OpenSystem => "Open With System App",
+            Action::CopyPath => "Copy Path",
+            Action::CopyRelativePath => "Copy Relative Path",
+            Action::ShowTopFiles => "Largest Entries",
+            Action::Refresh => "Refresh",
+            Action::FocusBadge => "Focus Badge",
+            Action::ToggleBadge => "Toggle Badge",
+            Action::ScanStats => "Scan Statistics",
+            Action::HistoryBack => "History Back",
+            Action::HistoryForward => "History Forward",
+            Action::HalfPageDown => "Half Page Down",
+            Action::HalfPageUp => "Half Page Up",
+            Action::ToggleSelect => "Toggle Select",
+            Action::ClearSelection => "Clear Selection",
+            Action::PinSelected => "Pin Selected",
+            Action::OpenInEditor => "Open In Editor",
+            Action::OpenInPager => "Open In Pager",
+            Action::OpenTerminal => "Open Terminal Here",
+            Action::IncreaseDepth => "Increase Depth",
+            Action::DecreaseDepth => "Decrease Depth",
+            Action::CopyChecksum => "Copy SHA-256",
+            Action::TypeAhead => "Type-ahead Jump",
             Action::Quit => "Quit",
         }
     }
 
+    /// One-line explanation shown in the Controls popup; at most 60
+    /// columns so it fits the popup.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::MoveUp => "Move the selection up (tree, search results, pins).",
+            Action::MoveDown => "Move the selection down (tree, search results, pins).",
+            Action::Expand => "Expand a directory or group; on a file, toggle its pin.",
+            Action::Collapse => "Collapse, or go to the parent; on the root, go up a level.",
+            Action::JumpSiblingUp => "Jump to the previous directory at the same depth.",
+            Action::JumpSiblingDown => "Jump to the next directory at the same depth.",
+            Action::JumpFirstSibling => "Jump to the first directory at the same depth in the same parent.",
+            Action::JumpLastSibling => "Jump to the last directory at the same depth in the same parent.",
+            Action::CdIntoDir => "Quit and cd here (files: copy path, cd to the parent).",
+            Action::ToggleHidden => "Show or hide dotfiles and rebuild the tree.",
+            Action::OpenSettings => "Open the settings menu (from any pane).",
+            Action::ToggleDebug => "Show or hide the debug overlay.",
+            Action::PinAllImages => "Pin every image in the selected directory.",
+            Action::ToggleHeat => "Tint tree rows by their share of the root's total size.",
+            Action::SavePinSet => "Save the current pins under a name.",
+            Action::LoadPinSet => "Open the saved pin sets to load one.",
+            Action::StageDelete => "Stage or unstage the selection for deletion.",
+            Action::ShowDeletions => "Show the staged deletions tab in the right pane.",
+            Action::CommitDeletions => "Delete everything staged, after a typed confirmation.",
+            Action::RetrySizeScan => "Restart a failed size scan; finished directories are kept.",
+            Action::ToggleMouse => "Toggle mouse capture (off allows native text selection).",
+            Action::GrowTreePane => "Give the tree pane more room.",
+            Action::ShrinkTreePane => "Give the inspector pane more room.",
+            Action::RescanSelected => "Recompute sizes under the selected directory.",
+            Action::CycleSort => "Cycle the tree order: name, size, modification time.",
+            Action::Delete => "Delete the selection now (to the trash by default).",
+            Action::Rename => "Rename the selected entry in place.",
+            Action::NewDir => "Create a directory in the selected directory.",
+            Action::ExpandAll => "Expand every directory below the selection.",
+            Action::CollapseAll => "Collapse every directory below the root and select the root.",
+            Action::JumpLargestChild => "Expand and select the largest child of the directory.",
+            Action::JumpLargestBack => "Step back along the path taken with Jump to Largest Child.",
+            Action::GoToPath => "Type a path to reveal (re-roots if it lies outside).",
+            Action::AddBookmark => "Bookmark the selected directory.",
+            Action::OpenBookmarks => "List bookmarks to jump to or delete.",
+            Action::OpenSystem => "Open the selection with the system default app.",
+            Action::CopyPath => "Copy the selection's absolute path to the clipboard.",
+            Action::CopyRelativePath => "Copy the selection's path relative to the root.",
+            Action::ShowTopFiles => "Show the largest directories and files found so far.",
+            Action::Refresh => "Re-read the selected directory (the whole tree on the root).",
+            Action::FocusBadge => "Cycle focus through the status-bar badges",
+            Action::ToggleBadge => "Flip the setting behind the focused badge",
+            Action::ScanStats => "Show where the time went in the last completed size scan.",
+            Action::HistoryBack => "Re-root to the previous directory in the history.",
+            Action::HistoryForward => "Re-root to the next directory in the history.",
+            Action::HalfPageDown => "Move the tree selection and view down half a page.",
+            Action::HalfPageUp => "Move the tree selection and view up half a page.",
+            Action::ToggleSelect => "Add the selected row to the multi-selection, or take it out.",
+            Action::ClearSelection => "Empty the multi-selection (Esc also does while it is non-empty).",
+            Action::PinSelected => "Pin every file in the multi-selection, then clear it.",
+            Action::OpenInEditor => "Open the selection in $EDITOR (then $VISUAL, then vi).",
+            Action::OpenInPager => "View the selected file in $PAGER (or less).",
+            Action::OpenTerminal => "Launch a terminal emulator in the selected directory (a file's parent).",
+            Action::IncreaseDepth => "Walk one level deeper when loading the tree (up to 10).",
+            Action::DecreaseDepth => "Walk one level less deep when loading the tree (down to 1).",
+            Action::CopyChecksum => "Copy the selection's SHA-256 (the focused pin's in the inspector) to the clipboard.",
+            Action::TypeAhead => "Type a name prefix to jump to; every printable key is captured until Enter or Esc.",
+            Action::Quit => "Quit without changing directory.",
+        }
+    }
+
     /// Key used in the config file.
     fn config_key(self) -> &'static str {
         match self {
@@ -106,9 +519,57 @@ impl Action {
             Action::Collapse => "collapse",
             Action::JumpSiblingUp => "jump_sibling_up",
             Action::JumpSiblingDown => "jump_sibling_down",
+            Action::JumpFirstSibling => "jump_first_sibling",
+            Action::JumpLastSibling => "jump_last_sibling",
             Action::CdIntoDir => "enter_dir",
             Action::ToggleHidden => "toggle_hidden",
             Action::OpenSettings => "open_settings",
+            Action::ToggleDebug => "toggle_debug",
+            Action::PinAllImages => "pin_all_images",
+            Action::ToggleHeat => "toggle_heat",
+            Action::SavePinSet => "save_pin_set",
+            Action::LoadPinSet => "load_pin_set",
+            Action::StageDelete => "stage_delete",
+            Action::ShowDeletions => "show_deletions",
+            Action::CommitDeletions => "commit_deletions",
+            Action::RetrySizeScan => "retry_size_scan",
+            Action::ToggleMouse => "toggle_mouse",
+            Action::GrowTreePane => "grow_tree_pane",
+            Action::ShrinkTreePane => "shrink_tree_pane",
+            Action::RescanSelected => "rescan_selected",
+            Action::CycleSort => "cycle_sort",
+            Action::Delete => "delete",
+            Action::Rename => "rename",
+            Action::NewDir => "new_dir",
+            Action::ExpandAll => "expand_all",
+            Action::CollapseAll => "collapse_all",
+            Action::JumpLargestChild => "jump_largest_child",
+            Action::JumpLargestBack => "jump_largest_back",
+            Action::GoToPath => "go_to_path",
+            Action::AddBookmark => "add_bookmark",
+            Action::OpenBookmarks => "open_bookmarks",
+            Action::OpenSystem => "open_system",
+            Action::CopyPath => "copy_path",
+            Action::CopyRelativePath => "copy_relative_path",
+            Action::ShowTopFiles => "show_top_files",
+            Action::Refresh => "refresh",
+            Action::FocusBadge => "focus_badge",
+            Action::ToggleBadge => "toggle_badge",
+            Action::ScanStats => "scan_stats",
+            Action::HistoryBack => "history_back",
+            Action::HistoryForward => "history_forward",
+            Action::HalfPageDown => "half_page_down",
+            Action::HalfPageUp => "half_page_up",
+            Action::ToggleSelect => "toggle_select",
+            Action::ClearSelection => "clear_selection",
+            Action::PinSelected => "pin_selected",
+            Action::OpenInEditor => "open_in_editor",
+            Action::OpenInPager => "open_in_pager",
+            Action::OpenTerminal => "open_terminal",
+            Action::IncreaseDepth => "increase_depth",
+            Action::DecreaseDepth => "decrease_depth",
+            Action::CopyChecksum => "copy_checksum",
+            Action::TypeAhead => "type_ahead",
             Action::Quit => "quit",
         }
     }
@@ -121,9 +582,57 @@ impl Action {
             "collapse" => Some(Action::Collapse),
             "jump_sibling_up" => Some(Action::JumpSiblingUp),
             "jump_sibling_down" => Some(Action::JumpSiblingDown),
+            "jump_first_sibling" => Some(Action::JumpFirstSibling),
+            "jump_last_sibling" => Some(Action::JumpLastSibling),
             "enter_dir" => Some(Action::CdIntoDir),
             "toggle_hidden" => Some(Action::ToggleHidden),
             "open_settings" => Some(Action::OpenSettings),
+            "toggle_debug" => Some(Action::ToggleDebug),
+            "pin_all_images" => Some(Action::PinAllImages),
+            "toggle_heat" => Some(Action::ToggleHeat),
+            "save_pin_set" => Some(Action::SavePinSet),
+            "load_pin_set" => Some(Action::LoadPinSet),
+            "stage_delete" => Some(Action::StageDelete),
+            "show_deletions" => Some(Action::ShowDeletions),
+            "commit_deletions" => Some(Action::CommitDeletions),
+            "retry_size_scan" => Some(Action::RetrySizeScan),
+            "toggle_mouse" => Some(Action::ToggleMouse),
+            "grow_tree_pane" => Some(Action::GrowTreePane),
+            "shrink_tree_pane" => Some(Action::ShrinkTreePane),
+            "rescan_selected" => Some(Action::RescanSelected),
+            "cycle_sort" => Some(Action::CycleSort),
+            "delete" => Some(Action::Delete),
+            "rename" => Some(Action::Rename),
+            "new_dir" => Some(Action::NewDir),
+            "expand_all" => Some(Action::ExpandAll),
+            "collapse_all" => Some(Action::CollapseAll),
+            "jump_largest_child" => Some(Action::JumpLargestChild),
+            "jump_largest_back" => Some(Action::JumpLargestBack),
+            "go_to_path" => Some(Action::GoToPath),
+            "add_bookmark" => Some(Action::AddBookmark),
+            "open_bookmarks" => Some(Action::OpenBookmarks),
+            "open_system" => Some(Action::OpenSystem),
+            "copy_path" => Some(Action::CopyPath),
+            "copy_relative_path" => Some(Action::CopyRelativePath),
+            "show_top_files" => Some(Action::ShowTopFiles),
+            "refresh" => Some(Action::Refresh),
+            "focus_badge" => Some(Action::FocusBadge),
+            "toggle_badge" => Some(Action::ToggleBadge),
+            "scan_stats" => Some(Action::ScanStats),
+            "history_back" => Some(Action::HistoryBack),
+            "history_forward" => Some(Action::HistoryForward),
+            "half_page_down" => Some(Action::HalfPageDown),
+            "half_page_up" => Some(Action::HalfPageUp),
+            "toggle_select" => Some(Action::ToggleSelect),
+            "clear_selection" => Some(Action::ClearSelection),
+            "pin_selected" => Some(Action::PinSelected),
+            "open_in_editor" => Some(Action::OpenInEditor),
+            "open_in_pager" => Some(Action::OpenInPager),
+            "open_terminal" => Some(Action::OpenTerminal),
+            "increase_depth" => Some(Action::IncreaseDepth),
+            "decrease_depth" => Some(Action::DecreaseDepth),
+            "copy_checksum" => Some(Action::CopyChecksum),
+            "type_ahead" => Some(Action::TypeAhead),
             "quit" => Some(Action::Quit),
             _ => None,
         }
@@ -160,8 +669,40 @@ impl KeyBind {
         }
     }
 
-    /// User-friendly display string (e.g. `"Alt+↑"`, `"Ctrl+c"`, `"q"`).
-    pub fn display(&self) -> String {
+    /// Check that a captured binding can actually be matched later.
+    /// Returns a short user-facing reason when it can't.
+    pub fn validate(&self) -> Result<(), String> {
+        let ctrl = self.modifiers.contains(KeyModifiers::CONTROL);
+        match self.code {
+            KeyCode::Null => Err("Key not recognised by the terminal".into()),
+            KeyCode::Modifier(_) => {
+                Err(format!("{}… is modifier-only — press a key with it", self.display_modifiers()))
+            }
+            KeyCode::Media(_)
+            | KeyCode::CapsLock
+            | KeyCode::NumLock
+            | KeyCode::ScrollLock
+            | KeyCode::PrintScreen
+            | KeyCode::Pause
+            | KeyCode::Menu
+            | KeyCode::KeypadBegin => Err(format!("{} is not reported reliably", self.display())),
+            KeyCode::Tab | KeyCode::BackTab => Err("Tab is reserved for switching panes".into()),
+            KeyCode::Esc => Err("Esc is reserved for closing views".into()),
+            KeyCode::Home | KeyCode::End | KeyCode::PageUp | KeyCode::PageDown
+                if self.modifiers.is_empty() =>
+            {
+                Err(format!("{} is reserved for tree navigation", self.display()))
+            }
+            KeyCode::Char('c') if ctrl => Err("Ctrl+c is reserved for quitting".into()),
+            KeyCode::Char('f') if ctrl => Err("Ctrl+f is reserved for search".into()),
+            KeyCode::Char('/') if self.modifiers.is_empty() => {
+                Err("/ is reserved for search".into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn display_modifiers(&self) -> String {
         let mut s = String::new();
         if self.modifiers.contains(KeyModifiers::CONTROL) {
             s.push_str("Ctrl+");
@@ -172,6 +713,12 @@ impl KeyBind {
         if self.modifiers.contains(KeyModifiers::SHIFT) {
             s.push_str("Shift+");
         }
+        s
+    }
+
+    /// User-friendly display string (e.g. `"Alt+↑"`, `"Ctrl+c"`, `"q"`).
+    pub fn display(&self) -> String {
+        let mut s = self.display_modifiers();
         s.push_str(&match self.code {
             KeyCode::Char(' ') => "Space".into(),
             KeyCode::Char(c) => c.to_string(),
@@ -231,10 +778,17 @@ impl KeyBind {
     /// Parse a key string like `"Ctrl+c"`, `"Alt+Up"`, `"q"`, `"Enter"`.
     fn parse(s: &str) -> Option<Self> {
         let mut modifiers = KeyModifiers::NONE;
-        let parts: Vec<&str> = s.split('+').collect();
-        let key_part = parts.last()?;
+        // In `+` and `Shift++` the key itself is the separator.
+        let (prefix, key_part) = match s.strip_suffix('+') {
+            Some("") => ("", "+"),
+            Some(rest) if rest.ends_with('+') => (&rest[..rest.len() - 1], "+"),
+            _ => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+        if key_part.is_empty() {
+            return None;
+        }
 
-        for &part in &parts[..parts.len() - 1] {
+        for part in prefix.split('+').filter(|_| !prefix.is_empty()) {
             match part.to_lowercase().as_str() {
                 "ctrl" => modifiers |= KeyModifiers::CONTROL,
                 "alt" => modifiers |= KeyModifiers::ALT,
@@ -279,14 +833,92 @@ pub struct AppConfig {
     pub dedup_hard_links: bool,
     /// Stay on the same filesystem (don't cross mount points).
     pub one_file_system: bool,
+    /// Show apparent or allocated (on-disk) sizes.
+    pub size_view: SizeView,
     /// Double-click detection window for mouse directory activation.
     pub double_click_ms: u64,
+    /// Capture the mouse at startup (off keeps native terminal selection).
+    pub mouse: bool,
+    /// Print "cd → <path> (<size>)" after exiting into a directory.
+    pub confirm_cd: bool,
+    /// Slow-filesystem mode (sshfs, NFS, ...).
+    pub slow_fs: SlowFsMode,
+    /// Keep per-directory size results on disk between runs.
+    pub size_cache: bool,
+    /// `Delete` removes entries outright instead of moving them to the trash.
+    pub permanent_delete: bool,
+    /// Watch loaded directories and refresh the tree when they change.
+    pub watch_fs: bool,
+    /// Badge entries with their `git status` inside a work tree.
+    pub show_git_status: bool,
+    /// Show a `(N)` entry count after directory rows in the tree.
+    pub show_entry_counts: bool,
+    /// Show the recursive `(N files)` count after directory sizes.
+    pub show_file_counts: bool,
+    /// Syntax-highlight text previews in the inspector.
+    pub syntax_highlight: bool,
+    /// Draw `│` guide lines down the tree's indentation.
+    pub show_indent_guides: bool,
+    /// Shade every other tree row.
+    pub alternate_rows: bool,
+    /// Tree rows scrolled per mouse-wheel notch.
+    pub scroll_lines: usize,
+    /// Pull the selection along when the wheel would scroll it out of the
+    /// tree view; otherwise it stays put off-screen.
+    pub wheel_keeps_selection: bool,
+    /// Experimental screen-reader mode: plain-text rendering, status-bar
+    /// announcements, selected row pinned to the top of the tree.
+    pub a11y: bool,
+    /// Icons drawn before names in the tree.
+    pub icons: IconSet,
+    /// Size share column drawn before tree rows.
+    pub size_share: SizeShare,
     /// Current pane arrangement for tree + inspector.
     pub panel_layout: PanelLayoutMode,
     /// Split between tree and inspector in percent (10..=90).
     pub panel_split_pct: u16,
+    /// Height of the status bar: 0 hides it (messages pop up briefly),
+    /// 2 puts path / totals / scan progress on a line of their own.
+    pub status_lines: u16,
+    /// Tint tree rows by their share of the root's total size.
+    pub heat_mode: bool,
+    /// Endpoints of the heat gradient.
+    pub heat_gradient: HeatGradient,
+    /// Highlight entries modified within `recent_window`.
+    pub highlight_recent: bool,
+    pub recent_window: RecentWindow,
+    /// Maximum number of images pinned by a single "pin all images" action.
+    pub bulk_pin_limit: usize,
+    /// Levels below the selection that "expand all" opens.
+    pub expand_all_max_depth: usize,
+    /// Entries "expand all" visits before it stops.
+    pub expand_all_max_nodes: usize,
     /// Pinned file paths (persisted between sessions).
     pub pinned_paths: Vec<String>,
+    /// Named sets of pinned paths saved by the user.
+    pub pin_sets: BTreeMap<String, Vec<String>>,
+    /// Named directory shortcuts, in the order they were added.
+    pub bookmarks: Vec<Bookmark>,
+    /// Match mode the search tab starts in.
+    pub default_search_mode: SearchMode,
+    /// Search options (`[search]` section), toggled live from the search tab.
+    pub search: SearchOptions,
+    /// Globs (`[exclude]` section) for directories left out of size
+    /// totals, e.g. `target`, `.git`, `node_modules`.
+    pub exclude_patterns: Vec<String>,
+    /// Order of entries within each directory in the tree.
+    pub sort_mode: SortMode,
+    /// What files are bucketed by when grouped in the tree.
+    pub group_by: GroupBy,
+    /// Files per bucket before they are grouped; `0` disables grouping.
+    pub grouping_min_size: usize,
+    /// External previewers: `(pattern, command template)` in config order.
+    pub previewers: Vec<(String, String)>,
+    /// Kill an external previewer after this long.
+    pub previewer_timeout_ms: u64,
+    /// Terminal launched by "Open Terminal Here", tried before
+    /// `$TERMINAL` and the built-in list.
+    pub terminal_emulator: Option<String>,
 }
 
 impl AppConfig {
@@ -304,9 +936,56 @@ impl AppConfig {
         m.insert(Collapse, vec![KeyBind::new(Left, n), KeyBind::new(Char('h'), n)]);
         m.insert(JumpSiblingUp, vec![KeyBind::new(Up, alt)]);
         m.insert(JumpSiblingDown, vec![KeyBind::new(Down, alt)]);
+        m.insert(JumpFirstSibling, vec![KeyBind::new(Up, KeyModifiers::CONTROL)]);
+        m.insert(JumpLastSibling, vec![KeyBind::new(Down, KeyModifiers::CONTROL)]);
         m.insert(CdIntoDir, vec![KeyBind::new(Enter, n)]);
         m.insert(ToggleHidden, vec![KeyBind::new(Char('.'), n)]);
         m.insert(OpenSettings, vec![KeyBind::new(Char('?'), n)]);
+        m.insert(ToggleDebug, vec![KeyBind::new(F(12), n)]);
+        m.insert(PinAllImages, vec![KeyBind::new(Char('I'), KeyModifiers::SHIFT)]);
+        m.insert(ToggleHeat, vec![KeyBind::new(Char('H'), KeyModifiers::SHIFT)]);
+        m.insert(SavePinSet, vec![KeyBind::new(Char('S'), KeyModifiers::SHIFT)]);
+        m.insert(LoadPinSet, vec![KeyBind::new(Char('L'), KeyModifiers::SHIFT)]);
+        m.insert(StageDelete, vec![KeyBind::new(KeyCode::Delete, n)]);
+        m.insert(ShowDeletions, vec![KeyBind::new(Char('D'), KeyModifiers::SHIFT)]);
+        m.insert(CommitDeletions, vec![KeyBind::new(Char('X'), KeyModifiers::SHIFT)]);
+        m.insert(RetrySizeScan, vec![KeyBind::new(Char('r'), KeyModifiers::CONTROL)]);
+        m.insert(ToggleMouse, vec![KeyBind::new(Char('M'), KeyModifiers::SHIFT)]);
+        m.insert(GrowTreePane, vec![KeyBind::new(Right, alt | KeyModifiers::SHIFT)]);
+        m.insert(ShrinkTreePane, vec![KeyBind::new(Left, alt | KeyModifiers::SHIFT)]);
+        m.insert(RescanSelected, vec![KeyBind::new(Char('R'), KeyModifiers::SHIFT)]);
+        m.insert(CycleSort, vec![KeyBind::new(Char('s'), n)]);
+        m.insert(Action::Delete, vec![KeyBind::new(KeyCode::Delete, KeyModifiers::SHIFT)]);
+        m.insert(Rename, vec![KeyBind::new(F(2), n)]);
+        m.insert(NewDir, vec![KeyBind::new(F(7), n)]);
+        m.insert(ExpandAll, vec![KeyBind::new(Char('E'), KeyModifiers::SHIFT)]);
+        m.insert(CollapseAll, vec![KeyBind::new(Char('C'), KeyModifiers::SHIFT)]);
+        m.insert(JumpLargestChild, vec![KeyBind::new(Char('>'), n), KeyBind::new(Char('>'), KeyModifiers::SHIFT)]);
+        m.insert(JumpLargestBack, vec![KeyBind::new(Char('<'), n), KeyBind::new(Char('<'), KeyModifiers::SHIFT)]);
+        m.insert(GoToPath, vec![KeyBind::new(Char('g'), KeyModifiers::CONTROL)]);
+        m.insert(AddBookmark, vec![KeyBind::new(Char('b'), n)]);
+        m.insert(OpenBookmarks, vec![KeyBind::new(Char('B'), KeyModifiers::SHIFT)]);
+        m.insert(OpenSystem, vec![KeyBind::new(Char('o'), n)]);
+        m.insert(CopyPath, vec![KeyBind::new(Char('y'), n)]);
+        m.insert(CopyRelativePath, vec![KeyBind::new(Char('Y'), KeyModifiers::SHIFT)]);
+        m.insert(ShowTopFiles, vec![KeyBind::new(Char('t'), KeyModifiers::CONTROL)]);
+        m.insert(Refresh, vec![KeyBind::new(F(5), n), KeyBind::new(Char('r'), n)]);
+        m.insert(FocusBadge, vec![KeyBind::new(Char('f'), n)]);
+        m.insert(ToggleBadge, vec![KeyBind::new(Char('F'), KeyModifiers::SHIFT)]);
+        m.insert(ScanStats, vec![KeyBind::new(Char('s'), KeyModifiers::CONTROL)]);
+        m.insert(HistoryBack, vec![KeyBind::new(Left, alt)]);
+        m.insert(HistoryForward, vec![KeyBind::new(Right, alt)]);
+        m.insert(HalfPageDown, vec![KeyBind::new(Char('d'), KeyModifiers::CONTROL)]);
+        m.insert(HalfPageUp, vec![KeyBind::new(Char('u'), KeyModifiers::CONTROL)]);
+        m.insert(ToggleSelect, vec![KeyBind::new(Char(' '), n)]);
+        m.insert(PinSelected, vec![KeyBind::new(Char('P'), KeyModifiers::SHIFT)]);
+        m.insert(OpenInEditor, vec![KeyBind::new(Char('e'), n)]);
+        m.insert(OpenInPager, vec![KeyBind::new(Char('v'), n)]);
+        m.insert(OpenTerminal, vec![KeyBind::new(Char('T'), KeyModifiers::SHIFT)]);
+        m.insert(IncreaseDepth, vec![KeyBind::new(Char('='), n), KeyBind::new(Char('+'), n), KeyBind::new(Char('+'), KeyModifiers::SHIFT)]);
+        m.insert(DecreaseDepth, vec![KeyBind::new(Char('-'), n)]);
+        m.insert(CopyChecksum, vec![KeyBind::new(Char('y'), KeyModifiers::CONTROL)]);
+        m.insert(TypeAhead, vec![KeyBind::new(Char('\''), n)]);
         m.insert(Quit, vec![KeyBind::new(Char('q'), n)]);
 
         m
@@ -333,6 +1012,15 @@ impl AppConfig {
         best
     }
 
+    /// Action (other than `except`) that already uses `bind`, if any.
+    pub fn binding_owner(&self, bind: &KeyBind, except: Action) -> Option<Action> {
+        Action::ALL
+            .iter()
+            .copied()
+            .filter(|&a| a != except)
+            .find(|a| self.bindings.get(a).is_some_and(|b| b.contains(bind)))
+    }
+
     /// Add a binding for `action`.  Removes this key from any other action
     /// to prevent conflicts, then appends it to `action`'s bindings.
     pub fn add_binding(&mut self, action: Action, bind: KeyBind) {
@@ -380,31 +1068,19 @@ impl AppConfig {
 
     /// Load config from disk, falling back to defaults.
     pub fn load() -> Self {
+        Self::load_checked().0
+    }
+
+    /// Like [`Self::load`], also returning the lines that could not be
+    /// applied.
+    pub fn load_checked() -> (Self, Vec<ConfigWarning>) {
         let path = config_path();
         if path.exists() {
             if let Ok(contents) = std::fs::read_to_string(&path) {
-                let (bindings, dedup, ofs, dclick_ms, panel_layout, panel_split_pct, pinned) =
-                    Self::parse_config(&contents);
-                return Self {
-                    bindings,
-                    dedup_hard_links: dedup,
-                    one_file_system: ofs,
-                    double_click_ms: dclick_ms,
-                    panel_layout,
-                    panel_split_pct,
-                    pinned_paths: pinned,
-                };
+                return Self::parse_config(&contents);
             }
         }
-        Self {
-            bindings: Self::default_bindings(),
-            dedup_hard_links: true,
-            one_file_system: false,
-            double_click_ms: 250,
-            panel_layout: PanelLayoutMode::TreeLeft,
-            panel_split_pct: 60,
-            pinned_paths: Vec::new(),
-        }
+        (Self::default(), Vec::new())
     }
 
     /// Persist current config to disk.
@@ -417,52 +1093,140 @@ impl AppConfig {
         Ok(())
     }
 
-    fn parse_config(
-        s: &str,
-    ) -> (
-        HashMap<Action, Vec<KeyBind>>,
-        bool,
-        bool,
-        u64,
-        PanelLayoutMode,
-        u16,
-        Vec<String>,
-    ) {
-        let mut bindings = Self::default_bindings();
-        let mut dedup_hard_links = true;
-        let mut one_file_system = false;
-        let mut double_click_ms = 250;
-        let mut panel_layout = PanelLayoutMode::TreeLeft;
-        let mut panel_split_pct: u16 = 60;
-        let mut pinned_paths: Vec<String> = Vec::new();
-        let mut in_pinned_section = false;
-
-        for line in s.lines() {
+    /// Parse the config file format; unknown or malformed lines keep their
+    /// defaults and are reported as warnings.
+    fn parse_config(s: &str) -> (Self, Vec<ConfigWarning>) {
+        let mut config = Self::default();
+        let mut warnings = Vec::new();
+        let mut section = Section::Main;
+        // Key → (action, line) for binds set by this file, so a key given
+        // to two actions can be reported and resolved (later line wins).
+        let mut file_binds: HashMap<KeyBind, (Action, usize)> = HashMap::new();
+
+        for (idx, line) in s.lines().enumerate() {
             let line = line.trim();
+            let mut warn = |message: String| {
+                warnings.push(ConfigWarning {
+                    line: idx + 1,
+                    message,
+                })
+            };
 
-            // Detect [pinned] section header.
-            if line == "[pinned]" {
-                in_pinned_section = true;
-                continue;
-            }
-            // Any other section header ends the pinned section.
+            // Section headers.
             if line.starts_with('[') {
-                in_pinned_section = false;
+                section = match line {
+                    "[pinned]" => Section::Pinned,
+                    "[pin_sets]" => Section::PinSets,
+                    "[bookmarks]" => Section::Bookmarks,
+                    "[previewers]" => Section::Previewers,
+                    "[search]" => Section::Search,
+                    "[exclude]" => Section::Exclude,
+                    _ => {
+                        warn(format!("unknown section {line}"));
+                        Section::Unknown
+                    }
+                };
                 continue;
             }
 
-            if in_pinned_section {
-                // Each line in [pinned] is a file path.
-                if !line.is_empty() && !line.starts_with('#') {
-                    pinned_paths.push(line.to_string());
+            match section {
+                Section::Main => {}
+                Section::Pinned => {
+                    // Each line in [pinned] is a file path.
+                    if !line.is_empty() && !line.starts_with('#') {
+                        config.pinned_paths.push(line.to_string());
+                    }
+                    continue;
                 }
-                continue;
+                Section::Bookmarks => {
+                    // Each line is `name = path`.
+                    if line.is_empty() || line.starts_with('#') {
+                        continue;
+                    }
+                    match line.split_once('=') {
+                        Some((name, path)) if !name.trim().is_empty() && !path.trim().is_empty() => {
+                            config.bookmarks.push(Bookmark {
+                                name: name.trim().to_string(),
+                                path: PathBuf::from(path.trim()),
+                            });
+                        }
+                        _ => warn(format!("expected `name = path`, found `{line}`")),
+                    }
+                    continue;
+                }
+                Section::PinSets => {
+                    // Each line is `set name = path`; repeated names accumulate.
+                    if line.starts_with('#') {
+                        continue;
+                    }
+                    if let Some((name, path)) = line.split_once('=') {
+                        let (name, path) = (name.trim(), path.trim());
+                        if !name.is_empty() && !path.is_empty() {
+                            config
+                                .pin_sets
+                                .entry(name.to_string())
+                                .or_default()
+                                .push(path.to_string());
+                        }
+                    }
+                    continue;
+                }
+                Section::Previewers => {
+                    // `pattern = command template`, first match wins.
+                    if line.starts_with('#') {
+                        continue;
+                    }
+                    if let Some((pattern, command)) = line.split_once('=') {
+                        let pattern = pattern.trim().trim_matches('"');
+                        let command = command.trim();
+                        let command = command
+                            .strip_prefix('"')
+                            .and_then(|c| c.strip_suffix('"'))
+                            .unwrap_or(command);
+                        if !pattern.is_empty() && !command.is_empty() {
+                            config
+                                .previewers
+                                .push((pattern.to_string(), command.to_string()));
+                        }
+                    }
+                    continue;
+                }
+                Section::Search => {
+                    if line.is_empty() || line.starts_with('#') {
+                        continue;
+                    }
+                    let Some((key, value)) = line.split_once('=') else {
+                        warn(format!("expected `key = value`, found `{line}`"));
+                        continue;
+                    };
+                    let value = value.trim() == "true";
+                    match key.trim() {
+                        "case_sensitive" => config.search.case_sensitive = value,
+                        "include_hidden" => config.search.include_hidden = value,
+                        "respect_gitignore" => config.search.respect_gitignore = value,
+                        key => warn(format!("unknown search key `{key}`")),
+                    }
+                    continue;
+                }
+                Section::Exclude => {
+                    // Each line is a glob naming directories to leave out.
+                    if line.is_empty() || line.starts_with('#') {
+                        continue;
+                    }
+                    match crate::core::size::exclude_pattern_error(line) {
+                        None => config.exclude_patterns.push(line.to_string()),
+                        Some(e) => warn(format!("invalid exclude pattern `{line}`: {e}")),
+                    }
+                    continue;
+                }
+                Section::Unknown => continue,
             }
 
             if line.is_empty() || line.starts_with('#') {
                 continue;
             }
             let Some((key, value)) = line.split_once('=') else {
+                warn(format!("expected `key = value`, found `{line}`"));
                 continue;
             };
             let key = key.trim();
@@ -471,28 +1235,204 @@ impl AppConfig {
             // Walk settings.
             match key {
                 "dedup_hard_links" => {
-                    dedup_hard_links = value == "true";
+                    config.dedup_hard_links = value == "true";
                     continue;
                 }
                 "one_file_system" => {
-                    one_file_system = value == "true";
+                    config.one_file_system = value == "true";
                     continue;
                 }
                 "double_click_ms" => {
                     if let Ok(v) = value.parse::<u64>() {
-                        double_click_ms = v.clamp(100, 2000);
+                        config.double_click_ms = v.clamp(100, 2000);
                     }
                     continue;
                 }
+                "confirm_cd" => {
+                    config.confirm_cd = value == "true";
+                    continue;
+                }
+                "size_view" => {
+                    if let Some(view) = SizeView::from_config_value(value.trim_matches('"')) {
+                        config.size_view = view;
+                    }
+                    continue;
+                }
+                // Also accepted: `size_mode = apparent|blocks` (as in `du`).
+                "size_mode" => {
+                    match value.trim_matches('"') {
+                        "blocks" => config.size_view = SizeView::Allocated,
+                        "apparent" => config.size_view = SizeView::Apparent,
+                        other => warn(format!("unknown size_mode `{other}` (expected apparent or blocks)")),
+                    }
+                    continue;
+                }
+                "size_cache" => {
+                    config.size_cache = value == "true";
+                    continue;
+                }
+                "slow_fs" => {
+                    if let Some(mode) = SlowFsMode::from_config_value(value.trim_matches('"')) {
+                        config.slow_fs = mode;
+                    }
+                    continue;
+                }
+                "permanent_delete" => {
+                    config.permanent_delete = value == "true";
+                    continue;
+                }
+                "watch_fs" => {
+                    config.watch_fs = value == "true";
+                    continue;
+                }
+                "show_git_status" => {
+                    config.show_git_status = value == "true";
+                    continue;
+                }
+                "show_entry_counts" => {
+                    config.show_entry_counts = value == "true";
+                    continue;
+                }
+                "show_file_counts" => {
+                    config.show_file_counts = value == "true";
+                    continue;
+                }
+                "syntax_highlight" => {
+                    config.syntax_highlight = value == "true";
+                    continue;
+                }
+                "show_indent_guides" => {
+                    config.show_indent_guides = value == "true";
+                    continue;
+                }
+                "alternate_rows" => {
+                    config.alternate_rows = value == "true";
+                    continue;
+                }
+                "scroll_lines" => {
+                    if let Ok(v) = value.parse::<usize>() {
+                        config.scroll_lines = v.clamp(1, 50);
+                    }
+                    continue;
+                }
+                "wheel_keeps_selection" => {
+                    config.wheel_keeps_selection = value == "true";
+                    continue;
+                }
+                "a11y" => {
+                    config.a11y = value == "true";
+                    continue;
+                }
+                "icons" => {
+                    if let Some(set) = IconSet::from_config_value(value.trim_matches('"')) {
+                        config.icons = set;
+                    }
+                    continue;
+                }
+                "size_share" => {
+                    if let Some(mode) = SizeShare::from_config_value(value.trim_matches('"')) {
+                        config.size_share = mode;
+                    }
+                    continue;
+                }
+                "mouse" => {
+                    config.mouse = value == "true";
+                    continue;
+                }
                 "panel_layout" => {
                     if let Some(mode) = PanelLayoutMode::from_config_value(value) {
-                        panel_layout = mode;
+                        config.panel_layout = mode;
                     }
                     continue;
                 }
                 "panel_split_pct" => {
                     if let Ok(v) = value.parse::<u16>() {
-                        panel_split_pct = v.clamp(10, 90);
+                        config.panel_split_pct = v.clamp(10, 90);
+                    }
+                    continue;
+                }
+                "status_lines" => {
+                    if let Ok(v) = value.parse::<u16>() {
+                        config.status_lines = v.min(2);
+                    }
+                    continue;
+                }
+                "heat_mode" => {
+                    config.heat_mode = value == "true";
+                    continue;
+                }
+                "highlight_recent" => {
+                    config.highlight_recent = value == "true";
+                    continue;
+                }
+                "recent_window" => {
+                    if let Some(window) = RecentWindow::from_config_value(value.trim_matches('"')) {
+                        config.recent_window = window;
+                    }
+                    continue;
+                }
+                "heat_color_cold" => {
+                    if let Some(rgb) = parse_hex_rgb(value.trim_matches('"')) {
+                        config.heat_gradient.cold = rgb;
+                    }
+                    continue;
+                }
+                "heat_color_hot" => {
+                    if let Some(rgb) = parse_hex_rgb(value.trim_matches('"')) {
+                        config.heat_gradient.hot = rgb;
+                    }
+                    continue;
+                }
+                "default_search_mode" => {
+                    if let Some(mode) = SearchMode::from_config_value(value.trim_matches('"')) {
+                        config.default_search_mode = mode;
+                    }
+                    continue;
+                }
+                "group_by" => {
+                    if let Some(mode) = GroupBy::from_config_value(value.trim_matches('"')) {
+                        config.group_by = mode;
+                    }
+                    continue;
+                }
+                "grouping_min_size" => {
+                    if let Ok(v) = value.parse::<usize>() {
+                        config.grouping_min_size = v.min(1000);
+                    }
+                    continue;
+                }
+                "sort_mode" => {
+                    if let Some(mode) = SortMode::from_config_value(value.trim_matches('"')) {
+                        config.sort_mode = mode;
+                    }
+                    continue;
+                }
+                "previewer_timeout_ms" => {
+                    if let Ok(v) = value.parse::<u64>() {
+                        config.previewer_timeout_ms = v.clamp(200, 30_000);
+                    }
+                    continue;
+                }
+                "terminal_emulator" => {
+                    let cmd = value.trim_matches('"').trim();
+                    config.terminal_emulator = (!cmd.is_empty()).then(|| cmd.to_string());
+                    continue;
+                }
+                "bulk_pin_limit" => {
+                    if let Ok(v) = value.parse::<usize>() {
+                        config.bulk_pin_limit = v.clamp(1, 500);
+                    }
+                    continue;
+                }
+                "expand_all_max_depth" => {
+                    if let Ok(v) = value.parse::<usize>() {
+                        config.expand_all_max_depth = v.clamp(1, 32);
+                    }
+                    continue;
+                }
+                "expand_all_max_nodes" => {
+                    if let Ok(v) = value.parse::<usize>() {
+                        config.expand_all_max_nodes = v.clamp(100, 100_000);
                     }
                     continue;
                 }
@@ -500,30 +1440,48 @@ impl AppConfig {
             }
 
             let Some(action) = Action::from_config_key(key) else {
+                warn(format!("unknown key `{key}`"));
                 continue;
             };
 
             let mut parsed = Vec::new();
             for part in value.split(',') {
                 let part = part.trim().trim_matches('"');
-                if let Some(bind) = KeyBind::parse(part) {
-                    parsed.push(bind);
+                match KeyBind::parse(part) {
+                    Some(bind) if !parsed.contains(&bind) => parsed.push(bind),
+                    Some(_) => {}
+                    None if part.is_empty() => {}
+                    None => warn(format!("can't parse key `{part}` for {key}; ignored")),
                 }
             }
-            if !parsed.is_empty() {
-                bindings.insert(action, parsed);
+            if parsed.is_empty() {
+                warn(format!("no valid key in `{value}` for {key}"));
+                continue;
             }
+            // A repeated action line replaces its earlier keys.
+            file_binds.retain(|_, (owner, _)| *owner != action);
+            for bind in &parsed {
+                if let Some(&(owner, owner_line)) = file_binds.get(bind) {
+                    if owner != action {
+                        warn(format!(
+                            "{} is also bound to {} (line {owner_line}); kept for {key} only",
+                            bind.display(),
+                            owner.config_key(),
+                        ));
+                    }
+                }
+                file_binds.insert(bind.clone(), (action, idx + 1));
+                // Also drops it from any default binding of another action.
+                for (&other, binds) in config.bindings.iter_mut() {
+                    if other != action {
+                        binds.retain(|b| b != bind);
+                    }
+                }
+            }
+            config.bindings.insert(action, parsed);
         }
 
-        (
-            bindings,
-            dedup_hard_links,
-            one_file_system,
-            double_click_ms,
-            panel_layout,
-            panel_split_pct,
-            pinned_paths,
-        )
+        (config, warnings)
     }
 
     fn serialise(&self) -> String {
@@ -533,9 +1491,47 @@ impl AppConfig {
             "# Walk settings".to_string(),
             format!("dedup_hard_links = {}", self.dedup_hard_links),
             format!("one_file_system = {}", self.one_file_system),
+            format!("size_view = {}", self.size_view.config_value()),
             format!("double_click_ms = {}", self.double_click_ms),
+            format!("mouse = {}", self.mouse),
+            format!("confirm_cd = {}", self.confirm_cd),
+            format!("permanent_delete = {}", self.permanent_delete),
+            format!("slow_fs = {}", self.slow_fs.config_value()),
+            format!("size_cache = {}", self.size_cache),
+            format!("watch_fs = {}", self.watch_fs),
+            format!("show_git_status = {}", self.show_git_status),
+            format!("show_entry_counts = {}", self.show_entry_counts),
+            format!("show_file_counts = {}", self.show_file_counts),
+            format!("syntax_highlight = {}", self.syntax_highlight),
+            format!("show_indent_guides = {}", self.show_indent_guides),
+            format!("alternate_rows = {}", self.alternate_rows),
+            format!("scroll_lines = {}", self.scroll_lines),
+            format!("wheel_keeps_selection = {}", self.wheel_keeps_selection),
+            format!("a11y = {}", self.a11y),
+            format!("icons = {}", self.icons.config_value()),
+            format!("size_share = {}", self.size_share.config_value()),
             format!("panel_layout = {}", self.panel_layout.config_value()),
             format!("panel_split_pct = {}", self.panel_split_pct),
+            format!("status_lines = {}", self.status_lines),
+            format!("bulk_pin_limit = {}", self.bulk_pin_limit),
+            format!("expand_all_max_depth = {}", self.expand_all_max_depth),
+            format!("expand_all_max_nodes = {}", self.expand_all_max_nodes),
+            format!("previewer_timeout_ms = {}", self.previewer_timeout_ms),
+            match &self.terminal_emulator {
+                Some(cmd) => format!("terminal_emulator = \"{cmd}\""),
+                None => "# terminal_emulator = \"alacritty\"".to_string(),
+            },
+            format!("default_search_mode = {}", self.default_search_mode.config_value()),
+            format!("sort_mode = {}", self.sort_mode.config_value()),
+            format!("group_by = {}", self.group_by.config_value()),
+            format!("grouping_min_size = {}", self.grouping_min_size),
+            String::new(),
+            "# Theme".to_string(),
+            format!("heat_mode = {}", self.heat_mode),
+            format!("heat_color_cold = \"{}\"", format_hex_rgb(self.heat_gradient.cold)),
+            format!("heat_color_hot = \"{}\"", format_hex_rgb(self.heat_gradient.hot)),
+            format!("highlight_recent = {}", self.highlight_recent),
+            format!("recent_window = {}", self.recent_window.config_value()),
             String::new(),
             "# Key bindings".to_string(),
             "# Format: action = Key1, Key2, ...".to_string(),
@@ -553,6 +1549,21 @@ impl AppConfig {
         }
         lines.push(String::new());
 
+        // Search defaults.
+        lines.push("[search]".to_string());
+        lines.push(format!("case_sensitive = {}", self.search.case_sensitive));
+        lines.push(format!("include_hidden = {}", self.search.include_hidden));
+        lines.push(format!("respect_gitignore = {}", self.search.respect_gitignore));
+        lines.push(String::new());
+
+        // Size-walk exclusions.
+        if !self.exclude_patterns.is_empty() {
+            lines.push("[exclude]".to_string());
+            lines.push("# Directories left out of size totals (globs on the name or full path)".to_string());
+            lines.extend(self.exclude_patterns.iter().cloned());
+            lines.push(String::new());
+        }
+
         // Pinned files section.
         if !self.pinned_paths.is_empty() {
             lines.push("[pinned]".to_string());
@@ -563,12 +1574,124 @@ impl AppConfig {
             lines.push(String::new());
         }
 
+        // Named pin sets.
+        if !self.pin_sets.is_empty() {
+            lines.push("[pin_sets]".to_string());
+            lines.push("# Saved pin sets: `name = path`, one line per pinned path".to_string());
+            for (name, paths) in &self.pin_sets {
+                for p in paths {
+                    lines.push(format!("{name} = {p}"));
+                }
+            }
+            lines.push(String::new());
+        }
+
+        // Bookmarks.
+        if !self.bookmarks.is_empty() {
+            lines.push("[bookmarks]".to_string());
+            lines.push("# Directory bookmarks: `name = path`".to_string());
+            for bookmark in &self.bookmarks {
+                lines.push(format!("{} = {}", bookmark.name, bookmark.path.display()));
+            }
+            lines.push(String::new());
+        }
+
+        // External previewers.
+        if !self.previewers.is_empty() {
+            lines.push("[previewers]".to_string());
+            lines.push("# `pattern = command`; MIME globs (text/*) or name globs (*.md),".to_string());
+            lines.push("# {path} is replaced with the quoted file path".to_string());
+            for (pattern, command) in &self.previewers {
+                lines.push(format!("{pattern} = {command}"));
+            }
+            lines.push(String::new());
+        }
+
         lines.join("\n")
     }
 }
 
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            bindings: Self::default_bindings(),
+            dedup_hard_links: true,
+            one_file_system: false,
+            size_view: SizeView::Apparent,
+            double_click_ms: 250,
+            mouse: true,
+            confirm_cd: false,
+            permanent_delete: false,
+            watch_fs: false,
+            show_git_status: true,
+            show_entry_counts: true,
+            show_file_counts: false,
+            syntax_highlight: true,
+            show_indent_guides: true,
+            alternate_rows: false,
+            scroll_lines: 3,
+            wheel_keeps_selection: true,
+            a11y: false,
+            icons: IconSet::Ascii,
+            size_share: SizeShare::Off,
+            slow_fs: SlowFsMode::Auto,
+            size_cache: true,
+            panel_layout: PanelLayoutMode::TreeLeft,
+            panel_split_pct: 60,
+            status_lines: 1,
+            heat_mode: false,
+            heat_gradient: HeatGradient::default(),
+            highlight_recent: false,
+            recent_window: RecentWindow::Hour,
+            bulk_pin_limit: 50,
+            expand_all_max_depth: 6,
+            expand_all_max_nodes: 5000,
+            pinned_paths: Vec::new(),
+            pin_sets: BTreeMap::new(),
+            bookmarks: Vec::new(),
+            default_search_mode: SearchMode::Substring,
+            search: SearchOptions::default(),
+            exclude_patterns: Vec::new(),
+            sort_mode: SortMode::Name,
+            group_by: GroupBy::Extension,
+            grouping_min_size: 5,
+            previewers: Vec::new(),
+            previewer_timeout_ms: 3000,
+            terminal_emulator: None,
+        }
+    }
+}
+
+/// A named directory shortcut (`[bookmarks]` section).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// A config file line that could not be applied.
+#[derive(Debug, Clone)]
+pub struct ConfigWarning {
+    /// 1-based line number.
+    pub line: usize,
+    pub message: String,
+}
+
+/// Config file sections.
+#[derive(Clone, Copy)]
+enum Section {
+    Main,
+    Pinned,
+    PinSets,
+    Bookmarks,
+    Previewers,
+    Search,
+    Exclude,
+    Unknown,
+}
+
 /// Return the config file path (`$XDG_CONFIG_HOME/dir-tree/config.toml`).
-fn config_path() -> PathBuf {
+pub fn config_path() -> PathBuf {
     let config_dir = std::env::var("XDG_CONFIG_HOME")
         .map(PathBuf::from)
         .unwrap_or_else(|_| {
@@ -578,3 +1701,115 @@ fn config_path() -> PathBuf {
     config_dir.join("dir-tree").join("config.toml")
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn f(n: u8) -> KeyBind {
+        KeyBind::new(KeyCode::F(n), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn unknown_action_is_reported_and_ignored() {
+        let (config, warnings) = AppConfig::parse_config("frobnicate = F9\n");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, 1);
+        assert!(warnings[0].message.contains("unknown key `frobnicate`"));
+        assert!(config.bindings.values().all(|binds| !binds.contains(&f(9))));
+    }
+
+    #[test]
+    fn key_given_to_two_actions_goes_to_the_later_line() {
+        let (config, warnings) = AppConfig::parse_config("quit = F9\nrefresh = F9\n");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, 2);
+        assert!(warnings[0].message.contains("also bound to quit (line 1)"));
+        assert_eq!(config.bindings[&Action::Refresh], vec![f(9)]);
+        assert!(config.bindings[&Action::Quit].is_empty());
+    }
+
+    #[test]
+    fn repeated_action_line_replaces_its_keys() {
+        let (config, warnings) = AppConfig::parse_config("quit = F9\nquit = F10\n");
+        assert!(warnings.is_empty());
+        assert_eq!(config.bindings[&Action::Quit], vec![f(10)]);
+    }
+
+    #[test]
+    fn user_key_moves_off_a_default_binding() {
+        // `r` is a default Refresh key.
+        let (config, _) = AppConfig::parse_config("quit = r\n");
+        let r = KeyBind::new(KeyCode::Char('r'), KeyModifiers::NONE);
+        assert_eq!(config.bindings[&Action::Quit], vec![r]);
+        assert_eq!(config.bindings[&Action::Refresh], vec![f(5)]);
+    }
+
+    #[test]
+    fn malformed_binding_is_skipped() {
+        let (config, warnings) = AppConfig::parse_config("quit = Hyper+q, F9\n");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("can't parse key `Hyper+q`"));
+        assert_eq!(config.bindings[&Action::Quit], vec![f(9)]);
+    }
+
+    #[test]
+    fn line_without_a_valid_key_keeps_the_default() {
+        let (config, warnings) = AppConfig::parse_config("quit = Hyper+q\n");
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[1].message.contains("no valid key"));
+        assert_eq!(config.bindings[&Action::Quit], AppConfig::default().bindings[&Action::Quit]);
+    }
+
+    #[test]
+    fn size_mode_is_read_as_size_view() {
+        let (config, warnings) = AppConfig::parse_config("size_mode = blocks\n");
+        assert!(warnings.is_empty());
+        assert_eq!(config.size_view, SizeView::Allocated);
+        let (config, _) = AppConfig::parse_config("size_view = allocated\nsize_mode = \"apparent\"\n");
+        assert_eq!(config.size_view, SizeView::Apparent);
+        let (config, warnings) = AppConfig::parse_config("size_mode = sectors\n");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(config.size_view, SizeView::Apparent);
+    }
+
+    #[test]
+    fn pin_sets_round_trip_through_the_config_file() {
+        let mut config = AppConfig::default();
+        config.pin_sets.insert(
+            "disk hogs".to_string(),
+            vec!["/var/log/big.log".to_string(), "/home/me/a=b c.iso".to_string()],
+        );
+        // Paths are stored as given; a set may name files deleted since.
+        config.pin_sets.insert(
+            "stale".to_string(),
+            vec!["/no/such/dir-tree-test-file".to_string()],
+        );
+        config.pinned_paths = vec!["/var/log/big.log".to_string()];
+
+        let (loaded, warnings) = AppConfig::parse_config(&config.serialise());
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(loaded.pin_sets, config.pin_sets);
+        assert_eq!(loaded.pinned_paths, config.pinned_paths);
+    }
+
+    #[test]
+    fn plus_key_round_trips() {
+        for bind in [
+            KeyBind::new(KeyCode::Char('+'), KeyModifiers::NONE),
+            KeyBind::new(KeyCode::Char('+'), KeyModifiers::SHIFT),
+            KeyBind::new(KeyCode::Char('+'), KeyModifiers::CONTROL | KeyModifiers::ALT),
+        ] {
+            assert_eq!(KeyBind::parse(&bind.to_config_string()), Some(bind));
+        }
+        assert_eq!(KeyBind::parse("Ctrl+"), None);
+        assert_eq!(KeyBind::parse("Ctrl++x"), None);
+    }
+
+    #[test]
+    fn unknown_modifier_or_key_name_fails_to_parse() {
+        let ctrl_alt_up = KeyBind::new(KeyCode::Up, KeyModifiers::CONTROL | KeyModifiers::ALT);
+        assert_eq!(KeyBind::parse("Ctrl+Alt+Up"), Some(ctrl_alt_up));
+        assert_eq!(KeyBind::parse("Meta+x"), None);
+        assert_eq!(KeyBind::parse("Fx"), None);
+    }
+}
@@ -3,11 +3,13 @@
 //! The walker respects `.gitignore` rules via the [`ignore`] crate and caps
 //! the depth to keep things snappy.
 
-use std::collections::{HashMap, VecDeque};
-use std::path::{Path, PathBuf};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use ignore::WalkBuilder;
 
+use super::grouping::SortMode;
 use super::tree::{DirTree, EntryMeta, NodeId};
 
 /// Configuration knobs for the traversal.
@@ -19,6 +21,12 @@ pub struct WalkConfig {
     pub respect_gitignore: bool,
     /// Show hidden (dot-prefixed) entries.
     pub show_hidden: bool,
+    /// `stat` each entry for its mtime.  Off on slow filesystems.
+    pub read_mtimes: bool,
+    /// Order of entries within each directory.
+    pub sort_mode: SortMode,
+    /// Run `git status` and badge changed entries.
+    pub show_git_status: bool,
 }
 
 impl Default for WalkConfig {
@@ -27,13 +35,17 @@ impl Default for WalkConfig {
             max_depth: 3,
             respect_gitignore: true,
             show_hidden: false,
+            read_mtimes: true,
+            sort_mode: SortMode::Name,
+            show_git_status: true,
         }
     }
 }
 
-/// Build an [`EntryMeta`] from an [`ignore::DirEntry`] without an extra `stat`
-/// call.  File type comes from `readdir` for free on Unix.
-fn meta_from_dir_entry(entry: &ignore::DirEntry) -> EntryMeta {
+/// Build an [`EntryMeta`] from an [`ignore::DirEntry`].  File type comes
+/// from `readdir` for free on Unix; the mtime costs one `lstat` and is only
+/// read when `read_mtimes` is set.
+fn meta_from_dir_entry(entry: &ignore::DirEntry, read_mtimes: bool) -> EntryMeta {
     let path = entry.path().to_path_buf();
     let ft = entry.file_type();
     let is_dir = ft.as_ref().map_or(false, |ft| ft.is_dir());
@@ -63,21 +75,50 @@ fn meta_from_dir_entry(entry: &ignore::DirEntry) -> EntryMeta {
         is_dir,
         is_symlink,
         size,
-        modified: None,
+        modified: if read_mtimes {
+            entry.metadata().ok().and_then(|m| m.modified().ok())
+        } else {
+            None
+        },
         path,
         symlink_target,
+        missing: false,
     }
 }
 
-/// Sort helper — case-insensitive by name.
-fn sort_by_name(entries: &mut [EntryMeta]) {
-    entries.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+/// Sort helper — case-insensitive by name, then by mtime for the modified
+/// orders.  Sizes aren't known at walk time; the tree widget applies the
+/// size orders as totals arrive.
+fn sort_entries(entries: &mut [EntryMeta], mode: SortMode) {
+    entries.sort_by(|a, b| compare_entries(a, b, mode));
+}
+
+fn compare_entries(a: &EntryMeta, b: &EntryMeta, mode: SortMode) -> std::cmp::Ordering {
+    let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+    match mode {
+        SortMode::Modified => a.modified.cmp(&b.modified).then_with(by_name),
+        SortMode::ModifiedDesc => b.modified.cmp(&a.modified).then_with(by_name),
+        SortMode::Name | SortMode::Size | SortMode::SizeDesc => by_name(),
+    }
+}
+
+/// Reorder every loaded directory's children for `mode`, directories
+/// first, as a fresh walk would have produced them.
+pub fn resort_tree(tree: &mut DirTree, mode: SortMode) {
+    for id in 0..tree.nodes.len() {
+        let mut children = std::mem::take(&mut tree.nodes[id].children);
+        children.sort_by(|&a, &b| {
+            let (a, b) = (&tree.nodes[a].meta, &tree.nodes[b].meta);
+            b.is_dir.cmp(&a.is_dir).then_with(|| compare_entries(a, b, mode))
+        });
+        tree.nodes[id].children = children;
+    }
 }
 
 /// Build a [`DirTree`] rooted at `root` using the given config.
 ///
-/// Uses a **single** `WalkBuilder` pass (one `.gitignore` parse, no redundant
-/// `stat` calls) and assembles the tree in BFS order afterward.
+/// Uses a **single** `WalkBuilder` pass (one `.gitignore` parse, no `stat`
+/// beyond the optional mtime) and assembles the tree in BFS order afterward.
 pub fn build_tree(root: &Path, config: &WalkConfig, one_file_system: bool) -> anyhow::Result<DirTree> {
     let root_meta = EntryMeta::from_path(root)?;
     let mut tree = DirTree::new(root_meta);
@@ -104,7 +145,7 @@ pub fn build_tree(root: &Path, config: &WalkConfig, one_file_system: bool) -> an
             None => continue,
         };
 
-        let meta = meta_from_dir_entry(&entry);
+        let meta = meta_from_dir_entry(&entry, config.read_mtimes);
         let (dirs, files) = children.entry(parent).or_default();
         if meta.is_dir {
             dirs.push(meta);
@@ -113,10 +154,10 @@ pub fn build_tree(root: &Path, config: &WalkConfig, one_file_system: bool) -> an
         }
     }
 
-    // Sort each group: dirs first (alphabetical), then files (alphabetical).
+    // Sort each group: dirs first, then files, each by `sort_mode`.
     for (dirs, files) in children.values_mut() {
-        sort_by_name(dirs);
-        sort_by_name(files);
+        sort_entries(dirs, config.sort_mode);
+        sort_entries(files, config.sort_mode);
     }
 
     // Assemble the tree in BFS order so parent nodes exist before children.
@@ -139,6 +180,110 @@ pub fn build_tree(root: &Path, config: &WalkConfig, one_file_system: bool) -> an
     Ok(tree)
 }
 
+/// Build a [`DirTree`] holding exactly `paths` plus the directories that
+/// lead to them, for browsing a list piped in with `--stdin`.
+///
+/// Relative paths are taken relative to `base`.  The root is `base`, or
+/// its deepest common ancestor with any listed path outside it.  Paths
+/// that don't exist are kept as [`EntryMeta::missing`] entries.
+pub fn build_tree_from_paths(
+    base: &Path,
+    paths: impl IntoIterator<Item = PathBuf>,
+    sort_mode: SortMode,
+) -> DirTree {
+    let paths: BTreeSet<PathBuf> = paths
+        .into_iter()
+        .map(|p| resolve_listed_path(base, &p))
+        .collect();
+    let root = paths
+        .iter()
+        .fold(base.to_path_buf(), |root, path| common_ancestor(&root, path));
+
+    let root_meta = EntryMeta::from_path(&root).unwrap_or_else(|_| EntryMeta::missing(&root, true));
+    let mut tree = DirTree::new(root_meta);
+    let mut ids: HashMap<PathBuf, NodeId> = HashMap::new();
+
+    for path in &paths {
+        // From just below the root down to the listed path itself.
+        let chain: Vec<&Path> = path.ancestors().take_while(|a| *a != root).collect();
+        let mut parent_id = tree.root;
+        for (depth, entry) in chain.iter().rev().enumerate() {
+            if let Some(&id) = ids.get(*entry) {
+                parent_id = id;
+                continue;
+            }
+            let is_leaf = depth + 1 == chain.len();
+            let meta = EntryMeta::from_path(entry)
+                .unwrap_or_else(|_| EntryMeta::missing(entry, !is_leaf));
+            parent_id = tree.add_child(parent_id, meta);
+            ids.insert(entry.to_path_buf(), parent_id);
+        }
+    }
+
+    resort_tree(&mut tree, sort_mode);
+    tree
+}
+
+/// Absolute form of a listed path.  The parent is canonicalized when it
+/// exists so paths match the tree's; the entry itself is not, so listed
+/// symlinks stay symlinks.
+fn resolve_listed_path(base: &Path, path: &Path) -> PathBuf {
+    let joined = normalize_lexically(&base.join(path));
+    match (joined.parent(), joined.file_name()) {
+        (Some(parent), Some(name)) => parent
+            .canonicalize()
+            .map(|parent| parent.join(name))
+            .unwrap_or(joined),
+        _ => joined,
+    }
+}
+
+/// Drop `.` components and fold `..` into the preceding component
+/// without touching the filesystem.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+fn common_ancestor(a: &Path, b: &Path) -> PathBuf {
+    a.components()
+        .zip(b.components())
+        .take_while(|(x, y)| x == y)
+        .map(|(x, _)| x)
+        .collect()
+}
+
+/// Entries stat'ed by [`probe_slow_fs`].
+const PROBE_ENTRIES: usize = 64;
+
+/// Mean per-entry latency above which a filesystem counts as slow.  Local
+/// disks, even with a cold cache, stay well under this.
+const SLOW_ENTRY_LATENCY: Duration = Duration::from_millis(4);
+
+/// Measure `read_dir` + `stat` latency on the first entries of `dir` and
+/// report whether it looks like a slow (network / FUSE) filesystem.
+pub fn probe_slow_fs(dir: &Path) -> bool {
+    let started = Instant::now();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    let mut probed: u32 = 1; // the readdir itself
+    for entry in entries.flatten().take(PROBE_ENTRIES) {
+        let _ = std::fs::symlink_metadata(entry.path());
+        probed += 1;
+    }
+    started.elapsed() / probed > SLOW_ENTRY_LATENCY
+}
+
 /// Lazily expand a single directory that hasn't been populated yet.
 /// Useful when the user expands a previously-collapsed node beyond the
 /// initial `max_depth`.
@@ -162,7 +307,7 @@ pub fn scan_immediate_children(
         if entry.path() == dir {
             continue;
         }
-        let meta = meta_from_dir_entry(&entry);
+        let meta = meta_from_dir_entry(&entry, config.read_mtimes);
         if meta.is_dir {
             dirs.push(meta);
         } else {
@@ -170,13 +315,29 @@ pub fn scan_immediate_children(
         }
     }
 
-    sort_by_name(&mut dirs);
-    sort_by_name(&mut files);
+    sort_entries(&mut dirs, config.sort_mode);
+    sort_entries(&mut files, config.sort_mode);
 
     dirs.extend(files);
     dirs
 }
 
+/// Permanently remove a file, symlink, or directory tree.  Symlinks are
+/// removed themselves, never followed.  A path that is already gone (e.g.
+/// inside a directory removed earlier in the same batch) counts as success.
+pub fn remove_path(path: &Path) -> std::io::Result<()> {
+    let meta = match std::fs::symlink_metadata(path) {
+        Ok(meta) => meta,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    if meta.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
 /// Lazily expand a single directory that hasn't been populated yet.
 /// Useful when the user expands a previously-collapsed node beyond the
 /// initial `max_depth`.
@@ -199,4 +360,64 @@ pub fn expand_node(
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    fn listed(tree: &DirTree) -> Vec<(PathBuf, bool, bool)> {
+        let mut nodes: Vec<_> = tree
+            .nodes
+            .iter()
+            .map(|n| (n.meta.path.clone(), n.meta.is_dir, n.meta.missing))
+            .collect();
+        nodes.sort();
+        nodes
+    }
+
+    #[test]
+    fn listed_paths_build_just_their_branches() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("src/unlisted")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "").unwrap();
+        std::fs::write(root.join("README"), "").unwrap();
+
+        let paths = ["src/main.rs", "./src/../README", "gone/deep/file.txt", "src/main.rs"];
+        let tree = build_tree_from_paths(&root, paths.map(PathBuf::from), SortMode::Name);
+
+        assert_eq!(tree.get(tree.root).meta.path, root);
+        assert_eq!(
+            listed(&tree),
+            [
+                (root.clone(), true, false),
+                (root.join("README"), false, false),
+                (root.join("gone"), true, true),
+                (root.join("gone/deep"), true, true),
+                (root.join("gone/deep/file.txt"), false, true),
+                (root.join("src"), true, false),
+                (root.join("src/main.rs"), false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_path_outside_the_base_moves_the_root_up() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("a/b")).unwrap();
+        std::fs::create_dir_all(root.join("c")).unwrap();
+
+        let tree = build_tree_from_paths(&root.join("a/b"), [PathBuf::from("../../c")], SortMode::Name);
+
+        assert_eq!(tree.get(tree.root).meta.path, root);
+        assert_eq!(listed(&tree), [(root.clone(), true, false), (root.join("c"), true, false)]);
+    }
+
+    #[test]
+    fn lexical_normalization_folds_dots() {
+        assert_eq!(normalize_lexically(Path::new("/a/./b/../c/")), Path::new("/a/c"));
+        assert_eq!(normalize_lexically(Path::new("/../a")), Path::new("/a"));
+        assert_eq!(common_ancestor(Path::new("/a/b/c"), Path::new("/a/bc")), Path::new("/a"));
+        assert_eq!(common_ancestor(Path::new("/a/b"), Path::new("/a/b/c")), Path::new("/a/b"));
+    }
+}
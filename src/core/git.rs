@@ -0,0 +1,102 @@
+//! Working-tree status from `git status`, for per-entry badges.
+//!
+//! Shells out to the `git` binary rather than linking libgit2; outside a
+//! repository (or without git installed) the result is simply empty.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Change state of one path relative to `HEAD` / the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitFileStatus {
+    Modified,
+    Added,
+    Deleted,
+    Untracked,
+}
+
+impl GitFileStatus {
+    /// One-character badge shown beside the entry.
+    pub fn badge(self) -> char {
+        match self {
+            Self::Modified => 'M',
+            Self::Added => 'A',
+            Self::Deleted => 'D',
+            Self::Untracked => '?',
+        }
+    }
+
+    /// Map a porcelain `XY` code to a status.
+    fn from_xy(x: u8, y: u8) -> Option<Self> {
+        match (x, y) {
+            (b'?', b'?') => Some(Self::Untracked),
+            (b'!', b'!') => None,
+            (b'D', _) | (_, b'D') => Some(Self::Deleted),
+            (b'A', _) => Some(Self::Added),
+            (b' ', b' ') => None,
+            _ => Some(Self::Modified),
+        }
+    }
+}
+
+/// Status of every changed path in the repository containing `dir`,
+/// keyed by absolute path.  Directories holding a change are marked
+/// [`GitFileStatus::Modified`] (or keep their own status when untracked
+/// as a whole).  Returns `None` when `dir` is not inside a work tree.
+pub fn repo_status(dir: &Path) -> Option<HashMap<PathBuf, GitFileStatus>> {
+    let toplevel = git_output(dir, &["rev-parse", "--show-toplevel"])?;
+    let toplevel = PathBuf::from(String::from_utf8_lossy(&toplevel).trim_end());
+    // Match the canonical paths the tree uses.
+    let toplevel = toplevel.canonicalize().unwrap_or(toplevel);
+
+    let raw = git_output(
+        dir,
+        &["status", "--porcelain=v1", "-z", "--untracked-files=normal"],
+    )?;
+    Some(parse_porcelain(&toplevel, &raw))
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Option<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output.status.success().then_some(output.stdout)
+}
+
+/// Parse `git status --porcelain=v1 -z` output.  Entries are `XY path`
+/// separated by NUL; renames and copies are followed by an extra NUL
+/// field holding the original path, which is skipped.
+fn parse_porcelain(toplevel: &Path, raw: &[u8]) -> HashMap<PathBuf, GitFileStatus> {
+    let mut statuses = HashMap::new();
+    let mut fields = raw.split(|&b| b == 0);
+    while let Some(field) = fields.next() {
+        if field.len() < 4 {
+            continue;
+        }
+        let (x, y) = (field[0], field[1]);
+        if matches!(x, b'R' | b'C') {
+            fields.next();
+        }
+        let Some(status) = GitFileStatus::from_xy(x, y) else {
+            continue;
+        };
+        let rel = String::from_utf8_lossy(&field[3..]);
+        let path = toplevel.join(rel.trim_end_matches('/'));
+
+        // Bubble the change up so collapsed directories show it too.
+        for ancestor in path.ancestors().skip(1) {
+            if !ancestor.starts_with(toplevel) || statuses.contains_key(ancestor) {
+                break;
+            }
+            statuses.insert(ancestor.to_path_buf(), GitFileStatus::Modified);
+        }
+        statuses.insert(path, status);
+    }
+    statuses
+}
@@ -4,8 +4,10 @@
 //! individually.  The grouping layer inspects the children of a node and
 //! produces [`GroupedEntry`] values that the UI renders instead of raw nodes.
 
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 use super::tree::{DirTree, NodeId};
 
@@ -32,14 +34,198 @@ pub enum GroupedEntry {
 /// Configuration for the grouping heuristics.
 #[derive(Debug, Clone)]
 pub struct GroupingConfig {
-    /// Minimum number of files sharing the same extension before we collapse
-    /// them into a group.
+    /// Minimum number of files sharing the same bucket before we collapse
+    /// them into a group; `0` turns grouping off.
     pub min_group_size: usize,
+    /// What files are bucketed by.
+    pub group_by: GroupBy,
 }
 
 impl Default for GroupingConfig {
     fn default() -> Self {
-        Self { min_group_size: 5 }
+        Self {
+            min_group_size: 5,
+            group_by: GroupBy::Extension,
+        }
+    }
+}
+
+/// Key files are bucketed by before grouping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    /// Raw extension: `*.png`.
+    #[default]
+    Extension,
+    /// Broad file type: Image, Code, ... (see [`CATEGORY_EXTENSIONS`]).
+    Category,
+    /// Size class: Tiny (<1 KiB) up to Huge.
+    Size,
+    /// Modification date: Today, This week, This month, Older.
+    Date,
+}
+
+impl GroupBy {
+    pub const ALL: [Self; 4] = [Self::Extension, Self::Category, Self::Size, Self::Date];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Extension => "extension",
+            Self::Category => "category",
+            Self::Size => "size",
+            Self::Date => "date",
+        }
+    }
+
+    pub fn config_value(self) -> &'static str {
+        self.label()
+    }
+
+    pub fn from_config_value(v: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.config_value() == v)
+    }
+
+    /// Next mode in `ALL`, wrapping around.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Broad file categories for [`GroupBy::Category`], in display order.
+/// Extensions are matched case-insensitively; anything unlisted is `Other`.
+const CATEGORY_EXTENSIONS: &[(&str, &[&str])] = &[
+    (
+        "Image",
+        &[
+            "png", "jpg", "jpeg", "gif", "bmp", "webp", "tif", "tiff", "svg", "ico", "heic", "avif", "raw",
+            "cr2", "nef", "psd",
+        ],
+    ),
+    ("Video", &["mp4", "mkv", "mov", "avi", "webm", "m4v", "wmv", "flv", "mpg", "mpeg"]),
+    ("Audio", &["mp3", "flac", "wav", "ogg", "opus", "m4a", "aac", "wma", "aiff"]),
+    (
+        "Code",
+        &[
+            "rs", "c", "h", "cc", "cpp", "hpp", "py", "js", "ts", "jsx", "tsx", "go", "java", "kt", "swift",
+            "rb", "php", "cs", "sh", "bash", "zsh", "fish", "lua", "pl", "hs", "ml", "scala", "sql", "html",
+            "css", "scss", "vue",
+        ],
+    ),
+    (
+        "Document",
+        &[
+            "pdf", "doc", "docx", "odt", "rtf", "txt", "md", "rst", "tex", "epub", "xls", "xlsx", "ods", "ppt",
+            "pptx", "odp",
+        ],
+    ),
+    (
+        "Data",
+        &[
+            "json", "yaml", "yml", "toml", "xml", "csv", "tsv", "ini", "db", "sqlite", "parquet", "bin", "dat",
+            "log", "zip", "tar", "gz", "xz", "zst", "bz2", "7z", "rar",
+        ],
+    ),
+];
+
+/// Size classes for [`GroupBy::Size`]: exclusive upper bound and label.
+const SIZE_BUCKETS: &[(u64, &str)] = &[
+    (1024, "Tiny (<1 KiB)"),
+    (1024 * 1024, "Small (<1 MiB)"),
+    (100 * 1024 * 1024, "Medium (<100 MiB)"),
+    (1024 * 1024 * 1024, "Large (<1 GiB)"),
+    (u64::MAX, "Huge (≥1 GiB)"),
+];
+
+/// Category of a file by extension.
+fn category_of(extension: Option<&str>) -> (usize, &'static str) {
+    let ext = extension.map(str::to_ascii_lowercase);
+    ext.and_then(|ext| {
+        CATEGORY_EXTENSIONS
+            .iter()
+            .position(|(_, exts)| exts.contains(&ext.as_str()))
+    })
+    .map_or((CATEGORY_EXTENSIONS.len(), "Other"), |i| (i, CATEGORY_EXTENSIONS[i].0))
+}
+
+/// Start of today, this week (Monday) and this month in local time.
+fn date_boundaries() -> [SystemTime; 3] {
+    use chrono::{Datelike, Duration, Local, TimeZone};
+    let today = Local::now().date_naive();
+    let week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let month = today.with_day(1).unwrap_or(today);
+    [today, week, month].map(|day| {
+        day.and_hms_opt(0, 0, 0)
+            .and_then(|t| Local.from_local_datetime(&t).earliest())
+            .map_or(SystemTime::UNIX_EPOCH, SystemTime::from)
+    })
+}
+
+/// Order of entries within a directory.  Directories always come before
+/// files; the mode decides the order inside each half.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortMode {
+    /// Case-insensitive name.
+    #[default]
+    Name,
+    /// Smallest first.
+    Size,
+    /// Least recently modified first.
+    Modified,
+    /// Largest first.
+    SizeDesc,
+    /// Most recently modified first.
+    ModifiedDesc,
+}
+
+impl SortMode {
+    /// Cycle order.
+    pub const ALL: &[SortMode] = &[
+        SortMode::Name,
+        SortMode::SizeDesc,
+        SortMode::Size,
+        SortMode::ModifiedDesc,
+        SortMode::Modified,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Size => "smallest",
+            Self::Modified => "oldest",
+            Self::SizeDesc => "largest",
+            Self::ModifiedDesc => "newest",
+        }
+    }
+
+    /// `size` / `modified` keep meaning largest / newest first, as they
+    /// did before the ascending orders existed.
+    pub fn config_value(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Size => "size_asc",
+            Self::Modified => "modified_asc",
+            Self::SizeDesc => "size",
+            Self::ModifiedDesc => "modified",
+        }
+    }
+
+    /// Orders that depend on computed sizes.
+    pub fn is_size(self) -> bool {
+        matches!(self, Self::Size | Self::SizeDesc)
+    }
+
+    pub fn is_descending(self) -> bool {
+        matches!(self, Self::SizeDesc | Self::ModifiedDesc)
+    }
+
+    pub fn from_config_value(value: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|m| m.config_value() == value)
+    }
+
+    /// Next mode in `ALL`, wrapping around.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
     }
 }
 
@@ -49,9 +235,37 @@ impl Default for GroupingConfig {
 ///
 /// Strategy:
 /// 1. Directories are always shown individually.
-/// 2. Files are bucketed by extension.
+/// 2. Files are bucketed by `config.group_by` (extension by default).
 /// 3. If a bucket has ≥ `min_group_size` entries it becomes a [`GroupedEntry::Group`].
 /// 4. Otherwise each file stays as [`GroupedEntry::Single`].
+///
+/// Every child appears exactly once in the result.  Group labels are
+/// unique within a parent and depend only on its children, so callers can
+/// key expand state on `parent:label` and have it survive redraws.
+///
+/// ```
+/// use std::path::{Path, PathBuf};
+/// use dir_tree::core::fs::build_tree_from_paths;
+/// use dir_tree::core::grouping::{group_children, GroupedEntry, GroupingConfig, SortMode};
+///
+/// let listed = ["a.png", "b.png", "c.png", "notes.txt", "src/main.rs"];
+/// let tree = build_tree_from_paths(
+///     Path::new("/project"),
+///     listed.iter().map(PathBuf::from),
+///     SortMode::Name,
+/// );
+/// let config = GroupingConfig { min_group_size: 3, ..GroupingConfig::default() };
+///
+/// let shown: Vec<String> = group_children(&tree, tree.root, &config, None)
+///     .iter()
+///     .map(|entry| match entry {
+///         GroupedEntry::Single(id) => tree.get(*id).meta.name.clone(),
+///         GroupedEntry::Group { label, count, .. } => format!("{label} ({count})"),
+///     })
+///     .collect();
+/// // Directories first, then buckets; the lone text file isn't grouped.
+/// assert_eq!(shown, ["src", "*.png (3)", "notes.txt"]);
+/// ```
 pub fn group_children(
     tree: &DirTree,
     parent_id: NodeId,
@@ -60,48 +274,58 @@ pub fn group_children(
 ) -> Vec<GroupedEntry> {
     let parent = tree.get(parent_id);
     let mut result: Vec<GroupedEntry> = Vec::new();
+    // Prefer the async-computed size; fall back to meta.size.
+    let file_size = |id: NodeId| {
+        let node = tree.get(id);
+        file_sizes
+            .and_then(|fs| fs.get(&node.meta.path).copied())
+            .unwrap_or(node.meta.size)
+    };
+    let dates = (config.group_by == GroupBy::Date).then(date_boundaries);
 
-    // Bucket files by extension.
-    let mut ext_buckets: HashMap<Option<String>, Vec<NodeId>> = HashMap::new();
+    // Bucket files; keys order the buckets (rank, then label).
+    let mut buckets: BTreeMap<(usize, String), Vec<NodeId>> = BTreeMap::new();
 
     for &child_id in &parent.children {
         let child = tree.get(child_id);
         if child.meta.is_dir {
             // Directories always show individually.
             result.push(GroupedEntry::Single(child_id));
-        } else {
-            ext_buckets
-                .entry(child.meta.extension.clone())
-                .or_default()
-                .push(child_id);
+            continue;
         }
+        let key = match config.group_by {
+            GroupBy::Extension => match &child.meta.extension {
+                Some(e) => (0, format!("*.{e}")),
+                None => (0, "(no extension)".to_string()),
+            },
+            GroupBy::Category => {
+                let (rank, label) = category_of(child.meta.extension.as_deref());
+                (rank, label.to_string())
+            }
+            GroupBy::Size => {
+                let size = file_size(child_id);
+                let rank = SIZE_BUCKETS.iter().position(|&(limit, _)| size < limit);
+                let rank = rank.unwrap_or(SIZE_BUCKETS.len() - 1);
+                (rank, SIZE_BUCKETS[rank].1.to_string())
+            }
+            GroupBy::Date => {
+                const LABELS: [&str; 4] = ["Today", "This week", "This month", "Older"];
+                let rank = match (child.meta.modified, &dates) {
+                    (Some(modified), Some(bounds)) => {
+                        bounds.iter().position(|&b| modified >= b).unwrap_or(3)
+                    }
+                    _ => 4,
+                };
+                (rank, LABELS.get(rank).copied().unwrap_or("Unknown date").to_string())
+            }
+        };
+        buckets.entry(key).or_default().push(child_id);
     }
 
     // Convert buckets to grouped entries.
-    let mut ext_keys: Vec<_> = ext_buckets.keys().cloned().collect();
-    ext_keys.sort_by(|a, b| {
-        let a_str = a.as_deref().unwrap_or("");
-        let b_str = b.as_deref().unwrap_or("");
-        a_str.cmp(b_str)
-    });
-
-    for ext in ext_keys {
-        let members = ext_buckets.remove(&ext).unwrap();
-        if members.len() >= config.min_group_size {
-            let total_size: u64 = members
-                .iter()
-                .map(|&id| {
-                    let node = tree.get(id);
-                    // Prefer the async-computed size; fall back to meta.size.
-                    file_sizes
-                        .and_then(|fs| fs.get(&node.meta.path).copied())
-                        .unwrap_or(node.meta.size)
-                })
-                .sum();
-            let label = match &ext {
-                Some(e) => format!("*.{e}"),
-                None => "(no extension)".to_string(),
-            };
+    for ((_, label), members) in buckets {
+        if config.min_group_size > 0 && members.len() >= config.min_group_size {
+            let total_size: u64 = members.iter().map(|&id| file_size(id)).sum();
             result.push(GroupedEntry::Group {
                 label,
                 count: members.len(),
@@ -115,10 +339,102 @@ pub fn group_children(
         }
     }
 
+    debug_assert_eq!(
+        result
+            .iter()
+            .map(|entry| match entry {
+                GroupedEntry::Single(_) => 1,
+                GroupedEntry::Group { members, .. } => members.len(),
+            })
+            .sum::<usize>(),
+        parent.children.len(),
+        "every child is shown exactly once",
+    );
     result
 }
 
-/// Human-readable size string.
+/// Reorder grouped entries (and group members) by `mode`.
+///
+/// The sort is stable, so ties keep the name order from the walk.  `Name`
+/// keeps the walk order as is.  Sizes
+/// come from the async maps when known, otherwise from `meta.size`; a
+/// directory without a computed total sorts as 0 until it arrives.
+pub fn sort_grouped(
+    tree: &DirTree,
+    entries: &mut [GroupedEntry],
+    mode: SortMode,
+    dir_sizes: Option<&HashMap<PathBuf, u64>>,
+    file_sizes: Option<&HashMap<PathBuf, u64>>,
+) {
+    if mode == SortMode::Name {
+        return;
+    }
+    let node_size = |id: NodeId| {
+        let meta = &tree.get(id).meta;
+        let sizes = if meta.is_dir { dir_sizes } else { file_sizes };
+        match sizes.and_then(|s| s.get(&meta.path).copied()) {
+            Some(size) => size,
+            None if meta.is_dir => 0,
+            None => meta.size,
+        }
+    };
+    let node_modified = |id: NodeId| tree.get(id).meta.modified;
+    let directed = |ord: Ordering| if mode.is_descending() { ord.reverse() } else { ord };
+    let is_dir = |entry: &GroupedEntry| match entry {
+        GroupedEntry::Single(id) => tree.get(*id).meta.is_dir,
+        GroupedEntry::Group { .. } => false,
+    };
+
+    match mode {
+        SortMode::Name => {}
+        SortMode::Size | SortMode::SizeDesc => {
+            let entry_size = |entry: &GroupedEntry| match entry {
+                GroupedEntry::Single(id) => node_size(*id),
+                GroupedEntry::Group { total_size, .. } => *total_size,
+            };
+            for entry in entries.iter_mut() {
+                if let GroupedEntry::Group { members, .. } = entry {
+                    members.sort_by(|&a, &b| directed(node_size(a).cmp(&node_size(b))));
+                }
+            }
+            entries.sort_by(|a, b| {
+                is_dir(b)
+                    .cmp(&is_dir(a))
+                    .then_with(|| directed(entry_size(a).cmp(&entry_size(b))))
+            });
+        }
+        SortMode::Modified | SortMode::ModifiedDesc => {
+            let entry_modified = |entry: &GroupedEntry| -> Option<SystemTime> {
+                match entry {
+                    GroupedEntry::Single(id) => node_modified(*id),
+                    GroupedEntry::Group { members, .. } => {
+                        members.iter().filter_map(|&id| node_modified(id)).max()
+                    }
+                }
+            };
+            for entry in entries.iter_mut() {
+                if let GroupedEntry::Group { members, .. } = entry {
+                    members.sort_by(|&a, &b| directed(node_modified(a).cmp(&node_modified(b))));
+                }
+            }
+            entries.sort_by(|a, b| {
+                is_dir(b)
+                    .cmp(&is_dir(a))
+                    .then_with(|| directed(entry_modified(a).cmp(&entry_modified(b))))
+            });
+        }
+    }
+}
+
+/// Human-readable size string, in binary units with one decimal.
+///
+/// ```
+/// use dir_tree::core::grouping::human_size;
+///
+/// assert_eq!(human_size(0), "0.0 B");
+/// assert_eq!(human_size(1536), "1.5 KiB");
+/// assert_eq!(human_size(5 * 1024 * 1024), "5.0 MiB");
+/// ```
 pub fn human_size(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
     let mut size = bytes as f64;
@@ -131,3 +447,104 @@ pub fn human_size(bytes: u64) -> String {
     format!("{size:.1} PiB")
 }
 
+/// Short count for progress text: `950`, `48k`, `1.2M`.
+pub fn human_count(n: u64) -> String {
+    match n {
+        0..=999 => n.to_string(),
+        1_000..=999_999 => format!("{}k", n / 1_000),
+        _ => format!("{:.1}M", n as f64 / 1_000_000.0),
+    }
+}
+
+/// Exact count with thousands separators.
+///
+/// ```
+/// use dir_tree::core::grouping::grouped_count;
+///
+/// assert_eq!(grouped_count(950), "950");
+/// assert_eq!(grouped_count(1204), "1,204");
+/// assert_eq!(grouped_count(3_000_000), "3,000,000");
+/// ```
+pub fn grouped_count(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Fixed-width (9 chars) form of [`human_size`] for columns that update
+/// in place: a right-aligned number of at most five characters and a
+/// left-aligned unit.  Bytes are whole numbers; other units keep one
+/// decimal below 100 and none above, so the number never grows wider.
+pub fn human_size_padded(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if bytes < 1024 {
+        return format!("{bytes:>5} B  ");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for &next in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+    let number = if size < 100.0 {
+        format!("{size:.1}")
+    } else {
+        format!("{size:.0}")
+    };
+    format!("{number:>5} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KIB: u64 = 1024;
+    const MIB: u64 = 1024 * KIB;
+    const GIB: u64 = 1024 * MIB;
+
+    #[test]
+    fn padded_size_has_a_fixed_width() {
+        let sizes = [
+            0,
+            1,
+            999,
+            KIB - 1,
+            KIB,
+            KIB + 1,
+            100 * KIB - 1,
+            MIB - 1,
+            MIB,
+            123 * MIB,
+            GIB - 1,
+            GIB,
+            5 * GIB,
+            1024 * GIB,
+            u64::MAX,
+        ];
+        for bytes in sizes {
+            let text = human_size_padded(bytes);
+            assert_eq!(text.chars().count(), 9, "{bytes} -> {text:?}");
+        }
+    }
+
+    #[test]
+    fn padded_size_at_unit_boundaries() {
+        assert_eq!(human_size_padded(0), "    0 B  ");
+        assert_eq!(human_size_padded(KIB - 1), " 1023 B  ");
+        assert_eq!(human_size_padded(KIB), "  1.0 KiB");
+        assert_eq!(human_size_padded(MIB - 1), " 1024 KiB");
+        assert_eq!(human_size_padded(MIB), "  1.0 MiB");
+        assert_eq!(human_size_padded(GIB - 1), " 1024 MiB");
+        assert_eq!(human_size_padded(GIB), "  1.0 GiB");
+        assert_eq!(human_size_padded(250 * GIB), "  250 GiB");
+    }
+}
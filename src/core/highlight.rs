@@ -0,0 +1,137 @@
+//! Syntax highlighting for the inspector's text previews.
+//!
+//! syntect's bundled grammars are only used to parse: every token is
+//! reduced to a [`TokenKind`], and the colours live in `ui::theme::Theme`.
+//! No UI types are used here.
+
+use std::path::Path;
+
+use syntect::parsing::{ParseState, Scope, ScopeStack, SyntaxReference, SyntaxSet};
+
+/// What a run of preview text is, as far as colouring goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Plain,
+    Comment,
+    String,
+    Number,
+    Constant,
+    Keyword,
+    Function,
+    Type,
+}
+
+/// One preview line split into coloured runs.
+pub type HighlightedLine = Vec<(TokenKind, String)>;
+
+/// Scope prefixes and the kind they map to, most specific first.
+const SCOPE_KINDS: &[(&str, TokenKind)] = &[
+    ("comment", TokenKind::Comment),
+    ("string", TokenKind::String),
+    ("constant.numeric", TokenKind::Number),
+    ("constant", TokenKind::Constant),
+    ("entity.name.type", TokenKind::Type),
+    ("entity.name.class", TokenKind::Type),
+    ("support.type", TokenKind::Type),
+    ("support.class", TokenKind::Type),
+    ("entity.name.function", TokenKind::Function),
+    ("support.function", TokenKind::Function),
+    // Operators stay plain, or every `=` is coloured.
+    ("keyword.operator", TokenKind::Plain),
+    ("keyword", TokenKind::Keyword),
+    ("storage", TokenKind::Keyword),
+];
+
+/// Bundled grammars plus the scope table.  Loading takes a few tens of
+/// milliseconds, so the inspector worker builds one on first use.
+pub struct Highlighter {
+    syntaxes: SyntaxSet,
+    scope_kinds: Vec<(Scope, TokenKind)>,
+}
+
+impl Default for Highlighter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Highlighter {
+    pub fn new() -> Self {
+        Self {
+            syntaxes: SyntaxSet::load_defaults_newlines(),
+            scope_kinds: SCOPE_KINDS
+                .iter()
+                .filter_map(|&(name, kind)| Scope::new(name).ok().map(|scope| (scope, kind)))
+                .collect(),
+        }
+    }
+
+    /// Highlight preview `lines` of `path`.  The grammar is picked by
+    /// extension, then by the first line (`#!` and mode lines); `None`
+    /// when neither matches or a line fails to parse.
+    pub fn highlight(&self, path: &Path, lines: &[String]) -> Option<Vec<HighlightedLine>> {
+        let syntax = self.syntax_for(path, lines.first().map(String::as_str))?;
+        let mut parser = ParseState::new(syntax);
+        let mut stack = ScopeStack::new();
+        let mut highlighted = Vec::with_capacity(lines.len());
+        for line in lines {
+            // The grammars expect each line to end in a newline.
+            let ops = parser.parse_line(&format!("{line}\n"), &self.syntaxes).ok()?;
+            let mut runs: HighlightedLine = Vec::new();
+            let mut start = 0;
+            for (offset, op) in ops {
+                let end = offset.min(line.len());
+                if end > start {
+                    self.push_run(&mut runs, &stack, &line[start..end]);
+                    start = end;
+                }
+                stack.apply(&op).ok()?;
+            }
+            if start < line.len() {
+                self.push_run(&mut runs, &stack, &line[start..]);
+            }
+            highlighted.push(runs);
+        }
+        Some(highlighted)
+    }
+
+    fn syntax_for(&self, path: &Path, first_line: Option<&str>) -> Option<&SyntaxReference> {
+        let by_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| self.syntaxes.find_syntax_by_extension(ext))
+            .or_else(|| {
+                // Names like `Makefile` and `Dockerfile` are listed as
+                // extensions too.
+                let name = path.file_name()?.to_str()?;
+                self.syntaxes.find_syntax_by_extension(name)
+            });
+        let syntax = by_extension.or_else(|| self.syntaxes.find_syntax_by_first_line(first_line?))?;
+        (syntax.name != "Plain Text").then_some(syntax)
+    }
+
+    /// Append `text` to `runs`, merging it into the last run when the
+    /// kind is unchanged.
+    fn push_run(&self, runs: &mut HighlightedLine, stack: &ScopeStack, text: &str) {
+        let kind = self.kind_of(stack);
+        match runs.last_mut() {
+            Some((last, run)) if *last == kind => run.push_str(text),
+            _ => runs.push((kind, text.to_string())),
+        }
+    }
+
+    /// Kind of the innermost scope on `stack` that the table knows.
+    fn kind_of(&self, stack: &ScopeStack) -> TokenKind {
+        stack
+            .as_slice()
+            .iter()
+            .rev()
+            .find_map(|scope| {
+                self.scope_kinds
+                    .iter()
+                    .find(|(prefix, _)| prefix.is_prefix_of(*scope))
+                    .map(|&(_, kind)| kind)
+            })
+            .unwrap_or(TokenKind::Plain)
+    }
+}
@@ -3,24 +3,49 @@
 //! This module performs filesystem reads and returns plain data structures.
 //! No UI or Ratatui types are used here.
 
-use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use image::ImageDecoder;
 
+use crate::core::grouping::human_size;
+use crate::core::highlight::{HighlightedLine, Highlighter};
+use crate::core::previewer::ExternalPreview;
+
 #[derive(Debug, Clone)]
 pub struct InspectorInfo {
     pub path: PathBuf,
     pub name: String,
     pub kind: String,
     pub detected_type: Option<String>,
+    /// Apparent size in bytes (directory total once computed).
     pub size_bytes: Option<u64>,
+    /// Directory total counting every hard-link occurrence.
+    pub apparent_bytes: Option<u64>,
+    /// Bytes allocated on disk.
+    pub allocated_bytes: Option<u64>,
+    /// Symlinks and special files anywhere below a directory.
+    pub entry_counts: Option<crate::core::size::EntryCounts>,
+    /// Present `allocated_bytes` as the size (allocated size view).
+    pub show_allocated: bool,
     pub readonly: bool,
     pub perms_symbolic: Option<String>,
     pub perms_octal: Option<String>,
     pub modified_unix: Option<u64>,
     pub created_unix: Option<u64>,
     pub symlink_target: Option<String>,
+    /// Every hop of a symlink chain, in order, ending at the final target
+    /// (or a `… (cycle)` / `… (too many hops)` marker).
+    pub symlink_chain: Vec<String>,
+    /// Final non-symlink path the chain resolves to (if it exists).
+    pub resolved_path: Option<PathBuf>,
+    /// Kind of the chain's final target (`"File"`, `"Directory"`, `"Missing"`, …).
+    pub target_kind: Option<String>,
+    /// Apparent size of the chain's final target (files only).
+    pub target_size: Option<u64>,
     pub subdirs: Option<u64>,
     pub subfiles: Option<u64>,
     pub others: Option<u64>,
@@ -30,105 +55,523 @@ pub struct InspectorInfo {
     pub image_height: Option<u32>,
     pub image_pixel_format: Option<String>,
     pub image_channels: Option<u8>,
+    /// EXIF make and model (JPEG/TIFF only).
+    pub exif_camera: Option<String>,
+    /// EXIF capture time, `YYYY-MM-DD HH:MM:SS`.
+    pub exif_datetime: Option<String>,
+    /// EXIF GPS position as signed decimal degrees, `lat, lon`.
+    pub exif_gps: Option<String>,
+    /// EXIF orientation tag (1–8).
+    pub exif_orientation: Option<u16>,
+    // ── text-specific metadata ──
+    /// `\n` count within the first [`TEXT_SCAN_BYTES`] of the file.
+    pub line_count: Option<u64>,
+    /// Charset from the BOM or a content guess (`"UTF-8"`, `"ASCII"`, …).
+    pub encoding: Option<String>,
+    pub has_bom: bool,
+    /// First [`TEXT_PREVIEW_LINES`] lines, tabs expanded and control
+    /// characters replaced.
+    pub text_preview: Option<Vec<String>>,
+    /// Sniffed as text, but the contents turned out to be binary.
+    pub looks_binary: bool,
+    /// [`Self::text_preview`] split into syntax-highlighted runs, when
+    /// highlighting is on and a grammar matches.
+    pub text_highlight: Option<Vec<HighlightedLine>>,
+    // ── archive-specific metadata ──
+    /// First [`ARCHIVE_LIST_MAX`] member names of a ZIP or tar archive.
+    pub archive_entries: Option<Vec<String>>,
+    /// Total member count (may exceed the listed entries).
+    pub archive_entry_count: Option<u64>,
+    /// Sum of the members' uncompressed sizes.
+    pub archive_unpacked_bytes: Option<u64>,
+    /// First listed member shown in the "Archive contents" section.
+    pub archive_scroll: usize,
+    // ── audio/video metadata ──
+    /// Playing time from the container header.
+    pub media_duration_secs: Option<f64>,
+    /// Short name of the audio codec (`"mp3"`, `"flac"`, `"aac"`, …).
+    pub media_codec: Option<String>,
+    /// Average bitrate in bits per second (file size over duration).
+    pub media_bitrate: Option<u64>,
+    /// Artist, title and album tags that are present, in that order.
+    pub media_tags: Vec<(String, String)>,
+    /// Lower-case hex SHA-256, filled in by a background thread.
+    pub checksum: Option<String>,
+    /// A checksum is being computed for this path.
+    pub checksum_pending: bool,
+    /// Output of a configured external previewer (current selection only).
+    pub external_preview: Option<ExternalPreview>,
 }
 
 impl InspectorInfo {
+    /// Blank record for `path` — every optional field unset.
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            name: path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| path.display().to_string()),
+            kind: "Unknown".to_string(),
+            detected_type: None,
+            size_bytes: None,
+            apparent_bytes: None,
+            allocated_bytes: None,
+            entry_counts: None,
+            show_allocated: false,
+            readonly: false,
+            perms_symbolic: None,
+            perms_octal: None,
+            modified_unix: None,
+            created_unix: None,
+            symlink_target: None,
+            symlink_chain: Vec::new(),
+            resolved_path: None,
+            target_kind: None,
+            target_size: None,
+            subdirs: None,
+            subfiles: None,
+            others: None,
+            error: None,
+            image_width: None,
+            image_height: None,
+            image_pixel_format: None,
+            image_channels: None,
+            exif_camera: None,
+            exif_datetime: None,
+            exif_gps: None,
+            exif_orientation: None,
+            line_count: None,
+            encoding: None,
+            has_bom: false,
+            text_preview: None,
+            looks_binary: false,
+            text_highlight: None,
+            archive_entries: None,
+            archive_entry_count: None,
+            archive_unpacked_bytes: None,
+            archive_scroll: 0,
+            media_duration_secs: None,
+            media_codec: None,
+            media_bitrate: None,
+            media_tags: Vec::new(),
+            checksum: None,
+            checksum_pending: false,
+            external_preview: None,
+        }
+    }
+
+    /// Placeholder shown while the background worker inspects `path`.
+    pub fn pending(path: &Path) -> Self {
+        let mut info = Self::new(path);
+        info.kind = "Loading…".to_string();
+        info
+    }
+
     /// True when the inspected path is a recognised image file.
     pub fn is_image(&self) -> bool {
         self.image_width.is_some()
     }
+
+    /// Path whose contents back the preview: the resolved target for
+    /// symlinks, otherwise the inspected path itself.  Image decodes are
+    /// keyed by this so several links to one file share a cache entry.
+    pub fn preview_path(&self) -> &Path {
+        self.resolved_path.as_deref().unwrap_or(&self.path)
+    }
 }
 
-pub fn inspect_path(path: &Path) -> InspectorInfo {
-    let mut info = InspectorInfo {
-        path: path.to_path_buf(),
-        name: path
-            .file_name()
-            .and_then(|s| s.to_str())
-            .map(str::to_string)
-            .unwrap_or_else(|| path.display().to_string()),
-        kind: "Unknown".to_string(),
-        detected_type: None,
-        size_bytes: None,
-        readonly: false,
-        perms_symbolic: None,
-        perms_octal: None,
-        modified_unix: None,
-        created_unix: None,
-        symlink_target: None,
-        subdirs: None,
-        subfiles: None,
-        others: None,
-        error: None,
-        image_width: None,
-        image_height: None,
-        image_pixel_format: None,
-        image_channels: None,
-    };
+/// Maximum number of symlink hops followed before giving up.
+const MAX_SYMLINK_HOPS: usize = 16;
 
-    let meta = match std::fs::symlink_metadata(path) {
-        Ok(m) => m,
-        Err(e) => {
-            info.error = Some(format!("stat error: {e}"));
-            return info;
+/// Upper bound on cached MIME detections before LRU eviction kicks in.
+const MIME_CACHE_CAPACITY: usize = 4096;
+
+/// Highlighted previews kept before the cache starts over; re-selecting a
+/// recent file skips the parse.
+const HIGHLIGHT_CACHE_CAPACITY: usize = 64;
+
+// ───────────────────────────────────────── MIME cache ────────
+
+/// Hit/miss counters for the MIME cache, shared with the UI thread so the
+/// debug overlay can display them without talking to the worker.
+#[derive(Debug, Default)]
+pub struct MimeCacheStats {
+    pub hits: AtomicU64,
+    pub misses: AtomicU64,
+    pub entries: AtomicUsize,
+}
+
+struct MimeEntry {
+    size: u64,
+    mtime: Option<SystemTime>,
+    mime: Option<String>,
+}
+
+/// Small LRU of detected MIME types.  An entry only counts as a hit when
+/// the file's size and mtime still match, so rewritten files are re-sniffed.
+struct MimeCache {
+    entries: lru::LruCache<PathBuf, MimeEntry>,
+    stats: Arc<MimeCacheStats>,
+}
+
+impl MimeCache {
+    fn new(capacity: usize, stats: Arc<MimeCacheStats>) -> Self {
+        let capacity = std::num::NonZeroUsize::new(capacity).unwrap_or(std::num::NonZeroUsize::MIN);
+        Self {
+            entries: lru::LruCache::new(capacity),
+            stats,
         }
-    };
+    }
 
-    let ft = meta.file_type();
-    info.readonly = meta.permissions().readonly();
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::MetadataExt;
-        let mode = meta.mode();
-        info.perms_symbolic = Some(mode_to_symbolic(mode));
-        info.perms_octal = Some(format!("{:04o}", mode & 0o7777));
-    }
-    info.modified_unix = to_unix_secs(meta.modified().ok());
-    info.created_unix = to_unix_secs(meta.created().ok());
-
-    if ft.is_dir() {
-        info.kind = "Directory".to_string();
-        let (subdirs, subfiles, others, err) = count_immediate_children(path);
-        info.subdirs = Some(subdirs);
-        info.subfiles = Some(subfiles);
-        info.others = Some(others);
-        if let Some(e) = err {
-            info.error = Some(e);
+    fn get_or_detect(&mut self, path: &Path, meta: &std::fs::Metadata) -> Option<String> {
+        let (size, mtime) = (meta.len(), meta.modified().ok());
+        if let Some(entry) = self.entries.get(path) {
+            if entry.size == size && entry.mtime == mtime {
+                self.stats.hits.fetch_add(1, Ordering::Relaxed);
+                return entry.mime.clone();
+            }
+        }
+        self.stats.misses.fetch_add(1, Ordering::Relaxed);
+
+        // Uses shared-mime-info signatures (magic) for robust content-based
+        // detection, not just extension matching.
+        let mime = tree_magic_mini::from_filepath(path).map(str::to_string);
+
+        // Evicts the least recently used entry once full.
+        self.entries.put(
+            path.to_path_buf(),
+            MimeEntry {
+                size,
+                mtime,
+                mime: mime.clone(),
+            },
+        );
+        self.stats.entries.store(self.entries.len(), Ordering::Relaxed);
+        mime
+    }
+}
+
+// ───────────────────────────────────────── inspection ────────
+
+/// Long-lived inspection context, owned by the background inspector worker.
+/// Holds caches that make re-inspecting the same entries cheap.
+pub struct InspectorCtx {
+    mime_cache: MimeCache,
+    /// Slow-filesystem mode: label types by extension instead of reading
+    /// file contents.
+    extension_only: Arc<AtomicBool>,
+    /// Syntax-highlight text previews.
+    highlight: Arc<AtomicBool>,
+    /// Built on the first highlighted preview.
+    highlighter: Option<Highlighter>,
+    /// Highlighted previews by path, with the mtime they were read at.
+    highlight_cache: HashMap<PathBuf, (Option<SystemTime>, Vec<HighlightedLine>)>,
+}
+
+impl InspectorCtx {
+    pub fn new(
+        stats: Arc<MimeCacheStats>,
+        extension_only: Arc<AtomicBool>,
+        highlight: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            mime_cache: MimeCache::new(MIME_CACHE_CAPACITY, stats),
+            extension_only,
+            highlight,
+            highlighter: None,
+            highlight_cache: HashMap::new(),
         }
-    } else if ft.is_symlink() {
-        info.kind = "Symlink".to_string();
-        info.size_bytes = Some(meta.len());
-        if let Ok(target) = std::fs::read_link(path) {
-            info.symlink_target = Some(target.display().to_string());
+    }
+
+    /// Fill [`InspectorInfo::text_highlight`] from the text preview of
+    /// `path`, reusing the cached result while the file's mtime holds.
+    fn highlight_preview(&mut self, path: &Path, info: &mut InspectorInfo) {
+        if !self.highlight.load(Ordering::Relaxed) {
+            return;
         }
-    } else if ft.is_file() {
-        info.kind = "File".to_string();
-        info.size_bytes = Some(meta.len());
-        info.detected_type = detect_file_type(path);
-        // Extract image metadata if this looks like an image.
-        // Check MIME from tree_magic_mini first; fall back to the image
-        // crate's own format guessing so we catch formats (webp, etc.)
-        // that tree_magic_mini's shared-mime-info DB may not know about.
-        let mime_says_image = info
-            .detected_type
-            .as_deref()
-            .map_or(false, |m| m.starts_with("image/"));
-        let image_crate_knows = || {
-            image::ImageReader::open(path)
-                .ok()
-                .and_then(|r| r.with_guessed_format().ok())
-                .and_then(|r| r.format())
-                .is_some()
+        let Some(preview) = &info.text_preview else {
+            return;
         };
-        if mime_says_image || image_crate_knows() {
-            extract_image_meta(path, &mut info);
+        let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        if let Some((cached_mtime, lines)) = self.highlight_cache.get(path) {
+            if *cached_mtime == mtime && mtime.is_some() {
+                info.text_highlight = Some(lines.clone());
+                return;
+            }
         }
-    } else {
-        info.kind = "Other".to_string();
-        info.size_bytes = Some(0);
+        // Markers aren't source text; the preview draws them itself.
+        let source: Vec<String> = preview
+            .iter()
+            .map(|line| split_truncated(line).0.to_string())
+            .collect();
+        let highlighter = self.highlighter.get_or_insert_with(Highlighter::new);
+        let Some(lines) = highlighter.highlight(path, &source) else {
+            self.highlight_cache.remove(path);
+            return;
+        };
+        if self.highlight_cache.len() >= HIGHLIGHT_CACHE_CAPACITY {
+            self.highlight_cache.clear();
+        }
+        self.highlight_cache.insert(path.to_path_buf(), (mtime, lines.clone()));
+        info.text_highlight = Some(lines);
+    }
+
+    fn sniff_contents(&self) -> bool {
+        !self.extension_only.load(Ordering::Relaxed)
+    }
+
+    /// Text files get a line count, encoding and preview, except in
+    /// slow-filesystem mode where contents aren't read.  Files whose MIME
+    /// type isn't `text/*` (scripts, JSON, unknown extensions) count when
+    /// their head is valid UTF-8.
+    fn is_sniffed_text(&self, info: &InspectorInfo, path: &Path) -> bool {
+        self.sniff_contents()
+            && (info.detected_type.as_deref().is_some_and(|m| m.starts_with("text/"))
+                || head_is_utf8(path))
+    }
+
+    /// ZIP and tar files get a member listing, except in slow-filesystem
+    /// mode.
+    fn is_listable_archive(&self, info: &InspectorInfo) -> bool {
+        self.sniff_contents()
+            && matches!(
+                info.detected_type.as_deref(),
+                Some("application/zip" | "application/x-tar")
+            )
+    }
+
+    /// Audio and video files get duration, codec and tags, except in
+    /// slow-filesystem mode.
+    fn is_probeable_media(&self, info: &InspectorInfo) -> bool {
+        self.sniff_contents()
+            && info
+                .detected_type
+                .as_deref()
+                .is_some_and(|m| m.starts_with("audio/") || m.starts_with("video/"))
+    }
+
+    /// Detect the MIME type of `path`, consulting the cache first.
+    pub fn detect_file_type(&mut self, path: &Path) -> Option<String> {
+        if !self.sniff_contents() {
+            return mime_from_extension(path).map(str::to_string);
+        }
+        let meta = std::fs::metadata(path).ok()?;
+        self.mime_cache.get_or_detect(path, &meta)
+    }
+
+    pub fn inspect_path(&mut self, path: &Path) -> InspectorInfo {
+        let mut info = InspectorInfo::new(path);
+
+        let meta = match std::fs::symlink_metadata(path) {
+            Ok(m) => m,
+            Err(e) => {
+                info.error = Some(format!("stat error: {e}"));
+                return info;
+            }
+        };
+
+        let ft = meta.file_type();
+        info.readonly = meta.permissions().readonly();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let mode = meta.mode();
+            info.perms_symbolic = Some(mode_to_symbolic(mode));
+            info.perms_octal = Some(format!("{:04o}", mode & 0o7777));
+        }
+        info.modified_unix = to_unix_secs(meta.modified().ok());
+        info.created_unix = to_unix_secs(meta.created().ok());
+
+        if ft.is_dir() {
+            info.kind = "Directory".to_string();
+            let (subdirs, subfiles, others, err) = count_immediate_children(path);
+            info.subdirs = Some(subdirs);
+            info.subfiles = Some(subfiles);
+            info.others = Some(others);
+            if let Some(e) = err {
+                info.error = Some(e);
+            }
+        } else if ft.is_symlink() {
+            info.kind = "Symlink".to_string();
+            info.size_bytes = Some(meta.len());
+            info.allocated_bytes = Some(crate::core::size::allocated_size(&meta));
+            if let Ok(target) = std::fs::read_link(path) {
+                info.symlink_target = Some(target.display().to_string());
+            }
+            resolve_symlink_chain(path, &mut info);
+            if let Some(resolved) = info.resolved_path.clone() {
+                if info.target_kind.as_deref() == Some("File") {
+                    info.detected_type = self.detect_file_type(&resolved);
+                    if looks_like_image(&resolved, info.detected_type.as_deref(), self.sniff_contents()) {
+                        extract_image_meta(&resolved, &mut info);
+                    } else if self.is_sniffed_text(&info, &resolved) {
+                        extract_text_meta(&resolved, &mut info);
+                        self.highlight_preview(&resolved, &mut info);
+                    } else if self.is_listable_archive(&info) {
+                        extract_archive_meta(&resolved, &mut info);
+                    } else if self.is_probeable_media(&info) {
+                        extract_media_meta(&resolved, &mut info);
+                    }
+                }
+            }
+        } else if ft.is_file() {
+            info.kind = "File".to_string();
+            info.size_bytes = Some(meta.len());
+            info.allocated_bytes = Some(crate::core::size::allocated_size(&meta));
+            info.detected_type = self.detect_file_type(path);
+            if looks_like_image(path, info.detected_type.as_deref(), self.sniff_contents()) {
+                extract_image_meta(path, &mut info);
+            } else if self.is_sniffed_text(&info, path) {
+                extract_text_meta(path, &mut info);
+                self.highlight_preview(path, &mut info);
+            } else if self.is_listable_archive(&info) {
+                extract_archive_meta(path, &mut info);
+            } else if self.is_probeable_media(&info) {
+                extract_media_meta(path, &mut info);
+            }
+        } else {
+            info.kind = "Other".to_string();
+            info.size_bytes = Some(0);
+            info.allocated_bytes = Some(0);
+        }
+
+        info
     }
+}
+
+/// Extract image metadata only if this looks like an image.
+///
+/// Check MIME from tree_magic_mini first; fall back to the image crate's own
+/// format guessing so we catch formats (webp, etc.) that tree_magic_mini's
+/// shared-mime-info DB may not know about.  Without `sniff` only the
+/// extension is consulted.
+fn looks_like_image(path: &Path, detected_type: Option<&str>, sniff: bool) -> bool {
+    let mime_says_image = detected_type.is_some_and(|m| m.starts_with("image/"));
+    if !sniff {
+        return mime_says_image || image::ImageFormat::from_path(path).is_ok();
+    }
+    mime_says_image
+        || image::ImageReader::open(path)
+            .ok()
+            .and_then(|r| r.with_guessed_format().ok())
+            .and_then(|r| r.format())
+            .is_some()
+}
+
+/// MIME type guessed from the file extension alone (slow-filesystem mode).
+fn mime_from_extension(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    if let Some(format) = image::ImageFormat::from_extension(&ext) {
+        return Some(format.to_mime_type());
+    }
+    Some(match ext.as_str() {
+        "txt" | "log" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "rs" => "text/rust",
+        "c" | "h" => "text/x-csrc",
+        "cpp" | "cc" | "hpp" => "text/x-c++src",
+        "py" => "text/x-python",
+        "js" | "mjs" => "text/javascript",
+        "ts" => "text/x-typescript",
+        "sh" | "bash" | "zsh" => "application/x-shellscript",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "toml" => "application/toml",
+        "yaml" | "yml" => "application/yaml",
+        "xml" => "application/xml",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" | "tgz" => "application/gzip",
+        "tar" => "application/x-tar",
+        "xz" => "application/x-xz",
+        "zst" => "application/zstd",
+        "7z" => "application/x-7z-compressed",
+        "mp3" => "audio/mpeg",
+        "flac" => "audio/flac",
+        "wav" => "audio/wav",
+        "ogg" => "audio/ogg",
+        "mp4" | "m4v" => "video/mp4",
+        "mkv" => "video/x-matroska",
+        "webm" => "video/webm",
+        "mov" => "video/quicktime",
+        _ => return None,
+    })
+}
+
+/// Follow a symlink chain hop by hop, recording each hop in
+/// `info.symlink_chain` and describing the final target.
+///
+/// Stops at the first non-symlink, a missing target, a revisited path
+/// (cycle) or after [`MAX_SYMLINK_HOPS`] hops.
+fn resolve_symlink_chain(path: &Path, info: &mut InspectorInfo) {
+    let mut current = path.to_path_buf();
+    let mut seen = HashSet::new();
+    seen.insert(normalize_lexically(path));
+
+    for _ in 0..MAX_SYMLINK_HOPS {
+        let target = match std::fs::read_link(&current) {
+            Ok(t) => t,
+            Err(_) => break,
+        };
+        let next = match current.parent() {
+            Some(parent) if target.is_relative() => parent.join(&target),
+            _ => target,
+        };
+        let shown = normalize_lexically(&next);
+        info.symlink_chain.push(shown.display().to_string());
+        if !seen.insert(shown) {
+            info.symlink_chain.push("… (cycle)".to_string());
+            info.target_kind = Some("Cycle".to_string());
+            return;
+        }
+
+        match std::fs::symlink_metadata(&next) {
+            Err(_) => {
+                info.target_kind = Some("Missing".to_string());
+                return;
+            }
+            Ok(meta) if !meta.file_type().is_symlink() => {
+                let kind = if meta.is_dir() {
+                    "Directory"
+                } else if meta.is_file() {
+                    info.target_size = Some(meta.len());
+                    "File"
+                } else {
+                    "Other"
+                };
+                info.target_kind = Some(kind.to_string());
+                // Canonical path so every link to the same file shares
+                // one preview cache entry.
+                info.resolved_path = std::fs::canonicalize(&next).ok().or(Some(next));
+                return;
+            }
+            Ok(_) => current = next,
+        }
+    }
+
+    info.symlink_chain.push("… (too many hops)".to_string());
+    info.target_kind = Some("Unresolved".to_string());
+}
 
-    info
+/// Resolve `.` and `..` components without touching the filesystem.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for comp in path.components() {
+        match comp {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push(comp);
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
 }
 
 fn count_immediate_children(path: &Path) -> (u64, u64, u64, Option<String>) {
@@ -179,8 +622,10 @@ fn extract_image_meta(path: &Path, info: &mut InspectorInfo) {
         info.image_height = Some(h);
     }
     // For color type we need the decoder, which is still cheap (no full decode).
+    let mut format = None;
     if let Ok(reader) = image::ImageReader::open(path) {
         if let Ok(reader) = reader.with_guessed_format() {
+            format = reader.format();
             if let Ok(decoder) = reader.into_decoder() {
                 let ct = decoder.color_type();
                 let (fmt, ch) = color_type_desc(ct);
@@ -189,6 +634,430 @@ fn extract_image_meta(path: &Path, info: &mut InspectorInfo) {
             }
         }
     }
+    if info.is_image() && matches!(format, Some(image::ImageFormat::Jpeg | image::ImageFormat::Tiff)) {
+        extract_exif_meta(path, info);
+    }
+}
+
+/// Populate the EXIF fields from IFD0 (and its GPS sub-IFD).  Missing or
+/// malformed EXIF is common and not an inspection error.
+fn extract_exif_meta(path: &Path, info: &mut InspectorInfo) {
+    use exif::{In, Tag};
+
+    let exif = match std::fs::File::open(path)
+        .map_err(exif::Error::Io)
+        .and_then(|f| exif::Reader::new().read_from_container(&mut std::io::BufReader::new(f)))
+    {
+        Ok(exif) => exif,
+        Err(e) => {
+            tracing::debug!("no EXIF for {}: {e}", path.display());
+            return;
+        }
+    };
+    let ascii = |tag: Tag| -> Option<String> {
+        match &exif.get_field(tag, In::PRIMARY)?.value {
+            exif::Value::Ascii(parts) => {
+                let text = String::from_utf8_lossy(parts.first()?);
+                let text = text.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+                (!text.is_empty()).then(|| text.to_string())
+            }
+            _ => None,
+        }
+    };
+
+    // Models usually repeat the make ("Canon" / "Canon EOS R5").
+    info.exif_camera = match (ascii(Tag::Make), ascii(Tag::Model)) {
+        (Some(make), Some(model)) if model.starts_with(&make) => Some(model),
+        (Some(make), Some(model)) => Some(format!("{make} {model}")),
+        (make, model) => make.or(model),
+    };
+    info.exif_datetime = ascii(Tag::DateTimeOriginal)
+        .or_else(|| ascii(Tag::DateTime))
+        .map(|raw| match exif::DateTime::from_ascii(raw.as_bytes()) {
+            Ok(dt) => dt.to_string(),
+            Err(_) => raw,
+        });
+    info.exif_orientation = exif
+        .get_field(Tag::Orientation, In::PRIMARY)
+        .and_then(|f| f.value.get_uint(0))
+        .and_then(|v| u16::try_from(v).ok());
+
+    let degrees = |tag: Tag, ref_tag: Tag, negative: &str| -> Option<f64> {
+        let exif::Value::Rational(dms) = &exif.get_field(tag, In::PRIMARY)?.value else {
+            return None;
+        };
+        let [d, m, s] = dms.as_slice() else {
+            return None;
+        };
+        let value = d.to_f64() + m.to_f64() / 60.0 + s.to_f64() / 3600.0;
+        let sign = if ascii(ref_tag).as_deref() == Some(negative) { -1.0 } else { 1.0 };
+        value.is_finite().then_some(sign * value)
+    };
+    if let (Some(lat), Some(lon)) = (
+        degrees(Tag::GPSLatitude, Tag::GPSLatitudeRef, "S"),
+        degrees(Tag::GPSLongitude, Tag::GPSLongitudeRef, "W"),
+    ) {
+        info.exif_gps = Some(format!("{lat:.5}, {lon:.5}"));
+    }
+}
+
+/// How much of a text file is read for its line count and encoding, so
+/// huge logs don't stall the inspector.
+pub const TEXT_SCAN_BYTES: u64 = 64 * 1024;
+
+/// Populate text-specific fields from the first [`TEXT_SCAN_BYTES`]: line
+/// count, BOM and charset.
+fn extract_text_meta(path: &Path, info: &mut InspectorInfo) {
+    use std::io::Read;
+
+    let mut buf = Vec::new();
+    let read = std::fs::File::open(path)
+        .and_then(|f| f.take(TEXT_SCAN_BYTES).read_to_end(&mut buf));
+    if read.is_err() {
+        return;
+    }
+    info.line_count = Some(buf.iter().filter(|&&b| b == b'\n').count() as u64);
+
+    let bom = if buf.starts_with(b"\xEF\xBB\xBF") {
+        Some("UTF-8")
+    } else if buf.starts_with(b"\xFF\xFE") {
+        Some("UTF-16LE")
+    } else if buf.starts_with(b"\xFE\xFF") {
+        Some("UTF-16BE")
+    } else {
+        None
+    };
+    info.has_bom = bom.is_some();
+    let whole_file = (buf.len() as u64) < TEXT_SCAN_BYTES;
+    let guess = || {
+        let mut detector = chardetng::EncodingDetector::new();
+        detector.feed(&buf, whole_file);
+        detector.guess(None, true)
+    };
+    info.encoding = Some(match bom {
+        Some(name) => name.to_string(),
+        None if buf.is_ascii() => "ASCII".to_string(),
+        None => guess().name().to_string(),
+    });
+
+    // Decoding sniffs the BOM itself, so UTF-16 files read as text.
+    let text = if buf.is_ascii() {
+        String::from_utf8_lossy(&buf)
+    } else {
+        guess().decode(&buf).0
+    };
+    if looks_binary(&text) {
+        info.looks_binary = true;
+        return;
+    }
+    let cut_short = (!whole_file && !text.ends_with('\n')).then(|| line_rest_len(path, buf.len() as u64));
+    info.text_preview = Some(preview_lines(&text, cut_short));
+}
+
+/// How far past the scanned bytes a cut line is followed to measure it.
+const LINE_MEASURE_MAX: u64 = 64 * 1024 * 1024;
+
+/// Bytes from `offset` to the end of the line it falls in (or the end of
+/// the file), and whether [`LINE_MEASURE_MAX`] stopped the count first.
+fn line_rest_len(path: &Path, offset: u64) -> (u64, bool) {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return (0, false);
+    };
+    if file.seek(SeekFrom::Start(offset)).is_err() {
+        return (0, false);
+    }
+    let mut chunk = vec![0u8; 64 * 1024];
+    let mut len = 0u64;
+    while len < LINE_MEASURE_MAX {
+        match file.read(&mut chunk) {
+            Ok(0) | Err(_) => return (len, false),
+            Ok(n) => match chunk[..n].iter().position(|&b| b == b'\n') {
+                Some(end) => return (len + end as u64, false),
+                None => len += n as u64,
+            },
+        }
+    }
+    (len, true)
+}
+
+/// Lines kept for the inspector's text preview.
+pub const TEXT_PREVIEW_LINES: usize = 100;
+
+/// Start of the marker appended to a preview line that doesn't end where
+/// the file's line does; see [`truncated_marker`].
+const TRUNCATED_MARKER_START: &str = " ⟨line truncated";
+
+/// Marker for a line whose full length is `len` bytes (`at_least` when
+/// measuring gave up first): `" ⟨line truncated, 1.2 MiB⟩"`.
+pub fn truncated_marker(len: u64, at_least: bool) -> String {
+    let over = if at_least { "over " } else { "" };
+    format!("{TRUNCATED_MARKER_START}, {over}{}⟩", human_size(len))
+}
+
+/// Split a preview line into its text and its truncation marker, if any.
+pub fn split_truncated(line: &str) -> (&str, Option<&str>) {
+    match line.rfind(TRUNCATED_MARKER_START) {
+        Some(at) if line.ends_with('⟩') => (&line[..at], Some(&line[at..])),
+        _ => (line, None),
+    }
+}
+
+/// The first [`TEXT_PREVIEW_LINES`] lines of `text`, made safe to draw.
+/// `cut_short` says `text` stops mid-line at the scan limit, with the
+/// length of the rest of that line from [`line_rest_len`]; the line is
+/// kept but marked with its full length, so a file of a few huge lines
+/// still previews.
+fn preview_lines(text: &str, cut_short: Option<(u64, bool)>) -> Vec<String> {
+    let raw: Vec<&str> = text.lines().take(TEXT_PREVIEW_LINES + 1).collect();
+    let mut lines: Vec<String> = raw.iter().map(|line| preview_line(line)).collect();
+    let within_cap = lines.len() <= TEXT_PREVIEW_LINES;
+    if let (Some((rest, at_least)), Some(raw_last), Some(last)) = (cut_short, raw.last(), lines.last_mut()) {
+        if within_cap {
+            // Replace a marker for the part that was read.
+            let text_len = split_truncated(last).0.len();
+            last.truncate(text_len);
+            last.push_str(&truncated_marker(raw_last.len() as u64 + rest, at_least));
+        }
+    }
+    lines.truncate(TEXT_PREVIEW_LINES);
+    lines
+}
+
+/// Characters kept per preview line; longer lines end in a
+/// [`truncated_marker`] and are never wrapped.
+const PREVIEW_LINE_MAX_CHARS: usize = 512;
+
+/// How much of a file with a non-text MIME type is checked for UTF-8.
+const UTF8_SNIFF_BYTES: u64 = 8 * 1024;
+
+/// Whether the first [`UTF8_SNIFF_BYTES`] of `path` are NUL-free UTF-8.
+/// A multi-byte character cut off at the end still counts.
+fn head_is_utf8(path: &Path) -> bool {
+    use std::io::Read;
+
+    let mut buf = Vec::new();
+    let read = std::fs::File::open(path)
+        .and_then(|f| f.take(UTF8_SNIFF_BYTES).read_to_end(&mut buf));
+    if read.is_err() || buf.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(&buf) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+/// Decoded text that is really binary: it holds NULs, or more than one
+/// character in 32 is a replacement or stray control character.
+fn looks_binary(text: &str) -> bool {
+    let mut total = 0usize;
+    let mut odd = 0usize;
+    for c in text.chars() {
+        if c == '\0' {
+            return true;
+        }
+        total += 1;
+        if c == char::REPLACEMENT_CHARACTER || (c.is_control() && !matches!(c, '\n' | '\r' | '\t' | '\x0c')) {
+            odd += 1;
+        }
+    }
+    odd * 32 > total
+}
+
+/// One preview line made safe to draw: `\r` dropped, tabs expanded to
+/// four-column stops, other control characters shown as `·`, and cut at
+/// [`PREVIEW_LINE_MAX_CHARS`] with a marker.
+fn preview_line(line: &str) -> String {
+    let mut out = String::new();
+    let mut col = 0;
+    let line = line.trim_end_matches('\r');
+    for c in line.chars().take(PREVIEW_LINE_MAX_CHARS) {
+        match c {
+            '\t' => {
+                let pad = 4 - col % 4;
+                out.extend(std::iter::repeat_n(' ', pad));
+                col += pad;
+            }
+            c if c.is_control() => {
+                out.push('·');
+                col += 1;
+            }
+            c => {
+                out.push(c);
+                col += 1;
+            }
+        }
+    }
+    if line.chars().nth(PREVIEW_LINE_MAX_CHARS).is_some() {
+        out.push_str(&truncated_marker(line.len() as u64, false));
+    }
+    out
+}
+
+/// Archive members listed in the inspector; the rest are only counted.
+pub const ARCHIVE_LIST_MAX: usize = 100;
+
+/// Populate the media fields from the container headers (MP3 frames,
+/// FLAC/Ogg/WAV headers, MP4 atoms, Matroska elements).  Nothing is
+/// decoded.  Only audio tracks are understood, so a video's codec and
+/// duration come from its soundtrack.  Unreadable files are logged and
+/// left blank.
+fn extract_media_meta(path: &Path, info: &mut InspectorInfo) {
+    use symphonia::core::codecs::CODEC_TYPE_NULL;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::{MetadataOptions, StandardTagKey};
+    use symphonia::core::probe::Hint;
+
+    let file = match std::fs::File::open(path) {
+        Ok(f) => f,
+        Err(_) => return,
+    };
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+    let source = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut probed = match symphonia::default::get_probe().format(
+        &hint,
+        source,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    ) {
+        Ok(p) => p,
+        Err(e) => {
+            tracing::debug!("can't probe media {}: {e}", path.display());
+            return;
+        }
+    };
+
+    if let Some(track) = probed
+        .format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+    {
+        let params = &track.codec_params;
+        info.media_codec = symphonia::default::get_codecs()
+            .get_codec(params.codec)
+            .map(|c| c.short_name.to_string());
+        let time_base = params
+            .time_base
+            .or_else(|| params.sample_rate.map(|rate| symphonia::core::units::TimeBase::new(1, rate)));
+        if let (Some(tb), Some(frames)) = (time_base, params.n_frames) {
+            let time = tb.calc_time(frames);
+            info.media_duration_secs = Some(time.seconds as f64 + time.frac);
+        }
+    }
+    if let (Some(secs), Some(bytes)) = (info.media_duration_secs, info.target_size.or(info.size_bytes)) {
+        if secs > 0.0 {
+            info.media_bitrate = Some((bytes as f64 * 8.0 / secs) as u64);
+        }
+    }
+
+    // ID3 tags sit in front of the container; the rest live inside it.
+    let mut tags = Vec::new();
+    if let Some(rev) = probed.metadata.get().as_ref().and_then(|m| m.current()) {
+        tags.extend(rev.tags().iter().cloned());
+    }
+    if let Some(rev) = probed.format.metadata().current() {
+        tags.extend(rev.tags().iter().cloned());
+    }
+    for (key, label) in [
+        (StandardTagKey::Artist, "Artist"),
+        (StandardTagKey::TrackTitle, "Title"),
+        (StandardTagKey::Album, "Album"),
+    ] {
+        if let Some(tag) = tags.iter().find(|t| t.std_key == Some(key)) {
+            let value = tag.value.to_string();
+            if !value.trim().is_empty() {
+                info.media_tags.push((label.to_string(), value.trim().to_string()));
+            }
+        }
+    }
+}
+
+/// Populate the archive fields from a ZIP central directory or tar
+/// headers.  Member data is never decompressed.  A damaged archive is
+/// logged and left unlisted rather than reported as an error.
+fn extract_archive_meta(path: &Path, info: &mut InspectorInfo) {
+    let listed = match info.detected_type.as_deref() {
+        Some("application/zip") => list_zip(path),
+        Some("application/x-tar") => list_tar(path),
+        _ => return,
+    };
+    match listed {
+        Ok((names, count, bytes)) => {
+            info.archive_entries = Some(names);
+            info.archive_entry_count = Some(count);
+            info.archive_unpacked_bytes = Some(bytes);
+        }
+        Err(e) => tracing::debug!("can't list archive {}: {e}", path.display()),
+    }
+}
+
+/// `(first names, member count, uncompressed bytes)` of a ZIP file.
+fn list_zip(path: &Path) -> std::io::Result<(Vec<String>, u64, u64)> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    let mut zip = zip::ZipArchive::new(file).map_err(std::io::Error::other)?;
+    let mut names = Vec::new();
+    let mut bytes = 0u64;
+    for i in 0..zip.len() {
+        // Raw access reads the header only, whatever the compression.
+        let member = zip.by_index_raw(i).map_err(std::io::Error::other)?;
+        bytes = bytes.saturating_add(member.size());
+        if names.len() < ARCHIVE_LIST_MAX {
+            names.push(member.name().to_string());
+        }
+    }
+    Ok((names, zip.len() as u64, bytes))
+}
+
+/// `(first names, member count, uncompressed bytes)` of a tar file.
+fn list_tar(path: &Path) -> std::io::Result<(Vec<String>, u64, u64)> {
+    let mut archive = tar::Archive::new(std::fs::File::open(path)?);
+    let mut names = Vec::new();
+    let (mut count, mut bytes) = (0u64, 0u64);
+    // Seeking skips over member data instead of reading it.
+    for member in archive.entries_with_seek()? {
+        let member = member?;
+        count += 1;
+        bytes = bytes.saturating_add(member.size());
+        if names.len() < ARCHIVE_LIST_MAX {
+            names.push(String::from_utf8_lossy(&member.path_bytes()).into_owned());
+        }
+    }
+    Ok((names, count, bytes))
+}
+
+/// Files larger than this are only checksummed on request; hashing them
+/// unasked would hold a thread (and the disk) for too long.
+pub const CHECKSUM_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Lower-case hex SHA-256 of the file at `path`, streamed in chunks.
+/// Setting `cancel` stops it between chunks with an `Interrupted` error.
+pub fn sha256_file(path: &Path, cancel: &AtomicBool) -> std::io::Result<String> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(std::io::ErrorKind::Interrupted.into());
+        }
+        let n = match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&buf[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
 }
 
 fn color_type_desc(ct: image::ColorType) -> (&'static str, u8) {
@@ -207,12 +1076,6 @@ fn color_type_desc(ct: image::ColorType) -> (&'static str, u8) {
     }
 }
 
-fn detect_file_type(path: &Path) -> Option<String> {
-    // Uses shared-mime-info signatures (magic) for robust content-based
-    // detection, not just extension matching.
-    tree_magic_mini::from_filepath(path).map(str::to_string)
-}
-
 #[cfg(unix)]
 fn mode_to_symbolic(mode: u32) -> String {
     let mut s = String::new();
@@ -262,3 +1125,120 @@ fn mode_to_symbolic(mode: u32) -> String {
     s
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered(count: usize) -> String {
+        (1..=count).map(|i| format!("line {i}\n")).collect()
+    }
+
+    #[test]
+    fn huge_single_line_file_is_capped_and_marked_with_its_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("minified.js");
+        let line = "x".repeat(3 * 1024 * 1024);
+        for (contents, marker) in [
+            (line.clone(), " ⟨line truncated, 3.0 MiB⟩"),
+            (format!("{line}{line}\nnext\n"), " ⟨line truncated, 6.0 MiB⟩"),
+        ] {
+            std::fs::write(&path, contents).unwrap();
+            let mut info = InspectorInfo::new(&path);
+            extract_text_meta(&path, &mut info);
+            let lines = info.text_preview.unwrap();
+            assert_eq!(lines, [format!("{}{marker}", "x".repeat(PREVIEW_LINE_MAX_CHARS))]);
+            assert_eq!(split_truncated(&lines[0]), ("x".repeat(PREVIEW_LINE_MAX_CHARS).as_str(), Some(marker)));
+        }
+    }
+
+    #[test]
+    fn line_cut_by_scan_limit_is_kept() {
+        let lines = preview_lines("first\nsecond half of a lo", Some((31, false)));
+        assert_eq!(lines, ["first", "second half of a lo ⟨line truncated, 50.0 B⟩"]);
+    }
+
+    #[test]
+    fn long_line_cut_by_scan_limit_is_marked_once() {
+        let lines = preview_lines(&"y".repeat(2 * PREVIEW_LINE_MAX_CHARS), Some((0, true)));
+        assert_eq!(lines[0].matches(TRUNCATED_MARKER_START).count(), 1);
+        assert!(lines[0].ends_with(" ⟨line truncated, over 1.0 KiB⟩"));
+    }
+
+    #[test]
+    fn file_over_line_cap_keeps_first_lines() {
+        let lines = preview_lines(&numbered(TEXT_PREVIEW_LINES + 50), Some((5, false)));
+        assert_eq!(lines.len(), TEXT_PREVIEW_LINES);
+        assert_eq!(lines[0], "line 1");
+        assert_eq!(lines[TEXT_PREVIEW_LINES - 1], format!("line {TEXT_PREVIEW_LINES}"));
+        assert!(lines.iter().all(|l| split_truncated(l).1.is_none()));
+    }
+
+    #[test]
+    fn exact_caps_are_not_marked() {
+        let lines = preview_lines(&numbered(TEXT_PREVIEW_LINES), None);
+        assert_eq!(lines.len(), TEXT_PREVIEW_LINES);
+        assert!(lines.iter().all(|l| split_truncated(l).1.is_none()));
+
+        let full_width = "z".repeat(PREVIEW_LINE_MAX_CHARS);
+        assert_eq!(preview_line(&full_width), full_width);
+    }
+
+    #[test]
+    fn control_characters_are_made_visible() {
+        assert_eq!(preview_line("a\tb\x07c\r"), "a   b·c");
+    }
+
+    #[test]
+    fn binary_looking_text_is_detected() {
+        assert!(looks_binary("ELF\0\0\0"));
+        let noisy: String = "ab\u{1}\u{2}\u{fffd}".repeat(20);
+        assert!(looks_binary(&noisy));
+        assert!(!looks_binary("fn main() {\n\tprintln!(\"hi\");\r\n}\n"));
+    }
+
+    #[test]
+    fn mime_cache_evicts_the_least_recently_used_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let [a, b, c] = ["a.txt", "b.txt", "c.txt"].map(|name| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, "hello\n").unwrap();
+            path
+        });
+        let stats = Arc::new(MimeCacheStats::default());
+        let mut cache = MimeCache::new(2, stats.clone());
+        let mut detect = |path: &Path| {
+            let meta = std::fs::metadata(path).unwrap();
+            cache.get_or_detect(path, &meta);
+        };
+        let counts = || (stats.hits.load(Ordering::Relaxed), stats.misses.load(Ordering::Relaxed));
+
+        detect(&a);
+        detect(&b);
+        detect(&a); // `b` is now the oldest
+        assert_eq!(counts(), (1, 2));
+        detect(&c);
+        assert_eq!(stats.entries.load(Ordering::Relaxed), 2);
+        detect(&a);
+        assert_eq!(counts(), (2, 3));
+        detect(&b);
+        assert_eq!(counts(), (2, 4));
+    }
+
+    #[test]
+    fn mime_cache_resniffs_a_rewritten_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes");
+        std::fs::write(&path, "hello\n").unwrap();
+        let stats = Arc::new(MimeCacheStats::default());
+        let mut cache = MimeCache::new(4, stats.clone());
+
+        cache.get_or_detect(&path, &std::fs::metadata(&path).unwrap());
+        cache.get_or_detect(&path, &std::fs::metadata(&path).unwrap());
+        std::fs::write(&path, "hello again\n").unwrap();
+        cache.get_or_detect(&path, &std::fs::metadata(&path).unwrap());
+
+        assert_eq!(stats.hits.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.misses.load(Ordering::Relaxed), 2);
+        assert_eq!(stats.entries.load(Ordering::Relaxed), 1);
+    }
+}
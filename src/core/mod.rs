@@ -4,9 +4,16 @@
 //! Every type is `Send + Sync` so it can be shared across async tasks.
 
 pub mod fs;
+pub mod git;
 pub mod grouping;
+pub mod highlight;
 pub mod inspector;
+pub mod previewer;
+pub mod print;
 pub mod search;
 pub mod size;
+pub mod size_cache;
+pub mod thumbnail;
+pub mod trash;
 pub mod tree;
 
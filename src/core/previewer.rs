@@ -0,0 +1,243 @@
+//! External previewer commands (`[previewers]` config table).
+//!
+//! Users map a MIME glob (`application/pdf`, `text/*`) or a file-name glob
+//! (`*.md`) to a shell command template.  The command runs with stdin and
+//! the TTY detached; only its stdout is captured, ANSI-stripped and shown in
+//! the inspector.  No UI types are used here.
+
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Output captured from a previewer, in bytes.  Reaching it stops the
+/// command early — the inspector only shows the first screenful anyway.
+const OUTPUT_CAP_BYTES: usize = 64 * 1024;
+
+/// Lines kept from previewer output.
+pub const MAX_PREVIEW_LINES: usize = 200;
+
+/// Interval between exit / cancellation checks while a previewer runs.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// State of the external preview for the current selection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalPreview {
+    Running,
+    Output(Vec<String>),
+    /// Short diagnostic (non-zero exit, timeout, spawn error).
+    Failed(String),
+}
+
+/// Find the first previewer whose pattern matches `path` / `mime`.
+/// Patterns containing `/` are matched against the MIME type, all others
+/// against the file name.
+pub fn find_previewer<'a>(
+    previewers: &'a [(String, String)],
+    path: &Path,
+    mime: Option<&str>,
+) -> Option<&'a str> {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    previewers
+        .iter()
+        .find(|(pattern, _)| {
+            if pattern.contains('/') {
+                mime.is_some_and(|m| glob_match(pattern, m))
+            } else {
+                glob_match(pattern, name)
+            }
+        })
+        .map(|(_, command)| command.as_str())
+}
+
+/// Minimal glob: `*` matches any run of characters, `?` exactly one.
+/// Matching is case-insensitive.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.to_lowercase().chars().collect();
+    let t: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+/// Substitute `{path}` with the single-quoted path.
+pub fn expand_template(template: &str, path: &Path) -> String {
+    let quoted = format!("'{}'", path.to_string_lossy().replace('\'', r"'\''"));
+    template.replace("{path}", &quoted)
+}
+
+/// Run `command` through the shell and capture its stdout.
+///
+/// The child gets no stdin and no TTY; stderr is captured only for the
+/// failure diagnostic.  It is killed on `timeout` or when `cancel` is set.
+pub fn run_previewer(
+    command: &str,
+    timeout: Duration,
+    cancel: &AtomicBool,
+) -> Result<Vec<String>, String> {
+    let mut cmd = shell_command(command);
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        // Own process group so a whole pipeline can be killed at once.
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    let mut child = cmd.spawn().map_err(|e| format!("cannot start: {e}"))?;
+
+    let stdout = child.stdout.take();
+    let reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(out) = stdout {
+            let _ = out.take(OUTPUT_CAP_BYTES as u64).read_to_end(&mut buf);
+        }
+        buf
+    });
+    let stderr = child.stderr.take();
+    let err_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(err) = stderr {
+            let _ = err.take(4096).read_to_end(&mut buf);
+        }
+        buf
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        if cancel.load(Ordering::Relaxed) {
+            kill_tree(&mut child);
+            return Err("cancelled".to_string());
+        }
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if reader.is_finished() => {
+                // Output cap reached: we have all we will show.
+                kill_tree(&mut child);
+                break None;
+            }
+            Ok(None) if started.elapsed() >= timeout => {
+                kill_tree(&mut child);
+                return Err(format!("timed out after {}s", timeout.as_secs_f32()));
+            }
+            Ok(None) => std::thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                kill_tree(&mut child);
+                return Err(e.to_string());
+            }
+        }
+    };
+
+    let out = reader.join().unwrap_or_default();
+    let err = err_reader.join().unwrap_or_default();
+    if let Some(status) = status {
+        if !status.success() {
+            let first = String::from_utf8_lossy(&err)
+                .lines()
+                .map(str::trim)
+                .find(|l| !l.is_empty())
+                .map(strip_ansi)
+                .unwrap_or_default();
+            let code = status
+                .code()
+                .map_or_else(|| "signal".to_string(), |c| format!("exit {c}"));
+            return Err(if first.is_empty() {
+                code
+            } else {
+                format!("{code}: {first}")
+            });
+        }
+    }
+
+    Ok(String::from_utf8_lossy(&out)
+        .lines()
+        .take(MAX_PREVIEW_LINES)
+        .map(strip_ansi)
+        .collect())
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(not(unix))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+/// Kill the child and, on Unix, everything else in its process group.
+fn kill_tree(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        let _ = Command::new("kill")
+            .arg("-KILL")
+            .arg("--")
+            .arg(format!("-{}", child.id()))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Remove ANSI escape sequences and other control characters; tabs become
+/// spaces so column math stays simple.
+pub fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{1b}' => match chars.peek() {
+                // CSI: ESC [ params final-byte
+                Some('[') => {
+                    chars.next();
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: ESC ] ... BEL or ESC \
+                Some(']') => {
+                    chars.next();
+                    while let Some(c) = chars.next() {
+                        if c == '\u{7}' || (c == '\u{1b}' && chars.next_if_eq(&'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                _ => {
+                    chars.next();
+                }
+            },
+            '\t' => out.push_str("    "),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
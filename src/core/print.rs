@@ -0,0 +1,152 @@
+//! Tree output for the non-interactive `--print` / `--format` mode.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::core::grouping::human_size;
+use crate::core::tree::{DirTree, EntryMeta, NodeId};
+
+/// Output format of the non-interactive mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Indented tree with `├──` connectors.
+    Text,
+    /// Flat JSON array, one object per node.
+    Json,
+    /// Header row, then one row per node.
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// One node of the flat `json` / `csv` export.
+#[derive(Serialize)]
+struct NodeRecord<'a> {
+    path: Cow<'a, str>,
+    name: &'a str,
+    is_dir: bool,
+    depth: usize,
+    size_bytes: Option<u64>,
+}
+
+impl<'a> NodeRecord<'a> {
+    fn new(meta: &'a EntryMeta, depth: usize, size_bytes: Option<u64>) -> Self {
+        Self {
+            path: meta.path.to_string_lossy(),
+            name: &meta.name,
+            is_dir: meta.is_dir,
+            depth,
+            size_bytes,
+        }
+    }
+}
+
+/// Stream every node of `tree` to `out` as a JSON array, one object per
+/// line.  `size_of` runs just before each node is written, so output
+/// starts immediately and nothing is buffered per node.
+pub fn write_json(
+    tree: &DirTree,
+    mut size_of: impl FnMut(&EntryMeta) -> Option<u64>,
+    out: &mut impl io::Write,
+) -> io::Result<()> {
+    out.write_all(b"[")?;
+    for (i, node) in tree.nodes.iter().enumerate() {
+        out.write_all(if i == 0 { b"\n  " } else { b",\n  " })?;
+        let record = NodeRecord::new(&node.meta, node.depth, size_of(&node.meta));
+        serde_json::to_writer(&mut *out, &record)?;
+    }
+    out.write_all(b"\n]\n")
+}
+
+/// Stream every node of `tree` to `out` as CSV (RFC 4180 quoting).  An
+/// unknown size is an empty field.
+pub fn write_csv(
+    tree: &DirTree,
+    mut size_of: impl FnMut(&EntryMeta) -> Option<u64>,
+    out: &mut impl io::Write,
+) -> io::Result<()> {
+    writeln!(out, "path,name,is_dir,depth,size_bytes")?;
+    for node in &tree.nodes {
+        let record = NodeRecord::new(&node.meta, node.depth, size_of(&node.meta));
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            csv_field(&record.path),
+            csv_field(record.name),
+            record.is_dir,
+            record.depth,
+            record.size_bytes.map(|s| s.to_string()).unwrap_or_default(),
+        )?;
+    }
+    Ok(())
+}
+
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Render `tree` as an indented text tree (`├──` / `└──` connectors).
+/// Sizes are appended in parentheses when `sizes` has an entry.
+pub fn render_plain_tree(tree: &DirTree, sizes: Option<&HashMap<PathBuf, u64>>) -> String {
+    let mut out = String::new();
+    let root = tree.get(tree.root);
+    out.push_str(&root.meta.path.display().to_string());
+    push_size(&mut out, sizes, tree, tree.root);
+    out.push('\n');
+    render_children(tree, tree.root, sizes, "", &mut out);
+    out
+}
+
+fn render_children(
+    tree: &DirTree,
+    id: NodeId,
+    sizes: Option<&HashMap<PathBuf, u64>>,
+    prefix: &str,
+    out: &mut String,
+) {
+    let children = &tree.get(id).children;
+    for (i, &child) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let node = tree.get(child);
+        out.push_str(prefix);
+        out.push_str(if last { "└── " } else { "├── " });
+        out.push_str(&node.meta.name);
+        if node.meta.is_dir {
+            out.push('/');
+        }
+        if let Some(target) = &node.meta.symlink_target {
+            let _ = write!(out, " -> {target}");
+        }
+        push_size(out, sizes, tree, child);
+        out.push('\n');
+        if node.meta.is_dir && !node.meta.is_symlink {
+            let next = format!("{prefix}{}", if last { "    " } else { "│   " });
+            render_children(tree, child, sizes, &next, out);
+        }
+    }
+}
+
+fn push_size(out: &mut String, sizes: Option<&HashMap<PathBuf, u64>>, tree: &DirTree, id: NodeId) {
+    let path = &tree.get(id).meta.path;
+    if let Some(size) = sizes.and_then(|s| s.get(path).copied()) {
+        let _ = write!(out, " ({})", human_size(size));
+    }
+}
@@ -1,11 +1,15 @@
 //! Search index + ranking for filename/dirname lookup.
 //!
-//! Matching is name-substring based (with optional case sensitivity).
+//! Matching is against entry names, either by substring or by fuzzy
+//! subsequence scoring (see [`SearchMode`]), with optional case sensitivity.
 
 use std::cmp::Ordering;
 use std::path::{Path, PathBuf};
 
 use ignore::WalkBuilder;
+use regex::RegexBuilder;
+
+use super::tree::DirTree;
 
 #[derive(Debug, Clone)]
 pub struct SearchEntry {
@@ -21,12 +25,73 @@ pub struct SearchResult {
     pub path: PathBuf,
     pub name: String,
     pub is_dir: bool,
+    /// Char indices into `name` that matched the query (for highlighting).
+    pub match_indices: Vec<usize>,
+}
+
+/// How the query is matched against entry names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchMode {
+    #[default]
+    Substring,
+    /// Ordered subsequence with fzf-style scoring (`mrcfg` → `.markdownlintrc`).
+    Fuzzy,
+    /// Regular expression matched anywhere in the name.
+    Regex,
+}
+
+impl SearchMode {
+    pub const ALL: &[SearchMode] = &[SearchMode::Substring, SearchMode::Fuzzy, SearchMode::Regex];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Substring => "substring",
+            Self::Fuzzy => "fuzzy",
+            Self::Regex => "regex",
+        }
+    }
+
+    pub fn config_value(self) -> &'static str {
+        self.label()
+    }
+
+    pub fn from_config_value(value: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|m| m.config_value() == value)
+    }
+
+    /// Next mode in `ALL`, wrapping around.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Persisted search defaults (the `[search]` config section).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchOptions {
+    pub case_sensitive: bool,
+    /// Index dotfiles even when the tree hides them.
+    pub include_hidden: bool,
+    /// Leave out entries matched by `.gitignore` and friends.
+    pub respect_gitignore: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            case_sensitive: false,
+            include_hidden: false,
+            respect_gitignore: true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct RankKey {
     exact: bool,
     prefix: bool,
+    /// Fuzzy alignment score (higher is better); 0 for substring hits.
+    score: i32,
     match_pos: usize,
     name_len: usize,
     rel_depth: usize,
@@ -40,6 +105,7 @@ impl RankKey {
             .exact
             .cmp(&self.exact)
             .then_with(|| other.prefix.cmp(&self.prefix))
+            .then_with(|| other.score.cmp(&self.score))
             .then_with(|| self.match_pos.cmp(&other.match_pos))
             .then_with(|| self.name_len.cmp(&other.name_len))
             .then_with(|| self.rel_depth.cmp(&other.rel_depth))
@@ -103,61 +169,310 @@ pub fn build_index(
     out
 }
 
-/// Search pre-indexed entries using name substring matching.
+/// Index only the entries already loaded into `tree` under `root`.  Used on
+/// slow filesystems instead of [`build_index`], which walks everything.
+pub fn index_from_tree(tree: &DirTree, root: &Path) -> Vec<SearchEntry> {
+    tree.nodes
+        .iter()
+        .filter_map(|node| {
+            let rel = node.meta.path.strip_prefix(root).ok()?;
+            Some(SearchEntry {
+                path: node.meta.path.clone(),
+                name: node.meta.name.clone(),
+                name_lower: node.meta.name.to_lowercase(),
+                is_dir: node.meta.is_dir,
+                rel_depth: rel.components().count(),
+            })
+        })
+        .collect()
+}
+
+/// Search pre-indexed entries by name using `mode`.
+///
+/// Fails only in [`SearchMode::Regex`], with the compile error of an
+/// invalid pattern.
+///
+/// Results are ranked: exact name, then prefix match, then fuzzy score,
+/// earlier match position, shorter name, shallower entry, directories
+/// before files.  Remaining ties go by path, so the same index and query
+/// always give the same order.
+///
+/// ```
+/// use std::path::PathBuf;
+/// use dir_tree::core::search::{search_entries, SearchEntry, SearchMode};
+///
+/// let entry = |path: &str, is_dir: bool| {
+///     let path = PathBuf::from(path);
+///     let name = path.file_name().unwrap().to_string_lossy().into_owned();
+///     SearchEntry {
+///         name_lower: name.to_lowercase(),
+///         rel_depth: path.components().count(),
+///         path,
+///         name,
+///         is_dir,
+///     }
+/// };
+/// let index = [
+///     entry("docs/old-config.md", false),
+///     entry("config", true),
+///     entry("src/config.rs", false),
+///     entry("b/config", false),
+/// ];
+///
+/// let results = search_entries(&index, "config", false, SearchMode::Substring, 10).unwrap();
+/// let paths: Vec<_> = results.iter().map(|r| r.path.to_str().unwrap()).collect();
+/// // Exact names first (the shallower one ahead), then the prefix match,
+/// // then the mid-name match.
+/// assert_eq!(paths, ["config", "b/config", "src/config.rs", "docs/old-config.md"]);
+/// assert_eq!(results[3].match_indices, (4..10).collect::<Vec<_>>());
+/// ```
 pub fn search_entries(
     entries: &[SearchEntry],
     query: &str,
     case_sensitive: bool,
+    mode: SearchMode,
     limit: usize,
-) -> Vec<SearchResult> {
+) -> Result<Vec<SearchResult>, String> {
     let q = query.trim();
     if q.is_empty() || limit == 0 {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
+    // Compiled once per query, not per entry.
+    let regex = if mode == SearchMode::Regex {
+        let re = RegexBuilder::new(q)
+            .case_insensitive(!case_sensitive)
+            .build()
+            .map_err(|e| regex_error_summary(&e))?;
+        Some(re)
+    } else {
+        None
+    };
+
     let q_lower = if case_sensitive {
         String::new()
     } else {
         q.to_lowercase()
     };
+    let needle_chars: Vec<char> = if case_sensitive { q } else { q_lower.as_str() }
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
 
-    let mut ranked: Vec<(RankKey, &SearchEntry)> = Vec::new();
+    let mut ranked: Vec<(RankKey, &SearchEntry, Vec<usize>)> = Vec::new();
     for entry in entries {
         let (haystack, needle) = if case_sensitive {
             (entry.name.as_str(), q)
         } else {
             (entry.name_lower.as_str(), q_lower.as_str())
         };
-        let Some(pos) = haystack.find(needle) else {
-            continue;
+        let (score, pos, indices) = match mode {
+            SearchMode::Substring => {
+                let Some(pos) = haystack.find(needle) else {
+                    continue;
+                };
+                let start = haystack[..pos].chars().count();
+                (0, pos, (start..start + needle.chars().count()).collect())
+            }
+            SearchMode::Fuzzy => {
+                let Some((score, indices)) = fuzzy_match(&entry.name, haystack, &needle_chars)
+                else {
+                    continue;
+                };
+                (score, 0, indices)
+            }
+            SearchMode::Regex => {
+                let Some(re) = regex.as_ref() else {
+                    continue;
+                };
+                let Some(caps) = re.captures(&entry.name) else {
+                    continue;
+                };
+                let whole = caps.get(0).map_or(0..0, |m| m.range());
+                // Highlight capture groups when the pattern has any,
+                // otherwise the whole match.
+                let mut ranges: Vec<_> = caps.iter().skip(1).flatten().map(|m| m.range()).collect();
+                if ranges.is_empty() {
+                    ranges.push(whole.clone());
+                }
+                let indices = byte_ranges_to_char_indices(&entry.name, &ranges);
+                let exact = whole.start == 0 && whole.end == entry.name.len();
+                ranked.push((
+                    RankKey {
+                        exact,
+                        prefix: whole.start == 0 && !whole.is_empty(),
+                        score: 0,
+                        match_pos: whole.start,
+                        name_len: entry.name.chars().count(),
+                        rel_depth: entry.rel_depth,
+                        is_dir: entry.is_dir,
+                    },
+                    entry,
+                    indices,
+                ));
+                continue;
+            }
         };
         ranked.push((
             RankKey {
                 exact: haystack == needle,
                 prefix: haystack.starts_with(needle),
+                score,
                 match_pos: pos,
                 name_len: entry.name.chars().count(),
                 rel_depth: entry.rel_depth,
                 is_dir: entry.is_dir,
             },
             entry,
+            indices,
         ));
     }
 
-    ranked.sort_by(|(a_rank, a_entry), (b_rank, b_entry)| {
+    ranked.sort_by(|(a_rank, a_entry, _), (b_rank, b_entry, _)| {
         a_rank
             .cmp_better(*b_rank)
             .then_with(|| a_entry.path.cmp(&b_entry.path))
     });
     ranked.truncate(limit);
 
-    ranked
+    Ok(ranked
         .into_iter()
-        .map(|(_, e)| SearchResult {
+        .map(|(_, e, match_indices)| SearchResult {
             path: e.path.clone(),
             name: e.name.clone(),
             is_dir: e.is_dir,
+            match_indices,
         })
+        .collect())
+}
+
+/// Char indices of `name` covered by any of the byte `ranges`.
+fn byte_ranges_to_char_indices(name: &str, ranges: &[std::ops::Range<usize>]) -> Vec<usize> {
+    name.char_indices()
+        .enumerate()
+        .filter(|(_, (byte, _))| ranges.iter().any(|r| r.contains(byte)))
+        .map(|(i, _)| i)
         .collect()
 }
 
+/// One-line description of a regex compile error (the full message is a
+/// multi-line diagram).
+fn regex_error_summary(err: &regex::Error) -> String {
+    match err {
+        regex::Error::Syntax(msg) => msg
+            .lines()
+            .rev()
+            .find_map(|l| l.strip_prefix("error:"))
+            .map_or_else(|| "invalid pattern".to_string(), |l| l.trim().to_string()),
+        regex::Error::CompiledTooBig(_) => "pattern too large".to_string(),
+        _ => "invalid pattern".to_string(),
+    }
+}
+
+// ───────────────────────────────────────── fuzzy scoring ─────
+
+const SCORE_MATCH: i32 = 16;
+const GAP_START: i32 = 3;
+const GAP_EXTENSION: i32 = 1;
+/// Extra for a match immediately following the previous one.
+const BONUS_CONSECUTIVE: i32 = 4;
+/// Match at the start of the name or right after `.`, `_`, `-`, space.
+const BONUS_BOUNDARY: i32 = 8;
+/// Upper-case letter following a lower-case one (`fooBar`).
+const BONUS_CAMEL: i32 = 7;
+
+/// Score `needle` as an ordered subsequence of `haystack` (already
+/// case-folded as needed; `name` is the original for boundary bonuses).
+/// Returns the best alignment's score and matched char indices, or `None`
+/// when `needle` isn't a subsequence.
+///
+/// This follows fzf's v2 idea: a Smith-Waterman-style pass that rewards
+/// word boundaries and consecutive runs and penalises gaps, then a
+/// backtrack to recover the positions.
+fn fuzzy_match(name: &str, haystack: &str, needle: &[char]) -> Option<(i32, Vec<usize>)> {
+    let hay: Vec<char> = haystack.chars().collect();
+    let (n, m) = (hay.len(), needle.len());
+    if m == 0 || m > n {
+        return None;
+    }
+    // Cheap reject before the O(n·m) pass.
+    let mut it = hay.iter();
+    if !needle.iter().all(|c| it.any(|h| h == c)) {
+        return None;
+    }
+
+    let orig: Vec<char> = name.chars().collect();
+    let bonus: Vec<i32> = (0..n)
+        .map(|j| {
+            let cur = orig.get(j).copied().unwrap_or(' ');
+            match j.checked_sub(1).and_then(|p| orig.get(p)) {
+                None => BONUS_BOUNDARY,
+                Some('.' | '_' | '-' | ' ' | '/') => BONUS_BOUNDARY,
+                Some(prev) if prev.is_lowercase() && cur.is_uppercase() => BONUS_CAMEL,
+                _ => 0,
+            }
+        })
+        .collect();
+
+    const NONE: i32 = i32::MIN / 2;
+    // h[i * n + j]: best score with needle[i] matched at hay[j].
+    let mut h = vec![NONE; m * n];
+    for i in 0..m {
+        let mut gap_best = NONE;
+        for j in 0..n {
+            if i > 0 && j >= 2 {
+                let prev = h[(i - 1) * n + j - 2];
+                gap_best = (gap_best - GAP_EXTENSION).max(prev - GAP_START);
+            }
+            if hay[j] != needle[i] {
+                continue;
+            }
+            let base = SCORE_MATCH + bonus[j];
+            h[i * n + j] = if i == 0 {
+                base
+            } else {
+                let consecutive = if j >= 1 {
+                    h[(i - 1) * n + j - 1] + BONUS_CONSECUTIVE
+                } else {
+                    NONE
+                };
+                let prev = consecutive.max(gap_best);
+                if prev <= NONE / 2 {
+                    NONE
+                } else {
+                    prev + base
+                }
+            };
+        }
+    }
+
+    let last = (m - 1) * n;
+    let (mut j, score) = (0..n)
+        .map(|j| (j, h[last + j]))
+        .max_by_key(|&(j, s)| (s, std::cmp::Reverse(j)))?;
+    if score <= NONE / 2 {
+        return None;
+    }
+
+    // Backtrack: for each earlier needle char pick the predecessor that
+    // produced the current cell's score.
+    let mut indices = vec![j];
+    for i in (0..m - 1).rev() {
+        let best = (0..j)
+            .filter(|&k| h[i * n + k] > NONE / 2)
+            .max_by_key(|&k| {
+                let s = h[i * n + k];
+                let gap = (j - k - 1) as i32;
+                if gap == 0 {
+                    s + BONUS_CONSECUTIVE
+                } else {
+                    s - GAP_START - (gap - 1) * GAP_EXTENSION
+                }
+            })?;
+        indices.push(best);
+        j = best;
+    }
+    indices.reverse();
+    Some((score, indices))
+}
+
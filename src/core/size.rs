@@ -5,22 +5,164 @@
 //! for inode classification and device checks.  The orchestration (spawning
 //! workers, cascade finalization) lives in `main.rs`.
 
-use std::collections::HashMap;
-use std::path::Path;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::SystemTime;
 
-/// Map of hard-linked inodes: (dev, ino) → apparent size.
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::core::tree::{DirTree, EntryMeta, NodeId};
+
+/// Map of hard-linked inodes: (dev, ino) → size.
 /// Only files with nlink > 1 land here; nlink == 1 files are summed directly.
 pub type InodeMap = HashMap<(u64, u64), u64>;
 
+/// Entries found below a directory by kind, counted recursively.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryCounts {
+    /// Regular files, hard links included once per link.
+    pub files: u64,
+    pub symlinks: u64,
+    /// Sockets, FIFOs and device nodes.
+    pub specials: u64,
+    /// The part of `specials` that are device nodes or sockets.
+    pub devices: u64,
+}
+
+impl EntryCounts {
+    /// Count one entry that is neither a regular file, a directory nor a
+    /// symlink.
+    pub fn add_special(&mut self, ft: &std::fs::FileType) {
+        self.specials += 1;
+        if is_device_or_socket(ft) {
+            self.devices += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other: EntryCounts) {
+        self.files += other.files;
+        self.symlinks += other.symlinks;
+        self.specials += other.specials;
+        self.devices += other.devices;
+    }
+}
+
 /// Cached result from a directory's local walk.
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct DirLocalResult {
     /// Sum of apparent sizes for files with nlink == 1 (safely additive).
     pub unique_sum: u64,
     /// Hard-linked files: (dev, ino) → size.  Deduped within this subtree,
     /// but may overlap with sibling directories — the cascade merges these.
     pub hardlinks: InodeMap,
+    /// Sum over every hard-link occurrence (no dedup), for the apparent
+    /// total shown next to the deduped one.
+    pub hardlink_apparent: u64,
+    /// [`Self::unique_sum`] counted in allocated (on-disk) bytes.
+    pub allocated_unique_sum: u64,
+    /// [`Self::hardlinks`] counted in allocated (on-disk) bytes.
+    pub allocated_hardlinks: InodeMap,
+    /// Files, symlinks and special files walked.
+    pub counts: EntryCounts,
+    /// A subdirectory was skipped by an exclude pattern.
+    pub excluded: bool,
+    /// The directory's mtime when the walk started; the on-disk cache
+    /// drops the entry once it moves.
+    pub mtime: Option<SystemTime>,
+    /// Names of the hidden (non-tree) subdirectories walked into this
+    /// result.
+    pub folded: Vec<String>,
+    /// Latest mtime among the folded subdirectories and every directory
+    /// below them (see [`folded_mtime`]); the on-disk cache drops the entry
+    /// once it moves too.
+    pub folded_mtime: Option<SystemTime>,
+}
+
+impl DirLocalResult {
+    /// Count one regular file or symlink.  `inode_key` comes from
+    /// [`classify_file`]; hard-linked inodes are only counted once.
+    pub fn add_file(&mut self, apparent: u64, allocated: u64, inode_key: Option<(u64, u64)>) {
+        match inode_key {
+            None => {
+                self.unique_sum = self.unique_sum.saturating_add(apparent);
+                self.allocated_unique_sum = self.allocated_unique_sum.saturating_add(allocated);
+            }
+            Some(key) => {
+                self.hardlink_apparent = self.hardlink_apparent.saturating_add(apparent);
+                self.hardlinks.entry(key).or_insert(apparent);
+                self.allocated_hardlinks.entry(key).or_insert(allocated);
+            }
+        }
+    }
+
+    /// Undo [`Self::add_file`] for a file counted as unique, dropping it
+    /// from [`EntryCounts::files`] too.  Refuses
+    /// (returning `false`) while any hard links are recorded: the file may
+    /// have been one of them, and dedup can't be unwound locally.
+    pub fn remove_unique_file(&mut self, apparent: u64, allocated: u64) -> bool {
+        if !self.hardlinks.is_empty() || self.unique_sum < apparent {
+            return false;
+        }
+        self.unique_sum -= apparent;
+        self.allocated_unique_sum = self.allocated_unique_sum.saturating_sub(allocated);
+        self.counts.files = self.counts.files.saturating_sub(1);
+        true
+    }
+
+    /// Fold in the result of a nested walk (its mtime is ignored).
+    pub fn absorb(&mut self, sub: DirLocalResult) {
+        self.unique_sum = self.unique_sum.saturating_add(sub.unique_sum);
+        self.hardlink_apparent = self.hardlink_apparent.saturating_add(sub.hardlink_apparent);
+        self.allocated_unique_sum = self.allocated_unique_sum.saturating_add(sub.allocated_unique_sum);
+        for (k, v) in sub.hardlinks {
+            self.hardlinks.entry(k).or_insert(v);
+        }
+        for (k, v) in sub.allocated_hardlinks {
+            self.allocated_hardlinks.entry(k).or_insert(v);
+        }
+        self.counts.merge(sub.counts);
+        self.excluded |= sub.excluded;
+        self.folded_mtime = self.folded_mtime.max(sub.folded_mtime);
+    }
+}
+
+/// Union of two hard-link maps.  The larger map is used as the base to
+/// minimise insertions.
+pub fn merge_inode_maps(a: InodeMap, b: InodeMap) -> InodeMap {
+    let (mut base, other) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    for (k, v) in other {
+        base.entry(k).or_insert(v);
+    }
+    base
+}
+
+/// Running totals shared by size workers and read for progress reports.
+/// Updated once per directory read, not per file.
+#[derive(Default)]
+pub struct ScanCounters {
+    pub files: AtomicU64,
+    pub bytes: AtomicU64,
+}
+
+impl ScanCounters {
+    pub fn add(&self, files: u64, bytes: u64) {
+        if files > 0 {
+            self.files.fetch_add(files, Ordering::Relaxed);
+            self.bytes.fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Smallest dedup saving worth showing next to a directory total.
+const SAVINGS_MIN_BYTES: u64 = 1024 * 1024;
+
+/// Bytes saved by hard-link dedup when large enough to be worth showing:
+/// at least 1 MiB and at least 1% of the deduped total.
+pub fn hardlink_savings(total: u64, apparent: u64) -> Option<u64> {
+    let saved = apparent.saturating_sub(total);
+    (saved >= SAVINGS_MIN_BYTES && saved >= total / 100).then_some(saved)
 }
 
 // ───────────────────────────────────────── platform helpers ──
@@ -48,6 +190,47 @@ pub fn classify_file(meta: &std::fs::Metadata, _dedup: bool) -> (u64, Option<(u6
     (meta.len(), None)
 }
 
+/// Bytes actually allocated on disk for an entry: `st_blocks × 512` on
+/// Unix (smaller than the length for sparse or compressed files, larger
+/// for small files rounded up to a block).  Falls back to the apparent
+/// length elsewhere.
+#[cfg(unix)]
+pub fn allocated_size(meta: &std::fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    meta.blocks().saturating_mul(512)
+}
+
+#[cfg(not(unix))]
+pub fn allocated_size(meta: &std::fs::Metadata) -> u64 {
+    meta.len()
+}
+
+/// Whether a special file is a block/character device or a socket (as
+/// opposed to a FIFO).
+#[cfg(unix)]
+pub fn is_device_or_socket(ft: &std::fs::FileType) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    ft.is_block_device() || ft.is_char_device() || ft.is_socket()
+}
+
+#[cfg(not(unix))]
+pub fn is_device_or_socket(_ft: &std::fs::FileType) -> bool {
+    false
+}
+
+/// Whether two entries are the same file (same device and inode), e.g. a
+/// name and its case variant on a case-insensitive filesystem.
+#[cfg(unix)]
+pub fn is_same_file(a: &std::fs::Metadata, b: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+#[cfg(not(unix))]
+pub fn is_same_file(_a: &std::fs::Metadata, _b: &std::fs::Metadata) -> bool {
+    false
+}
+
 /// Check whether a path resides on the same device as the root.
 #[cfg(unix)]
 pub fn is_same_device(meta: &std::fs::Metadata, root_dev: u64) -> bool {
@@ -72,21 +255,183 @@ pub fn get_dev(_path: &Path) -> u64 {
     0
 }
 
+// ───────────────────────────────────────── exclusions ────────
+
+/// Compile the `exclude_patterns` globs into one matcher, once per scan.
+/// A trailing `/` is ignored (only directories are matched) and invalid
+/// patterns are skipped; [`exclude_pattern_error`] validates user input.
+pub fn compile_excludes(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        if let Ok(glob) = Glob::new(pattern.trim_end_matches('/')) {
+            builder.add(glob);
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
+
+/// Why `pattern` can't be used as an exclude glob, if it can't.
+pub fn exclude_pattern_error(pattern: &str) -> Option<String> {
+    Glob::new(pattern.trim_end_matches('/')).err().map(|e| e.kind().to_string())
+}
+
+/// Whether the walk skips directory `dir`: a pattern matches its name
+/// (`node_modules`) or its full path (`/home/*/scratch`).
+pub fn is_excluded(excludes: &GlobSet, dir: &Path) -> bool {
+    !excludes.is_empty()
+        && (dir.file_name().is_some_and(|name| excludes.is_match(name)) || excludes.is_match(dir))
+}
+
 // ───────────────────────────────────────── recursive walk ────
 
-/// Recursively compute the total apparent size of all files under `dir`.
+/// Size of every node in `tree`, computed on the calling thread (used by
+/// `--print`, where there is no UI to stream into).  Every file is read
+/// once: each tree directory walks its own files and the subdirectories
+/// not in the tree, then totals are summed bottom-up with hard links
+/// deduped per subtree exactly like the interactive cascade.  With
+/// `allocated`, sizes are on-disk bytes instead of lengths.
+pub fn tree_sizes_blocking(
+    tree: &DirTree,
+    dedup: bool,
+    one_file_system: bool,
+    allocated: bool,
+    excludes: &GlobSet,
+) -> HashMap<PathBuf, u64> {
+    tree_sizes_counted(tree, dedup, one_file_system, allocated, excludes, None)
+}
+
+fn tree_sizes_counted(
+    tree: &DirTree,
+    dedup: bool,
+    one_file_system: bool,
+    allocated: bool,
+    excludes: &GlobSet,
+    counters: Option<&ScanCounters>,
+) -> HashMap<PathBuf, u64> {
+    let root_dev = get_dev(&tree.get(tree.root).meta.path);
+    let cancel = AtomicBool::new(false);
+    let is_tree_dir = |meta: &EntryMeta| meta.is_dir && !meta.is_symlink;
+    let tree_dirs: HashSet<&Path> = tree
+        .nodes
+        .iter()
+        .filter(|n| is_tree_dir(&n.meta))
+        .map(|n| n.meta.path.as_path())
+        .collect();
+    // Breadth-first, so every parent comes before its children.
+    let mut order = vec![tree.root];
+    let mut next = 0;
+    while let Some(&id) = order.get(next) {
+        order.extend(tree.get(id).children.iter().copied());
+        next += 1;
+    }
+
+    let mut sizes = HashMap::new();
+    let mut locals: HashMap<NodeId, DirLocalResult> = HashMap::new();
+    for &id in order.iter().rev() {
+        let node = tree.get(id);
+        let path = &node.meta.path;
+        if !is_tree_dir(&node.meta) {
+            if let Ok(meta) = std::fs::symlink_metadata(path) {
+                sizes.insert(path.clone(), if allocated { allocated_size(&meta) } else { meta.len() });
+            }
+            continue;
+        }
+        let skip = |sub: &Path| tree_dirs.contains(sub);
+        let mut local = walk_dir_size(path, &cancel, dedup, one_file_system, root_dev, excludes, counters, skip);
+        for child in &node.children {
+            let Some(sub) = locals.remove(child) else {
+                continue;
+            };
+            // An excluded tree dir keeps its own total but adds nothing
+            // to its parent's.
+            if is_excluded(excludes, &tree.get(*child).meta.path) {
+                local.excluded = true;
+            } else {
+                local.absorb(sub);
+            }
+        }
+        let total = if allocated {
+            local.allocated_unique_sum.saturating_add(local.allocated_hardlinks.values().sum::<u64>())
+        } else {
+            local.unique_sum.saturating_add(local.hardlinks.values().sum::<u64>())
+        };
+        sizes.insert(path.clone(), total);
+        locals.insert(id, local);
+    }
+    sizes
+}
+
+/// Recursively compute the total apparent and allocated size of all files
+/// under `dir`.
+///
+/// The result is split by nlink so the cascade can merge hardlink maps
+/// bottom-up for per-subtree dedup.  Files are also tallied into
+/// `counters` as each directory is read.  Subdirectories matching
+/// `excludes` are skipped and flag the result as
+/// [`DirLocalResult::excluded`].
+///
+/// With `dedup`, a hard-linked inode contributes its size once to
+/// [`DirLocalResult::hardlinks`] however many of its links are walked,
+/// while [`DirLocalResult::hardlink_apparent`] counts every link.  Without
+/// it, every file is counted in [`DirLocalResult::unique_sum`].
+///
+/// ```
+/// use std::sync::atomic::AtomicBool;
+/// use dir_tree::core::size::{compile_excludes, recursive_dir_size};
+///
+/// let dir = std::env::temp_dir().join(format!("dir-tree-doc-{}", std::process::id()));
+/// std::fs::create_dir_all(dir.join("sub"))?;
+/// std::fs::write(dir.join("a.txt"), b"abc")?;
+/// std::fs::write(dir.join("sub/b.txt"), b"hello")?;
+/// std::fs::hard_link(dir.join("a.txt"), dir.join("sub/a-link.txt"))?;
+///
+/// let no_excludes = compile_excludes(&[]);
+/// let walk = |dedup| {
+///     recursive_dir_size(&dir, &AtomicBool::new(false), dedup, false, 0, &no_excludes, None)
+/// };
 ///
-/// Returns `(unique_sum, hardlinks)` — split by nlink so the cascade can
-/// merge hardlink maps bottom-up for per-subtree dedup.
+/// let every_link = walk(false);
+/// assert_eq!(every_link.unique_sum, 3 + 5 + 3);
+///
+/// let deduped = walk(true);
+/// if cfg!(unix) {
+///     let linked: u64 = deduped.hardlinks.values().sum();
+///     assert_eq!(deduped.unique_sum + linked, 3 + 5);
+///     assert_eq!(deduped.unique_sum + deduped.hardlink_apparent, 3 + 5 + 3);
+/// }
+///
+/// std::fs::remove_dir_all(&dir)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
 pub fn recursive_dir_size(
     dir: &Path,
     cancel: &AtomicBool,
     dedup: bool,
     one_file_system: bool,
     root_dev: u64,
-) -> (u64, InodeMap) {
-    let mut unique_sum: u64 = 0;
-    let mut hardlinks = InodeMap::new();
+    excludes: &GlobSet,
+    counters: Option<&ScanCounters>,
+) -> DirLocalResult {
+    walk_dir_size(dir, cancel, dedup, one_file_system, root_dev, excludes, counters, |_| false)
+}
+
+/// [`recursive_dir_size`], not descending into subdirectories `skip`
+/// picks (they are sized on their own).
+#[allow(clippy::too_many_arguments)]
+fn walk_dir_size(
+    dir: &Path,
+    cancel: &AtomicBool,
+    dedup: bool,
+    one_file_system: bool,
+    root_dev: u64,
+    excludes: &GlobSet,
+    counters: Option<&ScanCounters>,
+    skip: impl Fn(&Path) -> bool,
+) -> DirLocalResult {
+    let mut result = DirLocalResult {
+        mtime: dir_mtime(dir),
+        ..DirLocalResult::default()
+    };
     let mut stack = vec![dir.to_path_buf()];
 
     while let Some(current) = stack.pop() {
@@ -97,13 +442,22 @@ pub fn recursive_dir_size(
             Ok(e) => e,
             Err(_) => continue,
         };
+        let (mut files, mut bytes) = (0, 0);
         for entry in entries.flatten() {
+            // Huge directories would otherwise hold up a cancel.
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
             let ft = match entry.file_type() {
                 Ok(ft) => ft,
                 Err(_) => continue,
             };
             if ft.is_dir() {
-                if one_file_system {
+                if skip(&entry.path()) {
+                    // Sized on its own.
+                } else if is_excluded(excludes, &entry.path()) {
+                    result.excluded = true;
+                } else if one_file_system {
                     if let Ok(meta) = std::fs::metadata(&entry.path()) {
                         if is_same_device(&meta, root_dev) {
                             stack.push(entry.path());
@@ -115,21 +469,138 @@ pub fn recursive_dir_size(
             } else if ft.is_file() {
                 if let Ok(meta) = entry.metadata() {
                     let (size, inode_key) = classify_file(&meta, dedup);
-                    match inode_key {
-                        None => unique_sum = unique_sum.saturating_add(size),
-                        Some(key) => {
-                            hardlinks.entry(key).or_insert(size);
-                        }
-                    }
+                    result.add_file(size, allocated_size(&meta), inode_key);
+                    result.counts.files += 1;
+                    files += 1;
+                    bytes += meta.len();
                 }
             } else if ft.is_symlink() {
+                result.counts.symlinks += 1;
                 if let Ok(meta) = std::fs::symlink_metadata(&entry.path()) {
-                    unique_sum = unique_sum.saturating_add(meta.len());
+                    result.add_file(meta.len(), allocated_size(&meta), None);
                 }
+            } else {
+                result.counts.add_special(&ft);
             }
         }
+        if let Some(counters) = counters {
+            counters.add(files, bytes);
+        }
     }
 
-    (unique_sum, hardlinks)
+    debug_assert!(dedup || result.hardlinks.is_empty(), "hard links tracked without dedup");
+    debug_assert!(
+        result.hardlinks.values().sum::<u64>() <= result.hardlink_apparent,
+        "deduped hard links exceed their apparent total",
+    );
+    result
+}
+
+/// Modification time of a directory (follows symlinks).
+pub fn dir_mtime(dir: &Path) -> Option<SystemTime> {
+    std::fs::metadata(dir).and_then(|m| m.modified()).ok()
 }
 
+/// Latest mtime of `dirs` and of every directory below them that
+/// `descend` lets the size walk into.  Only directories are stat'ed, so
+/// this is much cheaper than re-walking the files.
+pub fn folded_mtime(mut dirs: Vec<PathBuf>, descend: impl Fn(&Path) -> bool) -> Option<SystemTime> {
+    let mut latest = None;
+    while let Some(dir) = dirs.pop() {
+        latest = latest.max(dir_mtime(&dir));
+        for entry in std::fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if entry.file_type().is_ok_and(|ft| ft.is_dir()) && descend(&path) {
+                dirs.push(path);
+            }
+        }
+    }
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn savings_need_a_mebibyte_and_one_percent() {
+        const MIB: u64 = 1024 * 1024;
+        assert_eq!(hardlink_savings(10 * MIB, 11 * MIB), Some(MIB));
+        assert_eq!(hardlink_savings(10 * MIB, 11 * MIB - 1), None);
+        // 1 MiB saved on 200 MiB is under 1%.
+        assert_eq!(hardlink_savings(200 * MIB, 201 * MIB), None);
+        assert_eq!(hardlink_savings(100 * MIB, 101 * MIB), Some(MIB));
+        // Apparent can't be below the deduped total; don't underflow.
+        assert_eq!(hardlink_savings(5 * MIB, MIB), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hard_links_count_once_deduped_and_every_time_apparent() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir(root.join("sub")).unwrap();
+        std::fs::write(root.join("plain"), [0u8; 10]).unwrap();
+        std::fs::write(root.join("linked"), [0u8; 100]).unwrap();
+        std::fs::hard_link(root.join("linked"), root.join("sub/again")).unwrap();
+        std::fs::hard_link(root.join("linked"), root.join("sub/third")).unwrap();
+
+        let walk = |dedup| {
+            recursive_dir_size(root, &AtomicBool::new(false), dedup, false, 0, &compile_excludes(&[]), None)
+        };
+        let deduped = walk(true);
+        assert_eq!(deduped.unique_sum, 10);
+        assert_eq!(deduped.hardlinks.values().sum::<u64>(), 100);
+        assert_eq!(deduped.hardlink_apparent, 300);
+        assert_eq!(deduped.counts.files, 4);
+
+        let plain = walk(false);
+        assert_eq!(plain.unique_sum, 310);
+        assert!(plain.hardlinks.is_empty());
+    }
+
+    #[test]
+    fn blocking_tree_sizes_read_each_file_once() {
+        use crate::core::fs::{build_tree, WalkConfig};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("a/b/c/d")).unwrap();
+        std::fs::write(root.join("top"), [0u8; 1]).unwrap();
+        std::fs::write(root.join("a/one"), [0u8; 10]).unwrap();
+        std::fs::write(root.join("a/b/two"), [0u8; 100]).unwrap();
+        std::fs::write(root.join("a/b/c/d/deep"), [0u8; 5000]).unwrap();
+        #[cfg(unix)]
+        std::fs::hard_link(root.join("a/b/two"), root.join("a/b/c/two-again")).unwrap();
+        let walk = WalkConfig {
+            max_depth: 2,
+            show_git_status: false,
+            ..WalkConfig::default()
+        };
+        let tree = build_tree(root, &walk, false).unwrap();
+        let no_excludes = compile_excludes(&[]);
+
+        for allocated in [false, true] {
+            let counters = ScanCounters::default();
+            let sizes = tree_sizes_counted(&tree, true, false, allocated, &no_excludes, Some(&counters));
+            let files = if cfg!(unix) { 5 } else { 4 };
+            assert_eq!(counters.files.load(Ordering::Relaxed), files);
+
+            for node in &tree.nodes {
+                let path = &node.meta.path;
+                let expected = if node.meta.is_dir {
+                    let r = recursive_dir_size(path, &AtomicBool::new(false), true, false, 0, &no_excludes, None);
+                    if allocated {
+                        r.allocated_unique_sum + r.allocated_hardlinks.values().sum::<u64>()
+                    } else {
+                        r.unique_sum + r.hardlinks.values().sum::<u64>()
+                    }
+                } else {
+                    let meta = std::fs::symlink_metadata(path).unwrap();
+                    if allocated { allocated_size(&meta) } else { meta.len() }
+                };
+                assert_eq!(sizes.get(path), Some(&expected), "{} (allocated: {allocated})", path.display());
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! On-disk cache of per-directory size results between sessions.
+//!
+//! Stored at `$XDG_CACHE_HOME/dir-tree/size_cache.bin` (default
+//! `~/.cache/dir-tree/size_cache.bin`) with `bincode`.  Each entry keeps the
+//! directory's [`DirLocalResult`] plus the sizes of its direct files, and is
+//! only trusted while the directory's mtime, and the latest mtime of the
+//! hidden subdirectories folded into it, still match the watermarks in the
+//! result.  An mtime only moves when entries are added, removed or
+//! renamed, so a file rewritten in place is not noticed until the next
+//! targeted rescan.
+//!
+//! The file is capped at [`MAX_BYTES`]; when a save would exceed it, the
+//! entries least recently used (seen in a session) are dropped first.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::size::{dir_mtime, folded_mtime, DirLocalResult};
+
+/// Bumped whenever the layout of [`CacheFile`] changes.
+const CACHE_VERSION: u32 = 7;
+
+/// Upper bound on the serialized entries, to keep the file (and startup)
+/// small.
+pub const MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// One cached directory.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub local: DirLocalResult,
+    /// Apparent and allocated sizes of the directory's direct files, by name.
+    pub files: Vec<(String, u64, u64)>,
+    /// Unix time (seconds) of the last session that used this entry; the
+    /// oldest are evicted first.
+    pub last_used: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    /// Walk settings the results were computed with; a mismatch
+    /// invalidates everything.
+    dedup_hard_links: bool,
+    one_file_system: bool,
+    exclude_patterns: Vec<String>,
+    entries: Vec<(PathBuf, CacheEntry)>,
+}
+
+/// Return the cache file path (`$XDG_CACHE_HOME/dir-tree/size_cache.bin`).
+pub fn cache_path() -> PathBuf {
+    let cache_dir = std::env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
+            PathBuf::from(home).join(".cache")
+        });
+    cache_dir.join("dir-tree").join("size_cache.bin")
+}
+
+/// Load every entry from `path`.  A missing, corrupt or incompatible file
+/// yields an empty cache.
+pub fn load(
+    path: &Path,
+    dedup_hard_links: bool,
+    one_file_system: bool,
+    exclude_patterns: &[String],
+) -> HashMap<PathBuf, CacheEntry> {
+    let Ok(bytes) = std::fs::read(path) else {
+        return HashMap::new();
+    };
+    match bincode::deserialize::<CacheFile>(&bytes) {
+        Ok(file)
+            if file.version == CACHE_VERSION
+                && file.dedup_hard_links == dedup_hard_links
+                && file.one_file_system == one_file_system
+                && file.exclude_patterns == exclude_patterns =>
+        {
+            file.entries.into_iter().collect()
+        }
+        _ => HashMap::new(),
+    }
+}
+
+/// Delete the cache file.  A missing file is not an error.
+pub fn clear(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Whether `dir` is unchanged since `entry` was computed.  `descend` is
+/// the size walk's rule for entering a hidden subdirectory, so the folded
+/// subtrees are fingerprinted the same way they were walked.
+pub fn is_fresh(dir: &Path, entry: &CacheEntry, descend: impl Fn(&Path) -> bool) -> bool {
+    let local = &entry.local;
+    local.mtime.is_some()
+        && dir_mtime(dir) == local.mtime
+        && (local.folded.is_empty()
+            || folded_mtime(local.folded.iter().map(|name| dir.join(name)).collect(), descend) == local.folded_mtime)
+}
+
+/// Write `entries` to `path` atomically (temp file + rename), keeping the
+/// most recently used ones that fit in [`MAX_BYTES`].
+pub fn save(
+    path: &Path,
+    dedup_hard_links: bool,
+    one_file_system: bool,
+    exclude_patterns: &[String],
+    entries: impl IntoIterator<Item = (PathBuf, CacheEntry)>,
+) -> anyhow::Result<()> {
+    let mut entries: Vec<_> = entries
+        .into_iter()
+        .filter(|(_, e)| e.local.mtime.is_some())
+        .collect();
+    entries.sort_by_key(|(_, e)| std::cmp::Reverse(e.last_used));
+    let mut budget = MAX_BYTES;
+    let keep = entries
+        .iter()
+        .take_while(|entry| {
+            let size = bincode::serialized_size(entry).unwrap_or(u64::MAX);
+            if size > budget {
+                return false;
+            }
+            budget -= size;
+            true
+        })
+        .count();
+    entries.truncate(keep);
+    let file = CacheFile {
+        version: CACHE_VERSION,
+        dedup_hard_links,
+        one_file_system,
+        exclude_patterns: exclude_patterns.to_vec(),
+        entries,
+    };
+    let bytes = bincode::serialize(&file)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("bin.tmp");
+    let mut out = std::fs::File::create(&tmp)?;
+    out.write_all(&bytes)?;
+    out.sync_all()?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! Thumbnail decoding for image previews.
+//!
+//! Runs on background threads.  The result is upright (EXIF orientation
+//! applied) and fully opaque (alpha composited over a backdrop colour), so
+//! the half-block renderer can map pixels straight to terminal colours.
+
+use std::path::Path;
+
+use image::{DynamicImage, ImageDecoder, ImageReader, RgbaImage};
+
+/// Decode `path`, shrink it to fit `max`×`max`, apply its EXIF orientation
+/// and flatten transparency onto `backdrop`.
+pub fn decode_thumbnail(path: &Path, max: u32, backdrop: (u8, u8, u8)) -> Option<RgbaImage> {
+    let mut decoder = ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .into_decoder()
+        .ok()?;
+    // Formats without orientation metadata report "no transform".
+    let orientation = decoder.orientation().ok();
+    let img = DynamicImage::from_decoder(decoder).ok()?;
+
+    let mut thumb = img.resize(max, max, image::imageops::FilterType::Triangle);
+    if let Some(orientation) = orientation {
+        thumb.apply_orientation(orientation);
+    }
+    let mut rgba = thumb.to_rgba8();
+    flatten_alpha(&mut rgba, backdrop);
+    Some(rgba)
+}
+
+/// Composite every pixel over `backdrop` and make it opaque.
+fn flatten_alpha(img: &mut RgbaImage, (br, bg, bb): (u8, u8, u8)) {
+    for px in img.pixels_mut() {
+        let a = px[3] as u32;
+        if a == 255 {
+            continue;
+        }
+        let mix = |c: u8, b: u8| ((c as u32 * a + b as u32 * (255 - a) + 127) / 255) as u8;
+        px.0 = [mix(px[0], br), mix(px[1], bg), mix(px[2], bb), 255];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageEncoder, Rgba};
+
+    const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+    const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+
+    /// Big-endian EXIF block holding just an orientation tag.
+    fn exif_orientation(value: u8) -> Vec<u8> {
+        let mut exif = b"MM\0\x2a\0\0\0\x08".to_vec();
+        exif.extend_from_slice(&[0, 1]); // one entry
+        exif.extend_from_slice(&[0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, value, 0, 0]);
+        exif.extend_from_slice(&[0, 0, 0, 0]); // no next IFD
+        exif
+    }
+
+    fn write_png(path: &Path, img: &RgbaImage, exif: Option<Vec<u8>>) {
+        let file = std::fs::File::create(path).unwrap();
+        let mut encoder = image::codecs::png::PngEncoder::new(file);
+        if let Some(exif) = exif {
+            encoder.set_exif_metadata(exif).unwrap();
+        }
+        encoder
+            .write_image(img.as_raw(), img.width(), img.height(), image::ExtendedColorType::Rgba8)
+            .unwrap();
+    }
+
+    #[test]
+    fn every_exif_orientation_is_applied() {
+        let dir = tempfile::tempdir().unwrap();
+        // A 3×2 image with its stored top-left pixel marked; each row is
+        // (orientation, upright size, where the marker ends up).
+        let img = RgbaImage::from_fn(3, 2, |x, y| if (x, y) == (0, 0) { RED } else { BLUE });
+        let cases = [
+            (1, (3, 2), (0, 0)), // as stored
+            (2, (3, 2), (2, 0)), // mirrored horizontally
+            (3, (3, 2), (2, 1)), // rotated 180°
+            (4, (3, 2), (0, 1)), // mirrored vertically
+            (5, (2, 3), (0, 0)), // transposed
+            (6, (2, 3), (1, 0)), // rotated 90° clockwise
+            (7, (2, 3), (1, 2)), // transversed
+            (8, (2, 3), (0, 2)), // rotated 90° counter-clockwise
+        ];
+        for (orientation, size, (mx, my)) in cases {
+            let path = dir.path().join(format!("orientation-{orientation}.png"));
+            write_png(&path, &img, Some(exif_orientation(orientation)));
+
+            let thumb = decode_thumbnail(&path, 3, (0, 0, 0)).unwrap();
+            assert_eq!(thumb.dimensions(), size, "orientation {orientation}");
+            for (x, y, pixel) in thumb.enumerate_pixels() {
+                let expected = if (x, y) == (mx, my) { RED } else { BLUE };
+                assert_eq!(*pixel, expected, "orientation {orientation} at ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn no_orientation_leaves_the_image_as_is() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plain.png");
+        let img = RgbaImage::from_fn(2, 1, |x, _| if x == 0 { RED } else { BLUE });
+        write_png(&path, &img, None);
+
+        let thumb = decode_thumbnail(&path, 2, (0, 0, 0)).unwrap();
+        assert_eq!(thumb, img);
+    }
+
+    #[test]
+    fn transparency_is_flattened_onto_the_backdrop() {
+        let mut img = RgbaImage::from_vec(
+            3,
+            1,
+            vec![10, 20, 30, 255, 200, 100, 0, 0, 255, 255, 255, 128],
+        )
+        .unwrap();
+        flatten_alpha(&mut img, (0, 50, 100));
+        assert_eq!(img.get_pixel(0, 0).0, [10, 20, 30, 255]);
+        assert_eq!(img.get_pixel(1, 0).0, [0, 50, 100, 255]);
+        assert_eq!(img.get_pixel(2, 0).0, [128, 153, 178, 255]);
+    }
+
+    #[test]
+    fn unreadable_files_decode_to_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("not-an-image.png");
+        std::fs::write(&path, "hello").unwrap();
+        assert!(decode_thumbnail(&path, 16, (0, 0, 0)).is_none());
+        assert!(decode_thumbnail(&dir.path().join("missing.png"), 16, (0, 0, 0)).is_none());
+    }
+}
@@ -0,0 +1,101 @@
+//! Move entries to the freedesktop.org (XDG) home trash.
+//!
+//! Layout: `$XDG_DATA_HOME/Trash/{files,info}` (default
+//! `~/.local/share/Trash`).  Each trashed entry gets a `.trashinfo` file
+//! recording its original path so file managers can restore it.  Only the
+//! home trash is used; entries on another filesystem cannot be renamed into
+//! it and fail with an error instead of being copied.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Give up finding a free name after this many `name.N` attempts.
+const MAX_NAME_ATTEMPTS: usize = 1000;
+
+/// Move `path` to the home trash.
+pub fn move_to_trash(path: &Path) -> io::Result<()> {
+    if !cfg!(all(unix, not(target_os = "macos"))) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "no XDG trash on this platform (enable permanent delete)",
+        ));
+    }
+    let path = std::path::absolute(path)?;
+    let trash = trash_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory"))?;
+    let files_dir = trash.join("files");
+    let info_dir = trash.join("info");
+    std::fs::create_dir_all(&files_dir)?;
+    std::fs::create_dir_all(&info_dir)?;
+
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "cannot trash a filesystem root"))?
+        .to_string_lossy()
+        .into_owned();
+
+    // Reserve a name by creating the info file exclusively, then move.
+    for attempt in 0..MAX_NAME_ATTEMPTS {
+        let candidate = if attempt == 0 {
+            name.clone()
+        } else {
+            format!("{name}.{attempt}")
+        };
+        let info_path = info_dir.join(format!("{candidate}.trashinfo"));
+        let mut info = match OpenOptions::new().write(true).create_new(true).open(&info_path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        };
+        let target = files_dir.join(&candidate);
+        if target.symlink_metadata().is_ok() {
+            // Stale entry without info file; leave it alone.
+            let _ = std::fs::remove_file(&info_path);
+            continue;
+        }
+        let written = write!(
+            info,
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            percent_encode(&path),
+            deletion_date(),
+        );
+        if let Err(e) = written.and_then(|_| std::fs::rename(&path, &target)) {
+            let _ = std::fs::remove_file(&info_path);
+            if e.kind() == io::ErrorKind::CrossesDevices {
+                return Err(io::Error::new(
+                    e.kind(),
+                    "trash is on another filesystem (enable permanent delete)",
+                ));
+            }
+            return Err(e);
+        }
+        return Ok(());
+    }
+    Err(io::Error::new(io::ErrorKind::AlreadyExists, "no free name in trash"))
+}
+
+fn trash_dir() -> Option<PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share")))?;
+    Some(data_home.join("Trash"))
+}
+
+/// Percent-encode a path for the `Path=` key (RFC 2396 style, `/` kept).
+fn percent_encode(path: &Path) -> String {
+    let mut out = String::new();
+    for &b in path.as_os_str().as_encoded_bytes() {
+        if b.is_ascii_alphanumeric() || b"/-_.~".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
+
+/// `YYYY-MM-DDThh:mm:ss` in local time, as the spec asks.
+fn deletion_date() -> String {
+    chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string()
+}
@@ -24,6 +24,8 @@ pub struct EntryMeta {
     pub extension: Option<String>,
     /// For symlinks: the target path string (for display with `→`).
     pub symlink_target: Option<String>,
+    /// Listed on stdin (`--stdin`) but not found on disk; drawn dimmed.
+    pub missing: bool,
 }
 
 impl EntryMeta {
@@ -60,8 +62,29 @@ impl EntryMeta {
                 .extension()
                 .map(|e| e.to_string_lossy().to_lowercase()),
             symlink_target,
+            missing: false,
         })
     }
+
+    /// Placeholder for a listed path that does not exist.
+    pub fn missing(path: &Path, is_dir: bool) -> Self {
+        Self {
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            path: path.to_path_buf(),
+            is_dir,
+            is_symlink: false,
+            size: 0,
+            modified: None,
+            extension: (!is_dir)
+                .then(|| path.extension().map(|e| e.to_string_lossy().to_lowercase()))
+                .flatten(),
+            symlink_target: None,
+            missing: true,
+        }
+    }
 }
 
 // ───────────────────────────────────────── tree node ─────────
@@ -124,6 +147,23 @@ impl DirTree {
         id
     }
 
+    /// Like [`add_child`](Self::add_child), but keeps the scanner's order:
+    /// directories first, then files, each by case-insensitive name.
+    pub fn insert_child_sorted(&mut self, parent_id: NodeId, meta: EntryMeta) -> NodeId {
+        let key = |m: &EntryMeta| (!m.is_dir, m.name.to_lowercase());
+        let new_key = key(&meta);
+        let id = self.add_child(parent_id, meta);
+        let mut children = std::mem::take(&mut self.nodes[parent_id].children);
+        children.pop();
+        let pos = children
+            .iter()
+            .position(|&c| key(&self.nodes[c].meta) > new_key)
+            .unwrap_or(children.len());
+        children.insert(pos, id);
+        self.nodes[parent_id].children = children;
+        id
+    }
+
     /// Iterate node ids that are currently visible (expanded ancestors).
     /// This is the flattened list the UI will render.
     pub fn visible_nodes(&self) -> Vec<NodeId> {
@@ -149,6 +189,82 @@ impl DirTree {
         }
     }
 
+    /// Remove `id` and all of its descendants, compacting the arena.
+    /// Node ids held elsewhere are invalidated.  The root cannot be removed.
+    pub fn remove_subtree(&mut self, id: NodeId) {
+        let Some(parent) = self.nodes[id].parent else {
+            return;
+        };
+        self.nodes[parent].children.retain(|&c| c != id);
+        self.remove_marked(vec![id]);
+    }
+
+    /// Remove every descendant of `id`, leaving it unlisted so the next
+    /// expand reads it afresh.  Node ids held elsewhere are invalidated.
+    pub fn clear_children(&mut self, id: NodeId) {
+        let children = std::mem::take(&mut self.nodes[id].children);
+        self.remove_marked(children);
+    }
+
+    /// Drop `tops` and their descendants, none of which may still be
+    /// referenced from a surviving parent, and compact the arena.
+    fn remove_marked(&mut self, tops: Vec<NodeId>) {
+        let mut removed = vec![false; self.nodes.len()];
+        let mut stack = tops;
+        while let Some(n) = stack.pop() {
+            removed[n] = true;
+            stack.extend(self.nodes[n].children.iter().copied());
+        }
+
+        // Old id → new id for surviving nodes.
+        let mut remap = vec![usize::MAX; self.nodes.len()];
+        let mut next = 0;
+        for (old, gone) in removed.iter().enumerate() {
+            if !gone {
+                remap[old] = next;
+                next += 1;
+            }
+        }
+
+        let nodes = std::mem::take(&mut self.nodes);
+        self.nodes = nodes
+            .into_iter()
+            .zip(removed)
+            .filter(|(_, gone)| !gone)
+            .map(|(mut node, _)| {
+                node.parent = node.parent.map(|p| remap[p]);
+                for child in &mut node.children {
+                    *child = remap[*child];
+                }
+                node
+            })
+            .collect();
+        self.root = remap[self.root];
+    }
+
+    /// Point `id` at `new_path` after it was renamed on disk: refreshes its
+    /// name and extension and rewrites the path of every descendant.
+    pub fn rename_node(&mut self, id: NodeId, new_path: PathBuf) {
+        let meta = &mut self.nodes[id].meta;
+        let old_path = std::mem::replace(&mut meta.path, new_path.clone());
+        meta.name = new_path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        meta.extension = new_path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase());
+
+        let mut stack = self.nodes[id].children.clone();
+        while let Some(n) = stack.pop() {
+            let node = &mut self.nodes[n];
+            if let Ok(rel) = node.meta.path.strip_prefix(&old_path) {
+                node.meta.path = new_path.join(rel);
+            }
+            stack.extend(node.children.iter().copied());
+        }
+    }
+
     /// Return a reference to a node.
     pub fn get(&self, id: NodeId) -> &TreeNode {
         &self.nodes[id]
@@ -0,0 +1,8 @@
+//! Filesystem algorithms behind the `dir-tree` TUI, usable without it:
+//! walking a directory into a [`core::tree::DirTree`], grouping its
+//! children, recursive sizes with hard-link dedup, and name search.
+//!
+//! The terminal UI, configuration and shell integration stay in the
+//! binary.
+
+pub mod core;
@@ -1,24 +1,28 @@
 //! A tree-based TUI to replace `cd` & `ls`.
 //!
 //! Run the binary to launch the interactive tree view.
-//! Run with `--init-bash` to print the shell function for your `.bashrc`.
+//! Run with `--init-bash` (or `--init-zsh`, `--init-powershell`) to print the
+//! shell function for your shell profile.
 
 mod app;
 mod config;
-mod core;
 mod shell;
 mod ui;
 
-use std::io::{self, stderr};
+use dir_tree::core;
+
+use std::io::{self, stderr, Write};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
 use clap::Parser;
 use crossterm::{
     event::{
-        DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags,
-        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste, EnableFocusChange,
+        EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
     },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
@@ -26,6 +30,7 @@ use crossterm::{
 use ratatui::{
     backend::CrosstermBackend,
     layout::Rect,
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Terminal,
 };
@@ -33,19 +38,38 @@ use ratatui::{
 use crate::app::{
     event::{spawn_event_reader, AppEvent},
     fs_runtime::{self, FsUpdate},
-    handler,
+    apply_pending_updates, handler, inspect_runtime, preview_runtime, watch_runtime,
     state::{ActiveView, AppState, PaneFocus, RightPaneTab},
+    updates::PendingUpdates,
 };
+use crate::config::{SizeView, SlowFsMode};
+use crate::core::inspector::InspectorInfo;
+use crate::core::print::OutputFormat;
 use crate::shell::integration;
 use crate::ui::{
-    inspector::InspectorWidget, layout::AppLayout, popup, spinner::ScanIndicator, theme::Theme,
+    badges, deletions::DeletionsWidget, inspector::InspectorWidget, layout::AppLayout, popup,
+    spinner::ScanIndicator, theme::Theme,
     search::SearchWidget, tree_widget::TreeWidget,
 };
 
 // ───────────────────────────────────────── CLI ───────────────
 
+/// Crate version plus the git hash and build date embedded by `build.rs`.
+pub const VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("DT_GIT_HASH"),
+    ", built ",
+    env!("DT_BUILD_DATE"),
+    ")"
+);
+
 #[derive(Parser, Debug)]
-#[command(name = env!("CARGO_PKG_NAME"), about = "Tree-based directory navigator")]
+#[command(
+    name = env!("CARGO_PKG_NAME"),
+    about = "Tree-based directory navigator",
+    version = VERSION
+)]
 struct Cli {
     /// Directory to open (defaults to `.`).
     #[arg(default_value = ".")]
@@ -59,6 +83,10 @@ struct Cli {
     #[arg(long = "init-zsh")]
     init_zsh: bool,
 
+    /// Print the PowerShell function and exit.
+    #[arg(long = "init-powershell")]
+    init_powershell: bool,
+
     /// Maximum tree depth.
     #[arg(long, default_value_t = 3)]
     depth: usize,
@@ -70,12 +98,79 @@ struct Cli {
     /// Stay on the same filesystem (don't cross mount points).
     #[arg(long = "one-file-system", short = 'x')]
     one_file_system: bool,
+
+    /// Experimental screen-reader mode: plain-text rows, spoken status
+    /// updates, selected row kept at the top of the tree.
+    #[arg(long)]
+    a11y: bool,
+
+    /// Don't capture the mouse (keeps native terminal text selection).
+    #[arg(long = "no-mouse")]
+    no_mouse: bool,
+
+    /// Print the tree to stdout and exit (no TUI; works without a TTY).
+    #[arg(long)]
+    print: bool,
+
+    /// With `--print` / `--format`: skip the recursive size walk.
+    #[arg(long = "no-sizes")]
+    no_sizes: bool,
+
+    /// Write the tree to stdout as text, json or csv and exit (no TUI).
+    #[arg(long, value_name = "FMT", value_parser = parse_output_format)]
+    format: Option<OutputFormat>,
+
+    /// Slow-filesystem mode for this session: auto, on or off (overrides
+    /// the `slow_fs` config key).
+    #[arg(long = "slow-fs", value_name = "MODE", value_parser = parse_slow_fs_mode)]
+    slow_fs: Option<SlowFsMode>,
+
+    /// Don't read or write the on-disk size cache this session.
+    #[arg(long = "no-cache")]
+    no_cache: bool,
+
+    /// Browse the newline-separated paths piped on stdin (e.g. from `fd`
+    /// or `git ls-files`) instead of walking PATH; relative paths are
+    /// resolved against PATH.
+    #[arg(long, conflicts_with_all = ["print", "format"])]
+    stdin: bool,
+}
+
+/// Read the `--stdin` path list, one path per line.  Keyboard input still
+/// works afterwards: the terminal is read through `/dev/tty`.
+fn read_stdin_paths() -> Result<Vec<PathBuf>> {
+    use std::io::{BufRead, IsTerminal};
+
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        anyhow::bail!("--stdin expects a list of paths piped on standard input");
+    }
+    let mut paths = Vec::new();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        let line = line.trim_end_matches('\r');
+        if !line.is_empty() {
+            paths.push(PathBuf::from(line));
+        }
+    }
+    if paths.is_empty() {
+        anyhow::bail!("no paths on standard input");
+    }
+    Ok(paths)
+}
+
+fn parse_slow_fs_mode(value: &str) -> Result<SlowFsMode, String> {
+    SlowFsMode::from_config_value(value).ok_or_else(|| "expected auto, on or off".to_string())
+}
+
+fn parse_output_format(value: &str) -> Result<OutputFormat, String> {
+    OutputFormat::from_name(value).ok_or_else(|| "expected text, json or csv".to_string())
 }
 
 // ───────────────────────────────────────── size computation ──
 
 use crate::app::size_runtime::{
-    apply_size_update, finalize_ready_dirs, start_size_computation, SizeComputeState, SizeUpdate,
+    self, mark_scan_failed, restart_size_computation, SizeComputeState, SizeUpdate,
 };
 
 // ───────────────────────────────────────── main ─────────────
@@ -99,6 +194,10 @@ async fn main() -> Result<()> {
         print!("{}", integration::zsh_function());
         return Ok(());
     }
+    if cli.init_powershell {
+        print!("{}", integration::powershell_function());
+        return Ok(());
+    }
 
     // ── build initial tree ────────────────────────────────────
     let root = cli.path.canonicalize()?;
@@ -107,6 +206,8 @@ async fn main() -> Result<()> {
     walk_config.show_hidden = cli.hidden;
 
     let mut user_config = config::AppConfig::load();
+    walk_config.sort_mode = user_config.sort_mode;
+    walk_config.show_git_status = user_config.show_git_status;
 
     // Apply persisted settings; CLI flags override.
     user_config.one_file_system = if cli.one_file_system {
@@ -115,18 +216,74 @@ async fn main() -> Result<()> {
         user_config.one_file_system
     };
 
-    let tree = core::fs::build_tree(&root, &walk_config, user_config.one_file_system)?;
+    // ── non-interactive print mode ────────────────────────────
+    if cli.print || cli.format.is_some() {
+        // Fail loudly (non-zero exit) if the root can't be listed.
+        std::fs::read_dir(&root)
+            .map_err(|e| anyhow::anyhow!("cannot read {}: {e}", root.display()))?;
+        let tree = core::fs::build_tree(&root, &walk_config, user_config.one_file_system)?;
+        let format = cli.format.unwrap_or(OutputFormat::Text);
+        let excludes = core::size::compile_excludes(&user_config.exclude_patterns);
+        let sizes = (!cli.no_sizes).then(|| {
+            core::size::tree_sizes_blocking(
+                &tree,
+                user_config.dedup_hard_links,
+                user_config.one_file_system,
+                user_config.size_view == SizeView::Allocated,
+                &excludes,
+            )
+        });
+        if format == OutputFormat::Text {
+            print!("{}", core::print::render_plain_tree(&tree, sizes.as_ref()));
+            return Ok(());
+        }
+
+        let size_of = |meta: &core::tree::EntryMeta| sizes.as_ref()?.get(&meta.path).copied();
+        let mut out = io::BufWriter::new(io::stdout().lock());
+        if format == OutputFormat::Json {
+            core::print::write_json(&tree, size_of, &mut out)?;
+        } else {
+            core::print::write_csv(&tree, size_of, &mut out)?;
+        }
+        out.flush()?;
+        return Ok(());
+    }
+
+    // Probe before the walk warms the cache.
+    let slow_fs_mode = cli.slow_fs.unwrap_or(user_config.slow_fs);
+    let slow_fs_detected = slow_fs_mode == SlowFsMode::Auto && core::fs::probe_slow_fs(&root);
+    walk_config.read_mtimes = !slow_fs_mode.resolve(slow_fs_detected);
+    let (root, tree) = if cli.stdin {
+        let tree = core::fs::build_tree_from_paths(&root, read_stdin_paths()?, walk_config.sort_mode);
+        (tree.get(tree.root).meta.path.clone(), tree)
+    } else {
+        let tree = core::fs::build_tree(&root, &walk_config, user_config.one_file_system)?;
+        (root, tree)
+    };
     let saved_pins = user_config.pinned_paths.clone();
     let mut state = AppState::new(root, tree, user_config);
+    state.fixed_tree = cli.stdin;
+    state.search_mode = state.config.default_search_mode;
     state.walk_config = walk_config;
     state.needs_size_recompute = true;
+    // `--no-mouse` only affects this session; the config key persists.
+    state.mouse_enabled = state.config.mouse && !cli.no_mouse;
+    // Same for `--a11y`; it must not reach config.toml on the next save.
+    state.a11y = state.config.a11y || cli.a11y;
+    // Cached results come from full directory walks, not the listed files.
+    state.size_cache_enabled = state.config.size_cache && !cli.no_cache && !cli.stdin;
+    state.slow_fs_mode = slow_fs_mode;
+    state.slow_fs_detected = slow_fs_detected;
+    handler::apply_slow_fs_mode(&mut state);
+    // Before the first scan, so unchanged directories finalize at once.
+    size_runtime::load_size_cache(&mut state);
 
     // Restore pinned files from last session (skip paths that no longer exist).
     for path_str in &saved_pins {
         let path = std::path::PathBuf::from(path_str);
         if path.exists() {
-            let info = crate::core::inspector::inspect_path(&path);
-            state.pinned_inspector.push(info);
+            state.pinned_inspector.push(InspectorInfo::pending(&path));
+            state.pending_inspections.push_back(path);
         }
     }
 
@@ -136,52 +293,125 @@ async fn main() -> Result<()> {
     execute!(
         stderr_handle,
         EnterAlternateScreen,
-        EnableMouseCapture,
+        EnableBracketedPaste,
+        // Focus reports tell us when a multiplexer reattaches.
+        EnableFocusChange,
         // Best effort: ask terminals that support enhanced keyboard protocol
         // to preserve modifier info for keys like Shift+Enter.
-        PushKeyboardEnhancementFlags(
-            KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
-                | KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES
-        )
+        PushKeyboardEnhancementFlags(KEYBOARD_FLAGS)
     )?;
+    if state.mouse_enabled {
+        execute!(stderr_handle, EnableMouseCapture)?;
+    }
+    let mut mouse_captured = state.mouse_enabled;
     let backend = CrosstermBackend::new(stderr());
     let mut terminal = Terminal::new(backend)?;
 
     // ── async channels ────────────────────────────────────────
-    let mut events = spawn_event_reader(Duration::from_millis(50));
+    let input_paused = Arc::new(AtomicBool::new(false));
+    let (events_tx, mut events) = spawn_event_reader(EVENT_TICK, Arc::clone(&input_paused));
     let (size_tx, mut size_rx) = tokio::sync::mpsc::unbounded_channel::<(u64, SizeUpdate)>();
     let (fs_tx, mut fs_rx) = tokio::sync::mpsc::unbounded_channel::<FsUpdate>();
     let mut size_compute: Option<SizeComputeState> = None;
     let mut tick_count: u64 = 0;
+    // Last time any terminal event (including ticks) was handled; a long
+    // gap means the process was stopped (Ctrl+Z / SIGSTOP).
+    let mut last_event_at = std::time::Instant::now();
+    let mut force_repaint = false;
+    let mut pending = PendingUpdates::default();
+    let mut input_lost = false;
+    let mut watcher: Option<watch_runtime::FsWatcher> = None;
+    // Message currently floated over the screen (status bar hidden).
+    let mut overlay_message: Option<(String, std::time::Instant)> = None;
+    let mut watch_failed = false;
 
     // Channel for background image decoding (decode + pre-resize off main thread).
-    let (img_tx, img_rx) = std::sync::mpsc::channel::<(PathBuf, image::RgbaImage)>();
+    // Failed decodes report `None` so their slot is released.
+    let (img_tx, img_rx) = std::sync::mpsc::channel::<(PathBuf, Option<image::RgbaImage>)>();
+
+    // Background inspector (MIME sniffing, image headers, dir listings).
+    let (inspect_tx, mut inspect_rx) = tokio::sync::mpsc::unbounded_channel::<InspectorInfo>();
+    let inspect_req_tx = inspect_runtime::spawn_inspector_worker(
+        inspect_tx,
+        state.mime_stats.clone(),
+        state.slow_fs.clone(),
+        state.syntax_highlight.clone(),
+    );
+
+    // SHA-256 digests of inspected files, one thread per file.
+    let (checksum_tx, mut checksum_rx) =
+        tokio::sync::mpsc::unbounded_channel::<inspect_runtime::ChecksumResult>();
+
+    // External previewer results (one run at a time).
+    let (preview_tx, mut preview_rx) =
+        tokio::sync::mpsc::unbounded_channel::<preview_runtime::PreviewResult>();
 
     // ── event loop ────────────────────────────────────────────
     loop {
         // Poll completed image decodes (non-blocking).
+        let mut decodes_finished = false;
         while let Ok((path, rgba)) = img_rx.try_recv() {
             state.image_decoding.remove(&path);
-            state.image_cache.insert(path, std::sync::Arc::new(rgba));
+            if let Some(rgba) = rgba {
+                state.image_cache.insert(path, std::sync::Arc::new(rgba));
+            }
+            decodes_finished = true;
+        }
+        if decodes_finished {
+            pump_image_decodes(&mut state, &img_tx);
         }
 
+        handler::run_due_search_reveal(&mut state);
         refresh_inspector(&mut state, &img_tx);
 
+        // Without a status bar, messages only stay up briefly.
+        if state.config.status_lines == 0 {
+            match (&state.status_message, &overlay_message) {
+                (Some(msg), Some((shown, at))) if msg == shown => {
+                    if at.elapsed() >= STATUS_OVERLAY_TIMEOUT {
+                        state.status_message = None;
+                        overlay_message = None;
+                    }
+                }
+                (Some(msg), _) => overlay_message = Some((msg.clone(), std::time::Instant::now())),
+                (None, _) => overlay_message = None,
+            }
+        }
+
         // ── draw first ─────────────────────────────────────────
         // Always render before doing any expensive work so the UI
         // stays responsive.  Sizes fill in asynchronously.
+        if force_repaint {
+            force_repaint = false;
+            terminal.autoresize()?;
+            terminal.clear()?;
+        }
+
         terminal.draw(|frame| {
             state.terminal_area = frame.area();
-            let layout =
-                AppLayout::from_area(frame.area(), state.config.panel_layout, state.config.panel_split_pct);
+            let layout = AppLayout::from_area(
+                frame.area(),
+                state.config.panel_layout,
+                state.config.panel_split_pct,
+                state.config.status_lines,
+            );
 
             let tree_focused = state.pane_focus == PaneFocus::Tree;
             let inspector_focused = state.pane_focus == PaneFocus::Inspector;
 
+            // Screen-reader mode keeps the layout but draws frames as spaces.
+            let a11y = state.a11y;
+            let border_set = if a11y {
+                ratatui::symbols::border::EMPTY
+            } else {
+                ratatui::symbols::border::PLAIN
+            };
             let tree_block = Block::default()
                 .title(format!(
-                    " Tree{} · Tab: switch pane ",
-                    if tree_focused { " [focused]" } else { "" }
+                    " Tree{}{} · Tab: switch pane ",
+                    if tree_focused { " [focused]" } else { "" },
+                    // Allocated totals differ from `ls`; say which is shown.
+                    if state.config.size_view == SizeView::Allocated { " · sizes on disk" } else { "" }
                 ))
                 .title_style(if tree_focused {
                     Theme::title_style()
@@ -190,12 +420,20 @@ async fn main() -> Result<()> {
                 })
                 .title_bottom(format!(" {} ", state.cwd.display()))
                 .borders(Borders::ALL)
+                .border_set(border_set)
                 .border_style(if tree_focused {
                     ratatui::style::Style::default().fg(ratatui::style::Color::LightBlue)
                 } else {
                     Theme::border_style()
                 });
 
+            // One clock reading per frame so every row uses the same cutoff.
+            let recent_since = state
+                .config
+                .highlight_recent
+                .then(|| std::time::SystemTime::now().checked_sub(state.config.recent_window.duration()))
+                .flatten();
+
             // Show pin hint only when nothing is pinned yet.
             let pin_hint = if state.pinned_inspector.is_empty() {
                 let key = state.config.short_binding(crate::config::Action::Expand);
@@ -204,19 +442,61 @@ async fn main() -> Result<()> {
                 None
             };
 
+            let inspected_entry_count = state.inspector_info.as_ref().and_then(|info| {
+                let count = info.subdirs? + info.subfiles? + info.others.unwrap_or(0);
+                Some((info.path.as_path(), count as usize))
+            });
+
+            state.tree_viewport_rows = tree_block.inner(layout.tree_area).height as usize;
             let tree_widget = TreeWidget::new(&state.tree, &state.grouping_config)
                 .dir_sizes(&state.dir_sizes)
                 .file_sizes(&state.file_sizes)
                 .expanded_groups(&state.expanded_groups)
                 .pin_hint(pin_hint)
+                .heat(state.config.heat_mode.then_some(state.config.heat_gradient))
+                .staged_for_deletion(&state.pending_deletions)
+                .marked(&state.selected_nodes)
+                .stale_sizes(&state.stale_sizes)
+                .excluded_sizes(&state.dir_excluded)
+                .entry_counts(&state.dir_entry_counts)
+                .entry_count(state.config.show_entry_counts, inspected_entry_count)
+                .file_count(state.config.show_file_counts)
+                .icons(state.config.icons)
+                .size_share(state.config.size_share)
+                .indent_guides(state.config.show_indent_guides)
+                .alternate_rows(state.config.alternate_rows)
+                .a11y(a11y)
+                // Hard-link savings are measured in apparent bytes.
+                .apparent_sizes(
+                    (state.config.size_view == SizeView::Apparent).then_some(&state.dir_apparent_sizes),
+                )
+                .sort_mode(state.walk_config.sort_mode)
+                .recent_since(recent_since)
+                .git_statuses(
+                    state
+                        .walk_config
+                        .show_git_status
+                        .then_some(&state.git_statuses),
+                )
                 .block(tree_block);
 
             frame.render_stateful_widget(tree_widget, layout.tree_area, &mut state.tree_state);
 
-            let tab_title = match state.right_pane_tab {
-                RightPaneTab::Inspector => "[Inspector] | Search",
-                RightPaneTab::Search => "Inspector | [Search]",
-            };
+            let tab_title = [
+                (RightPaneTab::Inspector, "Inspector"),
+                (RightPaneTab::Search, "Search"),
+                (RightPaneTab::Deletions, "Deletions"),
+            ]
+            .iter()
+            .map(|(tab, name)| {
+                if *tab == state.right_pane_tab {
+                    format!("[{name}]")
+                } else {
+                    name.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
             let inspector_block = Block::default()
                 .title(format!(
                     " Right Pane {tab_title}{} · Tab: switch pane ",
@@ -228,12 +508,22 @@ async fn main() -> Result<()> {
                     Theme::size_style()
                 })
                 .borders(Borders::ALL)
+                .border_set(border_set)
                 .border_style(if inspector_focused {
                     ratatui::style::Style::default().fg(ratatui::style::Color::LightBlue)
                 } else {
                     Theme::border_style()
                 });
-            if state.right_pane_tab == RightPaneTab::Inspector {
+            if state.right_pane_tab == RightPaneTab::Deletions {
+                frame.render_widget(
+                    DeletionsWidget {
+                        block: inspector_block,
+                        state: &state,
+                        has_focus: state.pane_focus == PaneFocus::Inspector,
+                    },
+                    layout.inspector_area,
+                );
+            } else if state.right_pane_tab == RightPaneTab::Inspector {
                 frame.render_widget(
                     InspectorWidget {
                         block: inspector_block,
@@ -250,6 +540,7 @@ async fn main() -> Result<()> {
                         },
                         has_focus: state.pane_focus == PaneFocus::Inspector,
                         image_cache: &state.image_cache,
+                        a11y,
                     },
                     layout.inspector_area,
                 );
@@ -260,7 +551,9 @@ async fn main() -> Result<()> {
                         block: inspector_block,
                         root: &state.search_root,
                         query: &state.search_query,
-                        case_sensitive: state.search_case_sensitive,
+                        options: state.search_options,
+                        mode: state.search_mode,
+                        error: state.search_error.as_deref(),
                         results: &state.search_results,
                         selected: if state.search_results.is_empty() {
                             None
@@ -280,6 +573,8 @@ async fn main() -> Result<()> {
                 ScanIndicator {
                     visible: state.scanning || state.fs_scanning,
                     tick: tick_count,
+                    detail: state.scanning.then(|| state.scan_progress.summary()),
+                    plain: a11y,
                 },
                 layout.tree_area,
             );
@@ -292,35 +587,145 @@ async fn main() -> Result<()> {
             );
             let selection_hint =
                 "Enter: open dir / copy file path | Shift+Enter: copy selected path";
-            let default_hint = format!("{nav_hint} | {selection_hint}");
+            let sort_hint = format!(
+                "{}: sort ({})",
+                state.config.short_binding(crate::config::Action::CycleSort),
+                state.walk_config.sort_mode.label(),
+            );
+            let sort_hint = if state.config.highlight_recent {
+                format!("{sort_hint} | green: changed < {}", state.config.recent_window.label())
+            } else {
+                sort_hint
+            };
+            let mut history_hint = Vec::new();
+            if state.root_history_index > 0 {
+                let key = state.config.short_binding(crate::config::Action::HistoryBack);
+                history_hint.push(format!("{key}: ← back"));
+            }
+            if state.root_history_index + 1 < state.root_history.len() {
+                let key = state.config.short_binding(crate::config::Action::HistoryForward);
+                history_hint.push(format!("{key}: → fwd"));
+            }
+            let nav_hint = if history_hint.is_empty() {
+                nav_hint
+            } else {
+                format!("{nav_hint} | {}", history_hint.join(" "))
+            };
+            let nav_hint = if state.selected_nodes.is_empty() {
+                nav_hint
+            } else {
+                format!("{} | {nav_hint}", handler::selection_summary(&state))
+            };
+            let default_hint = if state.is_slow_fs() {
+                let size_key = state.config.short_binding(crate::config::Action::RescanSelected);
+                format!("SLOW FS ({size_key}: size) | {nav_hint} | {sort_hint} | {selection_hint}")
+            } else {
+                format!("{nav_hint} | {sort_hint} | {selection_hint}")
+            };
+            let type_ahead = handler::active_type_ahead(&state).map(|buffer| format!("Jump to: {buffer}▏"));
             let status_text = match state.active_view {
-                ActiveView::Tree => state
-                    .status_message
+                ActiveView::Tree => type_ahead
                     .as_deref()
+                    .or(state.status_message.as_deref())
                     .unwrap_or(&default_hint),
-                ActiveView::SettingsMenu | ActiveView::ControlsSubmenu | ActiveView::Lightbox => "",
+                ActiveView::SettingsMenu
+                | ActiveView::ControlsSubmenu
+                | ActiveView::Lightbox
+                | ActiveView::PinSets
+                | ActiveView::Bookmarks
+                | ActiveView::TopFiles
+                | ActiveView::ConfirmDeletions
+                | ActiveView::ConfirmDelete
+                | ActiveView::Rename
+                | ActiveView::NewDir
+                | ActiveView::PathInput
+                | ActiveView::ConfirmQuit
+                | ActiveView::ConfirmResetSettings
+                | ActiveView::ConfigWarnings
+                | ActiveView::ExcludePatterns
+                | ActiveView::ScanStats => "",
             };
-            let status = Paragraph::new(status_text).style(Theme::status_bar_style());
-            frame.render_widget(status, layout.status_area);
+            let status = if state.active_view == ActiveView::PathInput {
+                let mut spans = vec![
+                    Span::raw("Go to: "),
+                    Span::styled(format!("{}▏", state.path_input), Theme::command_input_style()),
+                ];
+                if !state.path_completions.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  {}", state.path_completions.join("  ")),
+                        Theme::size_style(),
+                    ));
+                }
+                Paragraph::new(Line::from(spans))
+            } else {
+                Paragraph::new(status_text)
+            };
+            let status = status.style(Theme::status_bar_style());
+            let badge_row = Rect { height: layout.status_area.height.min(1), ..layout.status_area };
+            let cluster = (state.active_view == ActiveView::Tree && state.config.status_lines > 0)
+                .then(|| {
+                    let active = badges::active_badges(&state);
+                    badges::layout(&state, &active, state.badge_focus, badge_row, badge_row.width / 2)
+                });
+            let badge_width = cluster.as_ref().map_or(0, |c| c.width);
+            if state.config.status_lines > 0 {
+                let text_area = Rect {
+                    width: layout.status_area.width.saturating_sub(badge_width),
+                    ..layout.status_area
+                };
+                frame.render_widget(status, text_area);
+                if let Some(cluster) = &cluster {
+                    frame.render_widget(
+                        Paragraph::new(cluster.line.clone()).style(Theme::status_bar_style()),
+                        Rect { x: text_area.right(), width: badge_width, ..badge_row },
+                    );
+                }
+            } else if state.active_view == ActiveView::PathInput
+                || (state.active_view == ActiveView::Tree
+                    && (state.status_message.is_some() || type_ahead.is_some()))
+            {
+                // No bar: float the input / message over the bottom row.
+                let area = frame.area();
+                let row = Rect::new(area.x, area.bottom().saturating_sub(1), area.width, 1.min(area.height));
+                let width = if state.active_view == ActiveView::PathInput {
+                    row.width
+                } else {
+                    (status_text.chars().count() as u16 + 2).min(row.width)
+                };
+                let overlay = Rect { width, ..row };
+                frame.render_widget(ratatui::widgets::Clear, overlay);
+                frame.render_widget(status, overlay);
+            }
+            state.badge_hit_zones = cluster.map(|c| c.zones).unwrap_or_default();
+            if layout.info_area.height > 0 {
+                frame.render_widget(
+                    Paragraph::new(status_info_text(&state)).style(Theme::status_bar_style()),
+                    layout.info_area,
+                );
+            }
 
             match state.active_view {
                 ActiveView::SettingsMenu => {
-                    frame.render_widget(
+                    popup::render_popup(
+                        frame,
                         popup::SettingsPopup {
                             selected: state.settings_selected,
                             state: &state,
                         },
-                        frame.area(),
+                        state.popup_offset,
                     );
                 }
                 ActiveView::ControlsSubmenu => {
-                    frame.render_widget(
+                    popup::render_popup(
+                        frame,
                         popup::ControlsPopup {
                             config: &state.config,
                             selected: state.controls_selected,
                             awaiting_rebind: state.awaiting_rebind,
+                            pending: state.pending_rebind.as_ref(),
+                            warning: state.rebind_warning.as_deref(),
                         },
-                        frame.area(),
+                        state.popup_offset,
                     );
                 }
                 ActiveView::Lightbox => {
@@ -328,11 +733,130 @@ async fn main() -> Result<()> {
                         pinned: &state.pinned_inspector,
                         current: state.lightbox_index,
                         image_cache: &state.image_cache,
+                        mouse: state.mouse_enabled,
                     };
                     state.lightbox_hit_zones =
                         Some(lw.render_and_hit(frame.area(), frame.buffer_mut()));
                 }
-                ActiveView::Tree => {}
+                ActiveView::PinSets => {
+                    popup::render_popup(frame, popup::PinSetsPopup { state: &state }, state.popup_offset);
+                }
+                ActiveView::Bookmarks => {
+                    popup::render_popup(frame, popup::BookmarksPopup { state: &state }, state.popup_offset);
+                }
+                ActiveView::TopFiles => {
+                    popup::render_popup(frame, popup::TopFilesPopup { state: &state }, state.popup_offset);
+                }
+                ActiveView::ConfirmDeletions => {
+                    popup::render_popup(
+                        frame,
+                        popup::ConfirmDeletionsPopup {
+                            count: state.pending_deletions.len(),
+                            reclaimable: handler::reclaimable_bytes(&state),
+                            input: &state.deletion_confirm_input,
+                            permanent: state.config.permanent_delete,
+                        },
+                        state.popup_offset,
+                    );
+                }
+                ActiveView::ConfirmDelete => {
+                    if let Some(path) = &state.delete_target {
+                        let size = handler::staged_size(&state, path)
+                            .map(|bytes| format!(" ({})", core::grouping::human_size(bytes)))
+                            .unwrap_or_default();
+                        let (title, hint) = if state.config.permanent_delete {
+                            (" Delete permanently ", "y / Enter: delete  n / Esc: cancel")
+                        } else {
+                            (" Move to trash ", "y / Enter: trash  n / Esc: cancel")
+                        };
+                        popup::render_popup(
+                            frame,
+                            popup::ConfirmPopup {
+                                title,
+                                message: format!("{}{size}", path.display()),
+                                hint,
+                            },
+                            state.popup_offset,
+                        );
+                    }
+                }
+                ActiveView::Rename | ActiveView::NewDir => {
+                    let (target, title, action) = if state.active_view == ActiveView::Rename {
+                        (&state.rename_target, " Rename ", "rename")
+                    } else {
+                        (&state.new_dir_parent, " New Directory ", "create")
+                    };
+                    if let Some(path) = target {
+                        let context = path.display().to_string();
+                        popup::render_popup(
+                            frame,
+                            popup::NamePromptPopup {
+                                title,
+                                context: &context,
+                                input: &state.name_input,
+                                action,
+                            },
+                            state.popup_offset,
+                        );
+                    }
+                }
+                ActiveView::ConfirmQuit => {
+                    popup::render_popup(
+                        frame,
+                        popup::ConfirmPopup {
+                            title: " Quit ",
+                            message: format!(
+                                "{} staged deletions will be discarded.",
+                                state.pending_deletions.len()
+                            ),
+                            hint: "y: quit  n / Esc: stay",
+                        },
+                        state.popup_offset,
+                    );
+                }
+                ActiveView::ConfirmResetSettings => {
+                    popup::render_popup(
+                        frame,
+                        popup::SettingsPopup {
+                            selected: state.settings_selected,
+                            state: &state,
+                        },
+                        state.popup_offset,
+                    );
+                    popup::render_popup(
+                        frame,
+                        popup::ConfirmPopup {
+                            title: " Reset All Settings ",
+                            message: "Restore every setting to its default?".to_string(),
+                            hint: "y: reset  n / Esc: cancel",
+                        },
+                        state.popup_offset,
+                    );
+                }
+                ActiveView::ConfigWarnings => {
+                    popup::render_popup(
+                        frame,
+                        popup::ConfigWarningsPopup {
+                            warnings: &state.config_warnings,
+                        },
+                        state.popup_offset,
+                    );
+                }
+                ActiveView::ExcludePatterns => {
+                    popup::render_popup(
+                        frame,
+                        popup::ExcludePatternsPopup { state: &state },
+                        state.popup_offset,
+                    );
+                }
+                ActiveView::ScanStats => {
+                    popup::render_popup(frame, popup::ScanStatsPopup { state: &state }, state.popup_offset);
+                }
+                ActiveView::Tree | ActiveView::PathInput => {}
+            }
+
+            if state.show_debug_overlay {
+                frame.render_widget(crate::ui::debug::DebugOverlay { state: &state }, frame.area());
             }
         })?;
 
@@ -341,16 +865,22 @@ async fn main() -> Result<()> {
         // (expanded dirs, new entries).  Now we compute sizes — cached
         // dirs finalize immediately, uncached ones arrive via workers.
         // Sizes appear on the next frame; the expand itself is instant.
-        if state.needs_size_recompute {
+        if state.needs_size_recompute && state.fixed_tree {
             state.needs_size_recompute = false;
-            if let Some(ref old) = size_compute {
+            size_runtime::record_listed_sizes(&mut state);
+            state.stale_sizes.clear();
+        }
+        if state.needs_size_recompute && state.is_slow_fs() {
+            // Sizes are on demand only; just stop a scan still running.
+            state.needs_size_recompute = false;
+            if let Some(old) = size_compute.take() {
                 old.request_cancel();
             }
-            size_compute = Some(start_size_computation(&mut state, &size_tx));
-            if let Some(ref mut compute) = size_compute {
-                state.scanning = compute.is_scanning();
-                finalize_ready_dirs(&mut state, compute);
-            }
+            state.scanning = false;
+        }
+        if state.needs_size_recompute {
+            state.needs_size_recompute = false;
+            restart_size_computation(&mut state, &mut size_compute, &size_tx);
         }
 
         // Kick off queued background filesystem/search jobs.
@@ -369,6 +899,62 @@ async fn main() -> Result<()> {
             }
         }
 
+        // (Re)start the watcher for the current root, or stop it.  Network
+        // mounts rarely deliver events and each watch costs a round trip,
+        // so slow-filesystem mode never watches.
+        let want_watch = state.config.watch_fs && !state.is_slow_fs() && !state.fixed_tree;
+        if watcher.as_ref().is_some_and(|w| w.is_cancelled() || w.root() != state.cwd)
+            || !want_watch
+        {
+            if let Some(old) = watcher.take() {
+                old.cancel();
+            }
+            state.watch_changed.clear();
+            state.watch_changed_at = None;
+            state.watch_event_rate.clear();
+        }
+        if want_watch && watcher.is_none() && !watch_failed {
+            match watch_runtime::FsWatcher::spawn(fs_tx.clone(), state.cwd.clone()) {
+                Ok(mut w) => {
+                    w.sync(&state.tree);
+                    watcher = Some(w);
+                }
+                Err(e) => {
+                    watch_failed = true;
+                    state.status_message = Some(format!("Watch failed: {e}"));
+                }
+            }
+        }
+        if state
+            .watch_changed_at
+            .is_some_and(|t| t.elapsed() >= watch_runtime::WATCH_DEBOUNCE)
+        {
+            handler::flush_watch_changes(&mut state);
+        }
+        while let Some(path) = state.pending_rescan_paths.pop_front() {
+            fs_runtime::spawn_dir_rescan(
+                fs_tx.clone(),
+                path,
+                state.walk_config.clone(),
+                state.config.one_file_system,
+            );
+        }
+
+        // Re-read `git status` once change notifications settle.
+        if state.git_status_in_flight.is_none()
+            && state
+                .git_status_requested_at
+                .is_some_and(|t| t.elapsed() >= fs_runtime::GIT_STATUS_DEBOUNCE)
+        {
+            state.git_status_requested_at = None;
+            if state.walk_config.show_git_status {
+                state.git_status_generation = state.git_status_generation.wrapping_add(1);
+                let generation = state.git_status_generation;
+                state.git_status_in_flight = Some(generation);
+                fs_runtime::spawn_git_status(fs_tx.clone(), generation, state.cwd.clone());
+            }
+        }
+
         // Expand queued directories in background.
         while let Some(path) = state.pending_expand_paths.pop_front() {
             fs_runtime::spawn_dir_expand(
@@ -379,6 +965,23 @@ async fn main() -> Result<()> {
             );
         }
 
+        if let Some(dir) = state.size_on_demand.take() {
+            state.size_on_demand_cancel = Some(size_runtime::spawn_dir_total(
+                size_tx.clone(),
+                dir,
+                state.config.dedup_hard_links,
+                state.config.one_file_system,
+                state.config.exclude_patterns.clone(),
+            ));
+        }
+
+        if state.search_reindex_requested && (state.is_slow_fs() || state.fixed_tree) {
+            // Index only what is loaded; a full walk would hammer the mount
+            // (or, for a `--stdin` list, find entries that weren't listed).
+            state.search_reindex_requested = false;
+            state.search_index = core::search::index_from_tree(&state.tree, &state.search_root);
+            handler::refresh_search(&mut state);
+        }
         if state.search_reindex_requested && state.search_reindex_in_flight.is_none() {
             state.search_reindex_requested = false;
             state.search_reindex_generation = state.search_reindex_generation.wrapping_add(1);
@@ -388,11 +991,32 @@ async fn main() -> Result<()> {
                 fs_tx.clone(),
                 generation,
                 state.search_root.clone(),
-                state.walk_config.clone(),
+                state.search_options,
                 state.config.one_file_system,
             );
         }
 
+        if let Some((paths, permanent)) = state.deletion_job_requested.take() {
+            fs_runtime::spawn_deletions(fs_tx.clone(), paths, permanent);
+        }
+        if let Some((path, permanent)) = state.delete_job_requested.take() {
+            fs_runtime::spawn_delete(fs_tx.clone(), path, permanent);
+        }
+
+        inspect_runtime::send_inspections(&mut state, &inspect_req_tx);
+        inspect_runtime::cancel_unshown_checksums(&mut state);
+        inspect_runtime::enqueue_checksums(&mut state, &checksum_tx);
+
+        if let Some((path, command)) = state.preview_request.take() {
+            let timeout = Duration::from_millis(state.config.previewer_timeout_ms);
+            state.preview_cancel = Some(preview_runtime::spawn_previewer(
+                preview_tx.clone(),
+                path,
+                command,
+                timeout,
+            ));
+        }
+
         state.fs_scanning = state.tree_rebuild_in_flight.is_some()
             || !state.expand_in_flight.is_empty()
             || state.search_reindex_in_flight.is_some();
@@ -400,113 +1024,133 @@ async fn main() -> Result<()> {
         tokio::select! {
             biased;
 
-            Some(event) = events.recv() => {
+            event = events.recv() => {
+                let Some(event) = event else {
+                    // The reader task only exits when the terminal stops
+                    // delivering input; nothing can drive the UI any more.
+                    input_lost = true;
+                    break;
+                };
+                // Resumed after a stop: the alternate screen may have been
+                // wiped, so repaint everything rather than diffing.
+                if last_event_at.elapsed() >= EVENT_GAP_REPAINT {
+                    force_repaint = true;
+                }
+                last_event_at = std::time::Instant::now();
                 match event {
                     AppEvent::Key(k) => handler::handle_key(&mut state, k),
                     AppEvent::Mouse(m) => handler::handle_mouse(&mut state, m),
+                    AppEvent::Paste(text) => handler::handle_paste(&mut state, &text),
                     AppEvent::Resize(w, h) => {
                         state.terminal_area = Rect::new(0, 0, w, h);
+                        force_repaint = true;
+                    }
+                    // Ticks keep coming while a multiplexer is detached, so
+                    // a reattach shows up as focus or a resize instead.
+                    AppEvent::FocusGained => force_repaint = true,
+                    AppEvent::OpenFailed(reason) => {
+                        state.status_message = Some(format!("Open failed: {reason}"));
                     }
                     AppEvent::Tick => {
                         tick_count = tick_count.wrapping_add(1);
+                        // Some multiplexers resize panes without sending a
+                        // Resize event; poll the real size about once a second.
+                        if tick_count.is_multiple_of(SIZE_POLL_TICKS) {
+                            if let Ok((w, h)) = crossterm::terminal::size() {
+                                let actual = Rect::new(0, 0, w, h);
+                                if actual != state.terminal_area {
+                                    state.terminal_area = actual;
+                                    force_repaint = true;
+                                }
+                            }
+                        }
                         // Drive smooth-scroll animation (detect target change → inject row offset → decay).
                         state.pin_scroll_anim.set_target(state.inspector_pin_scroll, 12.0);
                         state.pin_scroll_anim.tick();
+                        if let Some(ref mut compute) = size_compute {
+                            compute.check_workers();
+                            if let Some(reason) = compute.take_failure() {
+                                mark_scan_failed(&mut state, reason);
+                            }
+                            state.scanning = compute.is_scanning();
+                        }
                     }
                 }
             }
 
-            Some((generation, update)) = size_rx.recv() => {
-                // Process the first message, then batch-drain all remaining
-                // available messages before redrawing.  This prevents stale
-                // messages from old (cancelled) workers from causing
-                // per-message redraws that stall visible progress.
-                let mut need_finalize = false;
-                need_finalize |= apply_size_update(
-                    &mut state, &mut size_compute, generation, update,
-                );
+            Some(msg) = size_rx.recv() => {
+                pending.sizes.push(msg);
+            }
 
-                // Drain everything currently queued without blocking.
-                while let Ok((gen, upd)) = size_rx.try_recv() {
-                    need_finalize |= apply_size_update(
-                        &mut state, &mut size_compute, gen, upd,
-                    );
+            Some(info) = inspect_rx.recv() => {
+                inspect_runtime::apply_inspected(&mut state, info);
+                while let Ok(info) = inspect_rx.try_recv() {
+                    inspect_runtime::apply_inspected(&mut state, info);
                 }
+                enqueue_image_decodes(&mut state, &img_tx);
+            }
 
-                if need_finalize {
-                    if let Some(ref mut compute) = size_compute {
-                        finalize_ready_dirs(&mut state, compute);
-                    }
-                }
+            Some(result) = checksum_rx.recv() => {
+                inspect_runtime::apply_checksum(&mut state, result);
+            }
 
-                // Update scanning flag.
-                if let Some(ref compute) = size_compute {
-                    state.scanning = compute.is_scanning();
-                }
+            Some(result) = preview_rx.recv() => {
+                preview_runtime::apply_preview_result(&mut state, result);
             }
 
             Some(update) = fs_rx.recv() => {
-                match update {
-                    FsUpdate::TreeRebuilt { generation, root, result } => {
-                        if state.tree_rebuild_in_flight == Some(generation) {
-                            state.tree_rebuild_in_flight = None;
-                            match result {
-                                Ok(tree) => {
-                                    state.cwd = root;
-                                    state.tree = tree;
-                                    state.tree_state.selected = 0;
-                                    state.tree_state.offset = 0;
-                                    state.dir_sizes.clear();
-                                    state.file_sizes.clear();
-                                    state.dir_local_sums.clear();
-                                    state.needs_size_recompute = true;
-
-                                    state.search_root = state.cwd.clone();
-                                    state.search_index.clear();
-                                    state.search_reindex_requested = true;
-                                    handler::refresh_search(&mut state);
-                                }
-                                Err(_) => {
-                                    state.status_message = Some("Cannot open directory".to_string());
-                                }
-                            }
-                        }
-                    }
-                    FsUpdate::DirExpanded { path, result } => {
-                        state.expand_in_flight.remove(&path);
-                        if let Ok(children) = result {
-                            if let Some((parent_id, _)) = state
-                                .tree
-                                .nodes
-                                .iter()
-                                .enumerate()
-                                .find(|(_, n)| n.meta.path == path)
-                            {
-                                if state.tree.get(parent_id).children.is_empty() {
-                                    for meta in children {
-                                        state.tree.add_child(parent_id, meta);
-                                    }
-                                    state.dir_local_sums.remove(&path);
-                                    state.needs_size_recompute = true;
-                                }
-                            }
-                        }
-                    }
-                    FsUpdate::SearchIndexed { generation, root, entries } => {
-                        if state.search_reindex_in_flight == Some(generation)
-                            && root == state.search_root
-                        {
-                            state.search_reindex_in_flight = None;
-                            state.search_index = entries;
-                            handler::refresh_search(&mut state);
-                        }
-                    }
+                pending.fs.push(update);
+            }
+        }
+
+        // Drain whatever else has arrived and apply it as one ordered batch
+        // so the next frame sees a consistent tree, selection and sizes.
+        while let Ok(msg) = size_rx.try_recv() {
+            pending.sizes.push(msg);
+        }
+        while let Ok(update) = fs_rx.try_recv() {
+            pending.fs.push(update);
+        }
+        if !pending.is_empty() {
+            let tree_may_change = !pending.fs.is_empty();
+            apply_pending_updates(&mut state, &mut size_compute, std::mem::take(&mut pending));
+            if tree_may_change {
+                if let Some(w) = watcher.as_mut() {
+                    w.sync(&state.tree);
                 }
+            }
+        }
 
-                handler::retry_pending_reveal(&mut state);
-                state.fs_scanning = state.tree_rebuild_in_flight.is_some()
-                    || !state.expand_in_flight.is_empty()
-                    || state.search_reindex_in_flight.is_some();
+        if std::mem::take(&mut state.edit_config_requested) {
+            edit_config_file(&mut terminal, &mut state, mouse_captured, &input_paused)?;
+        }
+        if let Some(path) = state.system_open_requested.take() {
+            let events_tx = events_tx.clone();
+            let opened = integration::open_with_system(&path, move |reason| {
+                if let Some(tx) = events_tx.upgrade() {
+                    let _ = tx.send(AppEvent::OpenFailed(reason));
+                }
+            });
+            state.status_message = Some(match opened {
+                Ok(()) => format!("Opened {}", path.display()),
+                Err(e) => format!("Open failed: {e}"),
+            });
+        }
+        if let Some((command, path)) = state.external_open_requested.take() {
+            let opened = run_suspended(&mut terminal, mouse_captured, &input_paused, || {
+                integration::run_external_command(&command, &path)
+            })?;
+            if let Err(e) = opened {
+                state.status_message = Some(format!("Open failed: {e}"));
+            }
+        }
+
+        if state.mouse_enabled != mouse_captured {
+            mouse_captured = state.mouse_enabled;
+            if mouse_captured {
+                execute!(terminal.backend_mut(), EnableMouseCapture)?;
+            } else {
+                execute!(terminal.backend_mut(), DisableMouseCapture)?;
             }
         }
 
@@ -516,106 +1160,293 @@ async fn main() -> Result<()> {
     }
 
     // ── teardown ──────────────────────────────────────────────
+    if let Some(ref w) = watcher {
+        w.cancel();
+    }
+    // Stop walking the disk now; the workers are joined after the
+    // terminal is restored.  Without a receiver their sends fail fast.
+    drop(size_rx);
+    if let Some(ref compute) = size_compute {
+        compute.request_cancel();
+    }
+    if let Some(cancel) = state.size_on_demand_cancel.take() {
+        cancel.store(true, Ordering::Relaxed);
+    }
+    inspect_runtime::cancel_all_checksums(&mut state);
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture,
+        DisableBracketedPaste,
+        DisableFocusChange,
         PopKeyboardEnhancementFlags
     )?;
+    if mouse_captured {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
     terminal.show_cursor()?;
 
-    integration::print_exit_payload(state.selected_dir.as_deref(), state.copied_path.as_deref());
+    // Best effort: a failed write only costs the next launch a rescan.
+    let _ = size_runtime::save_size_cache(&state);
+
+    if let Some(compute) = size_compute.take() {
+        compute.shutdown(QUIT_JOIN_TIMEOUT);
+    }
+
+    if input_lost {
+        anyhow::bail!("terminal input stopped unexpectedly; exiting");
+    }
+
+    // Use whatever size is known; never wait on the scan to exit.
+    let cd_size = state
+        .selected_dir
+        .as_ref()
+        .and_then(|dir| state.dir_sizes.get(dir).copied());
+    if state.config.confirm_cd {
+        if let Some(dir) = state.selected_dir.as_deref() {
+            integration::print_cd_confirmation(dir, cd_size);
+        }
+    }
+    integration::print_exit_payload(
+        state.selected_dir.as_deref(),
+        state.copied_path.as_deref(),
+        cd_size,
+    );
 
     Ok(())
 }
 
+/// Persistent info for the first line of a two-line status bar.
+fn status_info_text(state: &AppState) -> String {
+    let mut parts = Vec::new();
+    if let Some(path) = handler::selected_node_path(state) {
+        parts.push(path.display().to_string());
+    }
+    if let Some(total) = state.dir_sizes.get(&state.cwd) {
+        parts.push(format!("total {}", core::grouping::human_size(*total)));
+    }
+    if state.scanning {
+        parts.push(format!("scanning sizes… {}", state.scan_progress.summary()));
+    } else if state.fs_scanning {
+        parts.push("listing…".to_string());
+    }
+    if !state.pending_deletions.is_empty() {
+        parts.push(format!("{} staged", state.pending_deletions.len()));
+    }
+    parts.join(" | ")
+}
+
 fn refresh_inspector(
     state: &mut AppState,
-    img_tx: &std::sync::mpsc::Sender<(PathBuf, image::RgbaImage)>,
+    img_tx: &std::sync::mpsc::Sender<(PathBuf, Option<image::RgbaImage>)>,
 ) {
+    if state.inspect_due.is_some_and(|due| due <= std::time::Instant::now()) {
+        state.inspect_due = None;
+        if let Some(path) = state.inspector_path.clone() {
+            state.pending_inspections.push_back(path);
+        }
+    }
     let selected = handler::selected_node_path(state);
     if selected == state.inspector_path {
-        if let (Some(path), Some(info)) = (selected.as_ref(), state.inspector_info.as_mut()) {
-            if let Some(sz) = state.dir_sizes.get(path).copied() {
-                info.size_bytes = Some(sz);
-            } else if let Some(sz) = state.file_sizes.get(path).copied() {
-                info.size_bytes = Some(sz);
-            }
-        }
-        for pin in &mut state.pinned_inspector {
-            if let Some(sz) = state.dir_sizes.get(&pin.path).copied() {
-                pin.size_bytes = Some(sz);
-            } else if let Some(sz) = state.file_sizes.get(&pin.path).copied() {
-                pin.size_bytes = Some(sz);
-            }
-        }
+        fill_inspector_sizes(state);
         enqueue_image_decodes(state, img_tx);
         return;
     }
     state.inspector_path = selected.clone();
+    preview_runtime::cancel_preview(state);
+    // On slow filesystems wait for the selection to settle before
+    // touching the file.
+    let slow = state.is_slow_fs();
+    state.inspect_due = (slow && selected.is_some())
+        .then(|| std::time::Instant::now() + SLOW_FS_INSPECT_DEBOUNCE);
     state.inspector_info = selected.as_ref().map(|path| {
-        let mut info = crate::core::inspector::inspect_path(path);
-        if let Some(sz) = state.dir_sizes.get(path).copied() {
-            info.size_bytes = Some(sz);
-        } else if let Some(sz) = state.file_sizes.get(path).copied() {
-            info.size_bytes = Some(sz);
+        if !slow {
+            state.pending_inspections.push_back(path.clone());
         }
-        info
+        InspectorInfo::pending(path)
     });
+    fill_inspector_sizes(state);
+    enqueue_image_decodes(state, img_tx);
+}
+
+/// Release the terminal, run `run` (typically an external program that
+/// needs the tty), then take the terminal back and force a full repaint.
+/// The event reader is paused meanwhile so it doesn't eat the child's input.
+fn run_suspended<T>(
+    terminal: &mut Terminal<CrosstermBackend<io::Stderr>>,
+    mouse_captured: bool,
+    input_paused: &AtomicBool,
+    run: impl FnOnce() -> T,
+) -> Result<T> {
+    input_paused.store(true, Ordering::Relaxed);
+    // Let a poll already in progress run out before the child reads.
+    std::thread::sleep(EVENT_TICK * 2);
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableBracketedPaste,
+        DisableFocusChange,
+        PopKeyboardEnhancementFlags
+    )?;
+    if mouse_captured {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    terminal.show_cursor()?;
+
+    let result = run();
+
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableBracketedPaste,
+        EnableFocusChange,
+        PushKeyboardEnhancementFlags(KEYBOARD_FLAGS)
+    )?;
+    if mouse_captured {
+        execute!(terminal.backend_mut(), EnableMouseCapture)?;
+    }
+    terminal.clear()?;
+    input_paused.store(false, Ordering::Relaxed);
+    Ok(result)
+}
 
-    for pin in &mut state.pinned_inspector {
-        if let Some(sz) = state.dir_sizes.get(&pin.path).copied() {
-            pin.size_bytes = Some(sz);
-        } else if let Some(sz) = state.file_sizes.get(&pin.path).copied() {
-            pin.size_bytes = Some(sz);
+/// Open the config file in the user's editor (writing the defaults first
+/// if it doesn't exist yet), then re-read it and apply it live.
+fn edit_config_file(
+    terminal: &mut Terminal<CrosstermBackend<io::Stderr>>,
+    state: &mut AppState,
+    mouse_captured: bool,
+    input_paused: &AtomicBool,
+) -> Result<()> {
+    let path = config::config_path();
+    if !path.exists() {
+        if let Err(e) = config::AppConfig::default().save() {
+            state.status_message = Some(format!("Cannot create {}: {e}", path.display()));
+            return Ok(());
         }
     }
-    enqueue_image_decodes(state, img_tx);
+    let editor = integration::editor_command();
+    let edited = run_suspended(terminal, mouse_captured, input_paused, || {
+        integration::run_external_command(&editor, &path)
+    })?;
+    if let Err(e) = edited {
+        state.status_message = Some(format!("Editor failed: {e}"));
+        return Ok(());
+    }
+
+    let (config, warnings) = config::AppConfig::load_checked();
+    app::settings::apply_reloaded_config(state, config);
+    if warnings.is_empty() {
+        state.status_message = Some("Config reloaded".to_string());
+    } else {
+        state.status_message = Some(format!("Config reloaded with {} problem(s)", warnings.len()));
+        state.config_warnings = warnings;
+        state.active_view = ActiveView::ConfigWarnings;
+    }
+    Ok(())
+}
+
+/// Copy already-computed sizes into the current and pinned inspector
+/// records.
+fn fill_inspector_sizes(state: &mut AppState) {
+    let mut info = state.inspector_info.take();
+    let mut pins = std::mem::take(&mut state.pinned_inspector);
+    for info in info.iter_mut().chain(pins.iter_mut()) {
+        state.fill_known_sizes(info);
+    }
+    state.inspector_info = info;
+    state.pinned_inspector = pins;
 }
 
+/// Selection must stay put this long before it is inspected on a slow
+/// filesystem.
+const SLOW_FS_INSPECT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// How long quitting waits for cancelled size workers to exit.
+const QUIT_JOIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Poll interval of the event reader; one `Tick` per idle interval.
+const EVENT_TICK: Duration = Duration::from_millis(50);
+
+/// Enhanced keyboard reporting requested while the TUI owns the terminal.
+const KEYBOARD_FLAGS: KeyboardEnhancementFlags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+    .union(KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES);
+
+/// Ticks (50 ms each) between terminal size polls.
+const SIZE_POLL_TICKS: u64 = 20;
+
+/// How long a message stays up when the status bar is hidden.
+const STATUS_OVERLAY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Gap between handled events (the process was stopped) after which the
+/// screen is fully repainted.
+const EVENT_GAP_REPAINT: Duration = Duration::from_secs(2);
+
 /// Maximum pixel dimension for cached image thumbnails.
 /// Terminal previews are at most ~80×60 pixels so 200 is generous headroom.
 const IMG_THUMB_MAX: u32 = 200;
 
-/// Spawn background threads to decode + pre-resize any images that are needed
-/// for the current selection or pinned cards but aren't yet cached.
+/// Maximum number of image decodes running at once.  Bulk pinning can queue
+/// dozens of thumbnails; decoding them all in parallel starves the UI.
+const IMG_DECODE_CONCURRENCY: usize = 3;
+
+/// Queue decodes for any images that are needed for the current selection
+/// or pinned cards but aren't yet cached, then start as many as allowed.
+/// The current selection always jumps to the front of the queue; pins are
+/// decoded in card order.
 fn enqueue_image_decodes(
     state: &mut AppState,
-    tx: &std::sync::mpsc::Sender<(PathBuf, image::RgbaImage)>,
+    tx: &std::sync::mpsc::Sender<(PathBuf, Option<image::RgbaImage>)>,
 ) {
-    let mut needed: Vec<PathBuf> = Vec::new();
-
-    if let Some(info) = &state.inspector_info {
-        if info.is_image()
-            && !state.image_cache.contains_key(&info.path)
-            && !state.image_decoding.contains(&info.path)
+    // Keyed by the preview path so symlinks share their target's decode.
+    let selected = state.inspector_info.iter().map(|info| (info, true));
+    let pinned = state.pinned_inspector.iter().map(|info| (info, false));
+    let mut front: Option<PathBuf> = None;
+    for (info, is_selection) in selected.chain(pinned) {
+        let path = info.preview_path();
+        if !info.is_image()
+            || state.image_cache.contains_key(path)
+            || state.image_decoding.contains(path)
         {
-            needed.push(info.path.clone());
+            continue;
         }
-    }
-    for pin in &state.pinned_inspector {
-        if pin.is_image()
-            && !state.image_cache.contains_key(&pin.path)
-            && !state.image_decoding.contains(&pin.path)
+        if is_selection {
+            state.image_decode_queue.retain(|p| p != path);
+            front = Some(path.to_path_buf());
+        } else if !state.image_decode_queue.iter().any(|p| p == path)
+            && front.as_deref() != Some(path)
         {
-            needed.push(pin.path.clone());
+            state.image_decode_queue.push_back(path.to_path_buf());
         }
     }
+    if let Some(path) = front {
+        state.image_decode_queue.push_front(path);
+    }
 
-    for path in needed {
+    pump_image_decodes(state, tx);
+}
+
+/// Start queued decodes on background threads up to the concurrency limit.
+fn pump_image_decodes(
+    state: &mut AppState,
+    tx: &std::sync::mpsc::Sender<(PathBuf, Option<image::RgbaImage>)>,
+) {
+    while state.image_decoding.len() < IMG_DECODE_CONCURRENCY {
+        let Some(path) = state.image_decode_queue.pop_front() else {
+            break;
+        };
+        if state.image_cache.contains_key(&path) {
+            continue;
+        }
         state.image_decoding.insert(path.clone());
         let sender = tx.clone();
+        let backdrop = Theme::image_backdrop();
         std::thread::spawn(move || {
-            if let Ok(img) = image::open(&path) {
-                // Pre-resize so the cached bitmap is tiny and rendering is free.
-                let thumb = img.resize(
-                    IMG_THUMB_MAX,
-                    IMG_THUMB_MAX,
-                    image::imageops::FilterType::Triangle,
-                );
-                let _ = sender.send((path, thumb.to_rgba8()));
-            }
+            // Pre-resize so the cached bitmap is tiny and rendering is free.
+            let thumb = core::thumbnail::decode_thumbnail(&path, IMG_THUMB_MAX, backdrop);
+            let _ = sender.send((path, thumb));
         });
     }
 }
@@ -3,40 +3,64 @@
 use std::io::Write;
 use std::path::Path;
 use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
 const CD_PREFIX: &str = "__DT_CD__=";
 const CLIP_PREFIX: &str = "__DT_CLIP__=";
+const META_PREFIX: &str = "__DT_META__=";
 
 /// Emit machine-readable exit payload for shell wrappers.
-pub fn print_exit_payload(cd_dir: Option<&Path>, copied_path: Option<&Path>) {
+///
+/// `cd_size` is the cd target's total size in bytes, when already computed;
+/// it is emitted as `__DT_META__=size=<bytes>`.
+pub fn print_exit_payload(cd_dir: Option<&Path>, copied_path: Option<&Path>, cd_size: Option<u64>) {
     if let Some(path) = cd_dir {
         println!("{CD_PREFIX}{}", path.display());
+        if let Some(bytes) = cd_size {
+            println!("{META_PREFIX}size={bytes}");
+        }
     }
     if let Some(path) = copied_path {
         println!("{CLIP_PREFIX}{}", path.display());
     }
 }
 
+/// Human-readable confirmation of the cd target, printed to stderr after
+/// teardown (stdout is captured by the shell wrapper).
+pub fn print_cd_confirmation(cd_dir: &Path, cd_size: Option<u64>) {
+    match cd_size {
+        Some(bytes) => eprintln!(
+            "cd → {} ({})",
+            cd_dir.display(),
+            crate::core::grouping::human_size(bytes)
+        ),
+        None => eprintln!("cd → {}", cd_dir.display()),
+    }
+}
+
 /// Attempt to copy `path` into the system clipboard.
 pub fn copy_path_to_clipboard(path: &Path) -> bool {
-    let text = path.display().to_string();
+    copy_text_to_clipboard(&path.display().to_string())
+}
 
+/// Attempt to copy `text` into the system clipboard.
+pub fn copy_text_to_clipboard(text: &str) -> bool {
     #[cfg(target_os = "macos")]
     {
-        return run_clip_command("pbcopy", &[], &text);
+        return run_clip_command("pbcopy", &[], text);
     }
 
     #[cfg(target_os = "windows")]
     {
-        return run_clip_command("cmd", &["/C", "clip"], &text);
+        return run_clip_command("cmd", &["/C", "clip"], text);
     }
 
     #[cfg(target_os = "linux")]
     {
-        if run_clip_command("wl-copy", &[], &text) {
+        if run_clip_command("wl-copy", &[], text) {
             return true;
         }
-        return run_clip_command("xclip", &["-selection", "clipboard"], &text);
+        return run_clip_command("xclip", &["-selection", "clipboard"], text);
     }
 
     #[allow(unreachable_code)]
@@ -66,6 +90,132 @@ fn run_clip_command(cmd: &str, args: &[&str], input: &str) -> bool {
     child.wait().map(|s| s.success()).unwrap_or(false)
 }
 
+/// Editor for external edits: `$EDITOR`, then `$VISUAL`, then `vi`.
+pub fn editor_command() -> String {
+    ["EDITOR", "VISUAL"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|cmd| !cmd.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Pager for viewing files: `$PAGER`, then `less`.
+pub fn pager_command() -> String {
+    std::env::var("PAGER")
+        .ok()
+        .filter(|cmd| !cmd.trim().is_empty())
+        .unwrap_or_else(|| "less".to_string())
+}
+
+/// Run `cmd` (split on whitespace, so `"code --wait"` works) on `path` and
+/// wait for it.  The caller must have released the terminal first.
+///
+/// The child's stdout goes to stderr: the shell wrapper captures our
+/// stdout, and a full-screen program has to draw on the terminal.
+pub fn run_external_command(cmd: &str, path: &Path) -> anyhow::Result<()> {
+    let mut parts = cmd.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("no command configured"))?;
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .stdout(std::io::stderr())
+        .status()
+        .map_err(|e| anyhow::anyhow!("{program}: {e}"))?;
+    if !status.success() {
+        anyhow::bail!("{program} exited with {status}");
+    }
+    Ok(())
+}
+
+/// How long [`open_with_system`] watches the opener for a failure.
+const OPEN_FAILURE_WAIT: Duration = Duration::from_millis(1500);
+
+/// Open `path` with the platform's default application (`xdg-open`,
+/// `open` or `start`); directories open in the file manager.
+///
+/// The opener runs detached with its output nulled so it can't draw over
+/// the TUI.  A missing command is returned at once.  Openers normally hand
+/// off and exit quickly, so a background thread watches the child for
+/// [`OPEN_FAILURE_WAIT`] and passes a non-zero exit to `on_failure`; one
+/// still running after that is left alone and reaped.
+pub fn open_with_system(path: &Path, on_failure: impl FnOnce(String) + Send + 'static) -> anyhow::Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut c = Command::new("cmd");
+        // The empty string is `start`'s window title.
+        c.args(["/C", "start", ""]);
+        c
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = Command::new("xdg-open");
+
+    let program = command.get_program().to_string_lossy().into_owned();
+    let mut child = command
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("{program}: {e}"))?;
+
+    std::thread::spawn(move || {
+        let started = Instant::now();
+        while started.elapsed() < OPEN_FAILURE_WAIT {
+            match child.try_wait() {
+                Ok(Some(status)) if !status.success() => {
+                    return on_failure(format!("{program} exited with {status}"));
+                }
+                Ok(Some(_)) => return,
+                Ok(None) => std::thread::sleep(Duration::from_millis(20)),
+                Err(e) => return on_failure(format!("{program}: {e}")),
+            }
+        }
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// Terminals tried after the configured one and `$TERMINAL`.
+const FALLBACK_TERMINALS: &[&str] = &["alacritty", "kitty", "gnome-terminal", "xterm"];
+
+/// Launch a terminal emulator whose shell starts in `dir`, without waiting
+/// for it.  Tries `configured`, then `$TERMINAL`, then
+/// [`FALLBACK_TERMINALS`], skipping commands that aren't installed, and
+/// returns the program that started.
+pub fn open_terminal(dir: &Path, configured: Option<&str>) -> anyhow::Result<String> {
+    let from_env = std::env::var("TERMINAL").ok();
+    let candidates = configured
+        .into_iter()
+        .chain(from_env.as_deref())
+        .chain(FALLBACK_TERMINALS.iter().copied());
+    for cmd in candidates {
+        let mut parts = cmd.split_whitespace();
+        let Some(program) = parts.next() else {
+            continue;
+        };
+        let spawned = Command::new(program)
+            .args(parts)
+            .current_dir(dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match spawned {
+            Ok(mut child) => {
+                std::thread::spawn(move || child.wait());
+                return Ok(program.to_string());
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => anyhow::bail!("{program}: {e}"),
+        }
+    }
+    anyhow::bail!("no terminal emulator found (set terminal_emulator in the config)")
+}
+
 /// Returns the bash function that users should add to their `.bashrc`.
 ///
 /// The function name is `dt` and it invokes the binary by its package name
@@ -131,3 +281,75 @@ dt() {{
     )
 }
 
+/// Returns the PowerShell function that users should add to their
+/// `$PROFILE`.
+pub fn powershell_function() -> String {
+    let bin = env!("CARGO_PKG_NAME");
+    format!(
+        r#"
+# ── {bin}: tree-based directory navigator ──────────────────
+# Toggle with `dt`.  Enter on a directory changes the location, and copy
+# actions print a clipboard notice after the TUI exits.
+function dt {{
+    $output = & (Get-Command {bin} -CommandType Application | Select-Object -First 1) @args
+    $exitCode = $LASTEXITCODE
+    $dest = $null
+    $copied = $null
+    foreach ($line in ($output -split "`r?`n")) {{
+        if ($line -match '^{CD_PREFIX}(.*)$') {{ $dest = $Matches[1] }}
+        elseif ($line -match '^{CLIP_PREFIX}(.*)$') {{ $copied = $Matches[1] }}
+    }}
+    if ($exitCode -eq 0 -and $dest -and (Test-Path -LiteralPath $dest -PathType Container)) {{
+        Set-Location -LiteralPath $dest
+    }}
+    if ($exitCode -eq 0 -and $copied) {{
+        Write-Host "Copied to clipboard: $copied"
+    }}
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every `{` in the script is closed, in order.
+    fn balanced(script: &str) -> bool {
+        let mut depth = 0i32;
+        for c in script.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            if depth < 0 {
+                return false;
+            }
+        }
+        depth == 0
+    }
+
+    #[test]
+    fn powershell_function_reads_the_payload() {
+        let script = powershell_function();
+        assert!(script.contains("function dt {"));
+        assert!(script.contains(&format!("'^{CD_PREFIX}(.*)$'")));
+        assert!(script.contains(&format!("'^{CLIP_PREFIX}(.*)$'")));
+        assert!(script.contains("Set-Location -LiteralPath $dest"));
+        assert!(script.contains("Write-Host \"Copied to clipboard: $copied\""));
+        assert!(script.contains("$exitCode -eq 0"));
+        assert!(balanced(&script));
+    }
+
+    #[test]
+    fn posix_functions_read_the_payload() {
+        for script in [bash_function(), zsh_function()] {
+            assert!(script.contains("dt() {"));
+            assert!(script.contains(&format!("{CD_PREFIX}*) dest=\"${{line#{CD_PREFIX}}}\"")));
+            assert!(script.contains(&format!("{CLIP_PREFIX}*) copied=\"${{line#{CLIP_PREFIX}}}\"")));
+            assert!(script.contains("cd \"$dest\""));
+            assert!(balanced(&script));
+        }
+    }
+}
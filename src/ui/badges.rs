@@ -0,0 +1,128 @@
+//! Badge cluster at the right end of the status bar, flagging scan
+//! settings that change what the sizes mean (hidden files shown, hard
+//! links counted twice, ...), so a non-default state isn't forgotten.
+
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+
+use super::theme::Theme;
+use crate::app::state::AppState;
+
+/// One setting that can be flagged in the status bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusBadge {
+    /// Dot-files are listed.
+    Hidden,
+    /// Hard links are counted once per link.
+    NoDedup,
+    /// Scans stop at mount points.
+    OneFileSystem,
+    /// The tree is a path list read from stdin.
+    Listed,
+}
+
+impl StatusBadge {
+    pub const ALL: &'static [StatusBadge] = &[
+        StatusBadge::Hidden,
+        StatusBadge::NoDedup,
+        StatusBadge::OneFileSystem,
+        StatusBadge::Listed,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Hidden => "[.]",
+            Self::NoDedup => "[nolink]",
+            Self::OneFileSystem => "[1fs]",
+            Self::Listed => "[stdin]",
+        }
+    }
+
+    /// Whether the flagged state is currently in effect.
+    pub fn is_active(self, state: &AppState) -> bool {
+        match self {
+            Self::Hidden => state.walk_config.show_hidden,
+            Self::NoDedup => !state.config.dedup_hard_links,
+            Self::OneFileSystem => state.config.one_file_system,
+            Self::Listed => state.fixed_tree,
+        }
+    }
+
+    /// Whether clicking the badge may flip its setting.  The `--stdin`
+    /// tree can't be rebuilt, so badges that need a rebuild are inert
+    /// there.
+    pub fn is_toggleable(self, state: &AppState) -> bool {
+        match self {
+            Self::Hidden | Self::OneFileSystem => !state.fixed_tree,
+            Self::NoDedup => true,
+            Self::Listed => false,
+        }
+    }
+}
+
+/// Badges for the states currently in effect, in display order.
+pub fn active_badges(state: &AppState) -> Vec<StatusBadge> {
+    StatusBadge::ALL
+        .iter()
+        .copied()
+        .filter(|b| b.is_active(state))
+        .collect()
+}
+
+/// Rendered cluster plus the screen column span of each shown badge.
+pub struct BadgeCluster {
+    pub line: Line<'static>,
+    pub width: u16,
+    pub zones: Vec<(Rect, StatusBadge)>,
+}
+
+/// Lay `badges` out right-aligned on `row`, using at most `max_width`
+/// columns.  Badges that don't fit are dropped from the left (keeping the
+/// focused one) and counted in a leading `[+N]`.
+pub fn layout(
+    state: &AppState,
+    badges: &[StatusBadge],
+    focused: Option<StatusBadge>,
+    row: Rect,
+    max_width: u16,
+) -> BadgeCluster {
+    let mut shown: Vec<StatusBadge> = badges.to_vec();
+    let cluster_width = |shown: &[StatusBadge], elided: usize| -> usize {
+        let labels: usize = shown.iter().map(|b| b.label().chars().count() + 1).sum();
+        let more = if elided > 0 { format!("[+{elided}] ").len() } else { 0 };
+        labels + more
+    };
+    while !shown.is_empty() && cluster_width(&shown, badges.len() - shown.len()) > max_width as usize {
+        let drop = shown.iter().position(|&b| Some(b) != focused).unwrap_or(0);
+        shown.remove(drop);
+    }
+    let elided = badges.len() - shown.len();
+    if shown.is_empty() {
+        return BadgeCluster { line: Line::default(), width: 0, zones: Vec::new() };
+    }
+
+    let width = cluster_width(&shown, elided) as u16;
+    let mut x = row.right().saturating_sub(width);
+    let mut spans = Vec::new();
+    if elided > 0 {
+        let more = format!("[+{elided}] ");
+        x += more.len() as u16;
+        spans.push(Span::styled(more, Theme::status_badge_info_style()));
+    }
+    let mut zones = Vec::new();
+    for &badge in &shown {
+        let style = if Some(badge) == focused {
+            Theme::status_badge_focus_style()
+        } else if badge.is_toggleable(state) {
+            Theme::status_badge_style()
+        } else {
+            Theme::status_badge_info_style()
+        };
+        let w = badge.label().chars().count() as u16;
+        zones.push((Rect::new(x, row.y, w, 1), badge));
+        spans.push(Span::styled(badge.label(), style));
+        spans.push(Span::raw(" "));
+        x += w + 1;
+    }
+    BadgeCluster { line: Line::from(spans), width, zones }
+}
@@ -0,0 +1,36 @@
+//! Best-effort detection of terminal capabilities from the environment.
+
+/// What the terminal appears to support.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TerminalCaps {
+    /// 24-bit colour (`COLORTERM=truecolor|24bit`).
+    pub truecolor: bool,
+    /// Kitty graphics protocol (kitty, WezTerm, Ghostty).
+    pub kitty_graphics: bool,
+}
+
+impl TerminalCaps {
+    pub fn detect() -> Self {
+        let var = |name: &str| std::env::var(name).unwrap_or_default().to_lowercase();
+        let colorterm = var("COLORTERM");
+        let term = var("TERM");
+        let term_program = var("TERM_PROGRAM");
+        Self {
+            truecolor: colorterm == "truecolor" || colorterm == "24bit",
+            kitty_graphics: std::env::var_os("KITTY_WINDOW_ID").is_some()
+                || term.contains("kitty")
+                || term_program == "wezterm"
+                || term_program == "ghostty",
+        }
+    }
+
+    /// Short summary, e.g. `"truecolor, kitty graphics"`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        parts.push(if self.truecolor { "truecolor" } else { "256 colors" });
+        if self.kitty_graphics {
+            parts.push("kitty graphics");
+        }
+        parts.join(", ")
+    }
+}
@@ -0,0 +1,63 @@
+//! Debug overlay — a small box in the top-right corner with internal
+//! counters (caches, background workers).  Toggled with `F12` by default.
+
+use std::sync::atomic::Ordering;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::app::state::AppState;
+
+/// Debug counters overlay.
+pub struct DebugOverlay<'a> {
+    pub state: &'a AppState,
+}
+
+impl<'a> DebugOverlay<'a> {
+    fn lines(&self) -> Vec<String> {
+        let mime = &self.state.mime_stats;
+        vec![format!(
+            "MIME cache: {} entries, {} hits / {} misses",
+            mime.entries.load(Ordering::Relaxed),
+            mime.hits.load(Ordering::Relaxed),
+            mime.misses.load(Ordering::Relaxed),
+        )]
+    }
+}
+
+impl<'a> Widget for DebugOverlay<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = self.lines();
+        let content_w = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16;
+        let width = (content_w + 4).min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        if width < 8 || height < 3 {
+            return;
+        }
+        let popup = Rect::new(area.x + area.width - width, area.y, width, height);
+        Clear.render(popup, buf);
+
+        let block = Block::default()
+            .title(" Debug ")
+            .title_style(
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::DarkGray));
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let text: Vec<Line> = lines.into_iter().map(|l| Line::raw(format!(" {l}"))).collect();
+        Paragraph::new(text)
+            .style(Style::default().fg(Color::Gray))
+            .render(inner, buf);
+    }
+}
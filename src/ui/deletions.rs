@@ -0,0 +1,116 @@
+//! Deletions tab widget — staged deletions with sizes, running total,
+//! job progress, and per-item errors.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget},
+};
+
+use crate::app::handler::{reclaimable_bytes, staged_size};
+use crate::app::state::AppState;
+use crate::config::Action;
+use crate::core::grouping::human_size;
+use crate::ui::theme::Theme;
+
+pub struct DeletionsWidget<'a> {
+    pub block: Block<'a>,
+    pub state: &'a AppState,
+    pub has_focus: bool,
+}
+
+impl<'a> Widget for DeletionsWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let inner = self.block.inner(area);
+        self.block.render(area, buf);
+        if inner.width == 0 || inner.height == 0 {
+            return;
+        }
+
+        let state = self.state;
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let mut lines = vec![Line::from(vec![
+            Span::styled(format!("Staged deletions: {}", state.pending_deletions.len()), bold),
+            Span::styled(
+                format!("  · {} reclaimable", human_size(reclaimable_bytes(state))),
+                Theme::size_style(),
+            ),
+        ])];
+
+        if let Some((done, total)) = state.deletion_progress {
+            lines.push(Line::from(Span::styled(
+                format!("Deleting… {done}/{total}"),
+                Style::default().fg(Color::Yellow),
+            )));
+        } else {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{}: commit · d: unstage · Esc: back",
+                    state.config.short_binding(Action::CommitDeletions)
+                ),
+                Theme::size_style(),
+            )));
+        }
+
+        let error_rows = if state.deletion_errors.is_empty() {
+            0
+        } else {
+            (state.deletion_errors.len() + 2).min(inner.height as usize / 3)
+        };
+        let list_rows = (inner.height as usize)
+            .saturating_sub(lines.len() + 1 + error_rows)
+            .max(1);
+
+        lines.push(Line::raw(""));
+        if state.pending_deletions.is_empty() {
+            let key = state.config.short_binding(Action::StageDelete);
+            lines.push(Line::from(Span::styled(
+                format!("Nothing staged. {key} on a tree entry stages it."),
+                Theme::size_style(),
+            )));
+        } else {
+            let selected = state.deletions_selected;
+            let scroll = selected.saturating_sub(list_rows.saturating_sub(1));
+            for (i, path) in state
+                .pending_deletions
+                .iter()
+                .enumerate()
+                .skip(scroll)
+                .take(list_rows)
+            {
+                let is_selected = self.has_focus && i == selected;
+                let marker = if is_selected { "> " } else { "  " };
+                let size = staged_size(state, path)
+                    .map(human_size)
+                    .unwrap_or_else(|| "…".to_string());
+                let style = if is_selected {
+                    Theme::selected_style()
+                } else {
+                    Theme::file_style()
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{marker}{}", path.display()), style),
+                    Span::styled(format!("  {size}"), Theme::size_style()),
+                ]));
+            }
+        }
+
+        if error_rows > 0 {
+            lines.push(Line::raw(""));
+            lines.push(Line::from(Span::styled(
+                format!("Failed ({})", state.deletion_errors.len()),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )));
+            for (path, err) in state.deletion_errors.iter().take(error_rows - 2) {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}: {err}", path.display()),
+                    Style::default().fg(Color::Red),
+                )));
+            }
+        }
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
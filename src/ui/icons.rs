@@ -0,0 +1,181 @@
+//! Tree row prefixes for each [`IconSet`], including the nerd-font glyph
+//! table.
+//!
+//! Glyphs come from the Nerd Fonts patched set (Seti / Devicons / Font
+//! Awesome ranges); without a patched font they render as tofu, which is
+//! why the ASCII set stays the default.
+
+use phf::phf_map;
+
+use crate::config::IconSet;
+use crate::core::tree::EntryMeta;
+
+const FOLDER_CLOSED: &str = "\u{f07b} ";
+const FOLDER_OPEN: &str = "\u{f07c} ";
+const SYMLINK: &str = "\u{f0c1} ";
+const FILE: &str = "\u{f15b} ";
+
+/// Glyph (plus a trailing space) per lower-cased file extension.
+static EXTENSION_ICONS: phf::Map<&'static str, &'static str> = phf_map! {
+    // Languages
+    "rs" => "\u{e7a8} ",
+    "py" => "\u{e606} ",
+    "pyc" => "\u{e606} ",
+    "js" => "\u{e74e} ",
+    "mjs" => "\u{e74e} ",
+    "cjs" => "\u{e74e} ",
+    "ts" => "\u{e628} ",
+    "tsx" => "\u{e7ba} ",
+    "jsx" => "\u{e7ba} ",
+    "go" => "\u{e627} ",
+    "c" => "\u{e61e} ",
+    "h" => "\u{e61e} ",
+    "cpp" => "\u{e61d} ",
+    "cc" => "\u{e61d} ",
+    "hpp" => "\u{e61d} ",
+    "cs" => "\u{f81a} ",
+    "java" => "\u{e738} ",
+    "kt" => "\u{e634} ",
+    "swift" => "\u{e755} ",
+    "rb" => "\u{e739} ",
+    "php" => "\u{e73d} ",
+    "lua" => "\u{e620} ",
+    "hs" => "\u{e777} ",
+    "ex" => "\u{e62d} ",
+    "exs" => "\u{e62d} ",
+    "erl" => "\u{e7b1} ",
+    "scala" => "\u{e737} ",
+    "clj" => "\u{e768} ",
+    "dart" => "\u{e798} ",
+    "zig" => "\u{e6a9} ",
+    "vim" => "\u{e62b} ",
+    "sh" => "\u{f489} ",
+    "bash" => "\u{f489} ",
+    "zsh" => "\u{f489} ",
+    "fish" => "\u{f489} ",
+    "ps1" => "\u{f489} ",
+    "sql" => "\u{f1c0} ",
+    // Web
+    "html" => "\u{e736} ",
+    "htm" => "\u{e736} ",
+    "css" => "\u{e749} ",
+    "scss" => "\u{e603} ",
+    "sass" => "\u{e603} ",
+    "vue" => "\u{e6a0} ",
+    "svelte" => "\u{e697} ",
+    // Data & config
+    "json" => "\u{e60b} ",
+    "toml" => "\u{e6b2} ",
+    "yaml" => "\u{e6a8} ",
+    "yml" => "\u{e6a8} ",
+    "xml" => "\u{f05c0} ",
+    "ini" => "\u{e615} ",
+    "conf" => "\u{e615} ",
+    "cfg" => "\u{e615} ",
+    "env" => "\u{f462} ",
+    "lock" => "\u{f023} ",
+    "csv" => "\u{f1c3} ",
+    "tsv" => "\u{f1c3} ",
+    "db" => "\u{f1c0} ",
+    "sqlite" => "\u{f1c0} ",
+    // Documents
+    "md" => "\u{e609} ",
+    "markdown" => "\u{e609} ",
+    "txt" => "\u{f15c} ",
+    "log" => "\u{f18d} ",
+    "pdf" => "\u{f1c1} ",
+    "doc" => "\u{f1c2} ",
+    "docx" => "\u{f1c2} ",
+    "xls" => "\u{f1c3} ",
+    "xlsx" => "\u{f1c3} ",
+    "ppt" => "\u{f1c4} ",
+    "pptx" => "\u{f1c4} ",
+    "tex" => "\u{e69b} ",
+    // Images
+    "png" => "\u{f1c5} ",
+    "jpg" => "\u{f1c5} ",
+    "jpeg" => "\u{f1c5} ",
+    "gif" => "\u{f1c5} ",
+    "bmp" => "\u{f1c5} ",
+    "webp" => "\u{f1c5} ",
+    "ico" => "\u{f1c5} ",
+    "tiff" => "\u{f1c5} ",
+    "svg" => "\u{f0721} ",
+    "psd" => "\u{e7b8} ",
+    // Audio & video
+    "mp3" => "\u{f1c7} ",
+    "flac" => "\u{f1c7} ",
+    "wav" => "\u{f1c7} ",
+    "ogg" => "\u{f1c7} ",
+    "m4a" => "\u{f1c7} ",
+    "mp4" => "\u{f1c8} ",
+    "mkv" => "\u{f1c8} ",
+    "mov" => "\u{f1c8} ",
+    "avi" => "\u{f1c8} ",
+    "webm" => "\u{f1c8} ",
+    // Archives
+    "zip" => "\u{f1c6} ",
+    "tar" => "\u{f1c6} ",
+    "gz" => "\u{f1c6} ",
+    "tgz" => "\u{f1c6} ",
+    "bz2" => "\u{f1c6} ",
+    "xz" => "\u{f1c6} ",
+    "zst" => "\u{f1c6} ",
+    "7z" => "\u{f1c6} ",
+    "rar" => "\u{f1c6} ",
+    "deb" => "\u{f1c6} ",
+    "rpm" => "\u{f1c6} ",
+    // Binaries & misc
+    "exe" => "\u{f17a} ",
+    "dll" => "\u{f17a} ",
+    "so" => "\u{f471} ",
+    "o" => "\u{f471} ",
+    "a" => "\u{f471} ",
+    "wasm" => "\u{e6a1} ",
+    "iso" => "\u{f0a0} ",
+    "ttf" => "\u{f031} ",
+    "otf" => "\u{f031} ",
+    "woff" => "\u{f031} ",
+    "woff2" => "\u{f031} ",
+    "diff" => "\u{f440} ",
+    "patch" => "\u{f440} ",
+};
+
+/// Prefix drawn before a tree row's name (icon plus a trailing space), or
+/// nothing for [`IconSet::None`].
+pub fn row_prefix(set: IconSet, meta: &EntryMeta, expanded: bool) -> &'static str {
+    match set {
+        IconSet::None => "",
+        IconSet::Ascii => {
+            if meta.is_symlink {
+                "~ "
+            } else if meta.is_dir {
+                if expanded {
+                    "▼ "
+                } else {
+                    "▶ "
+                }
+            } else {
+                "  "
+            }
+        }
+        IconSet::NerdFont => nerd_font_prefix(meta, expanded),
+    }
+}
+
+fn nerd_font_prefix(meta: &EntryMeta, expanded: bool) -> &'static str {
+    if meta.is_dir {
+        if expanded {
+            FOLDER_OPEN
+        } else {
+            FOLDER_CLOSED
+        }
+    } else if meta.is_symlink {
+        SYMLINK
+    } else {
+        meta.extension
+            .as_deref()
+            .and_then(|ext| EXTENSION_ICONS.get(ext).copied())
+            .unwrap_or(FILE)
+    }
+}
@@ -8,8 +8,8 @@
 //! * **Text helpers** (`current_section_lines`, `info_detail_lines`, etc.)
 //!   — build `Line` vectors from `InspectorInfo`.  Pure, no side-effects.
 //! * **Render helpers** (`render_current_section`, `render_card`,
-//!   `render_scrollbar`, `render_image_halfblocks`) — each draws one
-//!   self-contained piece into a `Buffer`.
+//!   `render_image_halfblocks`) — each draws one self-contained piece into
+//!   a `Buffer`.
 //! * **Widget** (`InspectorWidget`) — thin orchestrator that calls the
 //!   helpers above.
 
@@ -21,11 +21,17 @@ use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::{Color, Modifier, Style},
+    symbols::border,
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
 
-use crate::core::{grouping, inspector::InspectorInfo};
+use crate::core::{
+    grouping,
+    inspector::{self, InspectorInfo},
+    previewer::ExternalPreview,
+    size,
+};
 use crate::ui::theme::Theme;
 
 // ─── constants ──────────────────────────────────────────────────
@@ -37,6 +43,8 @@ const CURRENT_PREVIEW_MAX: u16 = 12;
 const CARD_PREVIEW_ROWS: u16 = 6;
 const TEXT_COL_MAX: u16 = 42;
 const SIDE_BY_SIDE_MIN_WIDTH: u16 = 55;
+/// Archive members visible at once in an "Archive contents" section.
+pub const ARCHIVE_ROWS: u16 = 8;
 
 // ─── geometry ───────────────────────────────────────────────────
 
@@ -59,7 +67,7 @@ pub struct PinnedCardsGeometry {
 
 /// Height of the "Current Selection" section (text + optional image preview).
 pub fn current_section_total_height(info: Option<&InspectorInfo>, panel_width: u16) -> u16 {
-    let text_lines = current_section_lines(info).len() as u16;
+    let text_lines = current_section_lines(info, panel_width).len() as u16;
     let is_image = info.map_or(false, |i| i.is_image());
     if is_image {
         if panel_width >= SIDE_BY_SIDE_MIN_WIDTH {
@@ -161,6 +169,9 @@ pub struct InspectorWidget<'a> {
     pub selected_pin: Option<usize>,
     pub has_focus: bool,
     pub image_cache: &'a HashMap<PathBuf, Arc<image::RgbaImage>>,
+    /// Screen-reader mode: no halfblock previews, blank card borders and
+    /// no scrollbar.
+    pub a11y: bool,
 }
 
 impl<'a> Widget for InspectorWidget<'a> {
@@ -171,10 +182,13 @@ impl<'a> Widget for InspectorWidget<'a> {
             return;
         }
 
+        let no_images = HashMap::new();
+        let image_cache = if self.a11y { &no_images } else { self.image_cache };
+
         // ── current selection ────────────────────────────────────
         let section_h = render_current_section(
             self.info,
-            self.image_cache,
+            image_cache,
             inner,
             buf,
         );
@@ -264,14 +278,18 @@ impl<'a> Widget for InspectorWidget<'a> {
                 top_clipped,
                 bot_clipped,
                 content_skip,
-                self.image_cache,
+                image_cache,
+                self.a11y,
                 buf,
             );
         }
 
         // ── scrollbar (uses target scroll, not animated) ─────────
+        if self.a11y {
+            return;
+        }
         let geom = pinned_cards_geometry(inner, self.info, self.pinned, self.pin_scroll);
-        render_scrollbar(
+        super::scrollbar::render(
             cards_area,
             self.pinned.len(),
             self.pin_scroll,
@@ -290,7 +308,7 @@ fn render_current_section(
     inner: Rect,
     buf: &mut Buffer,
 ) -> u16 {
-    let lines = current_section_lines(info);
+    let lines = current_section_lines(info, inner.width);
     let text_h = (lines.len() as u16).min(inner.height);
 
     let is_image = info.map_or(false, |i| i.is_image());
@@ -307,7 +325,7 @@ fn render_current_section(
             .render(Rect::new(inner.x, inner.y, text_w, section_h), buf);
 
         if img_w > 2 && section_h > 0 {
-            if let Some(img) = info.and_then(|i| image_cache.get(&i.path)) {
+            if let Some(img) = info.and_then(|i| image_cache.get(i.preview_path())) {
                 render_image_halfblocks(
                     img,
                     Rect::new(img_x, inner.y, img_w, section_h),
@@ -325,7 +343,7 @@ fn render_current_section(
         let preview_h = if is_image {
             let avail = inner.height.saturating_sub(text_h).min(CURRENT_PREVIEW_MAX);
             if avail > 1 {
-                if let Some(img) = info.and_then(|i| image_cache.get(&i.path)) {
+                if let Some(img) = info.and_then(|i| image_cache.get(i.preview_path())) {
                     render_image_halfblocks(
                         img,
                         Rect::new(inner.x, inner.y + text_h, inner.width, avail),
@@ -363,6 +381,7 @@ fn render_pinned_header(focused: bool, x: u16, y: u16, w: u16, buf: &mut Buffer)
 /// `vis_rect` is the on-screen area the card occupies (already clamped to the
 /// visible region).  `top_clipped` / `bot_clipped` indicate which edges are
 /// off-screen.  `content_skip` is the number of content rows hidden at the top.
+/// `blank_borders` keeps the card's layout but draws its frame as spaces.
 fn render_animated_card(
     info: &InspectorInfo,
     vis_rect: Rect,
@@ -371,6 +390,7 @@ fn render_animated_card(
     bot_clipped: bool,
     content_skip: u16,
     image_cache: &HashMap<PathBuf, Arc<image::RgbaImage>>,
+    blank_borders: bool,
     buf: &mut Buffer,
 ) {
     let border_style = if is_selected {
@@ -399,6 +419,9 @@ fn render_animated_card(
     let mut block = Block::default()
         .borders(borders)
         .border_style(border_style);
+    if blank_borders {
+        block = block.border_set(border::EMPTY);
+    }
     if !top_clipped {
         block = block.title(Span::styled(
             format!(" {} ", card_title(info)),
@@ -443,6 +466,8 @@ fn render_animated_card(
     };
     let mut body = vec![kv_line("Type", &subtitle)];
     body.extend(info_detail_lines(info));
+    body.extend(archive_lines(info, ca.width));
+    body.extend(text_preview_lines(info, CARD_PREVIEW_ROWS as usize));
     let body_h = body.len() as u16;
 
     let card_sbs = info.is_image() && ca.width >= SIDE_BY_SIDE_MIN_WIDTH;
@@ -455,7 +480,7 @@ fn render_animated_card(
             .scroll((content_skip, 0))
             .render(Rect::new(ca.x, ca.y, tw, ca.height), buf);
         if iw > 2 {
-            if let Some(img) = image_cache.get(&info.path) {
+            if let Some(img) = image_cache.get(info.preview_path()) {
                 render_image_halfblocks(
                     img,
                     Rect::new(ca.x + tw + 1, ca.y, iw, ca.height),
@@ -468,7 +493,7 @@ fn render_animated_card(
             .scroll((content_skip, 0))
             .render(ca, buf);
         if info.is_image() && content_skip < body_h {
-            if let Some(img) = image_cache.get(&info.path) {
+            if let Some(img) = image_cache.get(info.preview_path()) {
                 let preview_start = body_h.saturating_sub(content_skip);
                 let ph = ca.height.saturating_sub(preview_start);
                 if ph > 1 {
@@ -485,7 +510,7 @@ fn render_animated_card(
 
 // ─── text helpers ───────────────────────────────────────────────
 
-fn current_section_lines(info: Option<&InspectorInfo>) -> Vec<Line<'static>> {
+fn current_section_lines(info: Option<&InspectorInfo>, width: u16) -> Vec<Line<'static>> {
     let mut lines = vec![
         Line::from(Span::styled(
             "Current Selection",
@@ -506,6 +531,12 @@ fn current_section_lines(info: Option<&InspectorInfo>) -> Vec<Line<'static>> {
         lines.push(Line::from(Span::styled(sub, Theme::size_style())));
         lines.push(Line::raw(""));
         lines.extend(info_detail_lines(info));
+        lines.extend(archive_lines(info, width));
+        if info.external_preview.is_some() {
+            lines.extend(external_preview_lines(info));
+        } else {
+            lines.extend(text_preview_lines(info, CURRENT_PREVIEW_MAX as usize));
+        }
     } else {
         lines.push(Line::from(Span::styled(
             "Select a file or directory to inspect.",
@@ -515,14 +546,163 @@ fn current_section_lines(info: Option<&InspectorInfo>) -> Vec<Line<'static>> {
     lines
 }
 
+/// Rows from a configured external previewer, capped like image previews.
+fn external_preview_lines(info: &InspectorInfo) -> Vec<Line<'static>> {
+    let Some(preview) = &info.external_preview else {
+        return Vec::new();
+    };
+    let mut l = vec![
+        Line::raw(""),
+        Line::from(Span::styled(
+            "Preview",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    ];
+    match preview {
+        ExternalPreview::Running => l.push(Line::from(Span::styled(
+            "running previewer…",
+            Style::default().fg(Color::DarkGray),
+        ))),
+        ExternalPreview::Failed(reason) => l.push(Line::from(Span::styled(
+            format!("Previewer failed: {reason}"),
+            Style::default().fg(Color::LightRed),
+        ))),
+        ExternalPreview::Output(rows) if rows.is_empty() => l.push(Line::from(Span::styled(
+            "(no output)",
+            Style::default().fg(Color::DarkGray),
+        ))),
+        ExternalPreview::Output(rows) => l.extend(
+            rows.iter()
+                .take(CURRENT_PREVIEW_MAX as usize)
+                .map(|row| Line::raw(row.clone())),
+        ),
+    }
+    l
+}
+
+/// "Preview" header and the first `rows` lines of a text file with line
+/// numbers, or a note when its contents turned out to be binary.
+fn text_preview_lines(info: &InspectorInfo, rows: usize) -> Vec<Line<'static>> {
+    let header = Line::from(Span::styled(
+        "Preview",
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+    if info.looks_binary {
+        return vec![
+            Line::raw(""),
+            header,
+            Line::from(Span::styled("binary file", Style::default().fg(Color::DarkGray))),
+        ];
+    }
+    let Some(preview) = &info.text_preview else {
+        return Vec::new();
+    };
+    let mut l = vec![Line::raw(""), header];
+    if preview.is_empty() {
+        l.push(Line::from(Span::styled("(empty)", Style::default().fg(Color::DarkGray))));
+    }
+    let shown = preview.len().min(rows);
+    let number_width = shown.to_string().len();
+    let highlighted = info.text_highlight.as_ref().filter(|h| h.len() >= shown);
+    l.extend((0..shown).map(|i| {
+        let mut spans = vec![Span::styled(format!("{:>number_width$} ", i + 1), Theme::size_style())];
+        let (text, marker) = inspector::split_truncated(&preview[i]);
+        match highlighted {
+            Some(lines) => spans.extend(
+                lines[i]
+                    .iter()
+                    .map(|(kind, text)| Span::styled(text.clone(), Theme::syntax_style(*kind))),
+            ),
+            None => spans.push(Span::raw(text.to_string())),
+        }
+        if let Some(marker) = marker {
+            spans.push(Span::styled(marker.to_string(), Style::default().fg(Color::DarkGray)));
+        }
+        Line::from(spans)
+    }));
+    l
+}
+
+/// "Archive contents" header and the [`ARCHIVE_ROWS`] members from
+/// `archive_scroll` on, with names cut to `width` columns.
+fn archive_lines(info: &InspectorInfo, width: u16) -> Vec<Line<'static>> {
+    let Some(entries) = &info.archive_entries else {
+        return Vec::new();
+    };
+    let mut header = vec![Span::styled(
+        "Archive contents",
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    let scroll = info.archive_scroll.min(max_archive_scroll(info));
+    let shown = entries.len().min(ARCHIVE_ROWS as usize);
+    if entries.len() > shown {
+        header.push(Span::styled(
+            format!("  {}–{} of {}", scroll + 1, scroll + shown, entries.len()),
+            Theme::size_style(),
+        ));
+    }
+    let mut l = vec![Line::raw(""), Line::from(header)];
+    if entries.is_empty() {
+        l.push(Line::from(Span::styled("(empty)", Style::default().fg(Color::DarkGray))));
+    }
+    l.extend(
+        entries[scroll..scroll + shown]
+            .iter()
+            .map(|name| Line::raw(truncate_name(name, width as usize))),
+    );
+    let unlisted = info.archive_entry_count.unwrap_or(0).saturating_sub(entries.len() as u64);
+    if unlisted > 0 {
+        l.push(Line::from(Span::styled(
+            format!("… {unlisted} more not listed"),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    l
+}
+
+/// Largest useful `archive_scroll` for `info`.
+pub fn max_archive_scroll(info: &InspectorInfo) -> usize {
+    info.archive_entries
+        .as_ref()
+        .map_or(0, |e| e.len().saturating_sub(ARCHIVE_ROWS as usize))
+}
+
+fn truncate_name(name: &str, width: usize) -> String {
+    if name.chars().count() <= width {
+        return name.to_string();
+    }
+    let mut cut: String = name.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
 fn info_detail_lines(info: &InspectorInfo) -> Vec<Line<'static>> {
     let mut l = Vec::new();
     l.push(kv_line("Path", &info.path.display().to_string()));
-    if let Some(sz) = info.size_bytes {
+    if info.show_allocated {
+        if let Some(sz) = info.allocated_bytes.or(info.size_bytes) {
+            l.push(kv_line(
+                "Size",
+                &format!("{} on disk ({sz} B)", grouping::human_size(sz)),
+            ));
+        }
+        if let Some(apparent) = info.size_bytes.filter(|&a| Some(a) != info.allocated_bytes) {
+            l.push(kv_line("Apparent", &grouping::human_size(apparent)));
+        }
+    } else if let Some(sz) = info.size_bytes {
         l.push(kv_line(
             "Size",
             &format!("{} ({sz} B)", grouping::human_size(sz)),
         ));
+        if let Some(on_disk) = info.allocated_bytes.filter(|&a| a != sz) {
+            l.push(kv_line("On disk", &grouping::human_size(on_disk)));
+        }
+        if let Some(apparent) = info.apparent_bytes {
+            if let Some(saved) = size::hardlink_savings(sz, apparent) {
+                l.push(kv_line("Apparent", &grouping::human_size(apparent)));
+                l.push(kv_line("Link savings", &grouping::human_size(saved)));
+            }
+        }
     }
     l.push(kv_line("Readonly", if info.readonly { "yes" } else { "no" }));
     if let (Some(sym), Some(oct)) = (&info.perms_symbolic, &info.perms_octal) {
@@ -537,6 +717,17 @@ fn info_detail_lines(info: &InspectorInfo) -> Vec<Line<'static>> {
     if let Some(t) = &info.symlink_target {
         l.push(kv_line("Symlink ->", t));
     }
+    if info.symlink_chain.len() > 1 {
+        let hops: Vec<&str> = info.symlink_chain.iter().map(String::as_str).collect();
+        l.push(kv_line("Chain", &format!("→ {}", hops.join(" → "))));
+    }
+    if let Some(kind) = &info.target_kind {
+        let target = match info.target_size {
+            Some(sz) => format!("{kind} · {}", grouping::human_size(sz)),
+            None => kind.clone(),
+        };
+        l.push(kv_line("Target", &target));
+    }
     if let Some(v) = info.subdirs {
         l.push(kv_line("Subdirs", &v.to_string()));
     }
@@ -546,6 +737,12 @@ fn info_detail_lines(info: &InspectorInfo) -> Vec<Line<'static>> {
     if let Some(v) = info.others {
         l.push(kv_line("Other entries", &v.to_string()));
     }
+    if let Some(c) = info.entry_counts {
+        l.push(kv_line(
+            "Symlinks",
+            &format!("{} · Special: {}", c.symlinks, c.specials),
+        ));
+    }
     if let (Some(w), Some(h)) = (info.image_width, info.image_height) {
         l.push(kv_line("Resolution", &format!("{w} × {h}")));
     }
@@ -555,6 +752,46 @@ fn info_detail_lines(info: &InspectorInfo) -> Vec<Line<'static>> {
     if let Some(ch) = info.image_channels {
         l.push(kv_line("Channels", &ch.to_string()));
     }
+    if let Some(camera) = &info.exif_camera {
+        l.push(kv_line("Camera", camera));
+    }
+    if let Some(taken) = &info.exif_datetime {
+        l.push(kv_line("Taken", taken));
+    }
+    if let Some(gps) = &info.exif_gps {
+        l.push(kv_line("GPS", gps));
+    }
+    if let Some(o) = info.exif_orientation {
+        l.push(kv_line("Orientation", &orientation_text(o)));
+    }
+    if let Some(secs) = info.media_duration_secs {
+        l.push(kv_line("Duration", &duration_text(secs)));
+    }
+    if let Some(codec) = &info.media_codec {
+        l.push(kv_line("Codec", codec));
+    }
+    if let Some(bps) = info.media_bitrate {
+        l.push(kv_line("Bitrate", &format!("~{} kb/s", (bps + 500) / 1000)));
+    }
+    for (label, value) in &info.media_tags {
+        l.push(kv_line(label, value));
+    }
+    if let Some(lines) = info.line_count {
+        // Only the first window is read; past it the count is a floor.
+        let partial = info
+            .target_size
+            .or(info.size_bytes)
+            .is_some_and(|sz| sz > inspector::TEXT_SCAN_BYTES);
+        let text = if partial { format!("{lines}+") } else { lines.to_string() };
+        l.push(kv_line("Lines", &text));
+    }
+    if let Some(enc) = &info.encoding {
+        let text = if info.has_bom { format!("{enc} (BOM)") } else { enc.clone() };
+        l.push(kv_line("Encoding", &text));
+    }
+    if let Some(checksum) = checksum_text(info) {
+        l.push(kv_line(CHECKSUM_LABEL, checksum));
+    }
     if let Some(e) = &info.error {
         l.push(Line::raw(""));
         l.push(Line::from(Span::styled(
@@ -565,19 +802,119 @@ fn info_detail_lines(info: &InspectorInfo) -> Vec<Line<'static>> {
     l
 }
 
+const CHECKSUM_LABEL: &str = "SHA-256";
+
+/// Playing time as `m:ss`, or `h:mm:ss` from an hour up.
+fn duration_text(secs: f64) -> String {
+    let total = secs.round() as u64;
+    let (h, m, s) = (total / 3600, total / 60 % 60, total % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m}:{s:02}")
+    }
+}
+
+/// EXIF orientation tag with what a viewer has to do to show it upright.
+fn orientation_text(tag: u16) -> String {
+    let desc = match tag {
+        1 => "normal",
+        2 => "mirrored",
+        3 => "rotated 180°",
+        4 => "mirrored, rotated 180°",
+        5 => "mirrored, rotated 90° CW",
+        6 => "rotated 90° CW",
+        7 => "mirrored, rotated 90° CCW",
+        8 => "rotated 90° CCW",
+        _ => return tag.to_string(),
+    };
+    format!("{tag} ({desc})")
+}
+
+/// Value of the SHA-256 row, or `None` when the row isn't shown.
+fn checksum_text(info: &InspectorInfo) -> Option<&str> {
+    if let Some(digest) = &info.checksum {
+        return Some(digest);
+    }
+    if info.kind != "File" {
+        return None;
+    }
+    if info.checksum_pending {
+        Some("computing…")
+    } else if info.size_bytes.is_some_and(|sz| sz > inspector::CHECKSUM_MAX_BYTES) {
+        Some("(c to compute)")
+    } else {
+        None
+    }
+}
+
+/// Index of the SHA-256 row within [`info_detail_lines`].
+fn checksum_line(info: &InspectorInfo) -> Option<u16> {
+    checksum_text(info)?;
+    info_detail_lines(info)
+        .iter()
+        .position(|line| {
+            line.spans
+                .first()
+                .is_some_and(|span| span.content.trim_end() == CHECKSUM_LABEL)
+        })
+        .map(|i| i as u16)
+}
+
+/// Screen row of the current selection's SHA-256 line, given the
+/// inspector's inner area.
+pub fn current_checksum_row(inner: Rect, info: &InspectorInfo) -> Option<u16> {
+    // Title, blank, name, kind and blank come before the details.
+    Some(inner.y + 5 + checksum_line(info)?)
+}
+
+/// Screen row of a pinned card's SHA-256 line.
+pub fn card_checksum_row(card_rect: Rect, info: &InspectorInfo) -> Option<u16> {
+    // Top border and the "Type" line come before the details.
+    Some(card_rect.y + 2 + checksum_line(info)?)
+}
+
+/// Screen rows of the current selection's listed archive members.
+pub fn current_archive_rows(inner: Rect, info: &InspectorInfo) -> Option<Rect> {
+    archive_rows(inner, inner.y + 5, info)
+}
+
+/// Screen rows of a pinned card's listed archive members.
+pub fn card_archive_rows(card_rect: Rect, info: &InspectorInfo) -> Option<Rect> {
+    archive_rows(card_rect, card_rect.y + 2, info)
+}
+
+/// Member rows of the archive section whose details start at `details_y`;
+/// a blank line and the section header sit between the two.
+fn archive_rows(area: Rect, details_y: u16, info: &InspectorInfo) -> Option<Rect> {
+    let entries = info.archive_entries.as_ref()?;
+    let y = details_y + info_detail_lines(info).len() as u16 + 2;
+    let h = (entries.len() as u16).min(ARCHIVE_ROWS);
+    Some(Rect::new(area.x, y, area.width, h).intersection(area))
+}
+
 fn card_title(info: &InspectorInfo) -> String {
-    if !info.name.is_empty() {
-        return info.name.clone();
-    }
-    info.path
-        .file_name()
-        .and_then(|s| s.to_str())
-        .map(str::to_string)
-        .unwrap_or_else(|| info.path.display().to_string())
+    let name = if !info.name.is_empty() {
+        info.name.clone()
+    } else {
+        info.path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| info.path.display().to_string())
+    };
+    match (info.archive_entry_count, info.archive_unpacked_bytes) {
+        (Some(1), Some(bytes)) => format!("{name} · 1 entry, {}", grouping::human_size(bytes)),
+        (Some(n), Some(bytes)) => format!("{name} · {n} entries, {}", grouping::human_size(bytes)),
+        _ => name,
+    }
 }
 
 fn card_height_for(info: &InspectorInfo) -> u16 {
-    let body = 1 + info_detail_lines(info).len();
+    let body = 1
+        + info_detail_lines(info).len()
+        + archive_lines(info, u16::MAX).len()
+        + text_preview_lines(info, CARD_PREVIEW_ROWS as usize).len();
     let preview = if info.is_image() {
         CARD_PREVIEW_ROWS as usize + 1
     } else {
@@ -665,40 +1002,3 @@ fn render_image_halfblocks(thumb: &image::RgbaImage, area: Rect, buf: &mut Buffe
     }
 }
 
-// ─── scrollbar ──────────────────────────────────────────────────
-
-fn render_scrollbar(
-    area: Rect,
-    total: usize,
-    offset: usize,
-    visible: usize,
-    buf: &mut Buffer,
-) {
-    use ratatui::layout::Position;
-
-    if total <= visible || area.height < 2 || area.width == 0 {
-        return;
-    }
-    let x = area.x + area.width.saturating_sub(1);
-    let h = area.height as f64;
-    let thumb_sz = ((visible as f64 / total as f64) * h).ceil().max(1.0) as u16;
-    let max_off = total.saturating_sub(visible) as f64;
-    let thumb_pos = if max_off > 0.0 {
-        ((offset as f64 / max_off) * (h - thumb_sz as f64)).round() as u16
-    } else {
-        0
-    };
-
-    for row in 0..area.height {
-        let y = area.y + row;
-        let is_thumb = row >= thumb_pos && row < thumb_pos + thumb_sz;
-        let (ch, fg) = if is_thumb {
-            ('█', Color::LightBlue)
-        } else {
-            ('│', Color::DarkGray)
-        };
-        if let Some(cell) = buf.cell_mut(Position::new(x, y)) {
-            cell.set_char(ch).set_fg(fg);
-        }
-    }
-}
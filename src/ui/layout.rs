@@ -8,24 +8,33 @@ pub struct AppLayout {
     pub tree_area: Rect,
     pub inspector_area: Rect,
     pub splitter_area: Rect,
+    /// Hints, messages and the command input.  Zero-height when the status
+    /// bar is hidden.
     pub status_area: Rect,
+    /// Persistent info (path, totals, scan progress).  Only has height with
+    /// a two-line status bar.
+    pub info_area: Rect,
     main_area: Rect,
     mode: PanelLayoutMode,
 }
 
 impl AppLayout {
-    /// Compute the layout from the full terminal area.
-    pub fn from_area(area: Rect, mode: PanelLayoutMode, split_pct: u16) -> Self {
+    /// Compute the layout from the full terminal area.  `status_lines` is
+    /// the height of the status bar (0..=2).
+    pub fn from_area(area: Rect, mode: PanelLayoutMode, split_pct: u16, status_lines: u16) -> Self {
+        let status_lines = status_lines.min(2);
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Min(3),    // main panes (tree + inspector)
-                Constraint::Length(1), // status / command bar
+                Constraint::Min(3),                                // main panes (tree + inspector)
+                Constraint::Length(status_lines.saturating_sub(1)), // info line
+                Constraint::Length(status_lines.min(1)),           // status / command bar
             ])
             .split(area);
 
         let main_area = chunks[0];
-        let status_area = chunks[1];
+        let info_area = chunks[1];
+        let status_area = chunks[2];
         let split_pct = split_pct.clamp(10, 90);
 
         let (tree_area, inspector_area, splitter_area) = match mode {
@@ -68,6 +77,7 @@ impl AppLayout {
             inspector_area,
             splitter_area,
             status_area,
+            info_area,
             main_area,
             mode,
         }
@@ -25,6 +25,8 @@ pub struct LightboxWidget<'a> {
     pub current: usize,
     /// Pre-resized thumbnail cache.
     pub image_cache: &'a HashMap<PathBuf, Arc<image::RgbaImage>>,
+    /// Draw the clickable close button (hidden when mouse capture is off).
+    pub mouse: bool,
 }
 
 /// Clickable regions returned after rendering, for mouse hit-testing.
@@ -105,13 +107,15 @@ impl<'a> LightboxWidget<'a> {
             3,
             1,
         );
-        Paragraph::new(Line::from(Span::styled(
-            "[X]",
-            Style::default()
-                .fg(Color::LightRed)
-                .add_modifier(Modifier::BOLD),
-        )))
-        .render(close_rect, buf);
+        if self.mouse {
+            Paragraph::new(Line::from(Span::styled(
+                "[X]",
+                Style::default()
+                    .fg(Color::LightRed)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .render(close_rect, buf);
+        }
 
         // Navigation arrows on the left/right edges (vertically centred).
         let arrow_y = area.y + area.height / 2;
@@ -135,7 +139,7 @@ impl<'a> LightboxWidget<'a> {
 
         // Render the image in the inner area (with some padding for arrows).
         if let Some(info) = info {
-            if let Some(thumb) = self.image_cache.get(&info.path) {
+            if let Some(thumb) = self.image_cache.get(info.preview_path()) {
                 let img_area = Rect::new(
                     inner.x.saturating_add(2),
                     inner.y,
@@ -3,10 +3,16 @@
 //! This layer takes the *core* data structures and turns them into pixels on
 //! the terminal.  No filesystem I/O happens here.
 
+pub mod badges;
+pub mod caps;
+pub mod debug;
+pub mod deletions;
+pub mod icons;
 pub mod inspector;
 pub mod layout;
 pub mod lightbox;
 pub mod popup;
+pub mod scrollbar;
 pub mod search;
 pub mod smooth_scroll;
 pub mod spinner;
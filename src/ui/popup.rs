@@ -10,7 +10,7 @@ use ratatui::{
 
 use crate::app::settings::{SettingsItem, SETTINGS_ITEMS};
 use crate::app::state::AppState;
-use crate::config::{Action, AppConfig};
+use crate::config::{Action, AppConfig, ConfigWarning, KeyBind};
 
 // ───────────────────────────────────────── settings popup ────
 
@@ -20,10 +20,14 @@ pub struct SettingsPopup<'a> {
     pub state: &'a AppState,
 }
 
+impl<'a> PopupSize for SettingsPopup<'a> {
+    fn preferred_size(&self) -> (u16, u16) {
+        (40, SETTINGS_ITEMS.len() as u16 + 6)
+    }
+}
+
 impl<'a> Widget for SettingsPopup<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let height = (SETTINGS_ITEMS.len() as u16) + 6;
-        let popup = centered_fixed(40, height, area);
+    fn render(self, popup: Rect, buf: &mut Buffer) {
         Clear.render(popup, buf);
 
         let block = Block::default()
@@ -40,8 +44,12 @@ impl<'a> Widget for SettingsPopup<'a> {
         let inner = block.inner(popup);
         block.render(popup, buf);
 
+        let compact = is_compact(inner);
         let mut lines = Vec::new();
-        lines.push(Line::raw(""));
+        if !compact {
+            lines.push(Line::raw(""));
+        }
+        let first_item_line = lines.len();
         for (i, item) in SETTINGS_ITEMS.iter().enumerate() {
             let (prefix, base_style) = if i == self.selected {
                 (
@@ -55,7 +63,9 @@ impl<'a> Widget for SettingsPopup<'a> {
                 ("   ", Style::default().fg(Color::White))
             };
 
-            let label = item.label();
+            // `*` marks settings changed from their defaults.
+            let modified = if item.is_default(self.state) { "" } else { "*" };
+            let label = format!("{}{modified}", item.label());
 
             match item {
                 SettingsItem::Submenu { .. } => {
@@ -77,6 +87,29 @@ impl<'a> Widget for SettingsPopup<'a> {
                         Span::styled(suffix, toggle_style),
                     ]));
                 }
+                SettingsItem::Info { value, .. } => {
+                    // Keep the tail of long values (paths) visible.
+                    let value = value(self.state);
+                    let room = (inner.width as usize)
+                        .saturating_sub(prefix.chars().count() + label.chars().count() + 2);
+                    let len = value.chars().count();
+                    let value = if len > room && room > 1 {
+                        let tail: String = value.chars().skip(len - (room - 1)).collect();
+                        format!("…{tail}")
+                    } else {
+                        value
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("{prefix}{label}  "), base_style),
+                        Span::styled(value, Style::default().fg(Color::DarkGray)),
+                    ]));
+                }
+                SettingsItem::Command { .. } => {
+                    lines.push(Line::from(Span::styled(
+                        format!("{prefix}⟳ {label}"),
+                        base_style,
+                    )));
+                }
                 SettingsItem::Cycle { value, .. } => {
                     lines.push(Line::from(vec![
                         Span::styled(format!("{prefix}{label}"), base_style),
@@ -90,11 +123,12 @@ impl<'a> Widget for SettingsPopup<'a> {
         }
         lines.push(Line::raw(""));
         lines.push(Line::from(Span::styled(
-            "  Enter/Space: toggle  Esc: close",
+            "  Enter/Space: toggle  Del: reset  Esc: close",
             Style::default().fg(Color::DarkGray),
         )));
 
-        Paragraph::new(lines).render(inner, buf);
+        let scroll = scroll_to_show(first_item_line + self.selected, lines.len(), inner.height);
+        Paragraph::new(lines).scroll((scroll, 0)).render(inner, buf);
     }
 }
 
@@ -105,13 +139,21 @@ pub struct ControlsPopup<'a> {
     pub config: &'a AppConfig,
     pub selected: usize,
     pub awaiting_rebind: bool,
+    /// Captured binding awaiting confirmation.
+    pub pending: Option<&'a KeyBind>,
+    /// Reason the last captured key was rejected.
+    pub warning: Option<&'a str>,
+}
+
+impl<'a> PopupSize for ControlsPopup<'a> {
+    fn preferred_size(&self) -> (u16, u16) {
+        // actions + 2 blanks + 1 reset + 1 description + 1 hint + 1 top pad + 2 border
+        (64, Action::ALL.len() as u16 + 8)
+    }
 }
 
 impl<'a> Widget for ControlsPopup<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        // Action::ALL.len() actions + 2 blanks + 1 reset + 1 hint + 2 border = ~17
-        let height = (Action::ALL.len() as u16) + 7;
-        let popup = centered_fixed(52, height, area);
+    fn render(self, popup: Rect, buf: &mut Buffer) {
         Clear.render(popup, buf);
 
         let block = Block::default()
@@ -129,9 +171,14 @@ impl<'a> Widget for ControlsPopup<'a> {
         block.render(popup, buf);
 
         let dim = Style::default().fg(Color::DarkGray);
+        let compact = is_compact(inner);
+        let label_width = if compact { 14 } else { 22 };
         let mut lines = Vec::new();
 
-        lines.push(Line::raw(""));
+        if !compact {
+            lines.push(Line::raw(""));
+        }
+        let first_item_line = lines.len();
 
         // ── Action rows ─────────────────────────────────────────
         for (i, &action) in Action::ALL.iter().enumerate() {
@@ -141,7 +188,10 @@ impl<'a> Widget for ControlsPopup<'a> {
             let label = action.label();
 
             let keys_display = if is_selected && self.awaiting_rebind {
-                "Press a key…".to_string()
+                match self.pending {
+                    Some(bind) => format!("{}?", bind.display()),
+                    None => "Press a key…".to_string(),
+                }
             } else {
                 self.config.display_bindings(action)
             };
@@ -169,9 +219,10 @@ impl<'a> Widget for ControlsPopup<'a> {
             };
 
             // Fixed-width columns: label left-aligned, keys right-aligned.
-            let label_col = format!("{prefix}{label:<22}");
+            let label: String = label.chars().take(label_width).collect();
+            let label_col = format!("{prefix}{label:<label_width$}");
             let inner_width = inner.width as usize;
-            let keys_width = inner_width.saturating_sub(label_col.len()).max(1);
+            let keys_width = inner_width.saturating_sub(label_col.chars().count()).max(1);
             let keys_col = format!("{keys_display:>keys_width$}");
 
             lines.push(Line::from(vec![
@@ -201,22 +252,713 @@ impl<'a> Widget for ControlsPopup<'a> {
 
         // ── Hint bar ────────────────────────────────────────────
         lines.push(Line::raw(""));
+        let action = Action::ALL.get(self.selected).copied();
+        let description = match action {
+            Some(action) if !self.awaiting_rebind => action.description(),
+            _ => "",
+        };
         lines.push(Line::from(Span::styled(
-            "  Enter: add key  Del: clear  Esc: back",
-            dim,
+            format!("  {description}"),
+            Style::default().fg(Color::Gray),
         )));
+        match (self.awaiting_rebind, self.pending, self.warning, action) {
+            (true, Some(bind), _, Some(action)) => {
+                let mut prompt = format!(
+                    "  Bind '{}' to {}? Enter: confirm",
+                    bind.display(),
+                    action.label()
+                );
+                if let Some(owner) = self.config.binding_owner(bind, action) {
+                    prompt.push_str(&format!(" (unbinds {})", owner.label()));
+                }
+                lines.push(Line::from(Span::styled(prompt, Style::default().fg(Color::Yellow))));
+            }
+            (true, None, Some(warning), _) => {
+                lines.push(Line::from(Span::styled(
+                    format!("  {warning}"),
+                    Style::default().fg(Color::Red),
+                )));
+            }
+            (true, _, _, _) => {
+                lines.push(Line::from(Span::styled("  Press a key combination  Esc: cancel", dim)));
+            }
+            _ => {
+                lines.push(Line::from(Span::styled(
+                    "  Enter: add key  Del: clear  Esc: back",
+                    dim,
+                )));
+            }
+        }
+
+        // The reset row sits after one blank line below the actions.
+        let selected_line = if self.selected < Action::ALL.len() {
+            first_item_line + self.selected
+        } else {
+            first_item_line + Action::ALL.len() + 1
+        };
+        let scroll = scroll_to_show(selected_line, lines.len(), inner.height);
+        Paragraph::new(lines).scroll((scroll, 0)).render(inner, buf);
+    }
+}
+
+// ───────────────────────────────────────── pin sets popup ────
+
+/// Save / load named pin sets.
+pub struct PinSetsPopup<'a> {
+    pub state: &'a AppState,
+}
+
+impl<'a> PopupSize for PinSetsPopup<'a> {
+    fn preferred_size(&self) -> (u16, u16) {
+        let body_rows = if self.state.pin_set_name_input.is_some() {
+            1
+        } else {
+            self.state.config.pin_sets.len().max(1) as u16
+        };
+        (52, body_rows + 6)
+    }
+}
+
+impl<'a> Widget for PinSetsPopup<'a> {
+    fn render(self, popup: Rect, buf: &mut Buffer) {
+        let sets = &self.state.config.pin_sets;
+        let saving = self.state.pin_set_name_input.as_deref();
+        Clear.render(popup, buf);
+
+        let title = if saving.is_some() { " Save Pin Set " } else { " Load Pin Set " };
+        let block = Block::default()
+            .title(title)
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut lines = Vec::new();
+        lines.push(Line::raw(""));
+
+        let hint = if let Some(name) = saving {
+            lines.push(Line::from(vec![
+                Span::styled("  Name: ", Style::default().fg(Color::White)),
+                Span::styled(format!("{name}▏"), Style::default().fg(Color::Yellow)),
+            ]));
+            format!("  Enter: save {} pins  Esc: cancel", self.state.pinned_inspector.len())
+        } else if sets.is_empty() {
+            lines.push(Line::from(Span::styled("  No saved pin sets", dim)));
+            "  Esc: close".to_string()
+        } else {
+            for (i, (name, paths)) in sets.iter().enumerate() {
+                let is_selected = i == self.state.pin_set_selected;
+                let prefix = if is_selected { " ▸ " } else { "   " };
+                let style = if is_selected {
+                    Style::default()
+                        .fg(Color::White)
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                let count = format!("{} pins", paths.len());
+                let label_col = format!("{prefix}{name}");
+                let count_width = (inner.width as usize)
+                    .saturating_sub(label_col.chars().count())
+                    .max(1);
+                lines.push(Line::from(vec![
+                    Span::styled(label_col, style),
+                    Span::styled(format!("{count:>count_width$}"), style.fg(Color::Yellow)),
+                ]));
+            }
+            "  Enter: replace  m: merge  d: delete  Esc".to_string()
+        };
+
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Span::styled(hint, dim)));
+
+        let selected_line = if saving.is_some() { 1 } else { 1 + self.state.pin_set_selected };
+        let scroll = scroll_to_show(selected_line, lines.len(), inner.height);
+        Paragraph::new(lines).scroll((scroll, 0)).render(inner, buf);
+    }
+}
+
+// ───────────────────────────────────────── bookmarks popup ───
+
+/// Saved directory bookmarks.
+pub struct BookmarksPopup<'a> {
+    pub state: &'a AppState,
+}
+
+impl<'a> PopupSize for BookmarksPopup<'a> {
+    fn preferred_size(&self) -> (u16, u16) {
+        (60, self.state.config.bookmarks.len().max(1) as u16 + 6)
+    }
+}
+
+impl<'a> Widget for BookmarksPopup<'a> {
+    fn render(self, popup: Rect, buf: &mut Buffer) {
+        let bookmarks = &self.state.config.bookmarks;
+        Clear.render(popup, buf);
 
+        let block = Block::default()
+            .title(" Bookmarks ")
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![Line::raw("")];
+        let hint = if bookmarks.is_empty() {
+            lines.push(Line::from(Span::styled("  No bookmarks yet", dim)));
+            format!("  {}: bookmark a directory  Esc: close", self.state.config.short_binding(Action::AddBookmark))
+        } else {
+            for (i, bookmark) in bookmarks.iter().enumerate() {
+                let is_selected = i == self.state.bookmark_selected;
+                let prefix = if is_selected { " ▸ " } else { "   " };
+                let style = if is_selected {
+                    Style::default()
+                        .fg(Color::White)
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{prefix}{}  ", bookmark.name), style),
+                    Span::styled(bookmark.path.display().to_string(), style.fg(Color::Gray)),
+                ]));
+            }
+            "  Enter: go  d: delete  Esc".to_string()
+        };
+
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Span::styled(hint, dim)));
+
+        let scroll = scroll_to_show(1 + self.state.bookmark_selected, lines.len(), inner.height);
+        Paragraph::new(lines).scroll((scroll, 0)).render(inner, buf);
+    }
+}
+
+// ───────────────────────────────────────── exclusions popup ──
+
+/// Settings submenu listing the size-walk exclude patterns.
+pub struct ExcludePatternsPopup<'a> {
+    pub state: &'a AppState,
+}
+
+impl<'a> PopupSize for ExcludePatternsPopup<'a> {
+    fn preferred_size(&self) -> (u16, u16) {
+        let input_rows = if self.state.exclude_input.is_some() { 2 } else { 0 };
+        (56, self.state.config.exclude_patterns.len().max(1) as u16 + input_rows + 6)
+    }
+}
+
+impl<'a> Widget for ExcludePatternsPopup<'a> {
+    fn render(self, popup: Rect, buf: &mut Buffer) {
+        let patterns = &self.state.config.exclude_patterns;
+        let input = self.state.exclude_input.as_deref();
+        Clear.render(popup, buf);
+
+        let block = Block::default()
+            .title(" Size Exclusions ")
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![Line::raw("")];
+        if patterns.is_empty() {
+            lines.push(Line::from(Span::styled("  Nothing excluded", dim)));
+        }
+        for (i, pattern) in patterns.iter().enumerate() {
+            let is_selected = input.is_none() && i == self.state.exclude_selected;
+            let prefix = if is_selected { " ▸ " } else { "   " };
+            let style = if is_selected {
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            lines.push(Line::from(Span::styled(format!("{prefix}{pattern}"), style)));
+        }
+
+        let (hint, hint_style) = if let Some(pattern) = input {
+            lines.push(Line::raw(""));
+            lines.push(Line::from(vec![
+                Span::styled("  Glob: ", Style::default().fg(Color::White)),
+                Span::styled(format!("{pattern}▏"), Style::default().fg(Color::Yellow)),
+            ]));
+            match crate::core::size::exclude_pattern_error(pattern.trim()) {
+                Some(e) if !pattern.trim().is_empty() => {
+                    (format!("  Invalid: {e}"), Style::default().fg(Color::Red))
+                }
+                _ => ("  Enter: add  Esc: cancel".to_string(), dim),
+            }
+        } else if patterns.is_empty() {
+            ("  a: add  ←: back  Esc: close".to_string(), dim)
+        } else {
+            ("  a: add  d: delete  ←: back  Esc".to_string(), dim)
+        };
+
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Span::styled(hint, hint_style)));
+
+        let focus = if input.is_some() { lines.len() - 3 } else { 1 + self.state.exclude_selected };
+        let scroll = scroll_to_show(focus, lines.len(), inner.height);
+        Paragraph::new(lines).scroll((scroll, 0)).render(inner, buf);
+    }
+}
+
+// ───────────────────────────────────────── top entries popup ─
+
+/// Width of the bar chart column in the top-entries popup.
+const TOP_BAR_WIDTH: usize = 12;
+
+/// Largest directories and files found by the size scan so far.
+pub struct TopFilesPopup<'a> {
+    pub state: &'a AppState,
+}
+
+impl<'a> PopupSize for TopFilesPopup<'a> {
+    fn preferred_size(&self) -> (u16, u16) {
+        let rows = self.state.top_dirs.len().max(1) + self.state.top_files.len().max(1);
+        (72, rows as u16 + 9)
+    }
+}
+
+impl<'a> TopFilesPopup<'a> {
+    /// Section header plus one row per entry, with bars scaled to the
+    /// section's largest entry.  `first` is the overall index of the
+    /// section's first row.
+    fn section_lines(
+        &self,
+        title: &str,
+        entries: &[(std::path::PathBuf, u64)],
+        first: usize,
+        lines: &mut Vec<Line<'static>>,
+    ) {
+        let dim = Style::default().fg(Color::DarkGray);
+        lines.push(Line::from(Span::styled(
+            format!("  {title}"),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )));
+        if entries.is_empty() {
+            lines.push(Line::from(Span::styled("    (none yet)", dim)));
+            return;
+        }
+        let max = entries.first().map_or(1, |(_, size)| (*size).max(1));
+        for (i, (path, size)) in entries.iter().enumerate() {
+            let is_selected = first + i == self.state.top_selected;
+            let prefix = if is_selected { " ▸ " } else { "   " };
+            let style = if is_selected {
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let filled = ((*size as f64 / max as f64) * TOP_BAR_WIDTH as f64).round() as usize;
+            let bar = format!(
+                "{}{}",
+                "#".repeat(filled.min(TOP_BAR_WIDTH)),
+                ".".repeat(TOP_BAR_WIDTH - filled.min(TOP_BAR_WIDTH))
+            );
+            let rel = path.strip_prefix(&self.state.cwd).unwrap_or(path);
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(format!("{bar} "), style.fg(Color::Yellow)),
+                Span::styled(format!("{} ", crate::core::grouping::human_size_padded(*size)), style.fg(Color::Gray)),
+                Span::styled(rel.display().to_string(), style),
+            ]));
+        }
+    }
+}
+
+impl<'a> Widget for TopFilesPopup<'a> {
+    fn render(self, popup: Rect, buf: &mut Buffer) {
+        Clear.render(popup, buf);
+
+        let block = Block::default()
+            .title(" Largest Entries ")
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let dirs = &self.state.top_dirs;
+        let files = &self.state.top_files;
+        let mut lines = vec![Line::raw("")];
+        self.section_lines("Directories", dirs, 0, &mut lines);
+        lines.push(Line::raw(""));
+        let files_header = lines.len();
+        self.section_lines("Files", files, dirs.len(), &mut lines);
+        lines.push(Line::raw(""));
+        let hint = if self.state.scanning { "  Enter: reveal  Esc · scanning…" } else { "  Enter: reveal  Esc" };
+        lines.push(Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))));
+
+        let selected_line = if self.state.top_selected < dirs.len() {
+            2 + self.state.top_selected
+        } else {
+            files_header + 1 + self.state.top_selected - dirs.len()
+        };
+        let scroll = scroll_to_show(selected_line, lines.len(), inner.height);
+        Paragraph::new(lines).scroll((scroll, 0)).render(inner, buf);
+    }
+}
+
+/// Where the time went in the last completed size scan.
+pub struct ScanStatsPopup<'a> {
+    pub state: &'a AppState,
+}
+
+impl<'a> PopupSize for ScanStatsPopup<'a> {
+    fn preferred_size(&self) -> (u16, u16) {
+        let slowest = self.state.last_scan_stats.as_ref().map_or(0, |s| s.slowest.len());
+        (72, slowest as u16 + 15)
+    }
+}
+
+impl<'a> Widget for ScanStatsPopup<'a> {
+    fn render(self, popup: Rect, buf: &mut Buffer) {
+        use crate::core::grouping::{human_count, human_size};
+
+        Clear.render(popup, buf);
+
+        let block = Block::default()
+            .title(" Scan Statistics ")
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let dim = Style::default().fg(Color::DarkGray);
+        let label = Style::default().fg(Color::Gray);
+        let row = |name: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("  {name:<20}"), label),
+                Span::styled(value, Style::default().fg(Color::White)),
+            ])
+        };
+
+        let mut lines = vec![Line::raw("")];
+        let Some(stats) = &self.state.last_scan_stats else {
+            let text = if self.state.scanning {
+                "  No scan has completed yet (one is running)."
+            } else {
+                "  No scan has completed yet."
+            };
+            lines.push(Line::from(Span::styled(text, dim)));
+            lines.push(Line::raw(""));
+            lines.push(Line::from(Span::styled("  Esc: close", dim)));
+            Paragraph::new(lines).render(inner, buf);
+            return;
+        };
+
+        lines.push(row("Wall time", format!("{:.2?} on {} workers", stats.wall_time, stats.workers)));
+        lines.push(row("Directories walked", human_count(stats.dirs_walked)));
+        lines.push(row("Files statted", human_count(stats.files_statted)));
+        lines.push(row("Bytes summed", human_size(stats.bytes_summed)));
+        lines.push(row(
+            "Cached tree dirs",
+            format!("{} reused, {} walked", stats.cache_hits, stats.cache_misses),
+        ));
+        let cwd = &self.state.cwd;
+        let dedup = match (self.state.dir_sizes.get(cwd), self.state.dir_apparent_sizes.get(cwd)) {
+            _ if !self.state.config.dedup_hard_links => "off".to_string(),
+            (Some(&total), Some(&apparent)) => format!("{} saved", human_size(apparent.saturating_sub(total))),
+            _ => "-".to_string(),
+        };
+        lines.push(row("Hard-link dedup", dedup));
+
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Span::styled(
+            "  Slowest directories",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )));
+        if stats.slowest.is_empty() {
+            lines.push(Line::from(Span::styled("    (none walked)", dim)));
+        }
+        for (path, elapsed) in &stats.slowest {
+            let rel = path.strip_prefix(cwd).unwrap_or(path);
+            let rel = if rel.as_os_str().is_empty() { "." } else { &rel.to_string_lossy() };
+            lines.push(Line::from(vec![
+                Span::styled(format!("    {:>9.2?}  ", elapsed), label),
+                Span::styled(rel.to_string(), Style::default().fg(Color::White)),
+            ]));
+        }
+        lines.push(Line::raw(""));
+        let hint = if self.state.scanning { "  Esc: close · a newer scan is running" } else { "  Esc: close" };
+        lines.push(Line::from(Span::styled(hint, dim)));
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+// ───────────────────────────────────────── confirm popups ────
+
+/// Typed confirmation before staged deletions are committed.
+pub struct ConfirmDeletionsPopup<'a> {
+    pub count: usize,
+    pub reclaimable: u64,
+    pub input: &'a str,
+    /// Remove outright instead of moving to the trash.
+    pub permanent: bool,
+}
+
+impl<'a> PopupSize for ConfirmDeletionsPopup<'a> {
+    fn preferred_size(&self) -> (u16, u16) {
+        (52, 8)
+    }
+}
+
+impl<'a> Widget for ConfirmDeletionsPopup<'a> {
+    fn render(self, popup: Rect, buf: &mut Buffer) {
+        Clear.render(popup, buf);
+
+        let block = confirm_block(" Commit Deletions ");
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let size = crate::core::grouping::human_size(self.reclaimable);
+        let question = if self.permanent {
+            format!("  Permanently delete {} items ({size})?", self.count)
+        } else {
+            format!("  Move {} items ({size}) to the trash?", self.count)
+        };
+        let lines = vec![
+            Line::raw(""),
+            Line::from(Span::styled(question, Style::default().fg(Color::White))),
+            Line::from(vec![
+                Span::styled("  Type yes: ", Style::default().fg(Color::White)),
+                Span::styled(format!("{}▏", self.input), Style::default().fg(Color::Yellow)),
+            ]),
+            Line::raw(""),
+            Line::from(Span::styled(
+                "  Enter: confirm  Esc: cancel",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+/// Simple yes/no confirmation.
+pub struct ConfirmPopup<'a> {
+    pub title: &'a str,
+    pub message: String,
+    pub hint: &'a str,
+}
+
+impl<'a> PopupSize for ConfirmPopup<'a> {
+    fn preferred_size(&self) -> (u16, u16) {
+        (52, 7)
+    }
+}
+
+impl<'a> Widget for ConfirmPopup<'a> {
+    fn render(self, popup: Rect, buf: &mut Buffer) {
+        Clear.render(popup, buf);
+
+        let block = confirm_block(self.title);
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let lines = vec![
+            Line::raw(""),
+            Line::from(Span::styled(
+                format!("  {}", self.message),
+                Style::default().fg(Color::White),
+            )),
+            Line::raw(""),
+            Line::from(Span::styled(
+                format!("  {}", self.hint),
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+/// Lines of the config file that could not be applied.
+pub struct ConfigWarningsPopup<'a> {
+    pub warnings: &'a [ConfigWarning],
+}
+
+impl<'a> PopupSize for ConfigWarningsPopup<'a> {
+    fn preferred_size(&self) -> (u16, u16) {
+        (64, self.warnings.len().min(12) as u16 + 6)
+    }
+}
+
+impl<'a> Widget for ConfigWarningsPopup<'a> {
+    fn render(self, popup: Rect, buf: &mut Buffer) {
+        Clear.render(popup, buf);
+
+        let block = confirm_block(" Config Problems ");
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let mut lines = vec![Line::raw("")];
+        lines.extend(self.warnings.iter().map(|w| {
+            Line::from(vec![
+                Span::styled(format!("  line {}: ", w.line), Style::default().fg(Color::Yellow)),
+                Span::styled(w.message.clone(), Style::default().fg(Color::White)),
+            ])
+        }));
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Span::styled(
+            "  Other settings were applied.  Enter / Esc: close",
+            Style::default().fg(Color::DarkGray),
+        )));
         Paragraph::new(lines).render(inner, buf);
     }
 }
 
+/// Single-line name input (rename, new directory).
+pub struct NamePromptPopup<'a> {
+    pub title: &'a str,
+    /// Path shown above the input for context.
+    pub context: &'a str,
+    pub input: &'a str,
+    /// Verb for the Enter hint.
+    pub action: &'a str,
+}
+
+impl<'a> PopupSize for NamePromptPopup<'a> {
+    fn preferred_size(&self) -> (u16, u16) {
+        (52, 8)
+    }
+}
+
+impl<'a> Widget for NamePromptPopup<'a> {
+    fn render(self, popup: Rect, buf: &mut Buffer) {
+        Clear.render(popup, buf);
+
+        let block = Block::default()
+            .title(self.title)
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::DarkGray));
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let dim = Style::default().fg(Color::DarkGray);
+        let lines = vec![
+            Line::raw(""),
+            Line::from(Span::styled(format!("  {}", self.context), dim)),
+            Line::from(vec![
+                Span::styled("  Name: ", Style::default().fg(Color::White)),
+                Span::styled(format!("{}▏", self.input), Style::default().fg(Color::Yellow)),
+            ]),
+            Line::raw(""),
+            Line::from(Span::styled(format!("  Enter: {}  Esc: cancel", self.action), dim)),
+        ];
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+fn confirm_block(title: &str) -> Block<'_> {
+    Block::default()
+        .title(title)
+        .title_style(
+            Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::DarkGray))
+}
+
 // ───────────────────────────────────────── helpers ───────────
 
-/// Create a centered rectangle with fixed dimensions, clamped to the available area.
-fn centered_fixed(width: u16, height: u16, area: Rect) -> Rect {
+/// Popups report the size they'd like; the caller decides where they go.
+pub trait PopupSize {
+    fn preferred_size(&self) -> (u16, u16);
+}
+
+/// Below this inner width popups drop padding and narrow their columns.
+const COMPACT_WIDTH: u16 = 44;
+
+/// Place a popup of `size` centered in `area`, shifted by the user's
+/// `offset` (cols, rows) and clamped so it stays fully on screen.
+pub fn popup_rect((width, height): (u16, u16), area: Rect, offset: (i16, i16)) -> Rect {
     let w = width.min(area.width);
     let h = height.min(area.height);
-    let x = area.x + (area.width.saturating_sub(w)) / 2;
-    let y = area.y + (area.height.saturating_sub(h)) / 2;
-    Rect::new(x, y, w, h)
+    let shift = |start: u16, span: u16, size: u16, delta: i16| -> u16 {
+        let centered = start as i32 + (span.saturating_sub(size) / 2) as i32;
+        let max = start as i32 + span.saturating_sub(size) as i32;
+        (centered + delta as i32).clamp(start as i32, max) as u16
+    };
+    Rect::new(
+        shift(area.x, area.width, w, offset.0),
+        shift(area.y, area.height, h, offset.1),
+        w,
+        h,
+    )
+}
+
+/// Render a popup at its session position within `area`.
+pub fn render_popup<P: PopupSize + Widget>(
+    frame: &mut ratatui::Frame,
+    popup: P,
+    offset: (i16, i16),
+) {
+    let rect = popup_rect(popup.preferred_size(), frame.area(), offset);
+    frame.render_widget(popup, rect);
+}
+
+fn is_compact(inner: Rect) -> bool {
+    inner.width < COMPACT_WIDTH
+}
+
+/// Vertical scroll that keeps `line` visible when `total` lines don't fit.
+fn scroll_to_show(line: usize, total: usize, height: u16) -> u16 {
+    let height = height as usize;
+    if total <= height || height == 0 {
+        return 0;
+    }
+    line.saturating_sub(height - 1).min(total - height) as u16
 }
@@ -0,0 +1,58 @@
+//! Vertical scrollbar drawn in the last column of a scrolling list, shared
+//! by the tree, inspector pins and search results.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::Color;
+
+/// Thumb `(position, size)` in rows from the top of a `height`-row track.
+fn thumb(height: u16, total: usize, offset: usize, visible: usize) -> (u16, u16) {
+    let h = height as f64;
+    let size = ((visible as f64 / total as f64) * h).ceil().max(1.0) as u16;
+    let max_off = total.saturating_sub(visible) as f64;
+    let pos = if max_off > 0.0 {
+        ((offset.min(total - visible) as f64 / max_off) * (h - size as f64)).round() as u16
+    } else {
+        0
+    };
+    (pos, size)
+}
+
+/// Draw a scrollbar in the last column of `area` for `visible` of `total`
+/// rows starting at `offset`.  Nothing is drawn when everything fits.
+pub fn render(area: Rect, total: usize, offset: usize, visible: usize, buf: &mut Buffer) {
+    if total <= visible || area.height < 2 || area.width == 0 {
+        return;
+    }
+    let x = area.x + area.width.saturating_sub(1);
+    let (thumb_pos, thumb_sz) = thumb(area.height, total, offset, visible);
+
+    for row in 0..area.height {
+        let y = area.y + row;
+        let is_thumb = row >= thumb_pos && row < thumb_pos + thumb_sz;
+        let (ch, fg) = if is_thumb {
+            ('█', Color::LightBlue)
+        } else {
+            ('│', Color::DarkGray)
+        };
+        if let Some(cell) = buf.cell_mut(Position::new(x, y)) {
+            cell.set_char(ch).set_fg(fg);
+        }
+    }
+}
+
+/// Offset that puts the thumb's middle on screen row `row` of a scrollbar
+/// drawn by [`render`] in `area`, for click-to-jump and dragging.
+pub fn offset_at(area: Rect, total: usize, visible: usize, row: u16) -> usize {
+    let max_off = total.saturating_sub(visible);
+    if max_off == 0 || area.height == 0 {
+        return 0;
+    }
+    let (_, thumb_sz) = thumb(area.height, total, 0, visible);
+    let track = area.height.saturating_sub(thumb_sz);
+    if track == 0 {
+        return 0;
+    }
+    let pos = row.saturating_sub(area.y).saturating_sub(thumb_sz / 2).min(track);
+    ((pos as f64 / track as f64) * max_off as f64).round() as usize
+}
@@ -10,14 +10,17 @@ use ratatui::{
     widgets::{Block, Paragraph, Widget},
 };
 
-use crate::core::search::SearchResult;
+use crate::core::search::{SearchMode, SearchOptions, SearchResult};
 use crate::ui::theme::Theme;
 
 pub struct SearchWidget<'a> {
     pub block: Block<'a>,
     pub root: &'a Path,
     pub query: &'a str,
-    pub case_sensitive: bool,
+    pub options: SearchOptions,
+    pub mode: SearchMode,
+    /// Query error shown under the input (invalid regex).
+    pub error: Option<&'a str>,
     pub results: &'a [SearchResult],
     pub selected: Option<usize>,
     pub scroll: usize,
@@ -53,13 +56,32 @@ impl<'a> Widget for SearchWidget<'a> {
             return;
         }
 
-        let case_text = if self.case_sensitive {
-            "[x] case-sensitive (Alt+c)"
-        } else {
-            "[ ] case-sensitive (Alt+c)"
-        };
-        Paragraph::new(Line::from(vec![Span::styled(case_text, Theme::size_style())]))
-        .render(Rect::new(inner.x, y, inner.width, 1), buf);
+        if let Some(error) = self.error {
+            Paragraph::new(Line::from(Span::styled(format!("regex: {error}"), Theme::error_style())))
+                .render(Rect::new(inner.x, y, inner.width, 1), buf);
+            y = y.saturating_add(1);
+            if y >= bottom {
+                return;
+            }
+        }
+
+        let checkbox = |on: bool| if on { "[x]" } else { "[ ]" };
+        let options_text = format!(
+            "{} case (Alt+c)  {} hidden (Alt+h)  {} gitignore (Alt+g)",
+            checkbox(self.options.case_sensitive),
+            checkbox(self.options.include_hidden),
+            checkbox(self.options.respect_gitignore),
+        );
+        Paragraph::new(Line::from(Span::styled(options_text, Theme::size_style())))
+            .render(Rect::new(inner.x, y, inner.width, 1), buf);
+        y = y.saturating_add(1);
+        if y >= bottom {
+            return;
+        }
+
+        let mode_text = format!("mode: {} (Alt+m)  open: Alt+o  copy: Alt+y", self.mode.label());
+        Paragraph::new(Line::from(Span::styled(mode_text, Theme::size_style())))
+            .render(Rect::new(inner.x, y, inner.width, 1), buf);
         y = y.saturating_add(1);
         if y >= bottom {
             return;
@@ -87,6 +109,8 @@ impl<'a> Widget for SearchWidget<'a> {
         if self.results.is_empty() {
             let empty = if self.query.trim().is_empty() {
                 "Type to search."
+            } else if self.error.is_some() {
+                "Fix the pattern to search."
             } else {
                 "No matches."
             };
@@ -117,10 +141,9 @@ impl<'a> Widget for SearchWidget<'a> {
             let parent = result.path.parent().unwrap_or(self.root);
             let avail_for_parent = inner.width.saturating_sub(20) as usize;
             let compact_parent = truncate_parent_path(parent, avail_for_parent.max(8));
-            let mut spans = vec![Span::styled(
-                format!("{marker}{}  {}", result.name, compact_parent),
-                style,
-            )];
+            let mut spans = vec![Span::styled(marker, style)];
+            spans.extend(highlighted_name(&result.name, &result.match_indices, style));
+            spans.push(Span::styled(format!("  {compact_parent}"), style));
             if selected && !result.is_dir {
                 spans.push(Span::styled(
                     format!("  {} to pin file on inspector", self.pin_hint),
@@ -131,7 +154,7 @@ impl<'a> Widget for SearchWidget<'a> {
                 .render(Rect::new(inner.x, y + row_idx as u16, inner.width, 1), buf);
         }
 
-        render_scrollbar(
+        super::scrollbar::render(
             Rect::new(inner.x, y, inner.width, max_rows as u16),
             self.results.len(),
             scroll,
@@ -141,9 +164,30 @@ impl<'a> Widget for SearchWidget<'a> {
     }
 }
 
+/// Split `name` into spans, emphasising the chars at `indices`.
+fn highlighted_name(name: &str, indices: &[usize], style: Style) -> Vec<Span<'static>> {
+    let hit = style.patch(Theme::search_match_style());
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_is_hit = false;
+    for (i, ch) in name.chars().enumerate() {
+        let is_hit = indices.contains(&i);
+        if is_hit != run_is_hit && !run.is_empty() {
+            let s = if run_is_hit { hit } else { style };
+            spans.push(Span::styled(std::mem::take(&mut run), s));
+        }
+        run_is_hit = is_hit;
+        run.push(ch);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, if run_is_hit { hit } else { style }));
+    }
+    spans
+}
+
 /// Number of rows available for search results (below the header fields).
 pub fn search_results_capacity(inner: Rect) -> usize {
-    inner.height.saturating_sub(4) as usize
+    inner.height.saturating_sub(5) as usize
 }
 
 fn truncate_parent_path(path: &Path, max_chars: usize) -> String {
@@ -204,42 +248,3 @@ fn middle_ellipsis(s: &str, max_chars: usize) -> String {
     }
     format!("{left_part}...{right_part}")
 }
-
-fn render_scrollbar(
-    area: Rect,
-    total: usize,
-    offset: usize,
-    visible: usize,
-    buf: &mut Buffer,
-) {
-    use ratatui::layout::Position;
-
-    if total <= visible || area.height < 2 || area.width == 0 {
-        return;
-    }
-
-    let x = area.x + area.width.saturating_sub(1);
-    let h = area.height as f64;
-    let thumb_sz = ((visible as f64 / total as f64) * h).ceil().max(1.0) as u16;
-    let max_off = total.saturating_sub(visible) as f64;
-    let thumb_pos = if max_off > 0.0 {
-        ((offset as f64 / max_off) * (h - thumb_sz as f64)).round() as u16
-    } else {
-        0
-    };
-
-    for row in 0..area.height {
-        let y = area.y + row;
-        let is_thumb = row >= thumb_pos && row < thumb_pos + thumb_sz;
-        let (ch, fg) = if is_thumb {
-            ('█', ratatui::style::Color::LightBlue)
-        } else {
-            ('│', ratatui::style::Color::DarkGray)
-        };
-        if let Some(cell) = buf.cell_mut(Position::new(x, y)) {
-            cell.set_char(ch).set_fg(fg);
-        }
-    }
-}
-
-
@@ -21,6 +21,12 @@ pub struct ScanIndicator {
     pub visible: bool,
     /// Monotonically increasing tick counter (drives the spinner frame).
     pub tick: u64,
+    /// Progress text shown after the label when it fits (e.g. size scan
+    /// counters).
+    pub detail: Option<String>,
+    /// Plain text only: no spinner glyph or ellipsis, and the label
+    /// doesn't change between ticks.
+    pub plain: bool,
 }
 
 impl Widget for ScanIndicator {
@@ -29,10 +35,20 @@ impl Widget for ScanIndicator {
             return;
         }
 
-        let frame = SPINNER_FRAMES[(self.tick as usize) % SPINNER_FRAMES.len()];
-        let label = format!(" {frame} scanning ");
+        let (frame, ellipsis) = if self.plain {
+            ("", "...")
+        } else {
+            (SPINNER_FRAMES[(self.tick as usize) % SPINNER_FRAMES.len()], "…")
+        };
+        let mut label = format!(" {frame} scanning ").replace("  ", " ");
+        if let Some(detail) = &self.detail {
+            let full = format!(" {frame} scanning{ellipsis} {detail} ").replace("  ", " ");
+            if full.chars().count() as u16 + 4 <= area.width {
+                label = full;
+            }
+        }
 
-        let label_width = label.len() as u16;
+        let label_width = label.chars().count() as u16;
         // Position: top-right, inside the border (leave 1 col for the border char).
         let x = area.x + area.width.saturating_sub(label_width + 2);
         let y = area.y; // top border row
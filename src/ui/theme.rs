@@ -2,6 +2,9 @@
 
 use ratatui::style::{Color, Modifier, Style};
 
+use crate::core::git::GitFileStatus;
+use crate::core::highlight::TokenKind;
+
 /// Central theme — change colours here and they propagate everywhere.
 pub struct Theme;
 
@@ -27,6 +30,62 @@ impl Theme {
             .add_modifier(Modifier::ITALIC)
     }
 
+    pub fn staged_deletion_style() -> Style {
+        Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::CROSSED_OUT)
+    }
+
+    /// Entries in the multi-selection, and their `✓` marker.
+    pub fn marked_style() -> Style {
+        Style::default()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Entries modified within the "recently changed" window.
+    pub fn recent_style() -> Style {
+        Style::default().fg(Color::LightGreen)
+    }
+
+    /// `git status` badge beside an entry.
+    pub fn git_status_style(status: GitFileStatus) -> Style {
+        let color = match status {
+            GitFileStatus::Modified => Color::Yellow,
+            GitFileStatus::Added => Color::Green,
+            GitFileStatus::Deleted => Color::Red,
+            GitFileStatus::Untracked => Color::Magenta,
+        };
+        Style::default().fg(color).add_modifier(Modifier::BOLD)
+    }
+
+    /// Marker on directories holding device nodes or sockets.
+    pub fn special_marker_style() -> Style {
+        Style::default().fg(Color::Magenta)
+    }
+
+    /// Background of every other tree row when striping is on.
+    pub fn alt_row_style() -> Style {
+        Style::default().bg(Color::Rgb(28, 28, 32))
+    }
+
+    /// Vertical guide linking a directory's rows to its later siblings.
+    pub fn indent_guide_style() -> Style {
+        Style::default().fg(Color::DarkGray)
+    }
+
+    /// Query characters matched inside a search result name.
+    pub fn search_match_style() -> Style {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Inline error text (e.g. an invalid search pattern).
+    pub fn error_style() -> Style {
+        Style::default().fg(Color::Red)
+    }
+
     pub fn selected_style() -> Style {
         Style::default()
             .bg(Color::DarkGray)
@@ -48,10 +107,33 @@ impl Theme {
         Style::default().bg(Color::DarkGray).fg(Color::White)
     }
 
+    /// Status-bar badge for a non-default setting that can be flipped.
+    pub fn status_badge_style() -> Style {
+        Style::default().bg(Color::DarkGray).fg(Color::Yellow)
+    }
+
+    /// Status-bar badge that is informational only.
+    pub fn status_badge_info_style() -> Style {
+        Style::default().bg(Color::DarkGray).fg(Color::Gray)
+    }
+
+    /// Status-bar badge focused by the badge-focus key.
+    pub fn status_badge_focus_style() -> Style {
+        Style::default()
+            .bg(Color::Yellow)
+            .fg(Color::Black)
+            .add_modifier(Modifier::BOLD)
+    }
+
     pub fn size_style() -> Style {
         Style::default().fg(Color::DarkGray)
     }
 
+    /// Size share column (percentage or bar) before tree rows.
+    pub fn size_share_style() -> Style {
+        Style::default().fg(Color::Cyan)
+    }
+
     pub fn root_hint_style() -> Style {
         Style::default()
             .fg(Color::Rgb(200, 140, 60))
@@ -61,5 +143,77 @@ impl Theme {
     pub fn command_input_style() -> Style {
         Style::default().fg(Color::Yellow)
     }
+
+    // ── image previews ─────────────────────────────────────────
+    /// Colour transparent image pixels are composited onto.
+    pub fn image_backdrop() -> (u8, u8, u8) {
+        (24, 24, 24)
+    }
+
+    // ── text previews ──────────────────────────────────────────
+    /// Colour of a syntax-highlighted run in a text preview.
+    pub fn syntax_style(kind: TokenKind) -> Style {
+        match kind {
+            TokenKind::Plain => Style::default(),
+            TokenKind::Comment => Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+            TokenKind::String => Style::default().fg(Color::Green),
+            TokenKind::Number | TokenKind::Constant => Style::default().fg(Color::LightMagenta),
+            TokenKind::Keyword => Style::default().fg(Color::LightBlue),
+            TokenKind::Function => Style::default().fg(Color::Yellow),
+            TokenKind::Type => Style::default().fg(Color::Cyan),
+        }
+    }
 }
 
+// ───────────────────────────────────────── heat gradient ──────
+
+/// Endpoints of the size "heat" gradient used to tint tree rows by their
+/// share of the root's total size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeatGradient {
+    pub cold: (u8, u8, u8),
+    pub hot: (u8, u8, u8),
+}
+
+impl Default for HeatGradient {
+    fn default() -> Self {
+        Self {
+            cold: (90, 90, 100),
+            hot: (230, 40, 40),
+        }
+    }
+}
+
+impl HeatGradient {
+    /// Colour for an entry holding `share` (0..=1) of the root total.
+    /// Log-scaled over three decades so that 0.1% and below is fully cold
+    /// and only the few biggest entries approach the hot end.
+    pub fn color(&self, share: f64) -> Color {
+        let t = if share > 0.0 {
+            (1.0 + share.log10() / 3.0).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Color::Rgb(
+            lerp(self.cold.0, self.hot.0),
+            lerp(self.cold.1, self.hot.1),
+            lerp(self.cold.2, self.hot.2),
+        )
+    }
+}
+
+/// Parse a `#rrggbb` colour string.
+pub fn parse_hex_rgb(s: &str) -> Option<(u8, u8, u8)> {
+    let hex = s.trim().strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Format an RGB triple as `#rrggbb`.
+pub fn format_hex_rgb((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
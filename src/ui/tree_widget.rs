@@ -2,21 +2,27 @@
 //! collapsible tree with grouping support.
 
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, StatefulWidget, Widget},
 };
 
 use crate::core::{
-    grouping::{self, GroupedEntry, GroupingConfig},
-    tree::{DirTree, NodeId},
+    git::GitFileStatus,
+    grouping::{self, GroupedEntry, GroupingConfig, SortMode},
+    size::{self, EntryCounts},
+    tree::{DirTree, NodeId, TreeNode},
 };
 
-use super::theme::Theme;
+use super::icons;
+use super::theme::{HeatGradient, Theme};
+use crate::config::{IconSet, SizeShare};
 
 // ───────────────────────────────────────── state ─────────────
 
@@ -27,6 +33,9 @@ pub struct TreeWidgetState {
     pub selected: usize,
     /// Vertical scroll offset (first visible row).
     pub offset: usize,
+    /// Selection the wheel scrolled the view away from.  While it is
+    /// still the selection, drawing doesn't scroll back to it.
+    pub scrolled_away_from: Option<usize>,
 }
 
 impl TreeWidgetState {
@@ -40,11 +49,53 @@ impl TreeWidgetState {
         self.selected = self.selected.saturating_sub(1);
     }
 
+    /// Move the selection and the viewport together by `delta` rows, the
+    /// way `less` and vim page, rather than letting the selection settle
+    /// at the viewport edge.  `max` is the number of visible rows.
+    pub fn scroll_page(&mut self, delta: isize, height: usize, max: usize) {
+        if max == 0 {
+            return;
+        }
+        self.selected = self.selected.saturating_add_signed(delta).min(max - 1);
+        self.offset = self
+            .offset
+            .saturating_add_signed(delta)
+            .min(max.saturating_sub(height));
+    }
+
+    /// Scroll the view by `delta` rows without moving the selection, never
+    /// past the point where the last row reaches the bottom edge.  With
+    /// `keep_selection` the selection is pulled along only when it would
+    /// leave the view.
+    pub fn scroll_view(&mut self, delta: isize, height: usize, max: usize, keep_selection: bool) {
+        let offset = self.offset.saturating_add_signed(delta);
+        self.scroll_to(offset, height, max, keep_selection);
+    }
+
+    /// [`Self::scroll_view`] to an absolute offset.
+    pub fn scroll_to(&mut self, offset: usize, height: usize, max: usize, keep_selection: bool) {
+        if height == 0 || max == 0 {
+            return;
+        }
+        self.offset = offset.min(max.saturating_sub(height));
+        if keep_selection {
+            let last = (self.offset + height).min(max) - 1;
+            self.selected = self.selected.clamp(self.offset, last);
+            self.scrolled_away_from = None;
+        } else {
+            self.scrolled_away_from = Some(self.selected);
+        }
+    }
+
     /// Ensure the selected row is visible within the viewport of `height` rows.
     pub fn clamp_scroll(&mut self, height: usize) {
         if height == 0 {
             return;
         }
+        if self.scrolled_away_from == Some(self.selected) {
+            return;
+        }
+        self.scrolled_away_from = None;
         if self.selected < self.offset {
             self.offset = self.selected;
         } else if self.selected >= self.offset + height {
@@ -55,6 +106,51 @@ impl TreeWidgetState {
 
 // ───────────────────────────────────────── row model ─────────
 
+/// A row's size relative to its parent directory and its siblings, for
+/// the size share column.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RowShare {
+    /// Fraction of the parent's total; `None` until both are known.
+    pub of_parent: Option<f64>,
+    /// Fraction of the largest sibling's size.
+    pub of_largest: Option<f64>,
+}
+
+impl RowShare {
+    /// Width of [`Self::text`] in columns for every mode.
+    fn width(mode: SizeShare) -> usize {
+        match mode {
+            SizeShare::Off => 0,
+            SizeShare::Percent => 5,
+            SizeShare::Bar => BAR_CELLS + 3,
+        }
+    }
+
+    /// Fixed-width column text: `" 42% "`, `"[###-----] "`, or
+    /// placeholders while sizes are still cascading.
+    fn text(share: Option<Self>, mode: SizeShare) -> String {
+        match mode {
+            SizeShare::Off => String::new(),
+            SizeShare::Percent => match share.and_then(|s| s.of_parent) {
+                Some(f) => format!("{:>3.0}% ", (f * 100.0).min(100.0)),
+                None if share.is_some() => " --% ".to_string(),
+                None => " ".repeat(Self::width(mode)),
+            },
+            SizeShare::Bar => match share.and_then(|s| s.of_largest) {
+                Some(f) => {
+                    let filled = ((f * BAR_CELLS as f64).round() as usize).min(BAR_CELLS);
+                    format!("[{}{}] ", "#".repeat(filled), "-".repeat(BAR_CELLS - filled))
+                }
+                None if share.is_some() => format!("[{}] ", " ".repeat(BAR_CELLS)),
+                None => " ".repeat(Self::width(mode)),
+            },
+        }
+    }
+}
+
+/// Cells inside a size share bar's brackets.
+const BAR_CELLS: usize = 8;
+
 /// One rendered row in the tree view.
 #[derive(Debug)]
 pub enum TreeRow {
@@ -67,6 +163,15 @@ pub enum TreeRow {
         label: String,
         /// For symlinks: the target path (displayed as `→ target`).
         symlink_target: Option<String>,
+        /// Change state inside a git work tree, if any.
+        git_status: Option<GitFileStatus>,
+        /// Immediate children of a directory, when known and enabled.
+        entry_count: Option<usize>,
+        /// Per indent level, whether a guide line continues through this
+        /// row (empty when guides are off).
+        guides: Vec<bool>,
+        /// Size share, when the column is on; `None` for the root.
+        share: Option<RowShare>,
     },
     Group {
         depth: usize,
@@ -77,6 +182,10 @@ pub enum TreeRow {
         expanded: bool,
         /// Member node IDs (for expanding).
         members: Vec<NodeId>,
+        /// See [`TreeRow::Node::guides`].
+        guides: Vec<bool>,
+        /// See [`TreeRow::Node::share`].
+        share: Option<RowShare>,
     },
 }
 
@@ -93,6 +202,42 @@ pub struct TreeWidget<'a> {
     pin_hint: Option<String>,
     /// Keys of groups that are currently expanded.
     expanded_groups: Option<&'a HashSet<String>>,
+    /// When set, tint rows by their share of the root's total size.
+    heat: Option<HeatGradient>,
+    /// Paths staged for deletion (drawn struck through).
+    staged: &'a [PathBuf],
+    /// Paths in the multi-selection (drawn with a `✓`).
+    marked: Option<&'a HashSet<PathBuf>>,
+    /// Directory totals without hard-link dedup.
+    apparent_sizes: Option<&'a HashMap<PathBuf, u64>>,
+    entry_counts: Option<&'a HashMap<PathBuf, EntryCounts>>,
+    /// Directories whose size is being recomputed (drawn dimmed).
+    stale_sizes: Option<&'a HashSet<PathBuf>>,
+    /// Directories whose totals skipped an excluded subdirectory.
+    excluded_sizes: Option<&'a HashSet<PathBuf>>,
+    /// Order of entries within each directory.
+    sort_mode: SortMode,
+    /// Entries modified at or after this instant are highlighted.
+    recent_since: Option<SystemTime>,
+    /// `git status` of changed paths, for the badge column.
+    git_statuses: Option<&'a HashMap<PathBuf, GitFileStatus>>,
+    /// Show `(N)` after directory rows.
+    show_entry_count: bool,
+    /// Child count of the inspected directory, which also includes
+    /// entries the walk filters hide.
+    inspected_entry_count: Option<(&'a Path, usize)>,
+    /// Show the recursive `(N files)` after directory sizes.
+    show_file_count: bool,
+    /// Prefix style for names.
+    icons: IconSet,
+    /// Draw `│` guides down to each directory's later siblings.
+    indent_guides: bool,
+    /// Shade every other row's background.
+    alternate_rows: bool,
+    /// Screen-reader mode: plain-text rows, selection pinned to the top.
+    a11y: bool,
+    /// Size share column before each row.
+    size_share: SizeShare,
 }
 
 impl<'a> TreeWidget<'a> {
@@ -105,6 +250,24 @@ impl<'a> TreeWidget<'a> {
             block: None,
             pin_hint: None,
             expanded_groups: None,
+            heat: None,
+            staged: &[],
+            marked: None,
+            apparent_sizes: None,
+            entry_counts: None,
+            stale_sizes: None,
+            excluded_sizes: None,
+            sort_mode: SortMode::Name,
+            recent_since: None,
+            git_statuses: None,
+            show_entry_count: false,
+            inspected_entry_count: None,
+            show_file_count: false,
+            icons: IconSet::Ascii,
+            indent_guides: false,
+            alternate_rows: false,
+            a11y: false,
+            size_share: SizeShare::Off,
         }
     }
 
@@ -136,14 +299,172 @@ impl<'a> TreeWidget<'a> {
         self
     }
 
+    /// Enable size heat tinting with the given gradient.
+    pub fn heat(mut self, gradient: Option<HeatGradient>) -> Self {
+        self.heat = gradient;
+        self
+    }
+
+    /// Paths staged for deletion.
+    pub fn staged_for_deletion(mut self, staged: &'a [PathBuf]) -> Self {
+        self.staged = staged;
+        self
+    }
+
+    /// Paths in the multi-selection.
+    pub fn marked(mut self, marked: &'a HashSet<PathBuf>) -> Self {
+        self.marked = Some(marked);
+        self
+    }
+
+    /// Apparent (non-deduped) directory totals; shown when dedup saves
+    /// a noticeable amount.
+    pub fn apparent_sizes(mut self, sizes: Option<&'a HashMap<PathBuf, u64>>) -> Self {
+        self.apparent_sizes = sizes;
+        self
+    }
+
+    /// Recursive symlink / special file counts; directories holding device
+    /// nodes or sockets get a marker.
+    pub fn entry_counts(mut self, counts: &'a HashMap<PathBuf, EntryCounts>) -> Self {
+        self.entry_counts = Some(counts);
+        self
+    }
+
+    /// Directories whose size is out of date.
+    pub fn stale_sizes(mut self, stale: &'a HashSet<PathBuf>) -> Self {
+        self.stale_sizes = Some(stale);
+        self
+    }
+
+    /// Directories whose sizes get a `*` for leaving out excluded dirs.
+    pub fn excluded_sizes(mut self, excluded: &'a HashSet<PathBuf>) -> Self {
+        self.excluded_sizes = Some(excluded);
+        self
+    }
+
+    /// Order entries within each directory by `mode`.
+    pub fn sort_mode(mut self, mode: SortMode) -> Self {
+        self.sort_mode = mode;
+        self
+    }
+
+    /// Highlight entries modified at or after `since`.
+    pub fn recent_since(mut self, since: Option<SystemTime>) -> Self {
+        self.recent_since = since;
+        self
+    }
+
+    /// Badge entries with their `git status`; `None` hides the badges.
+    pub fn git_statuses(mut self, statuses: Option<&'a HashMap<PathBuf, GitFileStatus>>) -> Self {
+        self.git_statuses = statuses;
+        self
+    }
+
+    /// Show each directory's entry count; `inspected` overrides the loaded
+    /// child count for the directory the inspector is showing.
+    pub fn entry_count(mut self, show: bool, inspected: Option<(&'a Path, usize)>) -> Self {
+        self.show_entry_count = show;
+        self.inspected_entry_count = inspected;
+        self
+    }
+
+    /// Show each directory's recursive file count (from
+    /// [`Self::entry_counts`]) after its size.
+    pub fn file_count(mut self, show: bool) -> Self {
+        self.show_file_count = show;
+        self
+    }
+
+    /// Draw indent guide lines.
+    pub fn indent_guides(mut self, show: bool) -> Self {
+        self.indent_guides = show;
+        self
+    }
+
+    /// Shade even rows with [`Theme::alt_row_style`].
+    pub fn alternate_rows(mut self, on: bool) -> Self {
+        self.alternate_rows = on;
+        self
+    }
+
+    /// Screen-reader friendly rendering: no icons or guide lines, a
+    /// trailing `/` on directories, words instead of arrows, and the
+    /// selected row kept on the first line of the viewport.
+    pub fn a11y(mut self, on: bool) -> Self {
+        self.a11y = on;
+        self
+    }
+
+    /// Show each row's share of its parent's size before it.
+    pub fn size_share(mut self, mode: SizeShare) -> Self {
+        self.size_share = mode;
+        self
+    }
+
+    /// Icons drawn before names.
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    fn entry_count_of(&self, node: &TreeNode) -> Option<usize> {
+        if !self.show_entry_count || !node.meta.is_dir {
+            return None;
+        }
+        if let Some((path, count)) = self.inspected_entry_count {
+            if path == node.meta.path {
+                return Some(count);
+            }
+        }
+        // Unexpanded directories with no children may simply be unlisted.
+        (node.expanded || !node.children.is_empty()).then_some(node.children.len())
+    }
+
+    fn git_status_of(&self, path: &Path) -> Option<GitFileStatus> {
+        self.git_statuses.and_then(|s| s.get(path).copied())
+    }
+
     /// Build the flat list of rows (with grouping applied).
     pub fn build_rows(&self) -> Vec<TreeRow> {
         let mut rows = Vec::new();
-        self.collect_rows(self.tree.root, &mut rows);
+        self.collect_rows(self.tree.root, None, &mut rows, &mut Vec::new());
         rows
     }
 
-    fn collect_rows(&self, node_id: NodeId, rows: &mut Vec<TreeRow>) {
+    /// Shares of `sizes` (one per sibling, `None` while unknown) in a
+    /// directory totalling `parent_total`.  Empty when the column is off.
+    fn shares(&self, parent_total: Option<u64>, sizes: &[Option<u64>]) -> Vec<Option<RowShare>> {
+        if self.size_share == SizeShare::Off {
+            return vec![None; sizes.len()];
+        }
+        let largest = sizes.iter().flatten().copied().max().filter(|&l| l > 0);
+        let parent_total = parent_total.filter(|&t| t > 0);
+        sizes
+            .iter()
+            .map(|size| {
+                Some(RowShare {
+                    of_parent: size.zip(parent_total).map(|(s, t)| s as f64 / t as f64),
+                    of_largest: size.zip(largest).map(|(s, l)| s as f64 / l as f64),
+                })
+            })
+            .collect()
+    }
+
+    fn size_of(&self, meta: &crate::core::tree::EntryMeta) -> Option<u64> {
+        let sizes = if meta.is_dir { self.dir_sizes } else { self.file_sizes };
+        sizes.and_then(|s| s.get(&meta.path).copied())
+    }
+
+    /// `next_siblings[d]` says whether the ancestor at depth `d + 1` (the
+    /// node itself for the last entry) has a sibling after it.
+    fn collect_rows(
+        &self,
+        node_id: NodeId,
+        share: Option<RowShare>,
+        rows: &mut Vec<TreeRow>,
+        next_siblings: &mut Vec<bool>,
+    ) {
         let node = self.tree.get(node_id);
 
         // Push the node itself.
@@ -155,6 +476,10 @@ impl<'a> TreeWidget<'a> {
             expanded: node.expanded,
             label: node.meta.name.clone(),
             symlink_target: node.meta.symlink_target.clone(),
+            git_status: self.git_status_of(&node.meta.path),
+            entry_count: self.entry_count_of(node),
+            guides: self.guides_for(next_siblings),
+            share,
         });
 
         if !node.expanded || !node.meta.is_dir {
@@ -162,13 +487,25 @@ impl<'a> TreeWidget<'a> {
         }
 
         // Apply grouping to this node's children.
-        let grouped = grouping::group_children(self.tree, node_id, self.grouping_config, self.file_sizes);
+        let mut grouped = grouping::group_children(self.tree, node_id, self.grouping_config, self.file_sizes);
+        grouping::sort_grouped(self.tree, &mut grouped, self.sort_mode, self.dir_sizes, self.file_sizes);
         let parent_path = node.meta.path.display().to_string();
-
-        for entry in grouped {
+        let parent_total = self.size_of(&node.meta);
+        let sizes: Vec<Option<u64>> = grouped
+            .iter()
+            .map(|entry| match entry {
+                GroupedEntry::Single(id) => self.size_of(&self.tree.get(*id).meta),
+                GroupedEntry::Group { total_size, .. } => Some(*total_size),
+            })
+            .collect();
+        let shares = self.shares(parent_total, &sizes);
+
+        let count = grouped.len();
+        for (i, (entry, share)) in grouped.into_iter().zip(shares).enumerate() {
+            next_siblings.push(i + 1 < count);
             match entry {
                 GroupedEntry::Single(child_id) => {
-                    self.collect_rows(child_id, rows);
+                    self.collect_rows(child_id, share, rows, next_siblings);
                 }
                 GroupedEntry::Group {
                     label,
@@ -184,15 +521,24 @@ impl<'a> TreeWidget<'a> {
 
                     rows.push(TreeRow::Group {
                         depth,
-                        label: format!("{count} {label} files {}", grouping::human_size(total_size)),
+                        label: format!(
+                            "{count} {label} files {}",
+                            grouping::human_size_padded(total_size)
+                        ),
                         group_key,
                         expanded,
                         members: members.clone(),
+                        guides: self.guides_for(next_siblings),
+                        share,
                     });
 
                     // When expanded, show each member indented one level deeper.
                     if expanded {
-                        for &member_id in &members {
+                        let member_sizes: Vec<Option<u64>> =
+                            members.iter().map(|&m| self.size_of(&self.tree.get(m).meta)).collect();
+                        let member_shares = self.shares(parent_total, &member_sizes);
+                        for (j, (&member_id, share)) in members.iter().zip(member_shares).enumerate() {
+                            next_siblings.push(j + 1 < members.len());
                             let member = self.tree.get(member_id);
                             rows.push(TreeRow::Node {
                                 node_id: member_id,
@@ -202,15 +548,58 @@ impl<'a> TreeWidget<'a> {
                                 expanded: false,
                                 label: member.meta.name.clone(),
                                 symlink_target: member.meta.symlink_target.clone(),
+                                git_status: self.git_status_of(&member.meta.path),
+                                entry_count: None,
+                                guides: self.guides_for(next_siblings),
+                                share,
                             });
+                            next_siblings.pop();
                         }
                     }
                 }
             }
+            next_siblings.pop();
+        }
+    }
+
+    /// The size share column for a row, in front of everything else so a
+    /// narrow pane cuts off the name rather than the column.  Bars read
+    /// as noise to a screen reader, so a11y mode says the percentage.
+    fn share_span(&self, share: Option<RowShare>, is_selected: bool) -> Option<Span<'static>> {
+        let mode = match self.size_share {
+            SizeShare::Off => return None,
+            SizeShare::Bar if self.a11y => SizeShare::Percent,
+            mode => mode,
+        };
+        let style = if is_selected {
+            Theme::selected_style()
+        } else {
+            Theme::size_share_style()
+        };
+        Some(Span::styled(RowShare::text(share, mode), style))
+    }
+
+    fn guides_for(&self, next_siblings: &[bool]) -> Vec<bool> {
+        if self.indent_guides && !self.a11y {
+            next_siblings.to_vec()
+        } else {
+            Vec::new()
         }
     }
 }
 
+/// Leading indent for a row at `depth`, with a `│` at each level whose
+/// guide continues.
+fn indent_span(depth: usize, guides: &[bool]) -> Span<'static> {
+    if !guides.contains(&true) {
+        return Span::raw("  ".repeat(depth));
+    }
+    let indent: String = (0..depth)
+        .map(|d| if guides.get(d).copied().unwrap_or(false) { "│ " } else { "  " })
+        .collect();
+    Span::styled(indent, Theme::indent_guide_style())
+}
+
 impl<'a> StatefulWidget for TreeWidget<'a> {
     type State = TreeWidgetState;
 
@@ -225,7 +614,19 @@ impl<'a> StatefulWidget for TreeWidget<'a> {
         };
 
         let rows = self.build_rows();
-        state.clamp_scroll(inner.height as usize);
+        if self.a11y {
+            // Screen readers follow the cursor line; keep it on top.
+            state.offset = state.selected;
+        } else {
+            state.clamp_scroll(inner.height as usize);
+        }
+        let icon_set = if self.a11y { IconSet::None } else { self.icons };
+
+        // Heat is relative to the root total; without it rows stay neutral.
+        let root_total = self
+            .dir_sizes
+            .and_then(|sizes| sizes.get(&self.tree.get(self.tree.root).meta.path).copied())
+            .filter(|&total| total > 0);
 
         let visible_rows = rows
             .iter()
@@ -246,20 +647,32 @@ impl<'a> StatefulWidget for TreeWidget<'a> {
                     expanded,
                     label,
                     symlink_target,
+                    git_status,
+                    entry_count,
+                    guides,
+                    share,
                 } => {
-                    let indent = "  ".repeat(*depth);
-                    let icon = if *is_symlink {
-                        "~ "
-                    } else if *is_dir {
-                        if *expanded {
-                            "▼ "
-                        } else {
-                            "▶ "
-                        }
+                    let indent = indent_span(*depth, guides);
+                    let meta = &self.tree.get(*node_id).meta;
+                    let icon = icons::row_prefix(icon_set, meta, *expanded);
+                    let path = &meta.path;
+                    let is_recent = self
+                        .recent_since
+                        .zip(meta.modified)
+                        .is_some_and(|(since, modified)| modified >= since);
+                    let maybe_size = if *is_dir {
+                        self.dir_sizes.and_then(|sizes| sizes.get(path).copied())
                     } else {
-                        "  "
+                        self.file_sizes.and_then(|sizes| sizes.get(path).copied())
                     };
-                    let style = if is_selected {
+                    let heat_color = match (self.heat, root_total, maybe_size) {
+                        (Some(gradient), Some(total), Some(size)) => {
+                            Some(gradient.color(size as f64 / total as f64))
+                        }
+                        _ => None,
+                    };
+
+                    let mut style = if is_selected {
                         Theme::selected_style()
                     } else if *is_symlink {
                         Theme::symlink_style()
@@ -268,11 +681,36 @@ impl<'a> StatefulWidget for TreeWidget<'a> {
                     } else {
                         Theme::file_style()
                     };
+                    if let (Some(color), false) = (heat_color, is_selected) {
+                        style = style.fg(color);
+                    }
+                    if is_recent && !is_selected {
+                        style = style.patch(Theme::recent_style());
+                    }
+                    if meta.missing {
+                        style = style.add_modifier(Modifier::DIM);
+                    }
+                    if self.staged.iter().any(|p| p == path) {
+                        style = style.patch(Theme::staged_deletion_style());
+                    }
+                    let is_marked = self.marked.is_some_and(|m| m.contains(path));
+                    if is_marked && !is_selected {
+                        style = style.patch(Theme::marked_style());
+                    }
 
-                    let mut spans = vec![
-                        Span::raw(indent),
-                        Span::styled(format!("{icon}{label}"), style),
-                    ];
+                    let dir_suffix = if self.a11y && *is_dir { "/" } else { "" };
+                    let mut spans: Vec<Span> = self.share_span(*share, is_selected).into_iter().collect();
+                    spans.push(indent);
+                    if is_marked {
+                        let marker = if self.a11y { "selected: " } else { "✓ " };
+                        let marker_style = if is_selected {
+                            Theme::selected_style()
+                        } else {
+                            Theme::marked_style()
+                        };
+                        spans.push(Span::styled(marker, marker_style));
+                    }
+                    spans.push(Span::styled(format!("{icon}{label}{dir_suffix}"), style));
 
                     // Show symlink target as `→ target`.
                     if let Some(target) = symlink_target {
@@ -281,26 +719,85 @@ impl<'a> StatefulWidget for TreeWidget<'a> {
                         } else {
                             Theme::size_style()
                         };
-                        spans.push(Span::styled(format!(" → {target}"), target_style));
+                        let arrow = if self.a11y { "links to" } else { "→" };
+                        spans.push(Span::styled(format!(" {arrow} {target}"), target_style));
                     }
 
-                    let path = &self.tree.get(*node_id).meta.path;
-                    let maybe_size = if *is_dir {
-                        self.dir_sizes.and_then(|sizes| sizes.get(path).copied())
-                    } else {
-                        self.file_sizes.and_then(|sizes| sizes.get(path).copied())
-                    };
+                    if let Some(status) = git_status {
+                        let badge_style = if is_selected {
+                            Theme::selected_style()
+                        } else {
+                            Theme::git_status_style(*status)
+                        };
+                        spans.push(Span::styled(format!(" {}", status.badge()), badge_style));
+                    }
+
+                    let has_devices = *is_dir
+                        && self
+                            .entry_counts
+                            .and_then(|c| c.get(path))
+                            .is_some_and(|c| c.devices > 0);
+                    if has_devices {
+                        let marker_style = if is_selected {
+                            Theme::selected_style()
+                        } else {
+                            Theme::special_marker_style()
+                        };
+                        let marker = if self.a11y { " (devices)" } else { " ◆" };
+                        spans.push(Span::styled(marker, marker_style));
+                    }
 
                     if let Some(size) = maybe_size {
-                        let size_style = if is_selected {
+                        let mut size_style = if is_selected {
                             Theme::selected_style()
+                        } else if let Some(color) = heat_color {
+                            Style::default().fg(color)
                         } else {
                             Theme::size_style()
                         };
-                        spans.push(Span::styled(
-                            format!(" {}", grouping::human_size(size)),
-                            size_style,
-                        ));
+                        if *is_dir && self.stale_sizes.is_some_and(|s| s.contains(path)) {
+                            size_style = size_style.add_modifier(Modifier::DIM);
+                        }
+                        let savings = if *is_dir {
+                            self.apparent_sizes
+                                .and_then(|a| a.get(path).copied())
+                                .filter(|&apparent| size::hardlink_savings(size, apparent).is_some())
+                        } else {
+                            None
+                        };
+                        let text = match savings {
+                            Some(apparent) => format!(
+                                " {} ({} apparent)",
+                                grouping::human_size_padded(size),
+                                grouping::human_size(apparent)
+                            ),
+                            None => format!(" {}", grouping::human_size_padded(size)),
+                        };
+                        spans.push(Span::styled(text, size_style));
+                        if *is_dir && self.excluded_sizes.is_some_and(|s| s.contains(path)) {
+                            spans.push(Span::styled("*", size_style));
+                        }
+                        let file_count = self
+                            .entry_counts
+                            .filter(|_| *is_dir && self.show_file_count)
+                            .and_then(|c| c.get(path))
+                            .map(|c| c.files);
+                        if let Some(files) = file_count {
+                            let noun = if files == 1 { "file" } else { "files" };
+                            spans.push(Span::styled(
+                                format!(" ({} {noun})", grouping::grouped_count(files)),
+                                size_style.add_modifier(Modifier::DIM),
+                            ));
+                        }
+                    }
+
+                    if let Some(count) = entry_count {
+                        let count_style = if is_selected {
+                            Theme::selected_style()
+                        } else {
+                            Theme::size_style().add_modifier(Modifier::DIM)
+                        };
+                        spans.push(Span::styled(format!(" ({count})"), count_style));
                     }
 
                     // Hint on selected root: explain how to navigate above
@@ -328,26 +825,54 @@ impl<'a> StatefulWidget for TreeWidget<'a> {
                     depth,
                     label,
                     expanded,
+                    guides,
+                    share,
                     ..
                 } => {
-                    let indent = "  ".repeat(*depth);
-                    let icon = if *expanded { "− " } else { "+ " };
+                    let indent = indent_span(*depth, guides);
+                    let icon = match (*expanded, self.a11y) {
+                        (true, false) => "− ",
+                        (true, true) => "- ",
+                        (false, _) => "+ ",
+                    };
                     let style = if is_selected {
                         Theme::selected_style()
                     } else {
                         Theme::group_style()
                     };
-                    Line::from(vec![
-                        Span::raw(indent),
-                        Span::styled(format!("{icon}{label}"), style),
-                    ])
+                    let mut spans: Vec<Span> = self.share_span(*share, is_selected).into_iter().collect();
+                    spans.push(indent);
+                    spans.push(Span::styled(format!("{icon}{label}"), style));
+                    Line::from(spans)
                 }
             };
 
+            // Stripe under the spans, so their own colours (and the
+            // selection background) still win.
+            if self.alternate_rows && row_idx % 2 == 0 && !is_selected {
+                buf.set_style(Rect::new(inner.x, y, inner.width, 1), Theme::alt_row_style());
+            }
+
             // Render the line into the buffer.
             let line_width = inner.width as usize;
             buf.set_line(inner.x, y, &line, line_width as u16);
         }
+
+        if !self.a11y {
+            super::scrollbar::render(
+                scrollbar_area(area, inner),
+                rows.len(),
+                state.offset,
+                inner.height as usize,
+                buf,
+            );
+        }
     }
 }
 
+/// Where the tree's scrollbar goes: the pane's right edge (over the
+/// border, so no row is covered) beside the content rows.
+pub fn scrollbar_area(area: Rect, inner: Rect) -> Rect {
+    Rect::new(area.x, inner.y, area.width, inner.height)
+}
+